@@ -165,6 +165,20 @@ impl<'a, T: IbcClient> IbcClientCtx<'a, T> {
 
         Ok(status)
     }
+
+    pub fn expiry<Client: IbcClient>(
+        &self,
+        client_id: ClientId,
+    ) -> Result<Option<Timestamp>, IbcClientError<Client>> {
+        let client_impl = client_impl(&*self.deps.querier, &self.ibc_host, client_id)?;
+
+        let expiry = self.deps.querier.query_wasm_smart::<Option<Timestamp>>(
+            &client_impl,
+            &(QueryMsg::GetExpiry { client_id }),
+        )?;
+
+        Ok(expiry)
+    }
 }
 
 fn client_impl<T: IbcClient>(
@@ -302,6 +316,20 @@ pub trait IbcClient: Sized + 'static {
     /// Get the status of the client
     fn status(ctx: IbcClientCtx<Self>, client_state: &Self::ClientState) -> Status;
 
+    /// Get the time at which the client expires, or `None` if the client does not expire.
+    ///
+    /// A client is expected to report [`Status::Expired`] from [`IbcClient::status`] once the host
+    /// block time is past this timestamp.
+    fn expiry(
+        ctx: IbcClientCtx<Self>,
+        client_state: &Self::ClientState,
+    ) -> Result<Option<Timestamp>, IbcClientError<Self>> {
+        let _ = ctx;
+        let _ = client_state;
+
+        Ok(None)
+    }
+
     /// Verify the initial state of the client
     fn verify_creation(
         caller: Addr,
@@ -365,6 +393,15 @@ pub fn query<T: IbcClient>(
             );
             to_json_binary(&status).map_err(Into::into)
         }
+        QueryMsg::GetExpiry { client_id } => {
+            let ibc_host = deps.storage.read_item::<IbcHost>()?;
+            let client_state = read_client_state::<T>(&*deps.querier, &ibc_host, client_id)?;
+            let expiry = T::expiry(
+                IbcClientCtx::new(client_id, ibc_host, deps, env),
+                &client_state,
+            )?;
+            to_json_binary(&expiry).map_err(Into::into)
+        }
         QueryMsg::VerifyCreation {
             caller,
             client_id,
@@ -65,6 +65,11 @@ pub enum QueryMsg {
     GetStatus {
         client_id: ClientId,
     },
+    /// Returns `Option<Timestamp>`, the time at which the client expires, or `None` if the client
+    /// does not expire.
+    GetExpiry {
+        client_id: ClientId,
+    },
     /// NOTE: Reads state through the [`QueryStore`].
     VerifyCreation {
         caller: String,
@@ -18,6 +18,11 @@ pub enum QueryMsg {
     GetConsensusState { client_id: ClientId, height: u64 },
     #[cfg_attr(feature = "cw-orch-interface", returns(crate::lightclient::Status))]
     GetStatus { client_id: ClientId },
+    #[cfg_attr(
+        feature = "cw-orch-interface",
+        returns(Option<ibc_union_spec::Timestamp>)
+    )]
+    GetExpiry { client_id: ClientId },
     #[cfg_attr(feature = "cw-orch-interface", returns(String))]
     GetClientType { client_id: ClientId },
     #[cfg_attr(feature = "cw-orch-interface", returns(ibc_union_spec::Connection))]
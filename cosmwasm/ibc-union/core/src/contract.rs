@@ -11,8 +11,8 @@ use depolama::{RawStore, StorageExt};
 use frissitheto::{UpgradeError, UpgradeMsg};
 use ibc_union_msg::{
     lightclient::{
-        QueryMsg as LightClientQuery, Status, UpdateStateResponse, VerifyCreationResponse,
-        VerifyCreationResponseEvent,
+        MisbehaviourResponse, QueryMsg as LightClientQuery, Status, UpdateStateResponse,
+        VerifyCreationResponse, VerifyCreationResponseEvent,
     },
     module::{ExecuteMsg as ModuleMsg, IbcUnionMsg},
     msg::{
@@ -20,8 +20,8 @@ use ibc_union_msg::{
         MsgChannelCloseInit, MsgChannelOpenAck, MsgChannelOpenConfirm, MsgChannelOpenInit,
         MsgChannelOpenTry, MsgConnectionOpenAck, MsgConnectionOpenConfirm, MsgConnectionOpenInit,
         MsgConnectionOpenTry, MsgCreateClient, MsgIntentPacketRecv, MsgMigrateState,
-        MsgPacketAcknowledgement, MsgPacketRecv, MsgPacketTimeout, MsgRegisterClient,
-        MsgSendPacket, MsgUpdateClient, MsgWriteAcknowledgement,
+        MsgMisbehaviour, MsgPacketAcknowledgement, MsgPacketRecv, MsgPacketTimeout,
+        MsgRegisterClient, MsgSendPacket, MsgUpdateClient, MsgWriteAcknowledgement,
     },
     query::QueryMsg,
 };
@@ -55,6 +55,7 @@ pub mod events {
         pub const REGISTER: &str = "register_client";
         pub const CREATE: &str = "create_client";
         pub const UPDATE: &str = "update_client";
+        pub const MISBEHAVIOUR: &str = "client_misbehaviour";
     }
     pub mod connection {
         pub const OPEN_INIT: &str = "connection_open_init";
@@ -208,6 +209,21 @@ pub fn execute(
                 relayer,
             )
         }
+        ExecuteMsg::Misbehaviour(MsgMisbehaviour {
+            client_id,
+            client_message,
+            relayer,
+        }) => {
+            ensure_relayer(deps.storage, &info.sender)?;
+            let relayer = deps.api.addr_validate(&relayer)?;
+            misbehaviour(
+                deps.branch(),
+                info,
+                client_id,
+                client_message.to_vec(),
+                relayer,
+            )
+        }
         ExecuteMsg::ConnectionOpenInit(MsgConnectionOpenInit {
             client_id,
             counterparty_client_id,
@@ -787,6 +803,7 @@ fn timeout_packet(
     let commitment_key = BatchReceiptsPath::from_packets(&[packet.clone()]).key();
 
     let client_impl = client_impl(deps.as_ref(), connection.client_id)?;
+    ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
     query_light_client::<()>(
         deps.as_ref(),
         client_impl,
@@ -849,6 +866,7 @@ fn acknowledge_packet(
     let commitment_value = commit_acks(&acknowledgements);
 
     let client_impl = client_impl(deps.as_ref(), connection.client_id)?;
+    ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
     query_light_client::<()>(
         deps.as_ref(),
         client_impl,
@@ -1022,15 +1040,7 @@ fn update_client(
 ) -> Result<Response, ContractError> {
     let client_impl = client_impl(deps.as_ref(), client_id)?;
     let update = {
-        let status = query_light_client::<Status>(
-            deps.as_ref(),
-            client_impl.clone(),
-            LightClientQuery::GetStatus { client_id },
-        )?;
-
-        if !matches!(status, Status::Active) {
-            return Err(ContractError::ClientNotActive { client_id, status });
-        }
+        ensure_client_active(deps.as_ref(), client_impl.clone(), client_id)?;
 
         // Ugly hack to allow for >64K messages (not configurable) to be threaded for the query.
         // See https://github.com/CosmWasm/cosmwasm/blob/e17ecc44cdebc84de1caae648c7a4f4b56846f8f/packages/vm/src/imports.rs#L47
@@ -1093,6 +1103,42 @@ fn update_client(
     )
 }
 
+fn misbehaviour(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    client_id: ClientId,
+    client_message: Vec<u8>,
+    relayer: Addr,
+) -> Result<Response, ContractError> {
+    let client_impl = client_impl(deps.as_ref(), client_id)?;
+
+    ensure_client_active(deps.as_ref(), client_impl.clone(), client_id)?;
+
+    let response = query_light_client::<MisbehaviourResponse>(
+        deps.as_ref(),
+        client_impl,
+        LightClientQuery::Misbehaviour {
+            caller: info.sender.into(),
+            client_id,
+            message: client_message.into(),
+            relayer: relayer.into(),
+        },
+    )?;
+
+    store_commit(
+        deps.branch(),
+        &ClientStatePath { client_id }.key(),
+        &commit(&response.client_state),
+    );
+    deps.storage
+        .write::<ClientStates>(&client_id, &response.client_state.into_vec().into());
+
+    Ok(Response::new().add_event(
+        Event::new(events::client::MISBEHAVIOUR)
+            .add_attributes([(events::attribute::CLIENT_ID, client_id.to_string())]),
+    ))
+}
+
 fn connection_open_init(
     mut deps: DepsMut,
     client_id: ClientId,
@@ -1144,6 +1190,7 @@ fn connection_open_try(
     };
     if verify {
         let client_impl = client_impl(deps.as_ref(), client_id)?;
+        ensure_client_active(deps.as_ref(), client_impl.clone(), client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -1203,6 +1250,7 @@ fn connection_open_ack(
             counterparty_connection_id: Some(connection_id),
         };
         let client_impl = client_impl(deps.as_ref(), connection.client_id)?;
+        ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -1266,6 +1314,7 @@ fn connection_open_confirm(
     };
     if verify {
         let client_impl = client_impl(deps.as_ref(), connection.client_id)?;
+        ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -1388,6 +1437,7 @@ fn channel_open_try(
         .counterparty_channel_id
         .ok_or(ContractError::CounterpartyChannelIdInvalid)?;
     if verify {
+        ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -1478,6 +1528,7 @@ fn channel_open_ack(
     };
     let client_impl = client_impl(deps.as_ref(), connection.client_id)?;
     if verify {
+        ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -1558,6 +1609,7 @@ fn channel_open_confirm(
         .counterparty_channel_id
         .expect("channel state is try open; qed;");
     if verify {
+        ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -1681,6 +1733,7 @@ fn channel_close_confirm(
     let counterparty_channel_id = channel
         .counterparty_channel_id
         .expect("channel is open; qed;");
+    ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
     query_light_client::<()>(
         deps.as_ref(),
         client_impl,
@@ -1748,6 +1801,7 @@ fn process_receive(
     if !intent {
         let proof_commitment_key = BatchPacketsPath::from_packets(&packets).key();
         let client_impl = client_impl(deps.as_ref(), connection.client_id)?;
+        ensure_client_active(deps.as_ref(), client_impl.clone(), connection.client_id)?;
         query_light_client::<()>(
             deps.as_ref(),
             client_impl,
@@ -2171,6 +2225,15 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
             )?;
             Ok(to_json_binary(&status)?)
         }
+        QueryMsg::GetExpiry { client_id } => {
+            let client_impl = client_impl(deps, client_id)?;
+            let expiry = query_light_client::<Option<Timestamp>>(
+                deps,
+                client_impl,
+                LightClientQuery::GetExpiry { client_id },
+            )?;
+            Ok(to_json_binary(&expiry)?)
+        }
         QueryMsg::GetChannels { contract } => {
             let contract = deps.api.addr_validate(&contract)?;
             let channels = deps.storage.read::<ContractChannels>(&contract)?;
@@ -2209,6 +2272,23 @@ fn query_light_client<T: DeserializeOwned>(
         })
 }
 
+/// Ensure that the client is [`Status::Active`]. Frozen and expired clients must not be updated or
+/// used to verify proofs.
+fn ensure_client_active(
+    deps: Deps,
+    client_impl: Addr,
+    client_id: ClientId,
+) -> Result<(), ContractError> {
+    let status =
+        query_light_client::<Status>(deps, client_impl, LightClientQuery::GetStatus { client_id })?;
+
+    if !matches!(status, Status::Active) {
+        return Err(ContractError::ClientNotActive { client_id, status });
+    }
+
+    Ok(())
+}
+
 fn make_verify_creation_event(client_id: ClientId, event: VerifyCreationResponseEvent) -> Event {
     match event {
         VerifyCreationResponseEvent::CreateLensClient {
@@ -39,6 +39,7 @@ fn channel_open_init_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -84,6 +85,7 @@ fn channel_open_init_channel_claimed() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -120,6 +122,7 @@ fn channel_open_init_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -162,6 +165,7 @@ fn channel_open_try_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -214,6 +218,7 @@ fn channel_open_try_invalid_state() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -275,6 +280,7 @@ fn channel_open_try_channel_claimed() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -332,6 +338,7 @@ fn channel_open_try_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -395,6 +402,7 @@ fn channel_open_ack_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -457,6 +465,7 @@ fn channel_open_ack_not_found() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -508,6 +517,7 @@ fn channel_open_ack_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -581,6 +591,7 @@ fn channel_open_confirm_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -647,6 +658,7 @@ fn channel_open_try_invalid_counterparty() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -701,6 +713,7 @@ fn channel_open_confirm_not_found() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -750,6 +763,7 @@ fn channel_open_confirm_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
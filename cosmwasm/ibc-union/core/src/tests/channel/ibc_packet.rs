@@ -33,6 +33,7 @@ fn send_packet_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -82,6 +83,7 @@ fn send_packet_missing_timeout() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -131,6 +133,7 @@ fn send_packet_channel_does_not_exist() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -185,6 +188,7 @@ fn send_packet_module_is_not_channel_owner() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -234,6 +238,7 @@ fn recv_packet_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -270,6 +275,78 @@ fn recv_packet_ok() {
     .is_ok())
 }
 
+#[test]
+fn recv_packet_client_expired() {
+    let mut deps = mock_dependencies();
+    init(
+        deps.as_mut(),
+        InitMsg {
+            relayers_admin: None,
+            relayers: vec![mock_addr(SENDER).to_string()],
+        },
+    )
+    .expect("init is ok");
+    deps.querier
+        .update_wasm(wasm_query_handler(|msg| match msg {
+            LightClientQueryMsg::VerifyCreation { .. } => to_json_binary(&VerifyCreationResponse {
+                counterparty_chain_id: "testchain".to_owned(),
+                events: vec![],
+                storage_writes: Default::default(),
+                client_state_bytes: None,
+            }),
+            LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
+            LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
+            msg => panic!("should not be called: {:?}", msg),
+        }));
+
+    // Create client
+    register_client(deps.as_mut()).expect("register client ok");
+    create_client(deps.as_mut()).expect("create client ok");
+    // Create connection
+    connection_open_try(deps.as_mut()).expect("connection open try is ok");
+    connection_open_confirm(deps.as_mut()).expect("connection open confirm is ok");
+    // Create channel
+    channel_open_init(deps.as_mut()).expect("channel open init is ok");
+    channel_open_ack(deps.as_mut()).expect("channel open ack is ok");
+
+    // The client expires after the channel is open
+    deps.querier
+        .update_wasm(wasm_query_handler(|msg| match msg {
+            LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
+            LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Expired),
+            msg => panic!("should not be called: {:?}", msg),
+        }));
+
+    let msg = MsgPacketRecv {
+        packets: vec![Packet {
+            source_channel_id: ChannelId!(2),
+            destination_channel_id: ChannelId!(1),
+            data: vec![1, 2, 3].into(),
+            timeout_height: 0,
+            timeout_timestamp: Timestamp::from_nanos(2000000000000000000),
+        }],
+        relayer_msgs: vec![vec![1].into()],
+        relayer: mock_addr(RELAYER).to_string(),
+        proof: vec![1, 2, 3].into(),
+        proof_height: 1,
+    };
+
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&mock_addr(SENDER), &[]),
+            ExecuteMsg::PacketRecv(msg),
+        ),
+        Err(ContractError::ClientNotActive {
+            client_id: ClientId!(1),
+            status: Status::Expired
+        })
+    )
+}
+
 #[test]
 fn recv_packet_invalid_channel_state() {
     let mut deps = mock_dependencies();
@@ -291,6 +368,7 @@ fn recv_packet_invalid_channel_state() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -360,6 +438,7 @@ fn recv_packet_timeout_timestamp() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -417,6 +496,7 @@ fn recv_packet_timeout_height() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -474,6 +554,7 @@ fn recv_intent_packet_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -530,6 +611,7 @@ fn recv_intent_packet_timeout_timestamp() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -586,6 +668,7 @@ fn recv_intent_packet_timeout_height() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -642,6 +725,7 @@ fn acknowledge_packet_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -743,6 +827,7 @@ fn acknowledge_packet_tampered() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -844,6 +929,7 @@ fn acknowledge_packet_not_sent() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -938,6 +1024,7 @@ fn timeout_packet_timestamp_ok() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1014,6 +1101,7 @@ fn timeout_packet_timestamp_timestamp_not_reached() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&128),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1090,6 +1178,7 @@ fn write_acknowledgement_ok() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1200,6 +1289,7 @@ fn write_acknowledgement_module_is_not_channel_owner() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1305,6 +1395,7 @@ fn write_acknowledgement_packet_not_received() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1394,6 +1485,7 @@ fn write_acknowledgement_already_exists() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1520,6 +1612,7 @@ fn batch_send_ok() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1639,6 +1732,7 @@ fn batch_send_packet_not_sent() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1734,6 +1828,7 @@ fn batch_acks_ok() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1902,6 +1997,7 @@ fn batch_acks_packet_not_received() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
@@ -1999,6 +2095,7 @@ fn batch_acks_tampered_packet() {
             LightClientQueryMsg::VerifyNonMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetTimestamp { .. } => to_json_binary(&100000),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
 
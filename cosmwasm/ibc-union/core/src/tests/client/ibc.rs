@@ -1,16 +1,22 @@
 use cosmwasm_std::{
+    from_json,
     testing::{message_info, mock_dependencies, mock_env},
     to_json_binary, Addr, Event,
 };
 use depolama::StorageExt;
 use ibc_union_msg::{
-    lightclient::{QueryMsg as LightClientQueryMsg, UpdateStateResponse, VerifyCreationResponse},
-    msg::{ExecuteMsg, InitMsg, MsgUpdateClient},
+    lightclient::{
+        MisbehaviourResponse, QueryMsg as LightClientQueryMsg, UpdateStateResponse,
+        VerifyCreationResponse,
+    },
+    msg::{ExecuteMsg, InitMsg, MsgMisbehaviour, MsgUpdateClient},
+    query::QueryMsg,
 };
+use ibc_union_spec::Timestamp;
 
 use super::*;
 use crate::{
-    contract::{events, execute, init},
+    contract::{events, execute, init, query},
     state::{ClientConsensusStates, ClientImpls, ClientRegistry, ClientStates, ClientTypes},
     ContractError,
 };
@@ -342,3 +348,149 @@ fn update_client_commitments_saved() {
         vec![3, 2, 1]
     );
 }
+
+#[test]
+fn misbehaviour_freezes_client() {
+    let mut deps = mock_dependencies();
+
+    init(
+        deps.as_mut(),
+        InitMsg {
+            relayers_admin: None,
+            relayers: vec![mock_addr(SENDER).to_string()],
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_wasm(wasm_query_handler(|msg| match msg {
+            LightClientQueryMsg::VerifyCreation { .. } => to_json_binary(&VerifyCreationResponse {
+                counterparty_chain_id: "testchain".to_owned(),
+                events: vec![],
+                storage_writes: Default::default(),
+                client_state_bytes: None,
+            }),
+            LightClientQueryMsg::Misbehaviour { .. } => to_json_binary(&MisbehaviourResponse {
+                client_state: vec![4, 5, 6].into(),
+            }),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
+            LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            msg => panic!("should not be called: {:?}", msg),
+        }));
+
+    register_client(deps.as_mut()).expect("register client ok");
+    create_client(deps.as_mut()).expect("create client ok");
+
+    let msg = ExecuteMsg::Misbehaviour(MsgMisbehaviour {
+        client_id: ClientId!(1),
+        client_message: vec![3, 2, 1].into(),
+        relayer: mock_addr(RELAYER).into_string(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        message_info(&mock_addr(SENDER), &[]),
+        msg,
+    )
+    .expect("misbehaviour ok");
+
+    assert!(res
+        .events
+        .iter()
+        .any(|event| event.ty == events::client::MISBEHAVIOUR));
+    assert_eq!(
+        deps.storage.read::<ClientStates>(&ClientId!(1)).unwrap(),
+        vec![4, 5, 6]
+    );
+}
+
+#[test]
+fn misbehaviour_fails_when_client_not_active() {
+    let mut deps = mock_dependencies();
+
+    init(
+        deps.as_mut(),
+        InitMsg {
+            relayers_admin: None,
+            relayers: vec![mock_addr(SENDER).to_string()],
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_wasm(wasm_query_handler(|msg| match msg {
+            LightClientQueryMsg::VerifyCreation { .. } => to_json_binary(&VerifyCreationResponse {
+                counterparty_chain_id: "testchain".to_owned(),
+                events: vec![],
+                storage_writes: Default::default(),
+                client_state_bytes: None,
+            }),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Frozen),
+            LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            msg => panic!("should not be called: {:?}", msg),
+        }));
+
+    register_client(deps.as_mut()).expect("register client ok");
+    create_client(deps.as_mut()).expect("create client ok");
+
+    let msg = ExecuteMsg::Misbehaviour(MsgMisbehaviour {
+        client_id: ClientId!(1),
+        client_message: vec![3, 2, 1].into(),
+        relayer: mock_addr(RELAYER).into_string(),
+    });
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&mock_addr(SENDER), &[]),
+            msg,
+        ),
+        Err(ContractError::ClientNotActive {
+            client_id: ClientId!(1),
+            status: Status::Frozen
+        })
+    );
+}
+
+#[test]
+fn get_expiry_ok() {
+    let mut deps = mock_dependencies();
+
+    init(
+        deps.as_mut(),
+        InitMsg {
+            relayers_admin: None,
+            relayers: vec![mock_addr(SENDER).to_string()],
+        },
+    )
+    .unwrap();
+    deps.querier
+        .update_wasm(wasm_query_handler(|msg| match msg {
+            LightClientQueryMsg::VerifyCreation { .. } => to_json_binary(&VerifyCreationResponse {
+                counterparty_chain_id: "testchain".to_owned(),
+                events: vec![],
+                storage_writes: Default::default(),
+                client_state_bytes: None,
+            }),
+            LightClientQueryMsg::GetExpiry { .. } => {
+                to_json_binary(&Some(Timestamp::from_nanos(1000)))
+            }
+            LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            msg => panic!("should not be called: {:?}", msg),
+        }));
+
+    register_client(deps.as_mut()).expect("register client ok");
+    create_client(deps.as_mut()).expect("create client ok");
+
+    let expiry = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::GetExpiry {
+            client_id: ClientId!(1),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        from_json::<Option<Timestamp>>(expiry).unwrap(),
+        Some(Timestamp::from_nanos(1000))
+    );
+}
@@ -108,6 +108,7 @@ fn connection_open_try_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -151,6 +152,7 @@ fn connection_open_try_client_not_found() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -202,6 +204,7 @@ fn connection_open_try_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -255,6 +258,7 @@ fn connection_open_ack_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -303,6 +307,7 @@ fn connection_open_ack_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -356,6 +361,7 @@ fn connection_open_confirm_ok() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -403,6 +409,7 @@ fn connection_open_try_confirm_commitment_saved() {
             }),
             LightClientQueryMsg::VerifyMembership { .. } => to_json_binary(&()),
             LightClientQueryMsg::GetLatestHeight { .. } => to_json_binary(&1),
+            LightClientQueryMsg::GetStatus { .. } => to_json_binary(&Status::Active),
             msg => panic!("should not be called: {:?}", msg),
         }));
     register_client(deps.as_mut()).expect("register client ok");
@@ -1,4 +1,4 @@
-use arbitrum_light_client_types::{ClientState, ConsensusState, Header};
+use arbitrum_light_client_types::{ClientState, ConsensusState, Header, Misbehaviour};
 use cosmwasm_std::{Addr, Empty};
 use ethereum_light_client::client::EthereumLightClient;
 use ethereum_light_client_types::StorageProof;
@@ -6,7 +6,7 @@ use ibc_union_light_client::{
     spec::Timestamp, ClientCreationResult, IbcClient, IbcClientCtx, IbcClientError, StateUpdate,
 };
 use ibc_union_msg::lightclient::Status;
-use unionlabs::encoding::Bincode;
+use unionlabs::{encoding::Bincode, ibc::core::client::height::Height};
 
 use crate::errors::Error;
 
@@ -19,7 +19,7 @@ impl IbcClient for ArbitrumLightClient {
 
     type Header = Header;
 
-    type Misbehaviour = Header;
+    type Misbehaviour = Misbehaviour;
 
     type ClientState = ClientState;
 
@@ -106,12 +106,37 @@ impl IbcClient for ArbitrumLightClient {
     }
 
     fn misbehaviour(
-        _ctx: IbcClientCtx<Self>,
+        ctx: IbcClientCtx<Self>,
         _caller: Addr,
-        _misbehaviour: Self::Misbehaviour,
+        misbehaviour: Self::Misbehaviour,
         _relayer: Addr,
     ) -> Result<Self::ClientState, IbcClientError<Self>> {
-        Err(Error::Unimplemented.into())
+        let ClientState::V1(mut client_state) = ctx.read_self_client_state()?;
+
+        let l1_consensus_state_a = ctx
+            .read_consensus_state::<EthereumLightClient>(
+                client_state.l1_client_id,
+                misbehaviour.header_a.l1_height.height(),
+            )
+            .map_err(Into::<Error>::into)?;
+
+        let l1_consensus_state_b = ctx
+            .read_consensus_state::<EthereumLightClient>(
+                client_state.l1_client_id,
+                misbehaviour.header_b.l1_height.height(),
+            )
+            .map_err(Into::<Error>::into)?;
+
+        arbitrum_verifier::verify_misbehaviour_v1(
+            &client_state,
+            (&misbehaviour.header_a, l1_consensus_state_a.state_root),
+            (&misbehaviour.header_b, l1_consensus_state_b.state_root),
+        )
+        .map_err(Error::HeaderVerify)?;
+
+        client_state.frozen_height = Height::new(1);
+
+        Ok(ClientState::V1(client_state))
     }
 
     fn status(
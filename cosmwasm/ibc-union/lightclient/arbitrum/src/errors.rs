@@ -31,9 +31,6 @@ pub enum Error {
     #[error("failed to verify arbitrum header: {0}")]
     HeaderVerify(#[from] arbitrum_verifier::Error),
 
-    #[error("the operation has not been implemented yet")]
-    Unimplemented,
-
     #[error(transparent)]
     EvmIbcClient(#[from] IbcClientError<EthereumLightClient>),
 }
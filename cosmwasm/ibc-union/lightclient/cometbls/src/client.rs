@@ -132,6 +132,20 @@ impl<T: ZkpVerifier> IbcClient for CometblsLightClient<T> {
         }
     }
 
+    fn expiry(
+        ctx: IbcClientCtx<Self>,
+        client_state: &Self::ClientState,
+    ) -> Result<Option<Timestamp>, IbcClientError<Self>> {
+        let consensus_state = ctx.read_self_consensus_state(client_state.latest_height.height())?;
+
+        Ok(Some(
+            consensus_state
+                .timestamp
+                .plus_duration(client_state.trusting_period)
+                .ok_or(Error::MathOverflow)?,
+        ))
+    }
+
     fn verify_creation(
         _caller: Addr,
         _client_state: &Self::ClientState,
@@ -18,6 +18,7 @@ use ethereum_sync_protocol::{
 use evm_storage_verifier::{
     verify_account_storage_root, verify_storage_absence, verify_storage_proof,
 };
+use fork_schedules::ForkParameters;
 use ibc_union_light_client::{
     spec::Timestamp, ClientCreationResult, IbcClient, IbcClientCtx, IbcClientError, StateUpdate,
 };
@@ -88,26 +89,17 @@ impl IbcClient for EthereumLightClient {
     }
 
     fn get_latest_height(client_state: &Self::ClientState) -> u64 {
-        match client_state {
-            ClientState::V1(cs) => cs.latest_height,
-        }
+        client_state.latest_height()
     }
 
     fn get_counterparty_chain_id(client_state: &Self::ClientState) -> String {
-        match client_state {
-            ClientState::V1(cs) => cs.chain_id.to_string(),
-        }
+        client_state.chain_id().to_string()
     }
 
     fn status(ctx: IbcClientCtx<Self>, client_state: &Self::ClientState) -> Status {
         let _ = ctx;
 
-        if match client_state {
-            ClientState::V1(cs) => cs.frozen_height,
-        }
-        .height()
-            != 0
-        {
+        if client_state.frozen_height().height() != 0 {
             Status::Frozen
         } else {
             Status::Active
@@ -120,11 +112,10 @@ impl IbcClient for EthereumLightClient {
         consensus_state: &Self::ConsensusState,
         _relayer: Addr,
     ) -> Result<ClientCreationResult<Self>, IbcClientError<Self>> {
-        let ClientState::V1(client_state) = client_state;
-        let Some(initial_sync_committee) = client_state.initial_sync_committee.as_ref() else {
+        let (mut client_state, fork_parameters) = client_state.clone().into_parts();
+        let Some(initial_sync_committee) = client_state.initial_sync_committee.clone() else {
             return Err(Error::NoInitialSyncCommittee.into());
         };
-        let mut client_state = client_state.clone();
         // We only require this at the creation phase. The client then manages the committees in a separate storage.
         client_state.initial_sync_committee = None;
 
@@ -137,7 +128,7 @@ impl IbcClient for EthereumLightClient {
         // Set the client state so that it overwrites the one that is passed.
         // Also save the current and next sync committees with the corresponding epoch numbers.
         Ok(ClientCreationResult::new()
-            .overwrite_client_state(ClientState::V1(client_state))
+            .overwrite_client_state(ClientState::from_parts(client_state, fork_parameters))
             .add_storage_write::<SyncCommitteeStore>(
                 current_sync_period,
                 InverseSyncCommittee::take_inverse(&initial_sync_committee.current_sync_committee),
@@ -154,7 +145,7 @@ impl IbcClient for EthereumLightClient {
         header: Header,
         _relayer: Addr,
     ) -> Result<StateUpdate<Self>, IbcClientError<Self>> {
-        let ClientState::V1(client_state) = ctx.read_self_client_state()?;
+        let (client_state, fork_parameters) = ctx.read_self_client_state()?.into_parts();
         let consensus_state = ctx.read_self_consensus_state(header.trusted_height.height())?;
 
         let finalized_slot = header
@@ -168,6 +159,7 @@ impl IbcClient for EthereumLightClient {
             PresetBaseKind::Minimal => verify_header::<Minimal>(
                 &ctx,
                 client_state,
+                fork_parameters,
                 consensus_state,
                 ctx.read_self_storage::<SyncCommitteeStore>(
                     compute_sync_committee_period_at_slot::<Minimal>(finalized_slot),
@@ -177,6 +169,7 @@ impl IbcClient for EthereumLightClient {
             PresetBaseKind::Mainnet => verify_header::<Mainnet>(
                 &ctx,
                 client_state,
+                fork_parameters,
                 consensus_state,
                 ctx.read_self_storage::<SyncCommitteeStore>(
                     compute_sync_committee_period_at_slot::<Mainnet>(finalized_slot),
@@ -195,20 +188,28 @@ impl IbcClient for EthereumLightClient {
         let consensus_state =
             ctx.read_self_consensus_state(misbehaviour.trusted_height.height())?;
 
-        let ClientState::V1(mut client_state) = ctx.read_self_client_state()?;
+        let (mut client_state, fork_parameters) = ctx.read_self_client_state()?.into_parts();
 
         match client_state.chain_spec {
-            PresetBaseKind::Minimal => {
-                verify_misbehaviour::<Minimal>(&ctx, &client_state, consensus_state, misbehaviour)?
-            }
-            PresetBaseKind::Mainnet => {
-                verify_misbehaviour::<Mainnet>(&ctx, &client_state, consensus_state, misbehaviour)?
-            }
+            PresetBaseKind::Minimal => verify_misbehaviour::<Minimal>(
+                &ctx,
+                &client_state,
+                fork_parameters.as_ref(),
+                consensus_state,
+                misbehaviour,
+            )?,
+            PresetBaseKind::Mainnet => verify_misbehaviour::<Mainnet>(
+                &ctx,
+                &client_state,
+                fork_parameters.as_ref(),
+                consensus_state,
+                misbehaviour,
+            )?,
         }
 
         client_state.frozen_height = Height::new(1);
 
-        Ok(ClientState::V1(client_state))
+        Ok(ClientState::from_parts(client_state, fork_parameters))
     }
 }
 
@@ -279,6 +280,7 @@ pub fn check_commitment_key(path: H256, key: U256) -> Result<(), Error> {
 pub fn verify_header<C: ChainSpec>(
     ctx: &IbcClientCtx<EthereumLightClient>,
     client_state: ClientStateV1,
+    fork_parameters: Option<ForkParameters>,
     consensus_state: ConsensusState,
     sync_committee: InverseSyncCommittee,
     header: Header,
@@ -296,7 +298,9 @@ pub fn verify_header<C: ChainSpec>(
     };
 
     validate_light_client_update::<C, _>(
-        client_state.chain_id,
+        &fork_parameters
+            .clone()
+            .unwrap_or_else(|| client_state.fork_parameters()),
         &header.consensus_update.clone().into_light_client_update(),
         current_sync_committee,
         next_sync_committee,
@@ -325,11 +329,12 @@ pub fn verify_header<C: ChainSpec>(
     )
     .map_err(Error::VerifyAccountStorageRoot)?;
 
-    update_state::<C>(client_state, consensus_state, header)
+    update_state::<C>(client_state, fork_parameters, consensus_state, header)
 }
 
 fn update_state<C: ChainSpec>(
     mut client_state: ClientStateV1,
+    fork_parameters: Option<ForkParameters>,
     mut consensus_state: ConsensusState,
     mut header: Header,
 ) -> Result<StateUpdate<EthereumLightClient>, IbcClientError<EthereumLightClient>> {
@@ -360,7 +365,8 @@ fn update_state<C: ChainSpec>(
 
     let mut state_update = StateUpdate::new(updated_height, consensus_state);
     if client_state.latest_height == consensus_update.finalized_header.execution.block_number {
-        state_update = state_update.overwrite_client_state(ClientState::V1(client_state));
+        state_update = state_update
+            .overwrite_client_state(ClientState::from_parts(client_state, fork_parameters));
     }
 
     if let LightClientUpdate::SyncCommitteePeriodChange(update) = &mut header.consensus_update {
@@ -379,9 +385,14 @@ fn update_state<C: ChainSpec>(
 pub fn verify_misbehaviour<C: ChainSpec>(
     ctx: &IbcClientCtx<EthereumLightClient>,
     client_state: &ClientStateV1,
+    fork_parameters: Option<&ForkParameters>,
     consensus_state: ConsensusState,
     misbehaviour: Misbehaviour,
 ) -> Result<(), IbcClientError<EthereumLightClient>> {
+    let fork_parameters = fork_parameters
+        .cloned()
+        .unwrap_or_else(|| client_state.fork_parameters());
+
     // There is no point to check for misbehaviour when the headers are not for the same height
     let (slot_1, slot_2) = (
         misbehaviour
@@ -424,7 +435,7 @@ pub fn verify_misbehaviour<C: ChainSpec>(
 
     // Make sure both headers would have been accepted by the light client
     validate_light_client_update::<C, VerificationContext>(
-        client_state.chain_id,
+        &fork_parameters,
         &misbehaviour.update_1.clone().into_light_client_update(),
         current_sync_committee,
         next_sync_committee,
@@ -448,7 +459,7 @@ pub fn verify_misbehaviour<C: ChainSpec>(
     )?;
 
     validate_light_client_update::<C, VerificationContext>(
-        client_state.chain_id,
+        &fork_parameters,
         &misbehaviour.update_1.into_light_client_update(),
         current_sync_committee,
         next_sync_committee,
@@ -669,6 +680,7 @@ mod tests {
                 env,
             },
             client_state.clone(),
+            None,
             consensus_state,
             InverseSyncCommittee::take_inverse(&CURRENT_SYNC_COMMITTEE),
             Header {
@@ -755,6 +767,7 @@ mod tests {
                 env,
             },
             client_state.clone(),
+            None,
             consensus_state,
             InverseSyncCommittee::take_inverse(&NEXT_SYNC_COMMITTEE),
             Header {
@@ -134,7 +134,7 @@ mod tests {
 
         assert!(
             ethereum_sync_protocol::verify_signature::<Mainnet, VerificationContext>(
-                11155111,
+                &fork_schedules::SEPOLIA.into(),
                 &update,
                 update.sync_aggregate.clone().try_into().unwrap(),
                 H256::new(hex_literal::hex!(
@@ -150,19 +150,6 @@ impl IbcClient for EthermintLightClient {
     }
 
     fn status(ctx: IbcClientCtx<Self>, client_state: &Self::ClientState) -> Status {
-        let _ = ctx;
-
-        // FIXME: read latest consensus to verify if client expired
-        // if is_client_expired(
-        //     &consensus_state.timestamp,
-        //     client_state.trusting_period,
-        //     env.block
-        //         .time
-        //         .try_into()
-        //         .map_err(|_| Error::from(InvalidHostTimestamp(env.block.time)))?,
-        // ) {
-        //     return Ok(Status::Expired);
-        // }
         if client_state
             .tendermint_client_state
             .frozen_height
@@ -170,10 +157,33 @@ impl IbcClient for EthermintLightClient {
             .height()
             != 0
         {
-            Status::Frozen
-        } else {
-            Status::Active
+            return Status::Frozen;
         }
+
+        let current_block_time = Timestamp::from_nanos(ctx.env.block.time.nanos());
+
+        match Self::expiry(ctx, client_state) {
+            Ok(Some(expiry)) if expiry >= current_block_time => Status::Active,
+            // the latest consensus state is either missing or past the trusting period
+            _ => Status::Expired,
+        }
+    }
+
+    fn expiry(
+        ctx: IbcClientCtx<Self>,
+        client_state: &Self::ClientState,
+    ) -> Result<Option<Timestamp>, IbcClientError<Self>> {
+        let consensus_state = ctx.read_self_consensus_state(
+            client_state.tendermint_client_state.latest_height.height(),
+        )?;
+
+        Ok(Some(
+            tendermint_light_client::client::client_expiry(
+                &consensus_state.timestamp,
+                client_state.tendermint_client_state.trusting_period,
+            )
+            .map_err(Error::from)?,
+        ))
     }
 
     fn get_timestamp(consensus_state: &Self::ConsensusState) -> Timestamp {
@@ -4,7 +4,7 @@ use ibc_union_light_client::{
     spec::Timestamp, ClientCreationResult, IbcClient, IbcClientCtx, IbcClientError, StateUpdate,
 };
 use ibc_union_msg::lightclient::Status;
-use parlia_light_client_types::{ClientState, ConsensusState, Header};
+use parlia_light_client_types::{ClientState, ConsensusState, Header, Misbehaviour};
 use parlia_types::Valset;
 use parlia_verifier::VerificationContext;
 use unionlabs::{
@@ -21,7 +21,7 @@ impl IbcClient for ParliaLightClient {
 
     type Header = Header;
 
-    type Misbehaviour = ();
+    type Misbehaviour = Misbehaviour;
 
     type ClientState = ClientState;
 
@@ -168,12 +168,28 @@ impl IbcClient for ParliaLightClient {
     }
 
     fn misbehaviour(
-        _ctx: IbcClientCtx<Self>,
+        ctx: IbcClientCtx<Self>,
         _caller: Addr,
-        _misbehaviour: Self::Misbehaviour,
+        misbehaviour: Self::Misbehaviour,
         _relayer: Addr,
     ) -> Result<Self::ClientState, IbcClientError<Self>> {
-        Err(Error::Unimplemented.into())
+        let ClientState::V1(mut client_state) = ctx.read_self_client_state()?;
+
+        let Misbehaviour { header_a, header_b } = misbehaviour;
+
+        // verify that both attestations are signed by the trusted valsets and that they conflict
+        parlia_verifier::verify_misbehaviour(
+            (&header_a.source, &header_a.target, &header_a.attestation),
+            header_a.trusted_valset_epoch_number,
+            (&header_b.source, &header_b.target, &header_b.attestation),
+            header_b.trusted_valset_epoch_number,
+            CwContext { ctx },
+        )
+        .map_err(Into::<Error>::into)?;
+
+        client_state.frozen_height = 1;
+
+        Ok(ClientState::V1(client_state))
     }
 }
 
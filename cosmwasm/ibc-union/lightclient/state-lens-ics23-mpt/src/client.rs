@@ -76,32 +76,33 @@ impl IbcClient for StateLensIcs23MptLightClient {
     }
 
     fn status(ctx: IbcClientCtx<Self>, client_state: &Self::ClientState) -> Status {
-        let _ = ctx;
-        let _ = client_state;
-
-        // FIXME: expose the ctx to this call to allow threading this call to L1
-        // client. generally, we want to thread if a client is an L2 so always
-        // provide the ctx?
-        // let client_state: WasmClientState = read_client_state(deps)?;
-        // let l1_client_state = query_client_state::<WasmL1ClientState>(
-        //     deps,
-        //     env,
-        //     client_state.data.l1_client_id.clone(),
-        // )
-        // .map_err(Error::CustomQuery)?;
-
-        // if l1_client_state.data.frozen_height != Height::default() {
-        //     return Ok(Status::Frozen);
-        // }
-
-        // let Some(_) = read_consensus_state::<Self>(deps, &client_state.latest_height)? else {
-        //     return Ok(Status::Expired);
-        // };
-
-        // Ok(Status::Active)
+        // the state lens client verifies the L2 consensus through the L1 client, and as such it can
+        // only be as live as the L1 client
+        match ctx.status::<CometblsLightClient>(client_state.l1_client_id) {
+            Ok(Status::Active) => {}
+            Ok(status) => return status,
+            Err(_) => return Status::Expired,
+        }
+
+        if ctx
+            .read_self_consensus_state(client_state.l2_latest_height)
+            .is_err()
+        {
+            return Status::Expired;
+        }
+
         Status::Active
     }
 
+    fn expiry(
+        ctx: IbcClientCtx<Self>,
+        client_state: &Self::ClientState,
+    ) -> Result<Option<Timestamp>, IbcClientError<Self>> {
+        ctx.expiry::<CometblsLightClient>(client_state.l1_client_id)
+            .map_err(Into::<Error>::into)
+            .map_err(Into::into)
+    }
+
     fn verify_creation(
         _caller: Addr,
         client_state: &Self::ClientState,
@@ -6,7 +6,7 @@ use ibc_union_light_client::{
 use ibc_union_msg::lightclient::Status;
 use sui_light_client_types::{
     client_state::ClientState, committee::Committee, consensus_state::ConsensusState,
-    header::Header, storage_proof::StorageProof, U64,
+    header::Header, misbehaviour::Misbehaviour, storage_proof::StorageProof, U64,
 };
 use unionlabs::encoding::{Bincode, DecodeAs, EncodeAs};
 
@@ -21,7 +21,7 @@ impl IbcClient for SuiLightClient {
 
     type Header = Header;
 
-    type Misbehaviour = Header;
+    type Misbehaviour = Misbehaviour;
 
     type ClientState = ClientState;
 
@@ -81,9 +81,15 @@ impl IbcClient for SuiLightClient {
 
     fn status(
         _ctx: ibc_union_light_client::IbcClientCtx<Self>,
-        _client_state: &Self::ClientState,
+        client_state: &Self::ClientState,
     ) -> Status {
-        Status::Active
+        let ClientState::V1(client_state) = client_state;
+
+        if client_state.frozen_height != 0 {
+            Status::Frozen
+        } else {
+            Status::Active
+        }
     }
 
     fn verify_creation(
@@ -154,12 +160,38 @@ impl IbcClient for SuiLightClient {
     }
 
     fn misbehaviour(
-        _ctx: ibc_union_light_client::IbcClientCtx<Self>,
+        ctx: ibc_union_light_client::IbcClientCtx<Self>,
         _caller: cosmwasm_std::Addr,
-        _misbehaviour: Self::Misbehaviour,
+        misbehaviour: Self::Misbehaviour,
         _relayer: cosmwasm_std::Addr,
     ) -> Result<Self::ClientState, ibc_union_light_client::IbcClientError<Self>> {
-        todo!()
+        let ClientState::V1(mut client_state) = ctx.read_self_client_state()?;
+
+        let Misbehaviour { header_a, header_b } = misbehaviour;
+
+        let committee_a =
+            ctx.read_self_storage::<CommitteeStore>(header_a.checkpoint_summary.epoch)?;
+        let committee_b =
+            ctx.read_self_storage::<CommitteeStore>(header_b.checkpoint_summary.epoch)?;
+
+        sui_verifier::verify_misbehaviour(
+            (
+                &committee_a,
+                &header_a.checkpoint_summary,
+                &header_a.sign_info,
+            ),
+            (
+                &committee_b,
+                &header_b.checkpoint_summary,
+                &header_b.sign_info,
+            ),
+            &Verifier { deps: ctx.deps },
+        )
+        .map_err(Into::<Error>::into)?;
+
+        client_state.frozen_height = 1;
+
+        Ok(ClientState::V1(client_state))
     }
 }
 
@@ -9,7 +9,7 @@ use ibc_union_light_client::{
 use ibc_union_msg::lightclient::Status;
 use ibc_union_spec::path::IBC_UNION_COSMWASM_COMMITMENT_PREFIX;
 use ics23::ibc_api::SDK_SPECS;
-use tendermint_light_client_types::{ClientState, ConsensusState, Header};
+use tendermint_light_client_types::{ClientState, ConsensusState, Header, Misbehaviour};
 use tendermint_verifier::types::{HostFns, SignatureVerifier};
 use unionlabs::{
     bounded::BoundedI64,
@@ -39,8 +39,7 @@ impl IbcClient for TendermintLightClient {
 
     type Header = Header;
 
-    // TODO(aeryz): Change this to appropriate misbehavior type when it is implemented
-    type Misbehaviour = Header;
+    type Misbehaviour = Misbehaviour;
 
     type ClientState = ClientState;
 
@@ -119,33 +118,73 @@ impl IbcClient for TendermintLightClient {
     }
 
     fn misbehaviour(
-        _ctx: IbcClientCtx<Self>,
+        ctx: IbcClientCtx<Self>,
         _caller: Addr,
-        _misbehaviour: Self::Misbehaviour,
+        misbehaviour: Self::Misbehaviour,
         _relayer: Addr,
     ) -> Result<Self::ClientState, IbcClientError<Self>> {
-        Err(Error::Unimplemented.into())
+        let mut client_state = ctx.read_self_client_state()?;
+        let consensus_state_a =
+            ctx.read_self_consensus_state(misbehaviour.header_a.trusted_height.height())?;
+        let consensus_state_b =
+            ctx.read_self_consensus_state(misbehaviour.header_b.trusted_height.height())?;
+
+        match misbehaviour
+            .header_a
+            .validator_set
+            .validators
+            .first()
+            .map(|v| &v.pub_key)
+        {
+            #[cfg(feature = "bls")]
+            Some(PublicKey::Bls12_381(_)) => verify_misbehaviour(
+                &client_state,
+                consensus_state_a,
+                consensus_state_b,
+                misbehaviour,
+                ctx.env.block.time,
+                &SignatureVerifier::new(crate::verifier::bls::Bls12Verifier::new(ctx.deps)),
+            )?,
+            Some(PublicKey::Ed25519(_)) => verify_misbehaviour(
+                &client_state,
+                consensus_state_a,
+                consensus_state_b,
+                misbehaviour,
+                ctx.env.block.time,
+                &SignatureVerifier::new(Ed25519Verifier::new(ctx.deps)),
+            )?,
+            _ => return Err(Error::InvalidValidatorSet.into()),
+        }
+
+        client_state.frozen_height = Some(Height::new(1));
+
+        Ok(client_state)
     }
 
     fn status(ctx: IbcClientCtx<Self>, client_state: &Self::ClientState) -> Status {
-        let _ = ctx;
-
-        // FIXME: read latest consensus to verify if client expired
-        // if is_client_expired(
-        //     &consensus_state.timestamp,
-        //     client_state.trusting_period,
-        //     env.block
-        //         .time
-        //         .try_into()
-        //         .map_err(|_| Error::from(InvalidHostTimestamp(env.block.time)))?,
-        // ) {
-        //     return Ok(Status::Expired);
-        // }
         if client_state.frozen_height.unwrap_or_default().height() != 0 {
-            Status::Frozen
-        } else {
-            Status::Active
+            return Status::Frozen;
         }
+
+        let current_block_time = ibc_union_spec::Timestamp::from_nanos(ctx.env.block.time.nanos());
+
+        match Self::expiry(ctx, client_state) {
+            Ok(Some(expiry)) if expiry >= current_block_time => Status::Active,
+            // the latest consensus state is either missing or past the trusting period
+            _ => Status::Expired,
+        }
+    }
+
+    fn expiry(
+        ctx: IbcClientCtx<Self>,
+        client_state: &Self::ClientState,
+    ) -> Result<Option<ibc_union_spec::Timestamp>, IbcClientError<Self>> {
+        let consensus_state = ctx.read_self_consensus_state(client_state.latest_height.height())?;
+
+        Ok(Some(client_expiry(
+            &consensus_state.timestamp,
+            client_state.trusting_period,
+        )?))
     }
 
     fn get_timestamp(consensus_state: &Self::ConsensusState) -> ibc_union_spec::Timestamp {
@@ -277,6 +316,56 @@ pub fn verify_header<V: HostFns>(
     }
 }
 
+/// Verify that `misbehaviour` proves that the counterparty chain misbehaved.
+///
+/// Both headers must be valid updates from their respective trusted consensus states, and must
+/// either commit to different blocks at the same height, or violate BFT time monotonicity (i.e.
+/// `header_a` is higher than `header_b` but its timestamp is not after `header_b`'s).
+pub fn verify_misbehaviour<V: HostFns>(
+    client_state: &ClientState,
+    consensus_state_a: ConsensusState,
+    consensus_state_b: ConsensusState,
+    misbehaviour: Misbehaviour,
+    block_timestamp: cosmwasm_std::Timestamp,
+    signature_verifier: &SignatureVerifier<V>,
+) -> Result<(), Error> {
+    let header_a = &misbehaviour.header_a.signed_header;
+    let header_b = &misbehaviour.header_b.signed_header;
+
+    if header_a.header.height < header_b.header.height {
+        return Err(Error::InvalidMisbehaviourHeaderSequence);
+    }
+
+    if header_a.header.height == header_b.header.height {
+        // the block hash is checked against the header during verification, so differing block
+        // hashes here means that the validators signed two different blocks at the same height.
+        // the part set header is not compared, as the same block can be gossiped with different
+        // part set headers
+        if header_a.commit.block_id.hash == header_b.commit.block_id.hash {
+            return Err(Error::MisbehaviourNotFound);
+        }
+    } else if header_a.header.time > header_b.header.time {
+        return Err(Error::MisbehaviourNotFound);
+    }
+
+    verify_header(
+        client_state.clone(),
+        consensus_state_a,
+        misbehaviour.header_a,
+        block_timestamp,
+        signature_verifier,
+    )?;
+    verify_header(
+        client_state.clone(),
+        consensus_state_b,
+        misbehaviour.header_b,
+        block_timestamp,
+        signature_verifier,
+    )?;
+
+    Ok(())
+}
+
 pub fn set_total_voting_power(validator_set: &mut ValidatorSet) -> Result<(), MathOverflow> {
     validator_set.total_voting_power =
         validator_set
@@ -321,6 +410,25 @@ pub fn construct_partial_header(
     }
 }
 
+/// The time at which a client with the given `trusting_period` expires, if the timestamp of its
+/// latest consensus state is `consensus_state_timestamp`.
+pub fn client_expiry(
+    consensus_state_timestamp: &Timestamp,
+    trusting_period: Duration,
+) -> Result<ibc_union_spec::Timestamp, Error> {
+    let expiry = consensus_state_timestamp
+        .checked_add(trusting_period)
+        .ok_or(MathOverflow)?;
+
+    // the expiry may be within the bounds of a protobuf timestamp, but not representable as unix
+    // nanos in a u64
+    u64::try_from(
+        i128::from(expiry.seconds.inner()) * 1_000_000_000 + i128::from(expiry.nanos.inner()),
+    )
+    .map(ibc_union_spec::Timestamp::from_nanos)
+    .map_err(|_| MathOverflow.into())
+}
+
 pub fn is_client_expired(
     consensus_state_timestamp: &Timestamp,
     trusting_period: Duration,
@@ -414,6 +522,221 @@ pub fn verify_non_membership(
     .map_err(Error::VerifyMembership)
 }
 
+#[cfg(test)]
+mod tests {
+    use std::{num::NonZeroU64, sync::LazyLock};
+
+    use tendermint_light_client_types::Fraction;
+
+    use super::*;
+
+    static HEADER_288: LazyLock<Header> =
+        LazyLock::new(|| serde_json::from_str(include_str!("./test/header_288.json")).unwrap());
+    static HEADER_291: LazyLock<Header> =
+        LazyLock::new(|| serde_json::from_str(include_str!("./test/header_291.json")).unwrap());
+    static HEADER_294: LazyLock<Header> =
+        LazyLock::new(|| serde_json::from_str(include_str!("./test/header_294.json")).unwrap());
+
+    /// Signatures can't be forged for the crafted headers, so only the light client logic is
+    /// tested here. Signature verification itself is covered in `tendermint-verifier`.
+    struct AcceptAllVerifier;
+
+    impl HostFns for AcceptAllVerifier {
+        fn verify_signature(&self, _pubkey: &PublicKey, _msg: &[u8], _sig: &[u8]) -> bool {
+            true
+        }
+
+        fn verify_batch_signature(
+            &self,
+            _pubkeys: &[PublicKey],
+            _msgs: &[&[u8]],
+            _sigs: &[&[u8]],
+        ) -> bool {
+            true
+        }
+    }
+
+    fn mk_client_state() -> ClientState {
+        ClientState {
+            chain_id: "simd-devnet-1".to_owned(),
+            trust_level: Fraction {
+                numerator: 1,
+                denominator: NonZeroU64::new(3).unwrap(),
+            },
+            trusting_period: Duration::new(315576000000, 0).unwrap(),
+            unbonding_period: Duration::new(315576000000, 0).unwrap(),
+            max_clock_drift: Duration::new(100_000_000, 0).unwrap(),
+            frozen_height: None,
+            latest_height: Height::new_with_revision(1, 294),
+            proof_specs: vec![],
+            upgrade_path: vec![],
+            contract_address: H256::default(),
+        }
+    }
+
+    fn consensus_state_of(header: &Header) -> ConsensusState {
+        ConsensusState {
+            timestamp: header.signed_header.header.time,
+            root: MerkleRoot {
+                hash: (*header.signed_header.header.app_hash.get()).into(),
+            },
+            next_validators_hash: header.signed_header.header.next_validators_hash,
+        }
+    }
+
+    fn block_timestamp() -> cosmwasm_std::Timestamp {
+        cosmwasm_std::Timestamp::from_nanos(
+            HEADER_294.signed_header.header.time.as_unix_nanos() + 1_000_000_000,
+        )
+    }
+
+    /// Recompute the block hash of the (modified) header so that it is committed to by the commit.
+    fn rehash(header: &mut Header) {
+        header.signed_header.commit.block_id.hash = Some(
+            header
+                .signed_header
+                .header
+                .calculate_merkle_root()
+                .unwrap()
+                .into_encoding(),
+        );
+    }
+
+    fn verify(misbehaviour: Misbehaviour) -> Result<(), Error> {
+        verify_misbehaviour(
+            &mk_client_state(),
+            consensus_state_of(&HEADER_288),
+            consensus_state_of(&HEADER_288),
+            misbehaviour,
+            block_timestamp(),
+            &SignatureVerifier::new(AcceptAllVerifier),
+        )
+    }
+
+    #[test]
+    fn misbehaviour_conflicting_blocks_at_same_height() {
+        let mut header_b = HEADER_291.clone();
+        header_b.signed_header.header.app_hash = H256::new([0xAA; 32]);
+        rehash(&mut header_b);
+
+        assert_eq!(
+            verify(Misbehaviour {
+                header_a: HEADER_291.clone(),
+                header_b,
+            }),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn misbehaviour_time_monotonicity_violation() {
+        let mut header_a = HEADER_294.clone();
+        header_a.trusted_height = HEADER_291.trusted_height;
+        header_a.signed_header.header.time = HEADER_291.signed_header.header.time;
+        rehash(&mut header_a);
+
+        assert_eq!(
+            verify(Misbehaviour {
+                header_a,
+                header_b: HEADER_291.clone(),
+            }),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn misbehaviour_same_block_is_not_misbehaviour() {
+        assert_eq!(
+            verify(Misbehaviour {
+                header_a: HEADER_291.clone(),
+                header_b: HEADER_291.clone(),
+            }),
+            Err(Error::MisbehaviourNotFound)
+        );
+    }
+
+    #[test]
+    fn misbehaviour_same_block_different_part_set_header_is_not_misbehaviour() {
+        let mut header_b = HEADER_291.clone();
+        header_b.signed_header.commit.block_id.part_set_header.total += 1;
+
+        assert_eq!(
+            verify(Misbehaviour {
+                header_a: HEADER_291.clone(),
+                header_b,
+            }),
+            Err(Error::MisbehaviourNotFound)
+        );
+    }
+
+    #[test]
+    fn misbehaviour_monotonic_time_is_not_misbehaviour() {
+        let mut header_a = HEADER_294.clone();
+        header_a.trusted_height = HEADER_291.trusted_height;
+
+        assert_eq!(
+            verify(Misbehaviour {
+                header_a,
+                header_b: HEADER_291.clone(),
+            }),
+            Err(Error::MisbehaviourNotFound)
+        );
+    }
+
+    #[test]
+    fn misbehaviour_invalid_header_sequence() {
+        let mut header_a = HEADER_291.clone();
+        header_a.signed_header.header.app_hash = H256::new([0xAA; 32]);
+        rehash(&mut header_a);
+
+        let mut header_b = HEADER_294.clone();
+        header_b.trusted_height = HEADER_291.trusted_height;
+
+        assert_eq!(
+            verify(Misbehaviour { header_a, header_b }),
+            Err(Error::InvalidMisbehaviourHeaderSequence)
+        );
+    }
+
+    #[test]
+    fn misbehaviour_conflicting_header_must_be_committed() {
+        // the block id in the commit no longer matches the header
+        let mut header_b = HEADER_291.clone();
+        header_b.signed_header.header.app_hash = H256::new([0xAA; 32]);
+        header_b.signed_header.commit.block_id.hash = Some(H256::new([0xAA; 32]));
+
+        assert!(matches!(
+            verify(Misbehaviour {
+                header_a: HEADER_291.clone(),
+                header_b,
+            }),
+            Err(Error::TendermintVerify(_))
+        ));
+    }
+
+    #[test]
+    fn client_expiry_works() {
+        let timestamp = "2024-01-01T00:00:00Z".parse::<Timestamp>().unwrap();
+
+        assert_eq!(
+            client_expiry(&timestamp, Duration::new(60, 0).unwrap()),
+            Ok(ibc_union_spec::Timestamp::from_nanos(
+                1_704_067_260_000_000_000
+            ))
+        );
+    }
+
+    #[test]
+    fn client_expiry_overflow() {
+        let timestamp = "9999-12-31T23:59:59Z".parse::<Timestamp>().unwrap();
+
+        assert_eq!(
+            client_expiry(&timestamp, Duration::new(60, 0).unwrap()),
+            Err(MathOverflow.into())
+        );
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use std::fs;
@@ -60,6 +60,12 @@ pub enum Error {
 
     #[error("invalid or empty validator set, supported keys are: bls12381 and ed25519")]
     InvalidValidatorSet,
+
+    #[error("header_a.height must be greater than or equal to header_b.height")]
+    InvalidMisbehaviourHeaderSequence,
+
+    #[error("given headers don't prove a misbehaviour")]
+    MisbehaviourNotFound,
 }
 
 #[derive(Debug, Clone, PartialEq, thiserror::Error)]
@@ -85,6 +85,7 @@
                   "ethereum"
                   "tendermint"
                   "aptos"
+                  "sui"
                 ];
               };
               options.start_height = mkOption {
@@ -66,6 +66,8 @@ pub enum IndexerConfig {
     Ethereum(indexer::ethereum::config::Config),
     #[serde(rename = "tendermint")]
     Tendermint(indexer::tendermint::config::Config),
+    #[serde(rename = "sui")]
+    Sui(indexer::sui::config::Config),
     // #[serde(rename = "aptos")]
     // Aptos(indexer::aptos::config::Config),
 }
@@ -76,6 +78,7 @@ impl IndexerConfig {
             Self::Dummy(cfg) => &cfg.indexer_id,
             Self::Ethereum(cfg) => &cfg.indexer_id,
             Self::Tendermint(cfg) => &cfg.indexer_id,
+            Self::Sui(cfg) => &cfg.indexer_id,
             // Self::Aptos(cfg) => &cfg.indexer_id,
         }
     }
@@ -112,6 +115,14 @@ impl IndexerConfig {
                     .index()
                     .instrument(indexer_span)
                     .await
+            }
+            Self::Sui(cfg) => {
+                cfg.build(db)
+                    .instrument(initializer_span)
+                    .await?
+                    .index()
+                    .instrument(indexer_span)
+                    .await
             } // Self::Aptos(cfg) => {
               //     cfg.build(db)
               //         .instrument(initializer_span)
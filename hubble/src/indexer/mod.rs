@@ -6,6 +6,7 @@ mod fetcher;
 mod finalizer;
 mod fixer;
 mod postgres;
+pub mod sui;
 pub mod tendermint;
 
 use std::{future::Future, time::Duration};
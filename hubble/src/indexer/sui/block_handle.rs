@@ -0,0 +1,265 @@
+use std::collections::HashSet;
+
+use axum::async_trait;
+use color_eyre::eyre::Report;
+use futures::{stream::FuturesOrdered, Stream};
+use itertools::Itertools;
+use sqlx::Postgres;
+use time::OffsetDateTime;
+use tracing::{debug, trace};
+
+use crate::indexer::{
+    api::{
+        BlockHandle, BlockRange, BlockReference, BlockReferenceProvider, BlockSelection, FetchMode,
+        IndexerError,
+    },
+    sui::{
+        fetcher_client::SuiFetcherClient,
+        postgres::{
+            active_contracts, delete_sui_checkpoint_transactions_events, insert_sui_checkpoint,
+            PgCheckpoint, PgEvent, PgTransaction,
+        },
+        provider::{Checkpoint, Event, RpcProviderId, TransactionBlock},
+    },
+};
+
+impl BlockReferenceProvider for Checkpoint {
+    fn block_reference(&self) -> Result<BlockReference, Report> {
+        let timestamp_ms: i128 = self.timestamp_ms.into();
+        Ok(BlockReference {
+            height: self.sequence_number,
+            hash: self.digest.clone(),
+            timestamp: OffsetDateTime::from_unix_timestamp_nanos(timestamp_ms * 1_000_000)
+                .map_err(Report::from)?,
+        })
+    }
+}
+
+/// The package defining the type of `event`, i.e. the package that emitted it. This differs from
+/// [`Event::package_id`] when the package is called through another package.
+fn event_package(event: &Event) -> &str {
+    event
+        .typ
+        .split_once("::")
+        .map_or(event.typ.as_str(), |(package, _)| package)
+}
+
+/// Only events emitted by the configured packages are indexed.
+fn selected_events(events: Vec<Event>, active_contracts: &HashSet<String>) -> Vec<Event> {
+    events
+        .into_iter()
+        .filter(|event| active_contracts.contains(event_package(event)))
+        .collect_vec()
+}
+
+#[derive(Clone)]
+pub enum BlockDetails {
+    Lazy(Checkpoint),
+    Eager(Checkpoint, Vec<TransactionBlock>),
+}
+
+#[derive(Clone)]
+pub struct SuiBlockHandle {
+    pub internal_chain_id: i32,
+    pub reference: BlockReference,
+    pub details: BlockDetails,
+    pub sui_client: SuiFetcherClient,
+    pub provider_id: RpcProviderId,
+}
+
+#[async_trait]
+impl BlockHandle for SuiBlockHandle {
+    fn reference(&self) -> BlockReference {
+        self.reference.clone()
+    }
+
+    fn fetch_range(
+        &self,
+        block_range: BlockRange,
+        fetch_mode: FetchMode,
+    ) -> Result<impl Stream<Item = Result<Self, IndexerError>> + Send, IndexerError> {
+        debug!("{}: fetching", block_range);
+
+        Ok(FuturesOrdered::from_iter(
+            block_range.clone().into_iter().map(|height| async move {
+                self.sui_client
+                    .fetch_single_with_provider(
+                        BlockSelection::Height(height),
+                        fetch_mode,
+                        Some(self.provider_id),
+                    )
+                    .await
+            }),
+        ))
+    }
+
+    async fn insert(&self, tx: &mut sqlx::Transaction<'_, Postgres>) -> Result<(), IndexerError> {
+        let reference = self.reference();
+        debug!("{}: inserting", reference);
+
+        let (checkpoint, transactions) = match &self.details {
+            BlockDetails::Lazy(checkpoint) => (
+                checkpoint,
+                self.sui_client
+                    .fetch_transactions(checkpoint, self.provider_id)
+                    .await?,
+            ),
+            BlockDetails::Eager(checkpoint, transactions) => (checkpoint, transactions.clone()),
+        };
+
+        let active_contracts =
+            active_contracts(tx, self.internal_chain_id, checkpoint.sequence_number).await?;
+        trace!("{reference}: active contracts: {}", active_contracts.len());
+
+        let mut event_index_iter = 0..;
+
+        let transactions = transactions
+            .into_iter()
+            .enumerate()
+            .filter_map(|(transaction_index, transaction)| {
+                let events = selected_events(transaction.events, &active_contracts);
+
+                if events.is_empty() {
+                    trace!(
+                        "{reference}: no events of configured contracts: {}",
+                        transaction.digest
+                    );
+                    return None;
+                }
+
+                Some(PgTransaction {
+                    internal_chain_id: self.internal_chain_id,
+                    height: self.reference.height.try_into().unwrap(),
+                    transaction_digest: transaction.digest.clone(),
+                    transaction_index: transaction_index.try_into().unwrap(),
+                    events: events
+                        .into_iter()
+                        .map(|event| PgEvent {
+                            internal_chain_id: self.internal_chain_id,
+                            height: self.reference.height.try_into().unwrap(),
+                            transaction_digest: transaction.digest.clone(),
+                            index: event_index_iter.next().unwrap(),
+                            transaction_event_index: event.id.event_seq.try_into().unwrap(),
+                            package_id: event.package_id,
+                            module: event.transaction_module,
+                            sender: event.sender,
+                            typ: event.typ,
+                            data: event.parsed_json,
+                        })
+                        .collect_vec(),
+                })
+            })
+            .collect_vec();
+
+        if !transactions.is_empty() {
+            trace!(
+                "{}: transactions with matching events: {}",
+                reference,
+                transactions.len()
+            );
+
+            insert_sui_checkpoint(
+                tx,
+                PgCheckpoint {
+                    internal_chain_id: self.internal_chain_id,
+                    height: self.reference.height.try_into().unwrap(),
+                    checkpoint_digest: self.reference.hash.clone(),
+                    timestamp: self.reference.timestamp,
+                    epoch: checkpoint.epoch.try_into().unwrap(),
+                    transactions,
+                },
+            )
+            .await?;
+        } else {
+            trace!("{}: no matching events: ignore", reference);
+        }
+
+        debug!("{}: done", reference);
+        Ok(())
+    }
+
+    async fn update(&self, tx: &mut sqlx::Transaction<'_, Postgres>) -> Result<(), IndexerError> {
+        let reference = self.reference();
+        debug!("{}: updating", reference);
+
+        delete_sui_checkpoint_transactions_events(
+            tx,
+            self.internal_chain_id,
+            self.reference.height,
+        )
+        .await?;
+        self.insert(tx).await?;
+
+        debug!("{}: done", reference);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use serde_json::json;
+
+    use crate::indexer::sui::{block_handle::selected_events, provider::Event};
+
+    const IBC: &str = "0x4a7c3f5ecdc46ee6e8e9ba8bbf1ee82b7b1b8c9c5f9e8aee9b3e2ef65b2a9d01";
+    const ZKGM: &str = "0x9d2be5b8c5b3f0cd9cb1d8e6b2bf6e0c1a2f3c4d5e6f708192a3b4c5d6e7f801";
+
+    fn event(package_id: &str, typ: &str) -> Event {
+        serde_json::from_value(json!({
+            "id": {
+                "txDigest": "5Lk1zqmXyLgSd2KoGdCXpKPe4N5mrspYjmVXMPEbGYnR",
+                "eventSeq": "0"
+            },
+            "packageId": package_id,
+            "transactionModule": "zkgm",
+            "sender": "0x835e6a7d0e415c0f1791ae61241f59e1dd9d669d59c9cd056f8d1bd7a6ba5e0c",
+            "type": typ,
+            "parsedJson": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn selects_events_by_type_package() {
+        let active_contracts = HashSet::from([IBC.to_owned()]);
+
+        let selected = selected_events(
+            vec![
+                // emitted by ibc, called through zkgm
+                event(ZKGM, &format!("{IBC}::ibc::PacketSend")),
+                // emitted by zkgm
+                event(ZKGM, &format!("{ZKGM}::zkgm::Transfer")),
+                // emitted by the framework, called through ibc
+                event(IBC, "0x2::coin::CoinMetadata<0x2::sui::SUI>"),
+                // emitted by ibc, called directly
+                event(IBC, &format!("{IBC}::ibc::UpdateClient")),
+            ],
+            &active_contracts,
+        );
+
+        assert_eq!(
+            selected.iter().map(|event| &event.typ).collect::<Vec<_>>(),
+            [
+                &format!("{IBC}::ibc::PacketSend"),
+                &format!("{IBC}::ibc::UpdateClient"),
+            ]
+        );
+    }
+
+    #[test]
+    fn generic_events_are_selected_by_their_own_package() {
+        let active_contracts = HashSet::from([ZKGM.to_owned()]);
+
+        let selected = selected_events(
+            vec![event(
+                ZKGM,
+                &format!("0x2::coin::CoinMetadata<{ZKGM}::zkgm::ZKGM>"),
+            )],
+            &active_contracts,
+        );
+
+        assert!(selected.is_empty());
+    }
+}
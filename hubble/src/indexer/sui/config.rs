@@ -0,0 +1,46 @@
+use std::num::NonZeroU16;
+
+use color_eyre::eyre::Report;
+use sqlx::PgPool;
+use unionlabs::option_unwrap;
+use url::Url;
+
+use crate::indexer::{
+    api::{BlockHeight, IndexerId},
+    sui::{context::SuiContext, fetcher_client::SuiFetcherClient},
+    FinalizerConfig, Indexer,
+};
+
+const DEFAULT_CHUNK_SIZE: usize = 20;
+// the maximum amount of transactions that can be fetched in a single sui_multiGetTransactionBlocks
+// request
+const DEFAULT_TRANSACTIONS_MAX_PAGE_SIZE: NonZeroU16 = option_unwrap!(NonZeroU16::new(50));
+
+#[derive(Clone, Debug, serde::Deserialize)]
+pub struct Config {
+    pub indexer_id: IndexerId,
+    pub start_height: BlockHeight,
+    pub chunk_size: Option<usize>,
+    pub rpc_urls: Vec<Url>,
+    pub tx_search_max_page_size: Option<NonZeroU16>,
+    #[serde(default)]
+    pub finalizer: FinalizerConfig,
+}
+
+impl Config {
+    pub async fn build(self, pg_pool: PgPool) -> Result<Indexer<SuiFetcherClient>, Report> {
+        Ok(Indexer::new(
+            pg_pool,
+            self.indexer_id,
+            self.start_height,
+            self.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+            self.finalizer,
+            SuiContext {
+                rpc_urls: self.rpc_urls,
+                tx_search_max_page_size: self
+                    .tx_search_max_page_size
+                    .unwrap_or(DEFAULT_TRANSACTIONS_MAX_PAGE_SIZE),
+            },
+        ))
+    }
+}
@@ -0,0 +1,28 @@
+use std::{fmt::Display, num::NonZeroU16};
+
+use url::Url;
+
+#[derive(Clone)]
+pub struct SuiContext {
+    pub rpc_urls: Vec<Url>,
+    pub tx_search_max_page_size: NonZeroU16,
+}
+
+impl Display for SuiContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rpcs: {}, tx_search_max_page_size: {}",
+            to_indexed_url_string(&self.rpc_urls),
+            self.tx_search_max_page_size,
+        )
+    }
+}
+
+fn to_indexed_url_string(urls: &[Url]) -> String {
+    urls.iter()
+        .enumerate()
+        .map(|(index, url)| format!("{}: {}", index, url.as_str()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
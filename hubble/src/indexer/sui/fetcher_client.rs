@@ -0,0 +1,213 @@
+use std::{fmt::Display, num::NonZeroU16};
+
+use axum::async_trait;
+use color_eyre::Result;
+use cometbft_rpc::JsonRpcError;
+use tokio::task::JoinSet;
+use tracing::{debug, info, info_span, trace, Instrument};
+
+use crate::{
+    indexer::{
+        api::{
+            BlockHeight, BlockReferenceProvider, BlockSelection, FetchMode, FetcherClient,
+            IndexerError,
+        },
+        sui::{
+            block_handle::{BlockDetails, SuiBlockHandle},
+            context::SuiContext,
+            provider::{Checkpoint, Provider, RpcProviderId, TransactionBlock},
+        },
+    },
+    postgres::{fetch_chain_id_tx, ChainId},
+};
+
+#[derive(Clone)]
+pub struct SuiFetcherClient {
+    pub chain_id: ChainId,
+    pub provider: Provider,
+    pub tx_search_max_page_size: NonZeroU16,
+}
+
+impl Display for SuiFetcherClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "chain_id: {}", self.chain_id)
+    }
+}
+
+impl SuiFetcherClient {
+    pub async fn fetch_single_with_provider(
+        &self,
+        selection: BlockSelection,
+        mode: FetchMode,
+        provider_id: Option<RpcProviderId>,
+    ) -> Result<SuiBlockHandle, IndexerError> {
+        debug!("{}: fetching", selection);
+
+        match selection {
+            BlockSelection::LastFinalized => self.fetch_last_finalized(mode, provider_id).await,
+            BlockSelection::Height(height) => self.fetch_at_height(mode, provider_id, height).await,
+        }
+    }
+
+    // checkpoints are final once they are certified, so the latest checkpoint is also the last
+    // finalized checkpoint
+    async fn fetch_last_finalized(
+        &self,
+        mode: FetchMode,
+        provider_id: Option<RpcProviderId>,
+    ) -> Result<SuiBlockHandle, IndexerError> {
+        trace!("fetch latest checkpoint");
+
+        let (provider_id, height) = self
+            .provider
+            .get_latest_checkpoint_sequence_number(provider_id)
+            .await
+            .map(|result| (result.provider_id, result.response))?;
+
+        trace!(
+            "latest checkpoint: {height} using {:?} to fetch checkpoint",
+            provider_id
+        );
+
+        self.fetch_at_height(mode, Some(provider_id), height).await
+    }
+
+    async fn fetch_at_height(
+        &self,
+        mode: FetchMode,
+        provider_id: Option<RpcProviderId>,
+        height: BlockHeight,
+    ) -> Result<SuiBlockHandle, IndexerError> {
+        trace!("fetching checkpoint at height {height}");
+
+        let result = self
+            .provider
+            .get_checkpoint(height, provider_id)
+            .await
+            .map_err(|err| {
+                // map error to NoBlock if checkpoint not found, so it's not reported
+                if let JsonRpcError::Call(error_object) = &err {
+                    if error_object
+                        .message()
+                        .contains("Could not find the referenced checkpoint")
+                    {
+                        return IndexerError::NoBlock(BlockSelection::Height(height));
+                    }
+                }
+                err.into()
+            })?;
+
+        let checkpoint = result.response;
+
+        trace!(
+            "fetched checkpoint at height {height} using {:?}: {} transactions",
+            provider_id,
+            checkpoint.transactions.len()
+        );
+
+        Ok(SuiBlockHandle {
+            internal_chain_id: self.chain_id.db,
+            reference: checkpoint.block_reference()?,
+            details: match mode {
+                FetchMode::Lazy => BlockDetails::Lazy(checkpoint),
+                FetchMode::Eager => {
+                    let transactions = self
+                        .fetch_transactions(&checkpoint, result.provider_id)
+                        .await?;
+
+                    BlockDetails::Eager(checkpoint, transactions)
+                }
+            },
+            sui_client: self.clone(),
+            provider_id: result.provider_id,
+        })
+    }
+
+    pub async fn fetch_transactions(
+        &self,
+        checkpoint: &Checkpoint,
+        provider_id: RpcProviderId,
+    ) -> Result<Vec<TransactionBlock>, IndexerError> {
+        trace!(
+            "fetching transactions for checkpoint {} - transactions: {}",
+            checkpoint.sequence_number,
+            checkpoint.transactions.len()
+        );
+
+        let mut result = Vec::with_capacity(checkpoint.transactions.len());
+
+        for chunk in checkpoint
+            .transactions
+            .chunks(self.tx_search_max_page_size.get().into())
+        {
+            trace!(
+                "fetching chunk for checkpoint {} - transactions: {}",
+                checkpoint.sequence_number,
+                chunk.len()
+            );
+
+            result.extend(
+                self.provider
+                    .multi_get_transaction_blocks(chunk, Some(provider_id))
+                    .await?
+                    .response,
+            );
+        }
+
+        trace!(
+            "fetched transactions for checkpoint {} - transactions: {}",
+            checkpoint.sequence_number,
+            result.len()
+        );
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl FetcherClient for SuiFetcherClient {
+    type BlockHandle = SuiBlockHandle;
+    type Context = SuiContext;
+
+    async fn create(
+        pg_pool: sqlx::PgPool,
+        _join_set: &mut JoinSet<Result<(), IndexerError>>,
+        context: SuiContext,
+    ) -> Result<Self, IndexerError> {
+        let provider = Provider::new(context.rpc_urls)?;
+
+        info!("fetching chain-id from node");
+        let chain_id = provider
+            .get_chain_identifier(None)
+            .await
+            .inspect_err(|e| debug!(?e, "error fetching chain-id: {}", e))?
+            .response;
+
+        info!("fetched chain-id from node: {}", chain_id);
+
+        let indexing_span = info_span!("indexer", chain_id = chain_id).or_current();
+        async move {
+            let mut tx = pg_pool.begin().await?;
+
+            let chain_id = fetch_chain_id_tx(&mut tx, chain_id).await?;
+
+            tx.commit().await?;
+
+            Ok(SuiFetcherClient {
+                chain_id,
+                provider,
+                tx_search_max_page_size: context.tx_search_max_page_size,
+            })
+        }
+        .instrument(indexing_span)
+        .await
+    }
+
+    async fn fetch_single(
+        &self,
+        selection: BlockSelection,
+        mode: FetchMode,
+    ) -> Result<Self::BlockHandle, IndexerError> {
+        self.fetch_single_with_provider(selection, mode, None).await
+    }
+}
@@ -0,0 +1,6 @@
+mod block_handle;
+pub mod config;
+mod context;
+mod fetcher_client;
+mod postgres;
+mod provider;
@@ -0,0 +1,289 @@
+use std::result::Result;
+
+use cometbft_rpc::JsonRpcError;
+use jsonrpsee::{
+    core::client::ClientT,
+    http_client::{HttpClient, HttpClientBuilder},
+    rpc_params,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::{
+    indexer::api::BlockHeight,
+    race_client::{RaceClient, RaceClientId, RaceClientResponse},
+};
+
+#[derive(Clone, Debug)]
+pub struct Provider {
+    pub rpc_client: RaceClient<HttpClient>,
+}
+
+#[derive(Clone, Debug, Copy)]
+pub struct RpcProviderId {
+    race_client_id: RaceClientId,
+}
+
+impl From<RpcProviderId> for RaceClientId {
+    fn from(value: RpcProviderId) -> Self {
+        value.race_client_id
+    }
+}
+
+#[derive(Debug)]
+pub struct RpcResult<T> {
+    pub provider_id: RpcProviderId,
+    pub response: T,
+}
+
+impl<T> RpcResult<T> {
+    fn new(race_client_id: RaceClientId, result: T) -> Self {
+        Self {
+            provider_id: RpcProviderId { race_client_id },
+            response: result,
+        }
+    }
+}
+
+impl<T> From<RaceClientResponse<T>> for RpcResult<T> {
+    fn from(value: RaceClientResponse<T>) -> Self {
+        RpcResult::new(value.race_client_id, value.response)
+    }
+}
+
+/// A checkpoint as returned by `sui_getCheckpoint`. Only the fields used by the indexer are
+/// included.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    #[serde(with = "::serde_utils::string")]
+    pub epoch: u64,
+    #[serde(with = "::serde_utils::string")]
+    pub sequence_number: u64,
+    pub digest: String,
+    #[serde(with = "::serde_utils::string")]
+    pub timestamp_ms: u64,
+    /// The digests of the transactions in this checkpoint, in execution order.
+    pub transactions: Vec<String>,
+}
+
+/// A transaction as returned by `sui_multiGetTransactionBlocks` with `showEvents` enabled.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionBlock {
+    pub digest: String,
+    #[serde(default)]
+    pub events: Vec<Event>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub id: EventId,
+    /// The package of the function that emitted this event.
+    pub package_id: String,
+    pub transaction_module: String,
+    pub sender: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub parsed_json: Value,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventId {
+    pub tx_digest: String,
+    #[serde(with = "::serde_utils::string")]
+    pub event_seq: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(transparent)]
+struct CheckpointSequenceNumber(#[serde(with = "::serde_utils::string")] u64);
+
+impl Provider {
+    pub fn new(rpc_urls: Vec<Url>) -> Result<Self, JsonRpcError> {
+        Ok(Self {
+            rpc_client: RaceClient::new(
+                rpc_urls
+                    .into_iter()
+                    .map(|rpc_url| {
+                        HttpClientBuilder::default()
+                            .max_response_size(100 * 1024 * 1024)
+                            .build(rpc_url.as_str())
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+        })
+    }
+
+    // RPC
+    pub async fn get_chain_identifier(
+        &self,
+        provider_id: Option<RpcProviderId>,
+    ) -> Result<RpcResult<String>, JsonRpcError> {
+        self.rpc_client
+            .race(provider_id.map(Into::into), |c| {
+                c.request::<String, _>("sui_getChainIdentifier", rpc_params!())
+            })
+            .await
+            .map(Into::into)
+    }
+
+    pub async fn get_latest_checkpoint_sequence_number(
+        &self,
+        provider_id: Option<RpcProviderId>,
+    ) -> Result<RpcResult<BlockHeight>, JsonRpcError> {
+        self.rpc_client
+            .race(provider_id.map(Into::into), |c| async move {
+                c.request::<CheckpointSequenceNumber, _>(
+                    "sui_getLatestCheckpointSequenceNumber",
+                    rpc_params!(),
+                )
+                .await
+                .map(|sequence_number| sequence_number.0)
+            })
+            .await
+            .map(Into::into)
+    }
+
+    pub async fn get_checkpoint(
+        &self,
+        height: BlockHeight,
+        provider_id: Option<RpcProviderId>,
+    ) -> Result<RpcResult<Checkpoint>, JsonRpcError> {
+        self.rpc_client
+            .race(provider_id.map(Into::into), |c| {
+                c.request::<Checkpoint, _>("sui_getCheckpoint", rpc_params!(height.to_string()))
+            })
+            .await
+            .map(Into::into)
+    }
+
+    pub async fn multi_get_transaction_blocks(
+        &self,
+        digests: &[String],
+        provider_id: Option<RpcProviderId>,
+    ) -> Result<RpcResult<Vec<TransactionBlock>>, JsonRpcError> {
+        self.rpc_client
+            .race(provider_id.map(Into::into), |c| {
+                c.request::<Vec<TransactionBlock>, _>(
+                    "sui_multiGetTransactionBlocks",
+                    rpc_params!(digests, json!({ "showEvents": true })),
+                )
+            })
+            .await
+            .map(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::indexer::sui::provider::{Checkpoint, TransactionBlock};
+
+    #[test]
+    fn decodes_checkpoint() {
+        let checkpoint: Checkpoint = serde_json::from_value(json!({
+            "epoch": "781",
+            "sequenceNumber": "186201337",
+            "digest": "8SQBGqKkJvR6NKqVq2DMmnQ8qpsLmnKTytVfvSqEoQr3",
+            "networkTotalTransactions": "3912046720",
+            "previousDigest": "3W5pHF5WWoKqjeLShJqbQTBEJ6Lwcd9JZLDeZp8j6mqH",
+            "epochRollingGasCostSummary": {
+                "computationCost": "61212541000",
+                "storageCost": "281094563200",
+                "storageRebate": "268316047572",
+                "nonRefundableStorageFee": "2710263107"
+            },
+            "timestampMs": "1760778121457",
+            "transactions": [
+                "5Lk1zqmXyLgSd2KoGdCXpKPe4N5mrspYjmVXMPEbGYnR",
+                "GQ7qUeYfyCrEb5Gk3V5MwwXWv1rNHEmV7bWtvbBXpUyk"
+            ],
+            "checkpointCommitments": [],
+            "validatorSignature": "rJz1Qw7GxHjB3vPbTu7zAlF2cSvWg0i0pC3dN9d7bJ0nqK2lG6rWvG3X8qz3J4Rm"
+        }))
+        .unwrap();
+
+        assert_eq!(checkpoint.epoch, 781);
+        assert_eq!(checkpoint.sequence_number, 186_201_337);
+        assert_eq!(
+            checkpoint.digest,
+            "8SQBGqKkJvR6NKqVq2DMmnQ8qpsLmnKTytVfvSqEoQr3"
+        );
+        assert_eq!(checkpoint.timestamp_ms, 1_760_778_121_457);
+        assert_eq!(
+            checkpoint.transactions,
+            [
+                "5Lk1zqmXyLgSd2KoGdCXpKPe4N5mrspYjmVXMPEbGYnR",
+                "GQ7qUeYfyCrEb5Gk3V5MwwXWv1rNHEmV7bWtvbBXpUyk"
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_transaction_blocks() {
+        let transactions: Vec<TransactionBlock> = serde_json::from_value(json!([
+            {
+                "digest": "5Lk1zqmXyLgSd2KoGdCXpKPe4N5mrspYjmVXMPEbGYnR",
+                "events": [
+                    {
+                        "id": {
+                            "txDigest": "5Lk1zqmXyLgSd2KoGdCXpKPe4N5mrspYjmVXMPEbGYnR",
+                            "eventSeq": "1"
+                        },
+                        "packageId": "0x9d2be5b8c5b3f0cd9cb1d8e6b2bf6e0c1a2f3c4d5e6f708192a3b4c5d6e7f801",
+                        "transactionModule": "zkgm",
+                        "sender": "0x835e6a7d0e415c0f1791ae61241f59e1dd9d669d59c9cd056f8d1bd7a6ba5e0c",
+                        "type": "0x4a7c3f5ecdc46ee6e8e9ba8bbf1ee82b7b1b8c9c5f9e8aee9b3e2ef65b2a9d01::ibc::PacketSend",
+                        "parsedJson": {
+                            "channel_id": 1,
+                            "packet_hash": [1, 2, 3]
+                        },
+                        "bcsEncoding": "base64",
+                        "bcs": "AQAAAAMBAgM=",
+                        "timestampMs": "1760778121457"
+                    }
+                ],
+                "timestampMs": "1760778121457",
+                "checkpoint": "186201337"
+            },
+            {
+                "digest": "GQ7qUeYfyCrEb5Gk3V5MwwXWv1rNHEmV7bWtvbBXpUyk",
+                "events": [],
+                "timestampMs": "1760778121457",
+                "checkpoint": "186201337"
+            },
+            {
+                "digest": "3W5pHF5WWoKqjeLShJqbQTBEJ6Lwcd9JZLDeZp8j6mqH",
+                "timestampMs": "1760778121457",
+                "checkpoint": "186201337"
+            }
+        ]))
+        .unwrap();
+
+        assert_eq!(transactions.len(), 3);
+
+        let [event] = &transactions[0].events[..] else {
+            panic!("expected a single event");
+        };
+
+        assert_eq!(event.id.event_seq, 1);
+        assert_eq!(
+            event.package_id,
+            "0x9d2be5b8c5b3f0cd9cb1d8e6b2bf6e0c1a2f3c4d5e6f708192a3b4c5d6e7f801"
+        );
+        assert_eq!(event.transaction_module, "zkgm");
+        assert_eq!(
+            event.typ,
+            "0x4a7c3f5ecdc46ee6e8e9ba8bbf1ee82b7b1b8c9c5f9e8aee9b3e2ef65b2a9d01::ibc::PacketSend"
+        );
+        assert_eq!(event.parsed_json["channel_id"], 1);
+
+        assert!(transactions[1].events.is_empty());
+        assert!(transactions[2].events.is_empty());
+    }
+}
@@ -2,10 +2,12 @@ pub mod client_state;
 pub mod consensus_state;
 pub mod header;
 pub mod l2_header;
+pub mod misbehaviour;
 
 pub use crate::{
     client_state::{ClientState, ClientStateV1},
     consensus_state::ConsensusState,
     header::Header,
     l2_header::L2Header,
+    misbehaviour::Misbehaviour,
 };
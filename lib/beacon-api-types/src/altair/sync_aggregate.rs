@@ -50,4 +50,13 @@ pub mod ssz {
             })
         }
     }
+
+    impl<C: crate::chain_spec::SYNC_COMMITTEE_SIZE> From<SyncAggregateSsz<C>> for SyncAggregate {
+        fn from(value: SyncAggregateSsz<C>) -> Self {
+            Self {
+                sync_committee_bits: value.sync_committee_bits.into_bytes().to_vec().into(),
+                sync_committee_signature: value.sync_committee_signature,
+            }
+        }
+    }
 }
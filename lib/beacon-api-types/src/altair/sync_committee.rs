@@ -53,4 +53,13 @@ pub mod ssz {
             })
         }
     }
+
+    impl<C: SYNC_COMMITTEE_SIZE> From<SyncCommitteeSsz<C>> for SyncCommittee {
+        fn from(value: SyncCommitteeSsz<C>) -> Self {
+            Self {
+                pubkeys: value.pubkeys.into(),
+                aggregate_pubkey: value.aggregate_pubkey,
+            }
+        }
+    }
 }
@@ -18,7 +18,7 @@ pub struct BeaconBlock {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -31,3 +31,20 @@ pub struct BeaconBlockSsz<C: ChainSpec> {
     pub state_root: H256,
     pub body: BeaconBlockBodySsz<C>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<BeaconBlockSsz<C>> for BeaconBlock {
+        fn from(value: BeaconBlockSsz<C>) -> Self {
+            Self {
+                slot: value.slot,
+                proposer_index: value.proposer_index,
+                parent_root: value.parent_root,
+                state_root: value.state_root,
+                body: value.body.into(),
+            }
+        }
+    }
+}
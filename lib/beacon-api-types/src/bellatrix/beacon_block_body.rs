@@ -7,7 +7,7 @@ use {
         chain_spec::ChainSpec,
         phase0::{AttestationSsz, AttesterSlashingSsz, DepositSsz},
     },
-    ssz::{types::List, Ssz},
+    ::ssz::{types::List, Ssz},
 };
 
 use crate::{
@@ -52,3 +52,29 @@ pub struct BeaconBlockBodySsz<C: ChainSpec> {
     pub sync_aggregate: SyncAggregateSsz<C>,
     pub execution_payload: ExecutionPayloadSsz<C>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<BeaconBlockBodySsz<C>> for BeaconBlockBody {
+        fn from(value: BeaconBlockBodySsz<C>) -> Self {
+            Self {
+                randao_reveal: value.randao_reveal,
+                eth1_data: value.eth1_data,
+                graffiti: value.graffiti,
+                proposer_slashings: value.proposer_slashings.into(),
+                attester_slashings: value
+                    .attester_slashings
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                attestations: value.attestations.into_iter().map(Into::into).collect(),
+                deposits: value.deposits.into_iter().map(Into::into).collect(),
+                voluntary_exits: value.voluntary_exits.into(),
+                sync_aggregate: value.sync_aggregate.into(),
+                execution_payload: value.execution_payload.into(),
+            }
+        }
+    }
+}
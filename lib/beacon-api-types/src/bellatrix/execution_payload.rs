@@ -5,7 +5,7 @@ use {
         BYTES_PER_LOGS_BLOOM, MAX_BYTES_PER_TRANSACTION, MAX_EXTRA_DATA_BYTES,
         MAX_TRANSACTIONS_PER_PAYLOAD,
     },
-    ssz::types::{List, Vector},
+    ::ssz::types::{List, Vector},
 };
 
 use crate::custom_types::Gas;
@@ -37,7 +37,7 @@ pub struct ExecutionPayload {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExecutionPayloadSsz<
     C: BYTES_PER_LOGS_BLOOM
@@ -70,3 +70,39 @@ pub struct ExecutionPayloadSsz<
     #[cfg_attr(feature = "serde", serde(with = "::serde_utils::hex_string_list"))]
     pub transactions: List<List<u8, C::MAX_BYTES_PER_TRANSACTION>, C::MAX_TRANSACTIONS_PER_PAYLOAD>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<
+            C: BYTES_PER_LOGS_BLOOM
+                + MAX_EXTRA_DATA_BYTES
+                + MAX_BYTES_PER_TRANSACTION
+                + MAX_TRANSACTIONS_PER_PAYLOAD,
+        > From<ExecutionPayloadSsz<C>> for ExecutionPayload
+    {
+        fn from(value: ExecutionPayloadSsz<C>) -> Self {
+            Self {
+                parent_hash: value.parent_hash,
+                fee_recipient: value.fee_recipient,
+                state_root: value.state_root,
+                receipts_root: value.receipts_root,
+                logs_bloom: value.logs_bloom.into_iter().collect(),
+                prev_randao: value.prev_randao,
+                block_number: value.block_number,
+                gas_limit: value.gas_limit,
+                gas_used: value.gas_used,
+                timestamp: value.timestamp,
+                extra_data: value.extra_data.into_iter().collect(),
+                base_fee_per_gas: value.base_fee_per_gas,
+                block_hash: value.block_hash,
+                transactions: value
+                    .transactions
+                    .into_iter()
+                    .map(|transaction| transaction.into_iter().collect())
+                    .collect(),
+            }
+        }
+    }
+}
@@ -12,7 +12,7 @@ pub struct SignedBeaconBlock {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -22,3 +22,17 @@ pub struct SignedBeaconBlockSsz<C: ChainSpec> {
     pub message: BeaconBlockSsz<C>,
     pub signature: H768,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<SignedBeaconBlockSsz<C>> for SignedBeaconBlock {
+        fn from(value: SignedBeaconBlockSsz<C>) -> Self {
+            Self {
+                message: value.message.into(),
+                signature: value.signature,
+            }
+        }
+    }
+}
@@ -13,7 +13,7 @@ use {
         },
         phase0::{AttestationSsz, AttesterSlashingSsz, DepositSsz},
     },
-    ssz::{types::List, Ssz},
+    ::ssz::{types::List, Ssz},
 };
 
 use crate::{
@@ -77,3 +77,47 @@ pub struct BeaconBlockBodySsz<
     pub execution_payload: ExecutionPayloadSsz<C>,
     pub bls_to_execution_changes: List<SignedBlsToExecutionChange, C::MAX_BLS_TO_EXECUTION_CHANGES>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<
+            C: MAX_PROPOSER_SLASHINGS
+                + MAX_VALIDATORS_PER_COMMITTEE
+                + MAX_ATTESTER_SLASHINGS
+                + MAX_ATTESTATIONS
+                + DEPOSIT_CONTRACT_TREE_DEPTH
+                + MAX_DEPOSITS
+                + MAX_VOLUNTARY_EXITS
+                + BYTES_PER_LOGS_BLOOM
+                + MAX_EXTRA_DATA_BYTES
+                + MAX_BYTES_PER_TRANSACTION
+                + MAX_TRANSACTIONS_PER_PAYLOAD
+                + MAX_WITHDRAWALS_PER_PAYLOAD
+                + MAX_BLS_TO_EXECUTION_CHANGES
+                + MAX_BLOB_COMMITMENTS_PER_BLOCK
+                + SYNC_COMMITTEE_SIZE,
+        > From<BeaconBlockBodySsz<C>> for BeaconBlockBody
+    {
+        fn from(value: BeaconBlockBodySsz<C>) -> Self {
+            Self {
+                randao_reveal: value.randao_reveal,
+                eth1_data: value.eth1_data,
+                graffiti: value.graffiti,
+                proposer_slashings: value.proposer_slashings.into(),
+                attester_slashings: value
+                    .attester_slashings
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                attestations: value.attestations.into_iter().map(Into::into).collect(),
+                deposits: value.deposits.into_iter().map(Into::into).collect(),
+                voluntary_exits: value.voluntary_exits.into(),
+                sync_aggregate: value.sync_aggregate.into(),
+                execution_payload: value.execution_payload.into(),
+                bls_to_execution_changes: value.bls_to_execution_changes.into(),
+            }
+        }
+    }
+}
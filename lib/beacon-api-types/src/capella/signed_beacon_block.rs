@@ -2,7 +2,7 @@ use unionlabs::primitives::H768;
 #[cfg(feature = "ssz")]
 use {
     crate::{capella::BeaconBlockSsz, chain_spec::ChainSpec},
-    ssz::Ssz,
+    ::ssz::Ssz,
 };
 
 use crate::capella::BeaconBlock;
@@ -25,3 +25,17 @@ pub struct SignedBeaconBlockSsz<C: ChainSpec> {
     pub message: BeaconBlockSsz<C>,
     pub signature: H768,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<SignedBeaconBlockSsz<C>> for SignedBeaconBlock {
+        fn from(value: SignedBeaconBlockSsz<C>) -> Self {
+            Self {
+                message: value.message.into(),
+                signature: value.signature,
+            }
+        }
+    }
+}
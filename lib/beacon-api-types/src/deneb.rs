@@ -10,6 +10,9 @@ pub use crate::deneb::{
 pub use crate::deneb::{
     beacon_block::BeaconBlockSsz, beacon_block_body::BeaconBlockBodySsz,
     execution_payload::ExecutionPayloadSsz, execution_payload_header::ExecutionPayloadHeaderSsz,
+    light_client_bootstrap::LightClientBootstrapSsz,
+    light_client_finality_update::LightClientFinalityUpdateSsz,
+    light_client_header::LightClientHeaderSsz, light_client_update::LightClientUpdateSsz,
     signed_beacon_block::SignedBeaconBlockSsz,
 };
 
@@ -2,7 +2,7 @@ use unionlabs::primitives::{Bytes, H160, H256, U256};
 #[cfg(feature = "ssz")]
 use {
     crate::chain_spec::ChainSpec,
-    ssz::types::{List, Vector},
+    ::ssz::types::{List, Vector},
 };
 
 use crate::{capella::Withdrawal, custom_types::Gas};
@@ -41,7 +41,7 @@ pub struct ExecutionPayload {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -76,3 +76,36 @@ pub struct ExecutionPayloadSsz<C: ChainSpec> {
     pub blob_gas_used: Gas,
     pub excess_blob_gas: Gas,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<ExecutionPayloadSsz<C>> for ExecutionPayload {
+        fn from(value: ExecutionPayloadSsz<C>) -> Self {
+            Self {
+                parent_hash: value.parent_hash,
+                fee_recipient: value.fee_recipient,
+                state_root: value.state_root,
+                receipts_root: value.receipts_root,
+                logs_bloom: value.logs_bloom.into_iter().collect(),
+                prev_randao: value.prev_randao,
+                block_number: value.block_number,
+                gas_limit: value.gas_limit,
+                gas_used: value.gas_used,
+                timestamp: value.timestamp,
+                extra_data: value.extra_data.into_iter().collect(),
+                base_fee_per_gas: value.base_fee_per_gas,
+                block_hash: value.block_hash,
+                transactions: value
+                    .transactions
+                    .into_iter()
+                    .map(|transaction| transaction.into_iter().collect())
+                    .collect(),
+                withdrawals: value.withdrawals.into(),
+                blob_gas_used: value.blob_gas_used,
+                excess_blob_gas: value.excess_blob_gas,
+            }
+        }
+    }
+}
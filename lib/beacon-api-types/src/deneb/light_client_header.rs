@@ -1,5 +1,7 @@
 use unionlabs::primitives::H256;
 
+#[cfg(feature = "ssz")]
+use crate::{chain_spec::ChainSpec, deneb::ExecutionPayloadHeaderSsz};
 use crate::{
     consts::{floorlog2, EXECUTION_PAYLOAD_GINDEX},
     deneb::ExecutionPayloadHeader,
@@ -18,3 +20,31 @@ pub struct LightClientHeader {
     pub execution: ExecutionPayloadHeader,
     pub execution_branch: [H256; floorlog2(EXECUTION_PAYLOAD_GINDEX)],
 }
+
+#[cfg(feature = "ssz")]
+#[derive(Debug, Clone, PartialEq, ::ssz::Ssz)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "", deserialize = ""), deny_unknown_fields)
+)]
+pub struct LightClientHeaderSsz<C: ChainSpec> {
+    pub beacon: BeaconBlockHeader,
+    pub execution: ExecutionPayloadHeaderSsz<C>,
+    pub execution_branch: [H256; floorlog2(EXECUTION_PAYLOAD_GINDEX)],
+}
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<LightClientHeaderSsz<C>> for LightClientHeader {
+        fn from(value: LightClientHeaderSsz<C>) -> Self {
+            Self {
+                beacon: value.beacon,
+                execution: value.execution.into(),
+                execution_branch: value.execution_branch,
+            }
+        }
+    }
+}
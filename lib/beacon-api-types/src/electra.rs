@@ -12,7 +12,9 @@ pub use crate::electra::{
     attestation::AttestationSsz, attester_slashing::AttesterSlashingSsz,
     beacon_block::BeaconBlockSsz, beacon_block_body::BeaconBlockBodySsz,
     execution_requests::ExecutionRequestsSsz, indexed_attestation::IndexedAttestationSsz,
-    signed_beacon_block::SignedBeaconBlockSsz,
+    light_client_bootstrap::LightClientBootstrapSsz,
+    light_client_finality_update::LightClientFinalityUpdateSsz,
+    light_client_update::LightClientUpdateSsz, signed_beacon_block::SignedBeaconBlockSsz,
 };
 
 /// <https://github.com/ethereum/consensus-specs/blob/dev/specs/electra/beacon-chain.md#attestation>
@@ -2,7 +2,7 @@ use unionlabs::primitives::{Bytes, H768};
 #[cfg(feature = "ssz")]
 use {
     crate::chain_spec::ChainSpec,
-    ssz::{
+    ::ssz::{
         types::{BitList, BitVector},
         Ssz,
     },
@@ -37,3 +37,19 @@ pub struct AttestationSsz<C: ChainSpec> {
     pub signature: H768,
     pub committee_bits: BitVector<C::MAX_COMMITTEES_PER_SLOT>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<AttestationSsz<C>> for Attestation {
+        fn from(value: AttestationSsz<C>) -> Self {
+            Self {
+                aggregation_bits: value.aggregation_bits.into_bytes().to_vec().into(),
+                data: value.data,
+                signature: value.signature,
+                committee_bits: value.committee_bits.into_bytes().to_vec().into(),
+            }
+        }
+    }
+}
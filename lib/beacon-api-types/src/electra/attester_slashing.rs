@@ -1,12 +1,12 @@
 #[cfg(feature = "ssz")]
 use {
     crate::{chain_spec::ChainSpec, electra::IndexedAttestationSsz},
-    ssz::Ssz,
+    ::ssz::Ssz,
 };
 
 use crate::electra::IndexedAttestation;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -18,7 +18,7 @@ pub struct AttesterSlashing {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -28,3 +28,17 @@ pub struct AttesterSlashingSsz<C: ChainSpec> {
     pub attestation_1: IndexedAttestationSsz<C>,
     pub attestation_2: IndexedAttestationSsz<C>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<AttesterSlashingSsz<C>> for AttesterSlashing {
+        fn from(value: AttesterSlashingSsz<C>) -> Self {
+            Self {
+                attestation_1: value.attestation_1.into(),
+                attestation_2: value.attestation_2.into(),
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use unionlabs::primitives::H256;
 #[cfg(feature = "ssz")]
 use {
     crate::{chain_spec::ChainSpec, electra::BeaconBlockBodySsz},
-    ssz::Ssz,
+    ::ssz::Ssz,
 };
 
 use crate::{
@@ -38,3 +38,20 @@ pub struct BeaconBlockSsz<C: ChainSpec> {
     pub state_root: H256,
     pub body: BeaconBlockBodySsz<C>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<BeaconBlockSsz<C>> for BeaconBlock {
+        fn from(value: BeaconBlockSsz<C>) -> Self {
+            Self {
+                slot: value.slot,
+                proposer_index: value.proposer_index,
+                parent_root: value.parent_root,
+                state_root: value.state_root,
+                body: value.body.into(),
+            }
+        }
+    }
+}
@@ -5,18 +5,18 @@ use {
         altair::SyncAggregateSsz,
         chain_spec::ChainSpec,
         deneb::ExecutionPayloadSsz,
-        electra::ExecutionRequestsSsz,
-        phase0::{AttestationSsz, AttesterSlashingSsz, DepositSsz},
+        electra::{AttestationSsz, AttesterSlashingSsz, ExecutionRequestsSsz},
+        phase0::DepositSsz,
     },
-    ssz::{types::List, Ssz},
+    ::ssz::{types::List, Ssz},
 };
 
 use crate::{
     altair::SyncAggregate,
     capella::SignedBlsToExecutionChange,
     deneb::ExecutionPayload,
-    electra::{Attestation, ExecutionRequests},
-    phase0::{AttesterSlashing, Deposit, Eth1Data, ProposerSlashing, SignedVoluntaryExit},
+    electra::{Attestation, AttesterSlashing, ExecutionRequests},
+    phase0::{Deposit, Eth1Data, ProposerSlashing, SignedVoluntaryExit},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,3 +63,32 @@ pub struct BeaconBlockBodySsz<C: ChainSpec> {
     pub blob_kzg_commitments: List<FixedBytes<48>, C::MAX_BLOB_COMMITMENTS_PER_BLOCK>,
     pub execution_requests: ExecutionRequestsSsz<C>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<BeaconBlockBodySsz<C>> for BeaconBlockBody {
+        fn from(value: BeaconBlockBodySsz<C>) -> Self {
+            Self {
+                randao_reveal: value.randao_reveal,
+                eth1_data: value.eth1_data,
+                graffiti: value.graffiti,
+                proposer_slashings: value.proposer_slashings.into(),
+                attester_slashings: value
+                    .attester_slashings
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                attestations: value.attestations.into_iter().map(Into::into).collect(),
+                deposits: value.deposits.into_iter().map(Into::into).collect(),
+                voluntary_exits: value.voluntary_exits.into(),
+                sync_aggregate: value.sync_aggregate.into(),
+                execution_payload: value.execution_payload.into(),
+                bls_to_execution_changes: value.bls_to_execution_changes.into(),
+                blob_kzg_commitments: value.blob_kzg_commitments.into(),
+                execution_requests: value.execution_requests.into(),
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 #[cfg(feature = "ssz")]
 use {
     crate::chain_spec::ChainSpec,
-    ssz::{types::List, Ssz},
+    ::ssz::{types::List, Ssz},
 };
 
 use crate::electra::{ConsolidationRequest, DepositRequest, WithdrawalRequest};
@@ -30,3 +30,18 @@ pub struct ExecutionRequestsSsz<C: ChainSpec> {
     pub withdrawals: List<WithdrawalRequest, C::MAX_WITHDRAWAL_REQUESTS_PER_PAYLOAD>,
     pub consolidations: List<ConsolidationRequest, C::MAX_CONSOLIDATION_REQUESTS_PER_PAYLOAD>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<ExecutionRequestsSsz<C>> for ExecutionRequests {
+        fn from(value: ExecutionRequestsSsz<C>) -> Self {
+            Self {
+                deposits: value.deposits.into(),
+                withdrawals: value.withdrawals.into(),
+                consolidations: value.consolidations.into(),
+            }
+        }
+    }
+}
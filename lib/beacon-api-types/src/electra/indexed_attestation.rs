@@ -2,12 +2,12 @@ use unionlabs::primitives::H768;
 #[cfg(feature = "ssz")]
 use {
     crate::chain_spec::ChainSpec,
-    ssz::{types::List, Ssz},
+    ::ssz::{types::List, Ssz},
 };
 
 use crate::phase0::AttestationData;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -20,7 +20,7 @@ pub struct IndexedAttestation {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -31,3 +31,18 @@ pub struct IndexedAttestationSsz<C: ChainSpec> {
     pub data: AttestationData,
     pub signature: H768,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<IndexedAttestationSsz<C>> for IndexedAttestation {
+        fn from(value: IndexedAttestationSsz<C>) -> Self {
+            Self {
+                attesting_indices: value.attesting_indices.into(),
+                data: value.data,
+                signature: value.signature,
+            }
+        }
+    }
+}
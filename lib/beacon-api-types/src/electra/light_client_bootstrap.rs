@@ -5,6 +5,8 @@ use crate::{
     consts::{floorlog2, CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA},
     deneb::LightClientHeader,
 };
+#[cfg(feature = "ssz")]
+use crate::{altair::SyncCommitteeSsz, chain_spec::ChainSpec, deneb::LightClientHeaderSsz};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(
@@ -18,3 +20,32 @@ pub struct LightClientBootstrap {
     pub current_sync_committee: SyncCommittee,
     pub current_sync_committee_branch: [H256; floorlog2(CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA)],
 }
+
+#[cfg(feature = "ssz")]
+#[derive(Debug, Clone, PartialEq, ::ssz::Ssz)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "", deserialize = ""), deny_unknown_fields)
+)]
+pub struct LightClientBootstrapSsz<C: ChainSpec> {
+    pub header: LightClientHeaderSsz<C>,
+    /// Current sync committee corresponding to `beacon_header.state_root`
+    pub current_sync_committee: SyncCommitteeSsz<C>,
+    pub current_sync_committee_branch: [H256; floorlog2(CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA)],
+}
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<LightClientBootstrapSsz<C>> for LightClientBootstrap {
+        fn from(value: LightClientBootstrapSsz<C>) -> Self {
+            Self {
+                header: value.header.into(),
+                current_sync_committee: value.current_sync_committee.into(),
+                current_sync_committee_branch: value.current_sync_committee_branch,
+            }
+        }
+    }
+}
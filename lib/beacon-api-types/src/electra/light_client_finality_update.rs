@@ -6,6 +6,8 @@ use crate::{
     custom_types::Slot,
     deneb::LightClientHeader,
 };
+#[cfg(feature = "ssz")]
+use crate::{altair::SyncAggregateSsz, chain_spec::ChainSpec, deneb::LightClientHeaderSsz};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(
@@ -24,3 +26,39 @@ pub struct LightClientFinalityUpdate {
     /// Slot at which the aggregate signature was created (untrusted)
     pub signature_slot: Slot,
 }
+
+#[cfg(feature = "ssz")]
+#[derive(Debug, Clone, PartialEq, ::ssz::Ssz)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "", deserialize = ""), deny_unknown_fields)
+)]
+pub struct LightClientFinalityUpdateSsz<C: ChainSpec> {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeaderSsz<C>,
+    /// Finalized header corresponding to `attested_header.state_root`
+    pub finalized_header: LightClientHeaderSsz<C>,
+    pub finality_branch: [H256; floorlog2(FINALIZED_ROOT_GINDEX_ELECTRA)],
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregateSsz<C>,
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: Slot,
+}
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<LightClientFinalityUpdateSsz<C>> for LightClientFinalityUpdate {
+        fn from(value: LightClientFinalityUpdateSsz<C>) -> Self {
+            Self {
+                attested_header: value.attested_header.into(),
+                finalized_header: value.finalized_header.into(),
+                finality_branch: value.finality_branch,
+                sync_aggregate: value.sync_aggregate.into(),
+                signature_slot: value.signature_slot,
+            }
+        }
+    }
+}
@@ -6,6 +6,12 @@ use crate::{
     custom_types::Slot,
     deneb::LightClientHeader,
 };
+#[cfg(feature = "ssz")]
+use crate::{
+    altair::{SyncAggregateSsz, SyncCommitteeSsz},
+    chain_spec::ChainSpec,
+    deneb::LightClientHeaderSsz,
+};
 
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(
@@ -26,3 +32,43 @@ pub struct LightClientUpdate {
     /// Slot at which the aggregate signature was created (untrusted)
     pub signature_slot: Slot,
 }
+
+#[cfg(feature = "ssz")]
+#[derive(Debug, Clone, PartialEq, ::ssz::Ssz)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(bound(serialize = "", deserialize = ""), deny_unknown_fields)
+)]
+pub struct LightClientUpdateSsz<C: ChainSpec> {
+    /// Header attested to by the sync committee
+    pub attested_header: LightClientHeaderSsz<C>,
+    pub next_sync_committee: SyncCommitteeSsz<C>,
+    pub next_sync_committee_branch: [H256; floorlog2(NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA)],
+    /// Finalized header corresponding to `attested_header.state_root`
+    pub finalized_header: LightClientHeaderSsz<C>,
+    pub finality_branch: [H256; floorlog2(FINALIZED_ROOT_GINDEX_ELECTRA)],
+    /// Sync committee aggregate signature
+    pub sync_aggregate: SyncAggregateSsz<C>,
+    /// Slot at which the aggregate signature was created (untrusted)
+    pub signature_slot: Slot,
+}
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<LightClientUpdateSsz<C>> for LightClientUpdate {
+        fn from(value: LightClientUpdateSsz<C>) -> Self {
+            Self {
+                attested_header: value.attested_header.into(),
+                next_sync_committee: value.next_sync_committee.into(),
+                next_sync_committee_branch: value.next_sync_committee_branch,
+                finalized_header: value.finalized_header.into(),
+                finality_branch: value.finality_branch,
+                sync_aggregate: value.sync_aggregate.into(),
+                signature_slot: value.signature_slot,
+            }
+        }
+    }
+}
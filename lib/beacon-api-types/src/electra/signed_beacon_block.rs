@@ -2,7 +2,7 @@ use unionlabs::primitives::H768;
 #[cfg(feature = "ssz")]
 use {
     crate::{chain_spec::ChainSpec, electra::BeaconBlockSsz},
-    ssz::Ssz,
+    ::ssz::Ssz,
 };
 
 use crate::electra::BeaconBlock;
@@ -29,3 +29,17 @@ pub struct SignedBeaconBlockSsz<C: ChainSpec> {
     pub message: BeaconBlockSsz<C>,
     pub signature: H768,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<SignedBeaconBlockSsz<C>> for SignedBeaconBlock {
+        fn from(value: SignedBeaconBlockSsz<C>) -> Self {
+            Self {
+                message: value.message.into(),
+                signature: value.signature,
+            }
+        }
+    }
+}
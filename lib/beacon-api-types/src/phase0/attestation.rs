@@ -1,6 +1,6 @@
 use unionlabs::primitives::H768;
 #[cfg(feature = "ssz")]
-use {crate::chain_spec::MAX_VALIDATORS_PER_COMMITTEE, ssz::types::BitList};
+use {crate::chain_spec::MAX_VALIDATORS_PER_COMMITTEE, ::ssz::types::BitList};
 
 use crate::phase0::AttestationData;
 
@@ -18,7 +18,7 @@ pub struct Attestation {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -30,3 +30,18 @@ pub struct AttestationSsz<C: MAX_VALIDATORS_PER_COMMITTEE> {
     pub data: AttestationData,
     pub signature: H768,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: MAX_VALIDATORS_PER_COMMITTEE> From<AttestationSsz<C>> for Attestation {
+        fn from(value: AttestationSsz<C>) -> Self {
+            Self {
+                aggregation_bits: value.aggregation_bits.into_bytes().to_vec(),
+                data: value.data,
+                signature: value.signature,
+            }
+        }
+    }
+}
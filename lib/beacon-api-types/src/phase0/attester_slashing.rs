@@ -14,7 +14,7 @@ pub struct AttesterSlashing {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -24,3 +24,17 @@ pub struct AttesterSlashingSsz<C: MAX_VALIDATORS_PER_COMMITTEE> {
     pub attestation_1: IndexedAttestationSsz<C>,
     pub attestation_2: IndexedAttestationSsz<C>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: MAX_VALIDATORS_PER_COMMITTEE> From<AttesterSlashingSsz<C>> for AttesterSlashing {
+        fn from(value: AttesterSlashingSsz<C>) -> Self {
+            Self {
+                attestation_1: value.attestation_1.into(),
+                attestation_2: value.attestation_2.into(),
+            }
+        }
+    }
+}
@@ -5,7 +5,7 @@ use {
         chain_spec::ChainSpec,
         phase0::{AttestationSsz, AttesterSlashingSsz, DepositSsz},
     },
-    ssz::{types::List, Ssz},
+    ::ssz::{types::List, Ssz},
 };
 
 use crate::phase0::{
@@ -46,3 +46,27 @@ pub struct BeaconBlockBodySsz<C: ChainSpec> {
     pub deposits: List<DepositSsz<C>, C::MAX_DEPOSITS>,
     pub voluntary_exits: List<SignedVoluntaryExit, C::MAX_VOLUNTARY_EXITS>,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: ChainSpec> From<BeaconBlockBodySsz<C>> for BeaconBlockBody {
+        fn from(value: BeaconBlockBodySsz<C>) -> Self {
+            Self {
+                randao_reveal: value.randao_reveal,
+                eth1_data: value.eth1_data,
+                graffiti: value.graffiti,
+                proposer_slashings: value.proposer_slashings.into(),
+                attester_slashings: value
+                    .attester_slashings
+                    .into_iter()
+                    .map(Into::into)
+                    .collect(),
+                attestations: value.attestations.into_iter().map(Into::into).collect(),
+                deposits: value.deposits.into_iter().map(Into::into).collect(),
+                voluntary_exits: value.voluntary_exits.into(),
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use unionlabs::primitives::H256;
 #[cfg(feature = "ssz")]
-use {crate::chain_spec::DEPOSIT_CONTRACT_TREE_DEPTH, ssz::types::Vector};
+use {crate::chain_spec::DEPOSIT_CONTRACT_TREE_DEPTH, ::ssz::types::Vector};
 
 use crate::phase0::DepositData;
 
@@ -16,7 +16,7 @@ pub struct Deposit {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -27,3 +27,17 @@ pub struct DepositSsz<C: DEPOSIT_CONTRACT_TREE_DEPTH> {
     pub proof: Vector<H256, C::DEPOSIT_CONTRACT_TREE_DEPTH>,
     pub data: DepositData,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: DEPOSIT_CONTRACT_TREE_DEPTH> From<DepositSsz<C>> for Deposit {
+        fn from(value: DepositSsz<C>) -> Self {
+            Self {
+                proof: value.proof.into(),
+                data: value.data,
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use unionlabs::primitives::H768;
 #[cfg(feature = "ssz")]
-use {crate::chain_spec::MAX_VALIDATORS_PER_COMMITTEE, ssz::types::List};
+use {crate::chain_spec::MAX_VALIDATORS_PER_COMMITTEE, ::ssz::types::List};
 
 use crate::phase0::AttestationData;
 
@@ -17,7 +17,7 @@ pub struct IndexedAttestation {
 }
 
 #[cfg(feature = "ssz")]
-#[derive(Debug, Clone, PartialEq, Eq, ssz::Ssz)]
+#[derive(Debug, Clone, PartialEq, Eq, ::ssz::Ssz)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Serialize, serde::Deserialize),
@@ -28,3 +28,18 @@ pub struct IndexedAttestationSsz<C: MAX_VALIDATORS_PER_COMMITTEE> {
     pub data: AttestationData,
     pub signature: H768,
 }
+
+#[cfg(feature = "ssz")]
+pub mod ssz {
+    use super::*;
+
+    impl<C: MAX_VALIDATORS_PER_COMMITTEE> From<IndexedAttestationSsz<C>> for IndexedAttestation {
+        fn from(value: IndexedAttestationSsz<C>) -> Self {
+            Self {
+                attesting_indices: value.attesting_indices.into(),
+                data: value.data,
+                signature: value.signature,
+            }
+        }
+    }
+}
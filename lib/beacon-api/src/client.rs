@@ -5,18 +5,30 @@ use std::{
     time::Duration,
 };
 
-use beacon_api_types::custom_types::Slot;
+use beacon_api_types::{
+    chain_spec::{ChainSpec, Mainnet, Minimal, PresetBaseKind},
+    custom_types::{ForkDigest, Slot, Version},
+    phase0::ForkData,
+};
 use moka::{future::Cache, ops::compute::Op};
-use reqwest::{Client, StatusCode};
+use reqwest::{
+    header::{HeaderMap, ACCEPT, CONTENT_TYPE},
+    Client, StatusCode,
+};
+use rpc_pool::{InvalidPool, Pool, PoolConfig};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{value::RawValue, Value};
-use tracing::{debug, info, trace};
-use unionlabs::{primitives::H256, ErrorReporter};
+use ssz::Ssz;
+use tracing::{debug, info, trace, warn};
+use unionlabs::{
+    primitives::{FixedBytes, H256},
+    ErrorReporter,
+};
 
 use crate::{
-    errors::Error,
+    errors::{Error, SszError},
     routes::{
-        block::BeaconBlockResponse,
+        block::{BeaconBlockResponse, BeaconBlockResponseTypes},
         genesis::{GenesisData, GenesisResponse},
         header::BeaconBlockHeaderResponse,
         light_client_bootstrap::LightClientBootstrapResponseTypes,
@@ -28,19 +40,85 @@ use crate::{
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+const CONSENSUS_VERSION_HEADER: &str = "Eth-Consensus-Version";
+const EXECUTION_OPTIMISTIC_HEADER: &str = "Eth-Execution-Optimistic";
+const FINALIZED_HEADER: &str = "Eth-Finalized";
+
+const JSON: &str = "application/json";
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Prefer SSZ, but accept JSON from beacon nodes that don't support SSZ for a route.
+const ACCEPT_SSZ_OR_JSON: &str = "application/octet-stream;q=1.0,application/json;q=0.9";
+
+/// The consensus versions of the forks, in the same order as
+/// [`ForkParameters::into_fork_schedule_list`](fork_schedules::ForkParameters::into_fork_schedule_list).
+const FORK_VERSIONS: [&str; 7] = [
+    "phase0",
+    "altair",
+    "bellatrix",
+    "capella",
+    "deneb",
+    "electra",
+    "fulu",
+];
+
 #[derive(Debug, Clone)]
 pub struct BeaconApiClient {
     client: Client,
-    base_url: String,
+    /// The base urls of all of the configured beacon nodes.
+    base_urls: Pool<String>,
+    encoding: Encoding,
     spec: Cache<(), Spec>,
     genesis: Cache<(), GenesisData>,
 }
 
 impl BeaconApiClient {
     pub fn new(base_url: impl AsRef<str>) -> Self {
+        let base_url = base_url.as_ref().trim_end_matches('/').to_owned();
+
+        Self::with_base_urls(
+            Pool::new([(base_url.clone(), base_url)], PoolConfig::default())
+                .expect("pool contains one endpoint; qed;"),
+        )
+    }
+
+    /// Create a client backed by multiple beacon nodes, with automatic failover between them. See
+    /// [`rpc_pool`] for more information.
+    ///
+    /// This must be called from within a tokio runtime, as it spawns the background health checks.
+    pub fn new_pool(
+        base_urls: impl IntoIterator<Item = impl AsRef<str>>,
+        config: PoolConfig,
+    ) -> core::result::Result<Self, InvalidPool> {
+        let this = Self::with_base_urls(Pool::new(
+            base_urls.into_iter().map(|base_url| {
+                let base_url = base_url.as_ref().trim_end_matches('/').to_owned();
+                (base_url.clone(), base_url)
+            }),
+            config,
+        )?);
+
+        let client = this.client.clone();
+        this.base_urls.spawn_health_checks(move |base_url| {
+            let client = client.clone();
+
+            async move {
+                client
+                    .get(format!("{base_url}/eth/v1/node/health"))
+                    .send()
+                    .await?
+                    .error_for_status()
+            }
+        });
+
+        Ok(this)
+    }
+
+    fn with_base_urls(base_urls: Pool<String>) -> Self {
         Self {
             client: reqwest::Client::new(),
-            base_url: base_url.as_ref().trim_end_matches('/').into(),
+            base_urls,
+            encoding: Encoding::default(),
 
             // refresh these caches every 12 hours
             spec: moka::future::CacheBuilder::new(1)
@@ -58,6 +136,14 @@ impl BeaconApiClient {
         }
     }
 
+    /// Set the preferred response encoding for the routes that support SSZ. Defaults to
+    /// [`Encoding::Json`].
+    #[must_use]
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     pub async fn spec(&self) -> Result<Spec> {
         Ok(self
             .spec
@@ -77,8 +163,26 @@ impl BeaconApiClient {
     pub async fn finality_update(
         &self,
     ) -> Result<VersionedResponse<LightClientFinalityUpdateResponseTypes>> {
-        self.get_json("/eth/v1/beacon/light_client/finality_update")
+        self.get_ssz_or_json(
+            "/eth/v1/beacon/light_client/finality_update".to_owned(),
+            |ctx, res| ctx.decode_versioned(res.version()?, &res.body),
+        )
+        .await
+    }
+
+    /// Fetch the slot of the latest finalized block header. If multiple beacon nodes are configured,
+    /// this is read with a quorum across all of them (see [`Pool::quorum`]).
+    pub async fn finalized_slot(&self) -> core::result::Result<Slot, rpc_pool::Error<Error>> {
+        let path = &format!("/eth/v1/beacon/headers/{}", BlockId::Finalized);
+
+        self.base_urls
+            .quorum(|base_url| async move {
+                self.get_json_from::<BeaconBlockHeaderResponse>(base_url, path)
+                    .await
+                    .map(|res| res.data.header.message.slot.get())
+            })
             .await
+            .map(Slot::new)
     }
 
     pub async fn header(&self, block_id: BlockId) -> Result<BeaconBlockHeaderResponse> {
@@ -87,17 +191,25 @@ impl BeaconApiClient {
     }
 
     pub async fn block(&self, block_id: BlockId) -> Result<BeaconBlockResponse> {
-        self.get_json(format!("/eth/v2/beacon/blocks/{block_id}"))
-            .await
+        self.get_ssz_or_json(format!("/eth/v2/beacon/blocks/{block_id}"), |ctx, res| {
+            Ok(BeaconBlockResponse {
+                response: ctx
+                    .decode_versioned::<BeaconBlockResponseTypes>(res.version()?, &res.body)?,
+                execution_optimistic: res.bool_header(EXECUTION_OPTIMISTIC_HEADER),
+                finalized: res.bool_header(FINALIZED_HEADER),
+            })
+        })
+        .await
     }
 
     pub async fn bootstrap(
         &self,
         finalized_root: H256,
     ) -> Result<VersionedResponse<LightClientBootstrapResponseTypes>> {
-        self.get_json(format!(
-            "/eth/v1/beacon/light_client/bootstrap/{finalized_root}"
-        ))
+        self.get_ssz_or_json(
+            format!("/eth/v1/beacon/light_client/bootstrap/{finalized_root}"),
+            |ctx, res| ctx.decode_versioned(res.version()?, &res.body),
+        )
         .await
     }
 
@@ -125,9 +237,19 @@ impl BeaconApiClient {
         start_period: u64,
         count: u64,
     ) -> Result<Vec<VersionedResponse<LightClientUpdateResponseTypes>>> {
-        self.get_json(format!(
-            "/eth/v1/beacon/light_client/updates?start_period={start_period}&count={count}"
-        ))
+        self.get_ssz_or_json(
+            format!(
+                "/eth/v1/beacon/light_client/updates?start_period={start_period}&count={count}"
+            ),
+            |ctx, res| {
+                decode_response_chunks(&res.body)?
+                    .into_iter()
+                    .map(|(fork_digest, payload)| {
+                        ctx.decode_versioned(ctx.version_of_fork_digest(fork_digest)?, payload)
+                    })
+                    .collect()
+            },
+        )
         .await
     }
 
@@ -207,20 +329,102 @@ impl BeaconApiClient {
     // Helper functions
 
     async fn get_json<T: DeserializeOwned>(&self, path: impl Into<String>) -> Result<T> {
-        let url = format!("{}{}", self.base_url, path.into());
+        let path = path.into();
 
-        debug!(%url, "get_json");
+        self.base_urls
+            .request(|base_url| self.get_json_from(base_url, &path))
+            .await
+    }
 
-        let res = self.client.get(url).send().await?;
+    async fn get_json_from<T: DeserializeOwned>(&self, base_url: &str, path: &str) -> Result<T> {
+        let res = self.get_raw_from(base_url, path, JSON).await?;
 
-        match res.status() {
-            StatusCode::OK => {
-                let bytes = res.bytes().await?;
+        trace!(response = %String::from_utf8_lossy(&res.body), "get_json");
 
-                trace!(response = %String::from_utf8_lossy(&bytes), "get_json");
+        Ok(serde_json::from_slice(&res.body).map_err(Error::Json)?)
+    }
+
+    /// Fetch `path`, negotiating an SSZ response if the configured [`Encoding`] is
+    /// [`Encoding::Ssz`].
+    ///
+    /// SSZ responses are decoded with `decode_ssz`. If the beacon node responds with JSON instead,
+    /// or the SSZ response cannot be decoded (for example, for forks without SSZ types), the JSON
+    /// response is used.
+    async fn get_ssz_or_json<T: DeserializeOwned>(
+        &self,
+        path: String,
+        decode_ssz: impl FnOnce(&SszContext, &RawResponse) -> core::result::Result<T, SszError>,
+    ) -> Result<T> {
+        if self.encoding == Encoding::Ssz {
+            let res = self
+                .base_urls
+                .request(|base_url| self.get_raw_from(base_url, &path, ACCEPT_SSZ_OR_JSON))
+                .await;
 
-                Ok(serde_json::from_slice(&bytes).map_err(Error::Json)?)
+            match res {
+                Ok(res) if res.is_ssz() => {
+                    let ctx = self.ssz_context().await?;
+
+                    match decode_ssz(&ctx, &res) {
+                        Ok(t) => return Ok(t),
+                        Err(err) => warn!(
+                            %path,
+                            err = %ErrorReporter(err),
+                            "unable to decode ssz response, falling back to json"
+                        ),
+                    }
+                }
+                Ok(res) => {
+                    trace!(response = %String::from_utf8_lossy(&res.body), "get_json");
+
+                    return Ok(serde_json::from_slice(&res.body).map_err(Error::Json)?);
+                }
+                Err(Error::Other { code, .. })
+                    if code == StatusCode::NOT_ACCEPTABLE
+                        || code == StatusCode::UNSUPPORTED_MEDIA_TYPE =>
+                {
+                    debug!(%path, %code, "ssz not supported, falling back to json");
+                }
+                Err(err) => return Err(err),
             }
+        }
+
+        self.get_json(path).await
+    }
+
+    async fn ssz_context(&self) -> Result<SszContext> {
+        let spec = self.spec().await?;
+        let genesis = self.genesis().await?;
+
+        Ok(SszContext {
+            preset_base: spec.preset_base,
+            fork_digests: spec
+                .fork_parameters()
+                .into_fork_schedule_list()
+                .into_iter()
+                .zip(FORK_VERSIONS)
+                .map(|(fork, version)| {
+                    (
+                        compute_fork_digest(fork.current_version, genesis.genesis_validators_root),
+                        version,
+                    )
+                })
+                .collect(),
+        })
+    }
+
+    async fn get_raw_from(&self, base_url: &str, path: &str, accept: &str) -> Result<RawResponse> {
+        let url = format!("{base_url}{path}");
+
+        debug!(%url, %accept, "get");
+
+        let res = self.client.get(url).header(ACCEPT, accept).send().await?;
+
+        match res.status() {
+            StatusCode::OK => Ok(RawResponse {
+                headers: res.headers().clone(),
+                body: res.bytes().await?.to_vec(),
+            }),
             StatusCode::NOT_FOUND => {
                 let raw = res.json::<Value>().await?;
                 trace!(%raw, "not found");
@@ -239,8 +443,133 @@ impl BeaconApiClient {
     }
 }
 
+/// A successful response from a beacon node.
+struct RawResponse {
+    headers: HeaderMap,
+    body: Vec<u8>,
+}
+
+impl RawResponse {
+    fn is_ssz(&self) -> bool {
+        self.header(CONTENT_TYPE.as_str())
+            .is_some_and(|content_type| content_type.starts_with(OCTET_STREAM))
+    }
+
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    fn bool_header(&self, name: &str) -> Option<bool> {
+        self.header(name).and_then(|value| value.parse().ok())
+    }
+
+    fn version(&self) -> core::result::Result<&str, SszError> {
+        self.header(CONSENSUS_VERSION_HEADER)
+            .ok_or(SszError::MissingVersion)
+    }
+}
+
+/// The chain-specific information required to decode SSZ responses.
+struct SszContext {
+    preset_base: PresetBaseKind,
+    /// The fork digest of each fork of the chain, along with its consensus version.
+    fork_digests: Vec<(ForkDigest, &'static str)>,
+}
+
+impl SszContext {
+    fn decode_versioned<T: SszVersionedResponseTypes>(
+        &self,
+        version: &str,
+        bytes: &[u8],
+    ) -> core::result::Result<VersionedResponse<T>, SszError> {
+        match self.preset_base {
+            PresetBaseKind::Minimal => T::decode_ssz::<Minimal>(version, bytes),
+            PresetBaseKind::Mainnet => T::decode_ssz::<Mainnet>(version, bytes),
+        }
+    }
+
+    fn version_of_fork_digest(
+        &self,
+        fork_digest: ForkDigest,
+    ) -> core::result::Result<&'static str, SszError> {
+        self.fork_digests
+            .iter()
+            .find(|(digest, _)| *digest == fork_digest)
+            .map(|(_, version)| *version)
+            .ok_or(SszError::UnknownForkDigest(fork_digest))
+    }
+}
+
+/// <https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#compute_fork_digest>
+fn compute_fork_digest(current_version: Version, genesis_validators_root: H256) -> ForkDigest {
+    let root = ForkData {
+        current_version,
+        genesis_validators_root,
+    }
+    .tree_hash_root();
+
+    ForkDigest(FixedBytes::new(
+        root.get()[..4].try_into().expect("slice is 4 bytes; qed;"),
+    ))
+}
+
+/// Split a sequence of `response_chunk`s, as returned by the SSZ encoding of the light client
+/// updates route, into the fork digest and SSZ payload of each chunk.
+///
+/// ```text
+/// response_chunk ::= <length> | <context> | <ssz-payload>
+/// ```
+///
+/// where `length` is the little-endian `uint64` length of `context` and `ssz-payload`, and
+/// `context` is the fork digest of the fork the payload is encoded for.
+fn decode_response_chunks(
+    mut bytes: &[u8],
+) -> core::result::Result<Vec<(ForkDigest, &[u8])>, SszError> {
+    let mut chunks = vec![];
+
+    while !bytes.is_empty() {
+        let (len, rest) = bytes
+            .split_first_chunk::<8>()
+            .ok_or(SszError::InvalidChunk)?;
+
+        let len = usize::try_from(u64::from_le_bytes(*len)).map_err(|_| SszError::InvalidChunk)?;
+
+        if rest.len() < len {
+            return Err(SszError::InvalidChunk);
+        }
+
+        let (chunk, rest) = rest.split_at(len);
+
+        let (fork_digest, payload) = chunk
+            .split_first_chunk::<4>()
+            .ok_or(SszError::InvalidChunk)?;
+
+        chunks.push((ForkDigest(FixedBytes::new(*fork_digest)), payload));
+
+        bytes = rest;
+    }
+
+    Ok(chunks)
+}
+
+/// Decode `bytes` as the SSZ type `S`, and convert it to `T`.
+pub(crate) fn decode_ssz_as<S: Ssz, T: From<S>>(bytes: &[u8]) -> core::result::Result<T, SszError> {
+    Ok(S::from_ssz_bytes(bytes)?.into())
+}
+
+/// The preferred encoding of beacon node responses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Encoding {
+    /// Always request JSON.
+    ///
+    /// This is the default, since SSZ responses for forks this client doesn't know about (such as
+    /// the blob parameter only forks introduced in Fulu, which change the fork digest without a new
+    /// fork version) can't be decoded and have to be requested again as JSON.
+    #[default]
     Json,
+    /// Request SSZ (`application/octet-stream`) for the block and light client routes, falling back
+    /// to JSON if the beacon node doesn't support it or the response can't be decoded.
     Ssz,
 }
 
@@ -372,6 +701,16 @@ pub trait VersionedResponseTypes {
     type Electra: Debug + Serialize + DeserializeOwned;
 }
 
+/// [`VersionedResponseTypes`] that can also be decoded from their SSZ encoding.
+pub trait SszVersionedResponseTypes: VersionedResponseTypes + Sized {
+    /// Decode the SSZ encoding of the type for `version`, as returned in the
+    /// `Eth-Consensus-Version` header.
+    fn decode_ssz<C: ChainSpec>(
+        version: &str,
+        bytes: &[u8],
+    ) -> core::result::Result<VersionedResponse<Self>, SszError>;
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BlockId {
     Head,
@@ -404,3 +743,167 @@ impl Display for BlockId {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use beacon_api_types::phase0::Fork;
+    use fork_schedules::SEPOLIA;
+    use hex_literal::hex;
+
+    use super::*;
+
+    const SEPOLIA_GENESIS_VALIDATORS_ROOT: H256 = H256::new(hex!(
+        "d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078"
+    ));
+
+    fn sepolia_ssz_context() -> SszContext {
+        SszContext {
+            preset_base: PresetBaseKind::Mainnet,
+            fork_digests: SEPOLIA
+                .into_fork_schedule_list()
+                .into_iter()
+                .zip(FORK_VERSIONS)
+                .map(|(fork, version)| {
+                    (
+                        compute_fork_digest(fork.current_version, SEPOLIA_GENESIS_VALIDATORS_ROOT),
+                        version,
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Wrap the `data` of a beacon API response in a versioned JSON response.
+    fn versioned_json(version: &str, data: &str) -> String {
+        format!(r#"{{"version":"{version}","data":{data}}}"#)
+    }
+
+    // The JSON fixtures are responses from a Sepolia beacon node. The SSZ fixtures are their
+    // encodings, produced with a reference encoder written from the consensus specs independently
+    // of the `ssz` crate, so that they check the SSZ types against the wire format and not just
+    // against themselves.
+
+    #[test]
+    fn finality_update_ssz_matches_json() {
+        let json = include_str!(
+            "../../../cosmwasm/ibc-union/lightclient/ethereum/src/test/finality_update_7167008.json"
+        );
+        let ssz = include_bytes!("./test/light_client_finality_update_electra_7167008.ssz");
+
+        assert_eq!(
+            sepolia_ssz_context()
+                .decode_versioned::<LightClientFinalityUpdateResponseTypes>("electra", ssz)
+                .unwrap(),
+            serde_json::from_str(&versioned_json("electra", json)).unwrap(),
+        );
+    }
+
+    #[test]
+    fn light_client_updates_ssz_matches_json() {
+        let json =
+            include_str!("../../ethereum-sync-protocol/src/test/light_client_update_6553725.json");
+        let ssz = include_bytes!("./test/light_client_update_deneb_6553725.ssz");
+
+        let Fork {
+            current_version, ..
+        } = SEPOLIA.fork(fork_schedules::Forks::Deneb).unwrap();
+        let fork_digest = compute_fork_digest(current_version, SEPOLIA_GENESIS_VALIDATORS_ROOT);
+
+        let mut bytes = vec![];
+        bytes.extend((4 + ssz.len() as u64).to_le_bytes());
+        bytes.extend(fork_digest.0.get());
+        bytes.extend(ssz);
+
+        let ctx = sepolia_ssz_context();
+
+        let found = decode_response_chunks(&bytes)
+            .unwrap()
+            .into_iter()
+            .map(|(fork_digest, payload)| {
+                ctx.decode_versioned(ctx.version_of_fork_digest(fork_digest)?, payload)
+            })
+            .collect::<core::result::Result<Vec<_>, SszError>>()
+            .unwrap();
+
+        assert_eq!(
+            found,
+            [
+                serde_json::from_str::<VersionedResponse<LightClientUpdateResponseTypes>>(
+                    &versioned_json("deneb", json)
+                )
+                .unwrap()
+            ],
+        );
+    }
+
+    #[test]
+    fn response_chunks() {
+        let Fork {
+            current_version, ..
+        } = SEPOLIA.fork(fork_schedules::Forks::Deneb).unwrap();
+        let deneb = compute_fork_digest(current_version, SEPOLIA_GENESIS_VALIDATORS_ROOT);
+
+        let Fork {
+            current_version, ..
+        } = SEPOLIA.fork(fork_schedules::Forks::Electra).unwrap();
+        let electra = compute_fork_digest(current_version, SEPOLIA_GENESIS_VALIDATORS_ROOT);
+
+        let mut bytes = vec![];
+        for (fork_digest, payload) in [(deneb, &[1, 2, 3][..]), (electra, &[][..])] {
+            bytes.extend((4 + payload.len() as u64).to_le_bytes());
+            bytes.extend(fork_digest.0.get());
+            bytes.extend(payload);
+        }
+
+        let chunks = decode_response_chunks(&bytes).unwrap();
+
+        assert_eq!(chunks, [(deneb, &[1, 2, 3][..]), (electra, &[][..])]);
+
+        let ctx = sepolia_ssz_context();
+
+        assert_eq!(ctx.version_of_fork_digest(deneb).unwrap(), "deneb");
+        assert_eq!(ctx.version_of_fork_digest(electra).unwrap(), "electra");
+    }
+
+    #[test]
+    fn invalid_response_chunks() {
+        assert!(decode_response_chunks(&[]).unwrap().is_empty());
+
+        // truncated length prefix
+        assert!(matches!(
+            decode_response_chunks(&[0; 7]),
+            Err(SszError::InvalidChunk)
+        ));
+
+        // length is longer than the remaining bytes
+        assert!(matches!(
+            decode_response_chunks(&[5, 0, 0, 0, 0, 0, 0, 0, 1, 2, 3, 4]),
+            Err(SszError::InvalidChunk)
+        ));
+
+        // missing context
+        assert!(matches!(
+            decode_response_chunks(&[2, 0, 0, 0, 0, 0, 0, 0, 1, 2]),
+            Err(SszError::InvalidChunk)
+        ));
+    }
+
+    #[test]
+    fn unsupported_versions_are_rejected() {
+        assert!(matches!(
+            sepolia_ssz_context().decode_versioned::<BeaconBlockResponseTypes>("altair", &[]),
+            Err(SszError::UnsupportedVersion(version)) if version == "altair"
+        ));
+
+        assert!(matches!(
+            sepolia_ssz_context()
+                .decode_versioned::<LightClientBootstrapResponseTypes>("capella", &[]),
+            Err(SszError::UnsupportedVersion(version)) if version == "capella"
+        ));
+
+        assert!(matches!(
+            sepolia_ssz_context().version_of_fork_digest(ForkDigest(FixedBytes::new([0; 4]))),
+            Err(SszError::UnknownForkDigest(_))
+        ));
+    }
+}
@@ -1,4 +1,6 @@
+use beacon_api_types::custom_types::ForkDigest;
 use reqwest::StatusCode;
+use rpc_pool::EndpointError;
 use serde_json::Value;
 
 // REVIEW: Merge internal/not found with other?
@@ -15,3 +17,34 @@ pub enum Error {
     #[error("unknown error ({code}): {text}")]
     Other { code: StatusCode, text: String },
 }
+
+impl EndpointError for Error {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            Error::Http(_) => true,
+            Error::Other { code, .. } => {
+                code.is_server_error() || *code == StatusCode::TOO_MANY_REQUESTS
+            }
+            // the beacon node is reachable, but doesn't have (or failed to produce) the requested
+            // data; see `BeaconApiClient::bootstrap_for_slot`
+            Error::Internal(_) | Error::NotFound(_) | Error::Json(_) => false,
+        }
+    }
+}
+
+/// An error decoding an SSZ (`application/octet-stream`) response. These are never returned from
+/// [`BeaconApiClient`](crate::client::BeaconApiClient) methods; the request is retried with JSON
+/// instead.
+#[derive(Debug, thiserror::Error)]
+pub enum SszError {
+    #[error("missing consensus version header")]
+    MissingVersion,
+    #[error("ssz decoding is not supported for version {0}")]
+    UnsupportedVersion(String),
+    #[error("unknown fork digest {0:?}")]
+    UnknownForkDigest(ForkDigest),
+    #[error("invalid response chunk")]
+    InvalidChunk,
+    #[error("ssz decoding error")]
+    Decode(#[from] ssz::decode::DecodeError),
+}
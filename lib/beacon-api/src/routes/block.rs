@@ -1,9 +1,12 @@
-use beacon_api_types::{bellatrix, capella, deneb, electra, phase0};
+use beacon_api_types::{bellatrix, capella, chain_spec::ChainSpec, deneb, electra, phase0};
 use serde::{Deserialize, Serialize};
 use serde_json::value::RawValue;
 use unionlabs::{primitives::H256, ErrorReporter};
 
-use crate::client::{VersionedResponse, VersionedResponseTypes};
+use crate::{
+    client::{decode_ssz_as, SszVersionedResponseTypes, VersionedResponse, VersionedResponseTypes},
+    errors::SszError,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(try_from = "BeaconBlockResponseRaw")]
@@ -33,6 +36,37 @@ impl VersionedResponseTypes for BeaconBlockResponseTypes {
     type Electra = electra::SignedBeaconBlock;
 }
 
+impl SszVersionedResponseTypes for BeaconBlockResponseTypes {
+    fn decode_ssz<C: ChainSpec>(
+        version: &str,
+        bytes: &[u8],
+    ) -> Result<VersionedResponse<Self>, SszError> {
+        Ok(match version {
+            "phase0" => VersionedResponse::Phase0(decode_ssz_as::<
+                phase0::SignedBeaconBlockSsz<C>,
+                _,
+            >(bytes)?),
+            "bellatrix" => VersionedResponse::Bellatrix(decode_ssz_as::<
+                bellatrix::SignedBeaconBlockSsz<C>,
+                _,
+            >(bytes)?),
+            "capella" => VersionedResponse::Capella(decode_ssz_as::<
+                capella::SignedBeaconBlockSsz<C>,
+                _,
+            >(bytes)?),
+            "deneb" => {
+                VersionedResponse::Deneb(decode_ssz_as::<deneb::SignedBeaconBlockSsz<C>, _>(bytes)?)
+            }
+            "electra" => VersionedResponse::Electra(decode_ssz_as::<
+                electra::SignedBeaconBlockSsz<C>,
+                _,
+            >(bytes)?),
+            // altair blocks are returned as phase0 blocks, which don't have the same ssz encoding
+            version => return Err(SszError::UnsupportedVersion(version.to_owned())),
+        })
+    }
+}
+
 #[derive(Deserialize)]
 pub struct BeaconBlockResponseRaw<'a> {
     // TODO: Make not optional
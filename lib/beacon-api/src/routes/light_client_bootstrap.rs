@@ -1,8 +1,11 @@
-use beacon_api_types::{altair, capella, deneb, electra};
+use beacon_api_types::{altair, capella, chain_spec::ChainSpec, deneb, electra};
 use serde::{Deserialize, Serialize};
 use unionlabs::never::Never;
 
-use crate::client::VersionedResponseTypes;
+use crate::{
+    client::{decode_ssz_as, SszVersionedResponseTypes, VersionedResponse, VersionedResponseTypes},
+    errors::SszError,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum LightClientBootstrapResponseTypes {}
@@ -15,3 +18,22 @@ impl VersionedResponseTypes for LightClientBootstrapResponseTypes {
     type Deneb = deneb::LightClientBootstrap;
     type Electra = electra::LightClientBootstrap;
 }
+
+impl SszVersionedResponseTypes for LightClientBootstrapResponseTypes {
+    fn decode_ssz<C: ChainSpec>(
+        version: &str,
+        bytes: &[u8],
+    ) -> Result<VersionedResponse<Self>, SszError> {
+        Ok(match version {
+            "deneb" => VersionedResponse::Deneb(decode_ssz_as::<
+                deneb::LightClientBootstrapSsz<C>,
+                _,
+            >(bytes)?),
+            "electra" => VersionedResponse::Electra(decode_ssz_as::<
+                electra::LightClientBootstrapSsz<C>,
+                _,
+            >(bytes)?),
+            version => return Err(SszError::UnsupportedVersion(version.to_owned())),
+        })
+    }
+}
@@ -1,7 +1,8 @@
 use beacon_api_types::{
     chain_spec::PresetBaseKind,
-    custom_types::{Slot, Version},
+    custom_types::{Epoch, Slot, Version},
 };
+use fork_schedules::ForkParameters;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -25,20 +26,35 @@ pub struct Spec {
     #[serde(with = "::serde_utils::string")]
     pub genesis_delay: u64,
 
-    // pub altair_fork_version: Option<Version>,
-    // pub altair_fork_epoch: Option<u64>,
+    #[serde(default)]
+    pub altair_fork_version: Option<Version>,
+    #[serde(default)]
+    pub altair_fork_epoch: Option<Epoch>,
 
-    // pub bellatrix_fork_version: Option<Version>,
-    // pub bellatrix_fork_epoch: Option<u64>,
+    #[serde(default)]
+    pub bellatrix_fork_version: Option<Version>,
+    #[serde(default)]
+    pub bellatrix_fork_epoch: Option<Epoch>,
 
-    // pub capella_fork_version: Option<Version>,
-    // pub capella_fork_epoch: Option<u64>,
+    #[serde(default)]
+    pub capella_fork_version: Option<Version>,
+    #[serde(default)]
+    pub capella_fork_epoch: Option<Epoch>,
 
-    // pub deneb_fork_version: Option<Version>,
-    // pub deneb_fork_epoch: Option<u64>,
+    #[serde(default)]
+    pub deneb_fork_version: Option<Version>,
+    #[serde(default)]
+    pub deneb_fork_epoch: Option<Epoch>,
+
+    #[serde(default)]
+    pub electra_fork_version: Option<Version>,
+    #[serde(default)]
+    pub electra_fork_epoch: Option<Epoch>,
 
-    // pub electra_fork_version: Option<Version>,
-    // pub electra_fork_epoch: Option<u64>,
+    #[serde(default)]
+    pub fulu_fork_version: Option<Version>,
+    #[serde(default)]
+    pub fulu_fork_epoch: Option<Epoch>,
     #[serde(with = "::serde_utils::string")]
     pub seconds_per_slot: u64,
     // SECONDS_PER_ETH1_BLOCK: 14,
@@ -152,4 +168,66 @@ impl Spec {
     pub fn period(&self) -> u64 {
         self.epochs_per_sync_committee_period * self.slots_per_epoch.get()
     }
+
+    /// The fork parameters of this beacon chain, built from the `*_FORK_VERSION` and
+    /// `*_FORK_EPOCH` values of the spec. Forks that are not scheduled are not included.
+    pub fn fork_parameters(&self) -> ForkParameters {
+        ForkParameters::from_spec(
+            self.genesis_fork_version,
+            [
+                (self.altair_fork_version, self.altair_fork_epoch),
+                (self.bellatrix_fork_version, self.bellatrix_fork_epoch),
+                (self.capella_fork_version, self.capella_fork_epoch),
+                (self.deneb_fork_version, self.deneb_fork_epoch),
+                (self.electra_fork_version, self.electra_fork_epoch),
+                (self.fulu_fork_version, self.fulu_fork_epoch),
+            ]
+            .map(|(version, epoch)| version.zip(epoch)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fork_schedules::{ForkSchedule, Forks, SEPOLIA_CHAIN_ID};
+
+    use super::*;
+
+    #[test]
+    fn fork_parameters() {
+        let spec = serde_json::from_value::<SpecResponse>(serde_json::json!({
+            "data": {
+                "PRESET_BASE": "mainnet",
+                "CONFIG_NAME": "sepolia",
+                "GENESIS_FORK_VERSION": "0x90000069",
+                "GENESIS_DELAY": "86400",
+                "ALTAIR_FORK_VERSION": "0x90000070",
+                "ALTAIR_FORK_EPOCH": "50",
+                "BELLATRIX_FORK_VERSION": "0x90000071",
+                "BELLATRIX_FORK_EPOCH": "100",
+                "CAPELLA_FORK_VERSION": "0x90000072",
+                "CAPELLA_FORK_EPOCH": "56832",
+                "DENEB_FORK_VERSION": "0x90000073",
+                "DENEB_FORK_EPOCH": "132608",
+                "ELECTRA_FORK_VERSION": "0x90000074",
+                "ELECTRA_FORK_EPOCH": "222464",
+                "FULU_FORK_VERSION": "0x90000075",
+                "FULU_FORK_EPOCH": "18446744073709551615",
+                "SECONDS_PER_SLOT": "12",
+                "SLOTS_PER_EPOCH": "32",
+                "SYNC_COMMITTEE_SIZE": "512",
+                "EPOCHS_PER_SYNC_COMMITTEE_PERIOD": "256"
+            }
+        }))
+        .unwrap()
+        .data;
+
+        let fork_parameters = spec.fork_parameters();
+
+        assert_eq!(
+            fork_parameters,
+            ForkParameters::from(ForkSchedule::for_chain_id(SEPOLIA_CHAIN_ID))
+        );
+        assert_eq!(fork_parameters.fork(Forks::Fulu), None);
+    }
 }
@@ -0,0 +1,17 @@
+use crate::header::Header;
+
+/// Two headers that prove that two different L2 blocks have been committed to by finalized output
+/// proposals on the L1 `L2OutputOracle` at the same L2 block number.
+///
+/// Each header is proven against the L1 state root at its own `l1_height`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct Misbehaviour {
+    pub header_a: Header,
+    pub header_b: Header,
+}
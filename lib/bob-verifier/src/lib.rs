@@ -26,6 +26,8 @@ pub enum Error {
     InvalidIbcContractProof(#[source] evm_storage_verifier::error::Error),
     #[error("the l2 header is not finalized")]
     HeaderNotFinalized,
+    #[error("the headers do not prove a misbehaviour")]
+    MisbehaviourNotFound,
 }
 
 pub fn verify_header(
@@ -82,6 +84,42 @@ pub fn verify_header(
     Ok(())
 }
 
+/// Verify that the provided headers prove that two different L2 blocks have been committed to by
+/// finalized output proposals at the same L2 height.
+///
+/// Both headers are verified with [`verify_header`], against their respective L1 state roots. The
+/// L1 state roots are expected to be trusted by the caller (i.e. read from the L1 client).
+pub fn verify_misbehaviour(
+    client_state: &ClientStateV1,
+    (header_a, l1_state_root_a): (&Header, H256),
+    (header_b, l1_state_root_b): (&Header, H256),
+    current_timestamp: u64,
+    l2_finalization_period_seconds: u64,
+) -> Result<(), Error> {
+    if header_a.l2_header.number != header_b.l2_header.number
+        || header_a.l2_header.hash() == header_b.l2_header.hash()
+    {
+        return Err(Error::MisbehaviourNotFound);
+    }
+
+    verify_header(
+        client_state,
+        header_a,
+        l1_state_root_a,
+        current_timestamp,
+        l2_finalization_period_seconds,
+    )?;
+    verify_header(
+        client_state,
+        header_b,
+        l1_state_root_b,
+        current_timestamp,
+        l2_finalization_period_seconds,
+    )?;
+
+    Ok(())
+}
+
 pub fn compute_output_proposal_slot(l2_outputs_slot: U256, index: u32) -> U256 {
     let offset = Slot::Offset(l2_outputs_slot);
     // The size in slots of the OutputProposal structures, (sizeof(bytes32) + 2*sizeof(u128) / sizeof(u256)) = 2
@@ -121,14 +159,14 @@ pub fn verify_l2_header_is_related_to_output_root_proof(
 #[cfg(test)]
 mod tests {
     use bob_light_client_types::{header::Nullable, ClientStateV1, Header};
+    use evm_storage_verifier::test_utils::SecureTrie;
     use hex_literal::hex;
     use ibc_union_spec::ClientId;
     use unionlabs::encoding::{DecodeAs, Json};
 
     use super::*;
 
-    #[test]
-    fn test_update_ok() {
+    fn fixture() -> (ClientStateV1, Header, H256) {
         // Outputs slot is 3
         // OutputIndex 700 = L2 Block 15141600 (0xE70AE0)
         // Slot index = 87903029871075914254377627908054574944891091886930582284385770809450030038483
@@ -214,9 +252,16 @@ mod tests {
         )
         .unwrap();
 
-        let l1_state_root =
+        let l1_state_root: H256 =
             hex!("67bca5ce21aa5c48991a9aa2705371bd015fab01402dbcd2818c0b2cb19d5a6b").into();
 
+        (client_state, header, l1_state_root)
+    }
+
+    #[test]
+    fn test_update_ok() {
+        let (client_state, header, l1_state_root) = fixture();
+
         let r1 = verify_header(
             &client_state,
             &header,
@@ -238,6 +283,114 @@ mod tests {
         assert_eq!(r2, Err(Error::HeaderNotFinalized));
     }
 
+    #[test]
+    fn test_misbehaviour_same_header() {
+        let (client_state, header, l1_state_root) = fixture();
+
+        assert_eq!(
+            verify_misbehaviour(
+                &client_state,
+                (&header, l1_state_root),
+                (&header, l1_state_root),
+                header.l2_header.timestamp + FINALIZATION_PERIOD_SECONDS,
+                FINALIZATION_PERIOD_SECONDS,
+            ),
+            Err(Error::MisbehaviourNotFound)
+        );
+    }
+
+    #[test]
+    fn test_misbehaviour_different_heights() {
+        let (client_state, header, l1_state_root) = fixture();
+
+        let mut header_b = header.clone();
+        header_b.l2_header.number += U256::ONE;
+
+        assert_eq!(
+            verify_misbehaviour(
+                &client_state,
+                (&header, l1_state_root),
+                (&header_b, l1_state_root),
+                header.l2_header.timestamp + FINALIZATION_PERIOD_SECONDS,
+                FINALIZATION_PERIOD_SECONDS,
+            ),
+            Err(Error::MisbehaviourNotFound)
+        );
+    }
+
+    #[test]
+    fn test_misbehaviour_ok() {
+        let (client_state, header, l1_state_root) = fixture();
+
+        // a conflicting l2 block at the same height, with a different state root that contains
+        // the same ibc account
+        let mut header_b = header.clone();
+
+        let (l2_state_root, l2_ibc_account_proof) = SecureTrie::new()
+            .insert_account(
+                client_state.ibc_contract_address,
+                header.l2_ibc_account_proof.storage_root,
+            )
+            .root_and_proof();
+        header_b.l2_header.state_root = l2_state_root;
+        header_b.l2_ibc_account_proof.proof =
+            l2_ibc_account_proof.into_iter().map(Into::into).collect();
+
+        header_b.output_root_proof.state_root = l2_state_root;
+        header_b.output_root_proof.latest_block_hash = header_b.l2_header.hash();
+
+        // the conflicting block is committed to by the same output proposal on a (different) l1
+        let (l1_storage_root, l1_storage_proof) = SecureTrie::new()
+            .insert_storage(
+                compute_output_proposal_slot(
+                    client_state.l2_oracle_l2_outputs_slot,
+                    header.output_index,
+                ),
+                &compute_output_root_proof_hash(&header_b.output_root_proof),
+            )
+            .root_and_proof();
+        header_b.l2_oracle_account_proof.storage_root = l1_storage_root;
+        header_b.l2_oracle_l2_outputs_slot_proof.proof =
+            l1_storage_proof.into_iter().map(Into::into).collect();
+
+        let (l1_state_root_b, l1_account_proof) = SecureTrie::new()
+            .insert_account(client_state.l2_oracle_address, l1_storage_root)
+            .root_and_proof();
+        header_b.l2_oracle_account_proof.proof =
+            l1_account_proof.into_iter().map(Into::into).collect();
+
+        assert_eq!(
+            verify_misbehaviour(
+                &client_state,
+                (&header, l1_state_root),
+                (&header_b, l1_state_root_b),
+                header.l2_header.timestamp + FINALIZATION_PERIOD_SECONDS,
+                FINALIZATION_PERIOD_SECONDS,
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_misbehaviour_unproven_conflicting_header() {
+        let (client_state, header, l1_state_root) = fixture();
+
+        // a conflicting l2 header that is not committed to by the output proposal
+        let mut header_b = header.clone();
+        header_b.l2_header.state_root = H256::new([0xAA; 32]);
+
+        assert!(matches!(
+            verify_misbehaviour(
+                &client_state,
+                (&header, l1_state_root),
+                (&header_b, l1_state_root),
+                header.l2_header.timestamp + FINALIZATION_PERIOD_SECONDS,
+                FINALIZATION_PERIOD_SECONDS,
+            ),
+            Err(Error::OutputRootHashMismatch { .. })
+        ));
+    }
+
     #[test]
     fn test_verify_l2_header_is_related_to_output_root_proof() {
         verify_l2_header_is_related_to_output_root_proof(
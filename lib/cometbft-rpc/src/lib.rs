@@ -11,11 +11,13 @@ use jsonrpsee::{
         client::{BatchResponse, ClientT},
         params::BatchRequestBuilder,
         traits::ToRpcParams,
+        JsonRawValue,
     },
     http_client::{HttpClient, HttpClientBuilder},
     rpc_params,
     ws_client::{PingConfig, WsClientBuilder},
 };
+use rpc_pool::{Pool, PoolConfig};
 use tracing::{debug, debug_span, instrument, trace, Instrument};
 use unionlabs::{
     bounded::{BoundedI64, BoundedU8},
@@ -46,34 +48,30 @@ pub struct Client {
 
 impl Client {
     pub async fn new(url: impl AsRef<str>) -> Result<Self, JsonRpcError> {
-        let url = url.as_ref().to_owned();
-
-        let inner = match url.split_once("://") {
-            Some(("ws" | "wss", _)) => {
-                let client = reconnecting_jsonrpc_ws_client::Client::new(move || {
-                    WsClientBuilder::default()
-                        .enable_ws_ping(PingConfig::new())
-                        .build(url.clone())
-                        .instrument(debug_span!("cometbft_rpc_client", %url))
-                });
+        Ok(Self {
+            inner: ClientInner::Single(Connection::connect(url.as_ref().to_owned()).await?),
+        })
+    }
 
-                // TODO: Config
-                client
-                    .wait_until_connected(Duration::from_secs(5))
-                    .await
-                    .map_err(|e| JsonRpcError::Custom(e.to_string()))?;
+    /// Create a client backed by a [`Pool`] of endpoints for the same chain, with automatic
+    /// failover between them. See [`rpc_pool`] for more information.
+    pub async fn new_pool(
+        urls: impl IntoIterator<Item = impl Into<String>>,
+        config: PoolConfig,
+    ) -> Result<Self, JsonRpcError> {
+        let pool = Pool::connect(urls, config, Connection::connect)
+            .await
+            .map_err(pool_error)?;
 
-                ClientInner::Ws(client)
-            }
-            Some(("http" | "https", _)) => ClientInner::Http(Box::new(
-                HttpClientBuilder::default()
-                    .max_response_size(100 * 1024 * 1024)
-                    .build(url)?,
-            )),
-            _ => return Err(JsonRpcError::Custom(format!("invalid url {url}"))),
-        };
+        pool.spawn_health_checks(|connection| async move {
+            connection
+                .request::<StatusResponse, _>("status", rpc_params!())
+                .await
+        });
 
-        Ok(Self { inner })
+        Ok(Self {
+            inner: ClientInner::Pool(pool),
+        })
     }
 
     pub async fn commit(&self, height: Option<NonZeroU64>) -> Result<CommitResponse, JsonRpcError> {
@@ -242,6 +240,23 @@ impl Client {
         self.inner.request("status", rpc_params!()).await
     }
 
+    /// Fetch the latest block height. If this client is backed by a pool, this is read with a
+    /// quorum across all endpoints (see [`Pool::quorum`]).
+    pub async fn latest_height(&self) -> Result<u64, JsonRpcError> {
+        match &self.inner {
+            ClientInner::Single(_) => Ok(self.status().await?.sync_info.latest_block_height),
+            ClientInner::Pool(pool) => pool
+                .quorum(|connection| async move {
+                    connection
+                        .request::<StatusResponse, _>("status", rpc_params!())
+                        .await
+                        .map(|status| status.sync_info.latest_block_height)
+                })
+                .await
+                .map_err(pool_error),
+        }
+    }
+
     pub async fn block(&self, height: Option<NonZeroU64>) -> Result<BlockResponse, JsonRpcError> {
         self.inner
             .request("block", (height.map(|x| x.to_string()),))
@@ -336,20 +351,122 @@ impl Client {
     }
 }
 
+fn pool_error(err: rpc_pool::Error<JsonRpcError>) -> JsonRpcError {
+    match err {
+        rpc_pool::Error::Endpoint(err) => err,
+        err => JsonRpcError::Custom(ErrorReporter(err).to_string()),
+    }
+}
+
 #[derive(Debug, Clone)]
 enum ClientInner {
+    Single(Connection),
+    Pool(Pool<Connection>),
+}
+
+/// Already serialized params, such that a request can be sent to multiple endpoints.
+#[derive(Clone)]
+struct RawParams(Option<Box<JsonRawValue>>);
+
+impl ToRpcParams for RawParams {
+    fn to_rpc_params(self) -> Result<Option<Box<JsonRawValue>>, serde_json::Error> {
+        Ok(self.0)
+    }
+}
+
+impl ClientT for ClientInner {
+    async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), JsonRpcError>
+    where
+        Params: ToRpcParams + Send,
+    {
+        match self {
+            ClientInner::Single(connection) => connection.notification(method, params).await,
+            ClientInner::Pool(pool) => {
+                let params = RawParams(params.to_rpc_params()?);
+
+                pool.request(|connection| connection.notification(method, params.clone()))
+                    .await
+            }
+        }
+    }
+
+    async fn request<R, Params>(&self, method: &str, params: Params) -> Result<R, JsonRpcError>
+    where
+        R: DeserializeOwned,
+        Params: ToRpcParams + Send,
+    {
+        match self {
+            ClientInner::Single(connection) => connection.request(method, params).await,
+            ClientInner::Pool(pool) => {
+                let params = RawParams(params.to_rpc_params()?);
+
+                pool.request(|connection| connection.request(method, params.clone()))
+                    .await
+            }
+        }
+    }
+
+    async fn batch_request<'a, R>(
+        &self,
+        batch: BatchRequestBuilder<'a>,
+    ) -> Result<BatchResponse<'a, R>, JsonRpcError>
+    where
+        R: DeserializeOwned + fmt::Debug + 'a,
+    {
+        match self {
+            ClientInner::Single(connection) => connection.batch_request(batch).await,
+            ClientInner::Pool(pool) => {
+                pool.request(|connection| connection.batch_request(batch.clone()))
+                    .await
+            }
+        }
+    }
+}
+
+/// A connection to a single endpoint.
+#[derive(Debug, Clone)]
+enum Connection {
     Http(Box<HttpClient>),
     Ws(reconnecting_jsonrpc_ws_client::Client),
 }
 
-impl ClientT for ClientInner {
+impl Connection {
+    async fn connect(url: String) -> Result<Self, JsonRpcError> {
+        match url.split_once("://") {
+            Some(("ws" | "wss", _)) => {
+                let client = reconnecting_jsonrpc_ws_client::Client::new(move || {
+                    WsClientBuilder::default()
+                        .enable_ws_ping(PingConfig::new())
+                        .build(url.clone())
+                        .instrument(debug_span!("cometbft_rpc_client", %url))
+                });
+
+                // TODO: Config
+                client
+                    .wait_until_connected(Duration::from_secs(5))
+                    .await
+                    .map_err(|e| JsonRpcError::Custom(e.to_string()))?;
+
+                Ok(Connection::Ws(client))
+            }
+            Some(("http" | "https", _)) => Ok(Connection::Http(Box::new(
+                HttpClientBuilder::default()
+                    .max_response_size(100 * 1024 * 1024)
+                    .build(url)?,
+            ))),
+            _ => Err(JsonRpcError::Custom(format!("invalid url {url}"))),
+        }
+    }
+}
+
+impl ClientT for Connection {
     async fn notification<Params>(&self, method: &str, params: Params) -> Result<(), JsonRpcError>
     where
         Params: ToRpcParams + Send,
     {
         match self {
-            ClientInner::Http(client) => client.notification(method, params).await,
-            ClientInner::Ws(client) => client.notification(method, params).await,
+            Connection::Http(client) => client.notification(method, params).await,
+            Connection::Ws(client) => client.notification(method, params).await,
         }
     }
 
@@ -359,8 +476,8 @@ impl ClientT for ClientInner {
         Params: ToRpcParams + Send,
     {
         match self {
-            ClientInner::Http(client) => client.request(method, params).await,
-            ClientInner::Ws(client) => client.request(method, params).await,
+            Connection::Http(client) => client.request(method, params).await,
+            Connection::Ws(client) => client.request(method, params).await,
         }
     }
 
@@ -372,8 +489,8 @@ impl ClientT for ClientInner {
         R: DeserializeOwned + fmt::Debug + 'a,
     {
         match self {
-            ClientInner::Http(client) => client.batch_request(batch).await,
-            ClientInner::Ws(client) => client.batch_request(batch).await,
+            Connection::Http(client) => client.batch_request(batch).await,
+            Connection::Ws(client) => client.batch_request(batch).await,
         }
     }
 }
@@ -0,0 +1,371 @@
+//! Periodic signer balance checks for [`ChainKeyring`]s.
+//!
+//! Signers with a balance below [`BalanceMonitorConfig::min_balance`] are removed from the
+//! rotation of the keyring until their balance recovers, so that a drained signer does not cause a
+//! stream of failed transactions. If [`BalanceMonitorConfig::top_up`] is configured, signers with
+//! a balance below [`TopUpConfig::threshold`] are refilled from a designated funding key.
+
+use std::{fmt::Display, future::Future, hash::Hash, num::NonZeroU64, time::Duration};
+
+use opentelemetry::{
+    metrics::{Counter, Gauge},
+    KeyValue,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+use unionlabs::{option_unwrap, ErrorReporter};
+
+use crate::{ChainKeyring, ConcurrentKeyring, KeyringConfigEntry, SignerBalance};
+
+/// An error that occurred while topping up a signer.
+pub type TopUpError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
+#[derive(Debug, thiserror::Error)]
+#[error("the top up funding key {address} is also a signer in the keyring {keyring}")]
+pub struct FundingKeyInKeyring {
+    pub keyring: String,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BalanceMonitorConfig {
+    /// How often to check the balances of the signers, in seconds.
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: NonZeroU64,
+    /// Signers with a balance below this amount are removed from the rotation until their balance
+    /// is at least this amount again.
+    #[serde(with = "::serde_utils::string")]
+    pub min_balance: u128,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_up: Option<TopUpConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopUpConfig {
+    /// The key to send top ups from. This key must not also be in the keyring, see
+    /// [`check_funding_key`].
+    pub funding_key: KeyringConfigEntry,
+    /// Signers with a balance below this amount will be topped up.
+    #[serde(with = "::serde_utils::string")]
+    pub threshold: u128,
+    /// The balance to top signers up to.
+    #[serde(with = "::serde_utils::string")]
+    pub target_balance: u128,
+}
+
+/// Check that `funding_address`, the address of the [`TopUpConfig::funding_key`], is not also a
+/// signer in `keyring`. The funding key would otherwise be used to submit transactions concurrently
+/// with the top ups, and would be topped up from itself once its balance runs low.
+///
+/// This is intended to be called when the transaction plugin is started.
+pub fn check_funding_key<A, S>(
+    keyring: &ConcurrentKeyring<A, S>,
+    funding_address: &A,
+) -> Result<(), FundingKeyInKeyring>
+where
+    A: Hash + Eq + Clone + Display,
+    S: 'static,
+{
+    if keyring.keys().any(|address| address == funding_address) {
+        Err(FundingKeyInKeyring {
+            keyring: keyring.name.to_string(),
+            address: funding_address.to_string(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[must_use]
+pub const fn default_interval_seconds() -> NonZeroU64 {
+    option_unwrap!(NonZeroU64::new(60))
+}
+
+impl BalanceMonitorConfig {
+    pub fn interval(&self) -> Duration {
+        Duration::from_secs(self.interval_seconds.get())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalanceAction<A> {
+    /// Remove the signer from the rotation.
+    Disable(A),
+    /// Add the signer back to the rotation.
+    Enable(A),
+    /// Send `amount` to the signer from the funding key.
+    TopUp { address: A, amount: u128 },
+}
+
+/// The actions to take for a signer with the given balance, where `enabled` is whether the signer
+/// is currently in the rotation.
+pub fn balance_actions<A: Clone>(
+    config: &BalanceMonitorConfig,
+    balance: &SignerBalance<A>,
+    enabled: bool,
+) -> Vec<BalanceAction<A>> {
+    let mut actions = vec![];
+
+    let low = balance.balance < config.min_balance;
+
+    if low && enabled {
+        actions.push(BalanceAction::Disable(balance.address.clone()));
+    } else if !low && !enabled {
+        actions.push(BalanceAction::Enable(balance.address.clone()));
+    }
+
+    if let Some(top_up) = &config.top_up {
+        let amount = top_up.target_balance.saturating_sub(balance.balance);
+
+        if balance.balance < top_up.threshold && amount > 0 {
+            actions.push(BalanceAction::TopUp {
+                address: balance.address.clone(),
+                amount,
+            });
+        }
+    }
+
+    actions
+}
+
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    pub balance: Gauge<f64>,
+    pub enabled: Gauge<u64>,
+    pub top_up_count: Counter<u64>,
+    pub top_up_error_count: Counter<u64>,
+}
+
+impl Metrics {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            balance: opentelemetry::global::meter("concurrent_keyring")
+                .f64_gauge("keyring_signer_balance")
+                .with_description("The balance of the signer, per keyring and signer.")
+                .build(),
+            enabled: opentelemetry::global::meter("concurrent_keyring")
+                .u64_gauge("keyring_signer_enabled")
+                .with_description(
+                    "Whether the signer is in the keyring rotation (1) or has been removed due \
+                    to a low balance (0), per keyring and signer.",
+                )
+                .build(),
+            top_up_count: opentelemetry::global::meter("concurrent_keyring")
+                .u64_counter("keyring_top_up_count")
+                .with_description("Total count of signer top ups, per keyring and signer.")
+                .build(),
+            top_up_error_count: opentelemetry::global::meter("concurrent_keyring")
+                .u64_counter("keyring_top_up_error_count")
+                .with_description("Total count of failed signer top ups, per keyring and signer.")
+                .build(),
+        }
+    }
+}
+
+/// Check the balances of the signers in `keyring` every [`BalanceMonitorConfig::interval`],
+/// forever. `top_up` is called with the address and amount to send for every signer that needs to
+/// be topped up.
+///
+/// This is intended to be spawned as a background task when the transaction plugin is started.
+pub async fn monitor_balances<K, F, Fut>(keyring: K, config: BalanceMonitorConfig, top_up: F)
+where
+    K: ChainKeyring,
+    F: Fn(K::Address, u128) -> Fut,
+    Fut: Future<Output = Result<(), TopUpError>>,
+{
+    let metrics = Metrics::new();
+
+    loop {
+        for balance in keyring.balances().await {
+            let attributes = [
+                KeyValue::new("keyring", keyring.keyring().name.to_string()),
+                KeyValue::new("key_name", balance.key_name.clone()),
+                KeyValue::new("address", balance.address.to_string()),
+                KeyValue::new("denom", balance.denom.clone()),
+            ];
+
+            metrics.balance.record(balance.balance as f64, &attributes);
+
+            let enabled = keyring.keyring().is_enabled(&balance.address);
+
+            for action in balance_actions(&config, &balance, enabled) {
+                match action {
+                    BalanceAction::Disable(address) => {
+                        warn!(
+                            keyring = %keyring.keyring().name,
+                            %address,
+                            balance = balance.balance,
+                            min_balance = config.min_balance,
+                            "signer balance is low, removing it from the rotation"
+                        );
+
+                        keyring.keyring().disable(&address);
+                    }
+                    BalanceAction::Enable(address) => {
+                        info!(
+                            keyring = %keyring.keyring().name,
+                            %address,
+                            balance = balance.balance,
+                            "signer balance recovered, adding it back to the rotation"
+                        );
+
+                        keyring.keyring().enable(&address);
+                    }
+                    BalanceAction::TopUp { address, amount } => {
+                        match top_up(address.clone(), amount).await {
+                            Ok(()) => {
+                                info!(
+                                    keyring = %keyring.keyring().name,
+                                    %address,
+                                    amount,
+                                    "topped up signer"
+                                );
+
+                                metrics.top_up_count.add(1, &attributes);
+                            }
+                            Err(err) => {
+                                error!(
+                                    keyring = %keyring.keyring().name,
+                                    %address,
+                                    amount,
+                                    err = %ErrorReporter(&*err),
+                                    "error topping up signer"
+                                );
+
+                                metrics.top_up_error_count.add(1, &attributes);
+                            }
+                        }
+                    }
+                }
+            }
+
+            metrics.enabled.record(
+                keyring.keyring().is_enabled(&balance.address).into(),
+                &attributes,
+            );
+        }
+
+        tokio::time::sleep(config.interval()).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn balance(balance: u128) -> SignerBalance<&'static str> {
+        SignerBalance {
+            key_name: "key".to_owned(),
+            address: "address",
+            balance,
+            denom: "denom".to_owned(),
+        }
+    }
+
+    fn config(top_up: Option<TopUpConfig>) -> BalanceMonitorConfig {
+        BalanceMonitorConfig {
+            interval_seconds: default_interval_seconds(),
+            min_balance: 100,
+            top_up,
+        }
+    }
+
+    #[test]
+    fn funding_key_must_not_be_in_keyring() {
+        let keyring = ConcurrentKeyring::new(
+            "keyring",
+            [("a", ()), ("b", ())]
+                .into_iter()
+                .map(|(address, signer)| crate::KeyringEntry { address, signer }),
+        );
+
+        assert!(check_funding_key(&keyring, &"funding").is_ok());
+        assert_eq!(
+            check_funding_key(&keyring, &"b").unwrap_err().to_string(),
+            "the top up funding key b is also a signer in the keyring keyring"
+        );
+    }
+
+    fn top_up_config() -> TopUpConfig {
+        TopUpConfig {
+            funding_key: KeyringConfigEntry::Raw {
+                name: "funding".to_owned(),
+                key: vec![1; 32],
+            },
+            threshold: 500,
+            target_balance: 1000,
+        }
+    }
+
+    #[test]
+    fn disables_and_enables() {
+        assert_eq!(
+            balance_actions(&config(None), &balance(99), true),
+            [BalanceAction::Disable("address")]
+        );
+        assert!(balance_actions(&config(None), &balance(99), false).is_empty());
+        assert_eq!(
+            balance_actions(&config(None), &balance(100), false),
+            [BalanceAction::Enable("address")]
+        );
+        assert!(balance_actions(&config(None), &balance(100), true).is_empty());
+    }
+
+    #[test]
+    fn tops_up_to_target() {
+        let config = config(Some(top_up_config()));
+
+        assert_eq!(
+            balance_actions(&config, &balance(10), true),
+            [
+                BalanceAction::Disable("address"),
+                BalanceAction::TopUp {
+                    address: "address",
+                    amount: 990
+                }
+            ]
+        );
+        assert_eq!(
+            balance_actions(&config, &balance(499), true),
+            [BalanceAction::TopUp {
+                address: "address",
+                amount: 501
+            }]
+        );
+        assert!(balance_actions(&config, &balance(500), true).is_empty());
+    }
+
+    #[test]
+    fn config_parse() {
+        let json = r#"{
+            "min_balance": "100",
+            "top_up": {
+              "funding_key": {
+                "type": "raw",
+                "name": "funding",
+                "key": "0x0101010101010101010101010101010101010101010101010101010101010101"
+              },
+              "threshold": "500",
+              "target_balance": "1000"
+            }
+          }"#;
+
+        assert_eq!(
+            serde_json::from_str::<BalanceMonitorConfig>(json).unwrap(),
+            config(Some(top_up_config()))
+        );
+    }
+
+    #[test]
+    fn config_parse_zero_interval() {
+        let json = r#"{
+            "interval_seconds": 0,
+            "min_balance": "100"
+          }"#;
+
+        assert!(serde_json::from_str::<BalanceMonitorConfig>(json).is_err());
+    }
+}
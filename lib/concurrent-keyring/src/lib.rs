@@ -1,9 +1,18 @@
 #![feature(trait_alias)]
 
+pub mod balance;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
 pub mod private_key;
+pub mod remote;
 
 use std::{
-    collections::HashMap, fmt::Display, hash::Hash, panic::UnwindSafe, path::PathBuf, sync::Arc,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    panic::UnwindSafe,
+    path::PathBuf,
+    sync::{Arc, RwLock},
 };
 
 use crossbeam_queue::ArrayQueue;
@@ -11,7 +20,9 @@ use futures::{Future, FutureExt};
 use rand::prelude::SliceRandom;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info_span, warn, Instrument};
-use unionlabs::primitives::H256;
+use unionlabs::primitives::{encoding::HexPrefixedFromStrError, H256};
+
+use crate::remote::RemoteSigner;
 
 pub trait ChainKeyring {
     type Address: Hash + Eq + Clone + Display + Send + Sync;
@@ -39,6 +50,9 @@ pub struct ConcurrentKeyring<A: Hash + Eq, S> {
     addresses_buffer: Arc<ArrayQueue<A>>,
 
     signers: Arc<HashMap<A, S>>,
+
+    /// Addresses that are temporarily removed from the rotation, i.e. due to a low balance.
+    disabled: Arc<RwLock<HashSet<A>>>,
 }
 
 pub struct KeyringEntry<A, S> {
@@ -74,6 +88,7 @@ impl<A: Hash + Eq + Clone + Display, S: 'static> ConcurrentKeyring<A, S> {
             name: Arc::new(name.into()),
             addresses_buffer: Arc::new(addresses_buffer),
             signers: Arc::new(signers),
+            disabled: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
@@ -81,14 +96,63 @@ impl<A: Hash + Eq + Clone + Display, S: 'static> ConcurrentKeyring<A, S> {
         self.signers.keys()
     }
 
+    /// Whether the signer for `address` is currently in the rotation.
+    pub fn is_enabled(&self, address: &A) -> bool {
+        !self
+            .disabled
+            .read()
+            .expect("lock is not poisoned; qed;")
+            .contains(address)
+    }
+
+    /// Remove the signer for `address` from the rotation, until it is re-enabled with
+    /// [`Self::enable`]. Returns `true` if the signer was previously enabled.
+    pub fn disable(&self, address: &A) -> bool {
+        self.signers.contains_key(address)
+            && self
+                .disabled
+                .write()
+                .expect("lock is not poisoned; qed;")
+                .insert(address.clone())
+    }
+
+    /// Add the signer for `address` back to the rotation. Returns `true` if the signer was
+    /// previously disabled.
+    pub fn enable(&self, address: &A) -> bool {
+        self.disabled
+            .write()
+            .expect("lock is not poisoned; qed;")
+            .remove(address)
+    }
+
     pub async fn with<'a, F, Fut>(&'a self, f: F) -> Option<Fut::Output>
     where
         F: FnOnce(&'a S) -> Fut + 'a,
         Fut: Future<Output: 'a> + Sized + UnwindSafe + 'a,
     {
-        let Some(address) = self.addresses_buffer.pop() else {
-            debug!(keyring = %self.name, "high traffic in keyring");
-            return None;
+        let mut skipped = 0;
+
+        let address = loop {
+            let Some(address) = self.addresses_buffer.pop() else {
+                debug!(keyring = %self.name, "high traffic in keyring");
+                return None;
+            };
+
+            if self.is_enabled(&address) {
+                break address;
+            }
+
+            self.addresses_buffer
+                .push(address)
+                .ok()
+                .expect("no additional items are added; qed;");
+
+            skipped += 1;
+
+            if skipped >= self.addresses_buffer.capacity() {
+                warn!(keyring = %self.name, "all signers in keyring are disabled");
+                return None;
+            }
         };
 
         let secret = self.signers.get(&address).expect("key is present; qed;");
@@ -132,16 +196,58 @@ pub struct KeyringConfig {
     pub keys: Vec<KeyringConfigEntry>,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum KeyValueError {
+    #[error("unable to read key file {}", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        err: std::io::Error,
+    },
+    #[error("key file {} is in an invalid format", path.display())]
+    InvalidFormat {
+        path: PathBuf,
+        #[source]
+        err: HexPrefixedFromStrError,
+    },
+    #[error("the private key of remote keyring entry `{0}` is not available")]
+    Remote(String),
+}
+
 impl KeyringConfigEntry {
-    pub fn value(&self) -> Vec<u8> {
+    /// The private key of this entry.
+    ///
+    /// The private key of a [`KeyringConfigEntry::Remote`] entry is never available to the
+    /// relayer, and [`KeyValueError::Remote`] is returned for them. Check [`Self::remote_signer`]
+    /// first to support remote entries.
+    pub fn value(&self) -> Result<Vec<u8>, KeyValueError> {
         match &self {
-            KeyringConfigEntry::File { path } => std::fs::read_to_string(path)
-                .expect("key does not exist")
+            KeyringConfigEntry::File { path } => Ok(std::fs::read_to_string(path)
+                .map_err(|err| KeyValueError::Read {
+                    path: path.clone(),
+                    err,
+                })?
                 .trim()
                 .parse::<H256>()
-                .expect("key is in an invalid format")
-                .into(),
-            KeyringConfigEntry::Raw { name: _, key } => key.clone(),
+                .map_err(|err| KeyValueError::InvalidFormat {
+                    path: path.clone(),
+                    err,
+                })?
+                .into()),
+            KeyringConfigEntry::Raw { name: _, key } => Ok(key.clone()),
+            KeyringConfigEntry::Remote { name, .. } => Err(KeyValueError::Remote(name.clone())),
+        }
+    }
+
+    /// A [`RemoteSigner`] for this entry, if it is a [`KeyringConfigEntry::Remote`] entry.
+    pub fn remote_signer(&self) -> Option<RemoteSigner> {
+        match self {
+            KeyringConfigEntry::Remote {
+                name: _,
+                url,
+                public_key,
+            } => Some(RemoteSigner::new(url.clone(), public_key.clone())),
+            _ => None,
         }
     }
 }
@@ -157,4 +263,86 @@ pub enum KeyringConfigEntry {
         #[serde(with = "::serde_utils::hex_string")]
         key: Vec<u8>,
     },
+    /// A key held by a remote signer, see [`remote`].
+    Remote {
+        name: String,
+        url: String,
+        /// The public key of the key, as expected by the remote signer.
+        #[serde(with = "::serde_utils::hex_string")]
+        public_key: Vec<u8>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyring() -> ConcurrentKeyring<&'static str, u8> {
+        ConcurrentKeyring::new(
+            "keyring",
+            [("a", 1), ("b", 2)]
+                .into_iter()
+                .map(|(address, signer)| KeyringEntry { address, signer }),
+        )
+    }
+
+    #[test]
+    fn remote_entry_has_no_value() {
+        assert_eq!(
+            KeyringConfigEntry::Raw {
+                name: "raw".to_owned(),
+                key: vec![1; 32],
+            }
+            .value()
+            .unwrap(),
+            vec![1; 32]
+        );
+
+        assert!(matches!(
+            KeyringConfigEntry::Remote {
+                name: "remote".to_owned(),
+                url: "http://localhost".to_owned(),
+                public_key: vec![2; 33],
+            }
+            .value(),
+            Err(KeyValueError::Remote(name)) if name == "remote"
+        ));
+
+        assert!(matches!(
+            KeyringConfigEntry::File {
+                path: "/does/not/exist".into(),
+            }
+            .value(),
+            Err(KeyValueError::Read { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn disabled_signers_are_skipped() {
+        let keyring = keyring();
+
+        assert!(keyring.disable(&"a"));
+        assert!(!keyring.disable(&"a"));
+        assert!(!keyring.disable(&"unknown"));
+
+        for _ in 0..4 {
+            assert_eq!(
+                keyring.with(|signer| std::future::ready(*signer)).await,
+                Some(2)
+            );
+        }
+
+        assert!(keyring.disable(&"b"));
+        assert_eq!(
+            keyring.with(|signer| std::future::ready(*signer)).await,
+            None
+        );
+
+        assert!(keyring.enable(&"a"));
+        assert!(!keyring.enable(&"a"));
+        assert_eq!(
+            keyring.with(|signer| std::future::ready(*signer)).await,
+            Some(1)
+        );
+    }
 }
@@ -0,0 +1,179 @@
+//! A local mock of a remote signer, for use in tests.
+//!
+//! See the [`remote`](crate::remote) module for the signing protocol that is implemented.
+
+use std::{collections::HashMap, net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::post,
+    Json, Router,
+};
+use bip32::secp256k1::ecdsa::{signature::Signer, Signature, SigningKey};
+use unionlabs::primitives::{encoding::HexPrefixed, Bytes};
+
+use crate::remote::{RemoteSigner, SignRequest};
+
+/// A mock remote signer serving the keys it was spawned with, keyed by their
+/// [public key](MockKey::public_key).
+#[derive(Debug, Clone)]
+pub struct MockSigner {
+    addr: SocketAddr,
+}
+
+/// A key held by a [`MockSigner`].
+#[derive(Debug, Clone)]
+pub enum MockKey {
+    /// A secp256k1 key, supported by all namespaces.
+    Secp256k1(SigningKey),
+    /// An ed25519 key, only supported by the `sui` namespace.
+    Ed25519(ed25519_dalek::SigningKey),
+}
+
+impl From<SigningKey> for MockKey {
+    fn from(key: SigningKey) -> Self {
+        Self::Secp256k1(key)
+    }
+}
+
+impl From<&SigningKey> for MockKey {
+    fn from(key: &SigningKey) -> Self {
+        Self::Secp256k1(key.clone())
+    }
+}
+
+impl From<ed25519_dalek::SigningKey> for MockKey {
+    fn from(key: ed25519_dalek::SigningKey) -> Self {
+        Self::Ed25519(key)
+    }
+}
+
+impl From<&ed25519_dalek::SigningKey> for MockKey {
+    fn from(key: &ed25519_dalek::SigningKey) -> Self {
+        Self::Ed25519(key.clone())
+    }
+}
+
+impl MockKey {
+    /// The public key of this key: the compressed public key for secp256k1 keys, and the raw
+    /// public key for ed25519 keys.
+    pub fn public_key(&self) -> Vec<u8> {
+        match self {
+            MockKey::Secp256k1(key) => key
+                .verifying_key()
+                .to_encoded_point(true)
+                .as_bytes()
+                .to_vec(),
+            MockKey::Ed25519(key) => key.verifying_key().to_bytes().to_vec(),
+        }
+    }
+
+    /// The public key of this key as configured for the `sui` namespace, prefixed with the flag of
+    /// its signature scheme.
+    pub fn sui_public_key(&self) -> Vec<u8> {
+        let flag = match self {
+            MockKey::Ed25519(_) => 0x00,
+            MockKey::Secp256k1(_) => 0x01,
+        };
+
+        [&[flag][..], &self.public_key()].concat()
+    }
+}
+
+type Keys = Arc<HashMap<Vec<u8>, MockKey>>;
+
+impl MockSigner {
+    /// Spawn the mock signer on a random local port. The server runs until the runtime is shut
+    /// down.
+    pub async fn spawn(keys: impl IntoIterator<Item = impl Into<MockKey>>) -> Self {
+        let keys = keys
+            .into_iter()
+            .map(|key| {
+                let key = key.into();
+                (key.public_key(), key)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let app = Router::new()
+            .route("/api/v1/:namespace/sign/:identifier", post(sign))
+            .with_state(Arc::new(keys));
+
+        let server = axum::Server::bind(&([127, 0, 0, 1], 0).into()).serve(app.into_make_service());
+
+        let addr = server.local_addr();
+
+        tokio::spawn(server);
+
+        Self { addr }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A [`RemoteSigner`] for `key` that signs with this mock signer.
+    pub fn remote_signer(&self, key: impl Into<MockKey>) -> RemoteSigner {
+        RemoteSigner::new(self.url(), key.into().public_key())
+    }
+
+    /// A [`RemoteSigner`] for `key` that signs with this mock signer, configured for the `sui`
+    /// namespace.
+    pub fn sui_remote_signer(&self, key: impl Into<MockKey>) -> RemoteSigner {
+        RemoteSigner::new(self.url(), key.into().sui_public_key())
+    }
+}
+
+async fn sign(
+    Path((namespace, identifier)): Path<(String, String)>,
+    State(keys): State<Keys>,
+    Json(request): Json<SignRequest>,
+) -> Result<String, (StatusCode, String)> {
+    let identifier = identifier
+        .parse::<Bytes<HexPrefixed>>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("invalid identifier: {e}")))?;
+
+    let public_key = match namespace.as_str() {
+        // strip the signature scheme flag
+        "sui" => identifier.get(1..).unwrap_or_default(),
+        "eth1" | "cosmos" => &identifier[..],
+        _ => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("unknown namespace `{namespace}`"),
+            ))
+        }
+    };
+
+    let key = keys
+        .get(public_key)
+        .ok_or((StatusCode::NOT_FOUND, format!("unknown key {identifier}")))?;
+
+    let signature = match (namespace.as_str(), key) {
+        ("eth1", MockKey::Secp256k1(key)) => {
+            let (signature, recovery_id) = key
+                .sign_prehash_recoverable(&request.data)
+                .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+            [&signature.to_bytes()[..], &[recovery_id.to_byte()]].concat()
+        }
+        (_, MockKey::Secp256k1(key)) => {
+            let signature: Signature = key.sign(&request.data);
+
+            signature.to_bytes().to_vec()
+        }
+        ("sui", MockKey::Ed25519(key)) => {
+            let signature: ed25519_dalek::Signature = key.sign(&request.data);
+
+            signature.to_bytes().to_vec()
+        }
+        (_, MockKey::Ed25519(_)) => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("ed25519 keys are not supported by the `{namespace}` namespace"),
+            ))
+        }
+    };
+
+    Ok(Bytes::<HexPrefixed>::new(signature).to_string())
+}
@@ -0,0 +1,283 @@
+//! Client for remote signers, allowing keys to be held outside of the relayer process.
+//!
+//! The signing protocol is modeled after the [Web3Signer] REST API:
+//!
+//! ```text
+//! POST {url}/api/v1/{namespace}/sign/{identifier}
+//! { "data": "0x...", "sign_mode": "direct" }
+//! ```
+//!
+//! where `identifier` is the `0x`-prefixed hex encoded public key of the signer, and the response
+//! body is the `0x`-prefixed hex encoded signature. The supported namespaces are:
+//!
+//! - `eth1`: `data` is a 32 byte digest, which is signed as-is with secp256k1. The signature is
+//!   65 bytes (`r || s || v`).
+//! - `cosmos`: `data` is the encoded sign doc (as specified by `sign_mode`, either `direct` or
+//!   `amino_json`), which is signed with secp256k1 over it's sha256 hash. The signature is 64 bytes
+//!   (`r || s`).
+//! - `sui`: `data` is the 32 byte digest of the intent message, which is signed with the scheme of
+//!   the key. The identifier is the `flag || public key` encoding of the key, and the signature
+//!   is 64 bytes.
+//!
+//! [Web3Signer]: https://docs.web3signer.consensys.io/reference/api/rest
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use unionlabs::primitives::{encoding::HexPrefixed, Bytes, FixedBytes, H256, H512};
+
+/// How long to wait for the remote signer to respond to a signing request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteSignerError {
+    #[error("error sending signing request")]
+    Http(#[from] reqwest::Error),
+    #[error("remote signer responded with status {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("remote signer responded with an invalid signature `{0}`")]
+    InvalidSignature(String),
+    #[error("invalid signature length, expected {expected} but found {found}")]
+    InvalidSignatureLength { expected: usize, found: usize },
+}
+
+/// The format of the sign doc to be signed by a cosmos remote signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CosmosSignMode {
+    Direct,
+    AminoJson,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignRequest {
+    pub data: Bytes<HexPrefixed>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sign_mode: Option<CosmosSignMode>,
+}
+
+/// A signer that signs over HTTP with a key held by a remote signer.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: reqwest::Client,
+    url: String,
+    public_key: Bytes<HexPrefixed>,
+}
+
+impl RemoteSigner {
+    pub fn new(url: impl Into<String>, public_key: impl Into<Vec<u8>>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url: url.into().trim_end_matches('/').to_owned(),
+            public_key: Bytes::new(public_key.into()),
+        }
+    }
+
+    /// The public key of the key held by the remote signer, as configured.
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    /// Sign a 32 byte digest with the `eth1` namespace, returning the 65 byte recoverable
+    /// signature.
+    pub async fn sign_eth1(&self, digest: H256) -> Result<FixedBytes<65>, RemoteSignerError> {
+        self.sign(
+            "eth1",
+            SignRequest {
+                data: digest.into_bytes(),
+                sign_mode: None,
+            },
+        )
+        .await
+    }
+
+    /// Sign an encoded sign doc with the `cosmos` namespace.
+    pub async fn sign_cosmos(
+        &self,
+        sign_doc: &[u8],
+        sign_mode: CosmosSignMode,
+    ) -> Result<H512, RemoteSignerError> {
+        self.sign(
+            "cosmos",
+            SignRequest {
+                data: sign_doc.to_vec().into(),
+                sign_mode: Some(sign_mode),
+            },
+        )
+        .await
+    }
+
+    /// Sign the digest of a sui intent message with the `sui` namespace.
+    pub async fn sign_sui(&self, digest: [u8; 32]) -> Result<H512, RemoteSignerError> {
+        self.sign(
+            "sui",
+            SignRequest {
+                data: digest.to_vec().into(),
+                sign_mode: None,
+            },
+        )
+        .await
+    }
+
+    async fn sign<const N: usize>(
+        &self,
+        namespace: &str,
+        request: SignRequest,
+    ) -> Result<FixedBytes<N>, RemoteSignerError> {
+        let response = self
+            .client
+            .post(format!(
+                "{}/api/v1/{namespace}/sign/{}",
+                self.url, self.public_key
+            ))
+            .timeout(REQUEST_TIMEOUT)
+            .json(&request)
+            .send()
+            .await?;
+
+        let status = response.status();
+        let body = response.text().await?;
+
+        if !status.is_success() {
+            return Err(RemoteSignerError::Status {
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        let signature = body
+            .trim()
+            .trim_matches('"')
+            .parse::<Bytes<HexPrefixed>>()
+            .map_err(|_| RemoteSignerError::InvalidSignature(body.clone()))?;
+
+        FixedBytes::try_from(&*signature).map_err(|_| RemoteSignerError::InvalidSignatureLength {
+            expected: N,
+            found: signature.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bip32::secp256k1::ecdsa::{
+        signature::{hazmat::PrehashVerifier, Verifier},
+        RecoveryId, Signature, SigningKey, VerifyingKey,
+    };
+    use hex_literal::hex;
+    use sha2::Digest;
+
+    use super::*;
+    use crate::mock::{MockKey, MockSigner};
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&hex!(
+            "4e9444a6efd6d42725a250b650a781da2737ea308c839eaccb0f7f3dbd2fea77"
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sign_eth1() {
+        let mock = MockSigner::spawn([signing_key()]).await;
+
+        let signer = mock.remote_signer(&signing_key());
+
+        let digest = H256::new(sha2::Sha256::digest(b"digest").into());
+
+        let signature = signer.sign_eth1(digest).await.unwrap();
+
+        let recovered = VerifyingKey::recover_from_prehash(
+            digest.get(),
+            &Signature::from_slice(&signature.get()[..64]).unwrap(),
+            RecoveryId::from_byte(signature.get()[64]).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(&recovered, signing_key().verifying_key());
+        recovered
+            .verify_prehash(
+                digest.get(),
+                &Signature::from_slice(&signature.get()[..64]).unwrap(),
+            )
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sign_cosmos() {
+        let mock = MockSigner::spawn([signing_key()]).await;
+
+        let signature = mock
+            .remote_signer(&signing_key())
+            .sign_cosmos(b"sign doc", CosmosSignMode::Direct)
+            .await
+            .unwrap();
+
+        signing_key()
+            .verifying_key()
+            .verify(
+                b"sign doc",
+                &Signature::from_slice(signature.get()).unwrap(),
+            )
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn sign_sui() {
+        let ed25519_key = ed25519_dalek::SigningKey::from_bytes(&hex!(
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+        ));
+
+        let mock =
+            MockSigner::spawn([MockKey::from(signing_key()), ed25519_key.clone().into()]).await;
+
+        let digest: [u8; 32] = sha2::Sha256::digest(b"digest").into();
+
+        let signature = mock
+            .sui_remote_signer(&ed25519_key)
+            .sign_sui(digest)
+            .await
+            .unwrap();
+
+        ed25519_key
+            .verifying_key()
+            .verify_strict(
+                &digest,
+                &ed25519_dalek::Signature::from_bytes(signature.get()),
+            )
+            .unwrap();
+
+        let signature = mock
+            .sui_remote_signer(&signing_key())
+            .sign_sui(digest)
+            .await
+            .unwrap();
+
+        signing_key()
+            .verifying_key()
+            .verify(&digest, &Signature::from_slice(signature.get()).unwrap())
+            .unwrap();
+
+        let err = mock
+            .remote_signer(&ed25519_key)
+            .sign_cosmos(b"sign doc", CosmosSignMode::Direct)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, RemoteSignerError::Status { status: 400, .. }));
+    }
+
+    #[tokio::test]
+    async fn unknown_key() {
+        let mock = MockSigner::spawn(Vec::<MockKey>::new()).await;
+
+        let err = mock
+            .remote_signer(&signing_key())
+            .sign_cosmos(b"sign doc", CosmosSignMode::AminoJson)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, RemoteSignerError::Status { status: 404, .. }));
+    }
+}
@@ -27,7 +27,18 @@ impl GasFiller {
     pub async fn new(config: Config) -> Result<Self, cometbft_rpc::JsonRpcError> {
         let client = cometbft_rpc::Client::new(config.rpc_url).await?;
 
-        let denom = match config.denom {
+        Self::with_client(client, config.max_gas, config.gas_multiplier, config.denom).await
+    }
+
+    /// Create a gas filler that queries the fee market with an existing client, such as one backed
+    /// by a pool of endpoints.
+    pub async fn with_client(
+        client: cometbft_rpc::Client,
+        max_gas: u64,
+        gas_multiplier: Option<f64>,
+        denom: Option<String>,
+    ) -> Result<Self, cometbft_rpc::JsonRpcError> {
+        let denom = match denom {
             Some(denom) => denom,
             None => {
                 client
@@ -48,8 +59,8 @@ impl GasFiller {
         };
 
         Ok(Self {
-            max_gas: config.max_gas,
-            gas_multiplier: config.gas_multiplier.unwrap_or(1.0),
+            max_gas,
+            gas_multiplier: gas_multiplier.unwrap_or(1.0),
             denom,
             client,
         })
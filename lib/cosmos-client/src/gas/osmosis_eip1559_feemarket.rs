@@ -29,8 +29,27 @@ impl GasFiller {
     pub async fn new(config: Config) -> Result<Self, cometbft_rpc::JsonRpcError> {
         let client = cometbft_rpc::Client::new(config.rpc_url).await?;
 
+        Self::with_client(
+            client,
+            config.max_gas,
+            config.gas_multiplier,
+            config.base_fee_multiplier,
+            config.denom,
+        )
+        .await
+    }
+
+    /// Create a gas filler that queries the base fee with an existing client, such as one backed
+    /// by a pool of endpoints.
+    pub async fn with_client(
+        client: cometbft_rpc::Client,
+        max_gas: u64,
+        gas_multiplier: Option<f64>,
+        base_fee_multiplier: Option<f64>,
+        denom: Option<String>,
+    ) -> Result<Self, cometbft_rpc::JsonRpcError> {
         let denom =
-            match config.denom {
+            match denom {
                 Some(denom) => denom,
                 None => client
                     .grpc_abci_query::<_, protos::osmosis::txfees::v1beta1::QueryBaseDenomResponse>(
@@ -49,9 +68,9 @@ impl GasFiller {
         debug!("base denom is {denom}");
 
         Ok(Self {
-            max_gas: config.max_gas,
-            gas_multiplier: config.gas_multiplier.unwrap_or(1.0),
-            base_fee_multiplier: config.base_fee_multiplier.unwrap_or(1.0),
+            max_gas,
+            gas_multiplier: gas_multiplier.unwrap_or(1.0),
+            base_fee_multiplier: base_fee_multiplier.unwrap_or(1.0),
             denom,
             client,
         })
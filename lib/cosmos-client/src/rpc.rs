@@ -13,8 +13,13 @@ pub struct Rpc {
 
 impl Rpc {
     pub async fn new(rpc_url: String) -> Result<Self, cometbft_rpc::JsonRpcError> {
-        let client = cometbft_rpc::Client::new(rpc_url).await?;
+        Self::with_client(cometbft_rpc::Client::new(rpc_url).await?).await
+    }
 
+    /// Create an [`Rpc`] from an existing client, such as one backed by a pool of endpoints.
+    pub async fn with_client(
+        client: cometbft_rpc::Client,
+    ) -> Result<Self, cometbft_rpc::JsonRpcError> {
         let chain_id = client.status().await?.node_info.network;
 
         Ok(Self { client, chain_id })
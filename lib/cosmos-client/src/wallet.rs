@@ -4,12 +4,15 @@ use unionlabs::{
     signer::CosmosSigner,
 };
 
+/// An error that occurred while signing, i.e. with a remote signer.
+pub type SignError = Box<dyn core::error::Error + Send + Sync + 'static>;
+
 pub trait WalletT {
     fn address(&self) -> Bech32<H160>;
 
     fn public_key(&self) -> FixedBytes<33>;
 
-    fn sign(&self, bz: &[u8]) -> H512;
+    async fn sign(&self, bz: &[u8]) -> Result<H512, SignError>;
 }
 
 #[derive(Debug)]
@@ -38,12 +41,13 @@ impl WalletT for LocalSigner {
         self.signer.public_key()
     }
 
-    fn sign(&self, bz: &[u8]) -> H512 {
-        self.signer
+    async fn sign(&self, bz: &[u8]) -> Result<H512, SignError> {
+        Ok(self
+            .signer
             .try_sign(bz)
             .expect("infallible")
             .to_bytes()
-            .into()
+            .into())
     }
 }
 
@@ -56,7 +60,7 @@ impl<T: WalletT> WalletT for &T {
         (*self).public_key()
     }
 
-    fn sign(&self, bz: &[u8]) -> H512 {
-        (*self).sign(bz)
+    async fn sign(&self, bz: &[u8]) -> Result<H512, SignError> {
+        (*self).sign(bz).await
     }
 }
@@ -1,4 +1,5 @@
 use beacon_api_types::{altair::SyncCommittee, chain_spec::PresetBaseKind};
+use fork_schedules::{ForkParameters, ForkSchedule};
 use unionlabs::{
     ibc::core::client::height::Height,
     primitives::{H160, H256},
@@ -13,6 +14,7 @@ use unionlabs::{
 #[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
 pub enum ClientState {
     V1(ClientStateV1),
+    V2(ClientStateV2),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,6 +37,141 @@ pub struct ClientStateV1 {
     pub initial_sync_committee: Option<InitialSyncCommittee>,
 }
 
+impl ClientStateV1 {
+    /// The fork parameters of the tracked beacon chain, i.e. the well-known fork schedule for
+    /// [`Self::chain_id`].
+    #[must_use]
+    pub fn fork_parameters(&self) -> ForkParameters {
+        ForkSchedule::for_chain_id(self.chain_id).into()
+    }
+}
+
+/// The same structure as [`ClientStateV1`], except the fork schedule of the tracked beacon chain is
+/// stored in the client state instead of being derived from the chain id.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct ClientStateV2 {
+    pub chain_id: u64,
+    pub chain_spec: PresetBaseKind,
+    pub genesis_validators_root: H256,
+    pub genesis_time: u64,
+    pub latest_height: u64,
+    pub frozen_height: Height,
+    /// the ibc contract on the counterparty chain that contains the ICS23 commitments
+    pub ibc_contract_address: H160,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default),
+        serde(skip_serializing_if = "Option::is_none")
+    )]
+    pub initial_sync_committee: Option<InitialSyncCommittee>,
+    /// The fork schedule of the tracked beacon chain.
+    pub fork_parameters: ForkParameters,
+}
+
+impl ClientState {
+    /// Split this client state into the fields shared by all versions and the fork parameters
+    /// stored in [`ClientStateV2`], if any.
+    #[must_use]
+    pub fn into_parts(self) -> (ClientStateV1, Option<ForkParameters>) {
+        match self {
+            ClientState::V1(client_state) => (client_state, None),
+            ClientState::V2(ClientStateV2 {
+                chain_id,
+                chain_spec,
+                genesis_validators_root,
+                genesis_time,
+                latest_height,
+                frozen_height,
+                ibc_contract_address,
+                initial_sync_committee,
+                fork_parameters,
+            }) => (
+                ClientStateV1 {
+                    chain_id,
+                    chain_spec,
+                    genesis_validators_root,
+                    genesis_time,
+                    latest_height,
+                    frozen_height,
+                    ibc_contract_address,
+                    initial_sync_committee,
+                },
+                Some(fork_parameters),
+            ),
+        }
+    }
+
+    /// The inverse of [`Self::into_parts`]; builds a [`ClientStateV2`] if `fork_parameters` is
+    /// set, and a [`ClientStateV1`] otherwise.
+    #[must_use]
+    pub fn from_parts(
+        client_state: ClientStateV1,
+        fork_parameters: Option<ForkParameters>,
+    ) -> Self {
+        let Some(fork_parameters) = fork_parameters else {
+            return ClientState::V1(client_state);
+        };
+
+        let ClientStateV1 {
+            chain_id,
+            chain_spec,
+            genesis_validators_root,
+            genesis_time,
+            latest_height,
+            frozen_height,
+            ibc_contract_address,
+            initial_sync_committee,
+        } = client_state;
+
+        ClientState::V2(ClientStateV2 {
+            chain_id,
+            chain_spec,
+            genesis_validators_root,
+            genesis_time,
+            latest_height,
+            frozen_height,
+            ibc_contract_address,
+            initial_sync_committee,
+            fork_parameters,
+        })
+    }
+
+    #[must_use]
+    pub fn chain_id(&self) -> u64 {
+        match self {
+            ClientState::V1(client_state) => client_state.chain_id,
+            ClientState::V2(client_state) => client_state.chain_id,
+        }
+    }
+
+    #[must_use]
+    pub fn latest_height(&self) -> u64 {
+        match self {
+            ClientState::V1(client_state) => client_state.latest_height,
+            ClientState::V2(client_state) => client_state.latest_height,
+        }
+    }
+
+    #[must_use]
+    pub fn frozen_height(&self) -> Height {
+        match self {
+            ClientState::V1(client_state) => client_state.frozen_height,
+            ClientState::V2(client_state) => client_state.frozen_height,
+        }
+    }
+
+    /// The fork parameters of the tracked beacon chain.
+    #[must_use]
+    pub fn fork_parameters(&self) -> ForkParameters {
+        match self {
+            ClientState::V1(client_state) => client_state.fork_parameters(),
+            ClientState::V2(client_state) => client_state.fork_parameters.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
@@ -45,16 +182,17 @@ pub struct InitialSyncCommittee {
 
 #[cfg(test)]
 mod tests {
+    use hex_literal::hex;
     use unionlabs::{
-        encoding::{Bincode, Json},
+        encoding::{Bincode, DecodeAs, Json},
         primitives::H256,
         test_utils::assert_codec_iso,
     };
 
     use super::*;
 
-    fn mk_client_state() -> ClientState {
-        ClientState::V1(ClientStateV1 {
+    fn mk_client_state_v1() -> ClientStateV1 {
+        ClientStateV1 {
             chain_id: 1,
             chain_spec: PresetBaseKind::Minimal,
             genesis_validators_root: H256::new([0xAA; 32]),
@@ -63,7 +201,15 @@ mod tests {
             frozen_height: Height::new(1),
             ibc_contract_address: H160::new([0xAA; 20]),
             initial_sync_committee: None,
-        })
+        }
+    }
+
+    fn mk_client_state() -> ClientState {
+        ClientState::V1(mk_client_state_v1())
+    }
+
+    fn mk_client_state_v2() -> ClientState {
+        ClientState::from_parts(mk_client_state_v1(), Some(fork_schedules::LATEST.into()))
     }
 
     #[test]
@@ -75,4 +221,73 @@ mod tests {
     fn json_iso() {
         assert_codec_iso::<_, Json>(&mk_client_state());
     }
+
+    #[test]
+    fn bincode_iso_v2() {
+        assert_codec_iso::<_, Bincode>(&mk_client_state_v2());
+    }
+
+    #[test]
+    fn json_iso_v2() {
+        assert_codec_iso::<_, Json>(&mk_client_state_v2());
+    }
+
+    /// A client state stored on chain before [`ClientStateV2`] was introduced must still decode.
+    #[test]
+    fn decode_stored_v1() {
+        let client_state = ClientState::decode_as::<Bincode>(&hex!(
+            "00000000a736aa000000000001000000d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078607db062000000003d3279000000000000000000000000000006a409cbed33caa9bf8181ef3aff2c504e1cfb9500"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            client_state,
+            ClientState::V1(ClientStateV1 {
+                chain_id: 11155111,
+                chain_spec: PresetBaseKind::Mainnet,
+                genesis_validators_root: H256::new(hex!(
+                    "d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078"
+                )),
+                genesis_time: 1655733600,
+                latest_height: 7942717,
+                frozen_height: Height::new(0),
+                ibc_contract_address: H160::new(hex!("06a409cbed33caa9bf8181ef3aff2c504e1cfb95")),
+                initial_sync_committee: None,
+            })
+        );
+
+        assert_eq!(
+            client_state.fork_parameters(),
+            ForkParameters::from(fork_schedules::SEPOLIA)
+        );
+    }
+
+    #[test]
+    fn parts_roundtrip() {
+        assert_eq!(
+            ClientState::from_parts(mk_client_state().into_parts().0, None),
+            mk_client_state()
+        );
+
+        let (client_state, fork_parameters) = mk_client_state_v2().into_parts();
+
+        assert_eq!(client_state, mk_client_state_v1());
+        assert_eq!(
+            ClientState::from_parts(client_state, fork_parameters),
+            mk_client_state_v2()
+        );
+    }
+
+    #[test]
+    fn fork_parameters() {
+        assert_eq!(
+            mk_client_state().fork_parameters(),
+            ForkParameters::from(fork_schedules::MAINNET)
+        );
+
+        assert_eq!(
+            mk_client_state_v2().fork_parameters(),
+            ForkParameters::from(fork_schedules::LATEST)
+        );
+    }
 }
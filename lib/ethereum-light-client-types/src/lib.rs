@@ -9,7 +9,7 @@ pub mod storage_proof;
 
 pub use crate::{
     account_proof::AccountProof,
-    client_state::{ClientState, ClientStateV1},
+    client_state::{ClientState, ClientStateV1, ClientStateV2},
     consensus_state::ConsensusState,
     header::Header,
     light_client_update::{
@@ -17,7 +17,7 @@ use beacon_api_types::{
     deneb,
 };
 use ethereum_sync_protocol_types::LightClientHeader;
-use fork_schedules::{ForkSchedule, Forks};
+use fork_schedules::{ForkParameters, Forks};
 use ssz::Ssz;
 use typenum::Unsigned;
 use unionlabs::{
@@ -70,7 +70,7 @@ pub trait BlsVerify {
 /// [See in consensus-spec](https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#validate_light_client_update)
 #[allow(clippy::too_many_arguments)]
 pub fn validate_light_client_update<C: ChainSpec, V: BlsVerify>(
-    chain_id: u64,
+    fork_parameters: &ForkParameters,
     update: &ethereum_sync_protocol_types::LightClientUpdate,
     current_sync_committee: Option<&SyncCommittee>,
     next_sync_committee: Option<&SyncCommittee>,
@@ -88,7 +88,7 @@ pub fn validate_light_client_update<C: ChainSpec, V: BlsVerify>(
         Error::InsufficientSyncCommitteeParticipants(set_bits),
     )?;
 
-    is_valid_light_client_header::<C>(chain_id, &update.attested_header)?;
+    is_valid_light_client_header::<C>(fork_parameters, &update.attested_header)?;
 
     // verify that the update does not skip a sync committee period
     let update_attested_slot = update.attested_header.beacon.slot;
@@ -170,13 +170,13 @@ pub fn validate_light_client_update<C: ChainSpec, V: BlsVerify>(
     // Verify that the `finality_branch`, if present, confirms `finalized_header`
     // to match the finalized checkpoint root saved in the state of `attested_header`.
     // NOTE(aeryz): We always expect to get `finalized_header` and it's embedded into the type definition.
-    is_valid_light_client_header::<C>(chain_id, &update.finalized_header)?;
+    is_valid_light_client_header::<C>(fork_parameters, &update.finalized_header)?;
 
     // This confirms that the `finalized_header` is really finalized.
     validate_merkle_branch(
         &update.finalized_header.beacon.tree_hash_root(),
         &update.finality_branch,
-        finalized_root_gindex_at_slot::<C>(chain_id, update_attested_slot),
+        finalized_root_gindex_at_slot::<C>(fork_parameters, update_attested_slot),
         &update.attested_header.beacon.state_root,
     )?;
 
@@ -204,7 +204,7 @@ pub fn validate_light_client_update<C: ChainSpec, V: BlsVerify>(
                 .clone()
                 .unwrap_or_default()
                 .iter(),
-            next_sync_committee_gindex_at_slot::<C>(chain_id, update_attested_slot),
+            next_sync_committee_gindex_at_slot::<C>(fork_parameters, update_attested_slot),
             &update.attested_header.beacon.state_root,
         )?;
     }
@@ -217,7 +217,7 @@ pub fn validate_light_client_update<C: ChainSpec, V: BlsVerify>(
     };
 
     verify_signature::<C, V>(
-        chain_id,
+        fork_parameters,
         update,
         sync_aggregate,
         genesis_validators_root,
@@ -231,7 +231,7 @@ pub fn validate_light_client_update<C: ChainSpec, V: BlsVerify>(
 }
 
 pub fn verify_signature<C: ChainSpec, V: BlsVerify>(
-    chain_id: u64,
+    fork_parameters: &ForkParameters,
     update: &ethereum_sync_protocol_types::LightClientUpdate,
     sync_aggregate: SyncAggregateSsz<C>,
     genesis_validators_root: H256,
@@ -239,16 +239,16 @@ pub fn verify_signature<C: ChainSpec, V: BlsVerify>(
     bls_verifier: V,
 ) -> Result<(), Error> {
     let fork_version_slot = Slot::new(std::cmp::max(update.signature_slot.get(), 1) - 1);
-    let fork_version =
-        compute_fork_version(chain_id, compute_epoch_at_slot::<C>(fork_version_slot));
+    let fork_version = compute_fork_version(
+        fork_parameters,
+        compute_epoch_at_slot::<C>(fork_version_slot),
+    );
 
     let domain = compute_domain(
         DomainType::SYNC_COMMITTEE,
         Some(fork_version),
         Some(genesis_validators_root),
-        ForkSchedule::for_chain_id(chain_id)
-            .genesis()
-            .current_version,
+        fork_parameters.genesis().current_version,
     );
     let signing_root = compute_signing_root(&update.attested_header.beacon, domain);
 
@@ -278,13 +278,14 @@ pub fn verify_signature<C: ChainSpec, V: BlsVerify>(
 /// Computes the execution block root hash.
 ///
 /// [See in consensus-spec](https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/light-client/sync-protocol.md#modified-get_lc_execution_root)
-pub fn get_lc_execution_root<C: ChainSpec>(chain_id: u64, header: &LightClientHeader) -> H256 {
-    let fs = ForkSchedule::for_chain_id(chain_id);
-
+pub fn get_lc_execution_root<C: ChainSpec>(
+    fork_parameters: &ForkParameters,
+    header: &LightClientHeader,
+) -> H256 {
     let epoch = compute_epoch_at_slot::<C>(header.beacon.slot);
 
     // No new field in electra
-    if let Some(fork) = fs.fork(Forks::Electra)
+    if let Some(fork) = fork_parameters.fork(Forks::Electra)
         && epoch >= fork.epoch
     {
         return TryInto::<deneb::ExecutionPayloadHeaderSsz<C>>::try_into(header.execution.clone())
@@ -292,7 +293,7 @@ pub fn get_lc_execution_root<C: ChainSpec>(chain_id: u64, header: &LightClientHe
             .tree_hash_root();
     }
 
-    if let Some(fork) = fs.fork(Forks::Deneb)
+    if let Some(fork) = fork_parameters.fork(Forks::Deneb)
         && epoch >= fork.epoch
     {
         return TryInto::<deneb::ExecutionPayloadHeaderSsz<C>>::try_into(header.execution.clone())
@@ -314,29 +315,27 @@ pub fn get_lc_execution_root<C: ChainSpec>(chain_id: u64, header: &LightClientHe
 ///
 /// [See in consensus-spec](https://github.com/ethereum/consensus-specs/blob/dev/specs/deneb/light-client/sync-protocol.md#modified-is_valid_light_client_header)
 pub fn is_valid_light_client_header<C: ChainSpec>(
-    chain_id: u64,
+    fork_parameters: &ForkParameters,
     header: &LightClientHeader,
 ) -> Result<(), Error> {
-    let fs = ForkSchedule::for_chain_id(chain_id);
-
     let epoch = compute_epoch_at_slot::<C>(header.beacon.slot);
 
-    if let Some(fork) = fs.fork(Forks::Deneb) {
-        if epoch < fork.epoch {
-            ensure(
-                header.execution.blob_gas_used.is_zero()
-                    && header.execution.excess_blob_gas.is_zero(),
-                Error::MustBeDeneb,
-            )?;
-        }
+    // fork parameters are no longer limited to well-known chains, all of which support deneb
+    let fork = fork_parameters
+        .fork(Forks::Deneb)
+        .ok_or(Error::InvalidChainVersion)?;
 
-        ensure(epoch >= fork.epoch, Error::InvalidChainVersion)?;
-    } else {
-        unreachable!("all known chains support deneb");
+    if epoch < fork.epoch {
+        ensure(
+            header.execution.blob_gas_used.is_zero() && header.execution.excess_blob_gas.is_zero(),
+            Error::MustBeDeneb,
+        )?;
     }
 
+    ensure(epoch >= fork.epoch, Error::InvalidChainVersion)?;
+
     Ok(validate_merkle_branch(
-        &get_lc_execution_root::<C>(chain_id, header),
+        &get_lc_execution_root::<C>(fork_parameters, header),
         &header.execution_branch,
         EXECUTION_PAYLOAD_GINDEX,
         &header.beacon.body_root,
@@ -344,12 +343,13 @@ pub fn is_valid_light_client_header<C: ChainSpec>(
 }
 
 /// <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#finalized_root_gindex_at_slot>
-pub fn finalized_root_gindex_at_slot<C: ChainSpec>(chain_id: u64, slot: Slot) -> u64 {
-    let fs = ForkSchedule::for_chain_id(chain_id);
-
+pub fn finalized_root_gindex_at_slot<C: ChainSpec>(
+    fork_parameters: &ForkParameters,
+    slot: Slot,
+) -> u64 {
     let epoch = compute_epoch_at_slot::<C>(slot);
 
-    if let Some(fork) = fs.fork(Forks::Electra)
+    if let Some(fork) = fork_parameters.fork(Forks::Electra)
         && epoch >= fork.epoch
     {
         return FINALIZED_ROOT_GINDEX_ELECTRA;
@@ -359,12 +359,13 @@ pub fn finalized_root_gindex_at_slot<C: ChainSpec>(chain_id: u64, slot: Slot) ->
 }
 
 /// <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#current_sync_committee_gindex_at_slot>
-pub fn current_sync_committee_gindex_at_slot<C: ChainSpec>(chain_id: u64, slot: Slot) -> u64 {
-    let fs = ForkSchedule::for_chain_id(chain_id);
-
+pub fn current_sync_committee_gindex_at_slot<C: ChainSpec>(
+    fork_parameters: &ForkParameters,
+    slot: Slot,
+) -> u64 {
     let epoch = compute_epoch_at_slot::<C>(slot);
 
-    if let Some(fork) = fs.fork(Forks::Electra)
+    if let Some(fork) = fork_parameters.fork(Forks::Electra)
         && epoch >= fork.epoch
     {
         return CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA;
@@ -374,12 +375,13 @@ pub fn current_sync_committee_gindex_at_slot<C: ChainSpec>(chain_id: u64, slot:
 }
 
 /// <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/sync-protocol.md#next_sync_committee_gindex_at_slot>
-pub fn next_sync_committee_gindex_at_slot<C: ChainSpec>(chain_id: u64, slot: Slot) -> u64 {
-    let fs = ForkSchedule::for_chain_id(chain_id);
-
+pub fn next_sync_committee_gindex_at_slot<C: ChainSpec>(
+    fork_parameters: &ForkParameters,
+    slot: Slot,
+) -> u64 {
     let epoch = compute_epoch_at_slot::<C>(slot);
 
-    if let Some(fork) = fs.fork(Forks::Electra)
+    if let Some(fork) = fork_parameters.fork(Forks::Electra)
         && epoch >= fork.epoch
     {
         return NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA;
@@ -411,7 +413,9 @@ mod tests {
         }
     }
 
-    const SEPOLIA_CHAIN_ID: u64 = 11155111;
+    fn sepolia() -> ForkParameters {
+        fork_schedules::SEPOLIA.into()
+    }
 
     mod data_6553725 {
         use std::sync::LazyLock;
@@ -438,7 +442,7 @@ mod tests {
         bls_verifier: V,
     ) -> Result<(), Error> {
         validate_light_client_update::<Mainnet, V>(
-            SEPOLIA_CHAIN_ID,
+            &sepolia(),
             update,
             Some(&data_6553725::SYNC_COMMITTEE),
             None,
@@ -491,7 +495,7 @@ mod tests {
 
         assert!(matches!(
             validate_light_client_update::<Mainnet, AlwaysSuccessBlsVerifier>(
-                SEPOLIA_CHAIN_ID,
+                &sepolia(),
                 &update,
                 Some(&data_6553725::SYNC_COMMITTEE),
                 Some(&next_sync_committee),
@@ -534,7 +538,7 @@ mod tests {
                 let fork_version_slot =
                     Slot::new(std::cmp::max(data_6553725::UPDATE.signature_slot.get(), 1) - 1);
                 let fork_version = compute_fork_version(
-                    SEPOLIA_CHAIN_ID,
+                    &sepolia(),
                     compute_epoch_at_slot::<Mainnet>(fork_version_slot),
                 );
 
@@ -542,9 +546,7 @@ mod tests {
                     DomainType::SYNC_COMMITTEE,
                     Some(fork_version),
                     Some(data_6553725::GENESIS_VALIDATORS_ROOT),
-                    ForkSchedule::for_chain_id(SEPOLIA_CHAIN_ID)
-                        .genesis()
-                        .current_version,
+                    sepolia().genesis().current_version,
                 );
                 let signing_root =
                     compute_signing_root(&data_6553725::UPDATE.attested_header.beacon, domain);
@@ -617,7 +619,7 @@ mod tests {
 
         assert!(matches!(
             validate_light_client_update::<Mainnet, AlwaysSuccessBlsVerifier>(
-                SEPOLIA_CHAIN_ID,
+                &sepolia(),
                 &update,
                 Some(&data_6553725::SYNC_COMMITTEE),
                 None,
@@ -631,7 +633,7 @@ mod tests {
 
         assert!(matches!(
             validate_light_client_update::<Mainnet, AlwaysSuccessBlsVerifier>(
-                SEPOLIA_CHAIN_ID,
+                &sepolia(),
                 &update,
                 Some(&data_6553725::SYNC_COMMITTEE),
                 Some(&data_6553725::SYNC_COMMITTEE),
@@ -647,7 +649,7 @@ mod tests {
         update.signature_slot = original_signature_slot + Slot::new(256 * 32);
         assert_eq!(
             validate_light_client_update::<Mainnet, AlwaysSuccessBlsVerifier>(
-                SEPOLIA_CHAIN_ID,
+                &sepolia(),
                 &update,
                 Some(&data_6553725::SYNC_COMMITTEE),
                 update.next_sync_committee.as_ref(),
@@ -667,7 +669,7 @@ mod tests {
         // if the `update.attested.slot > finalized_slot`, then there must be no next sync committee given
         assert!(matches!(
             validate_light_client_update::<Mainnet, AlwaysSuccessBlsVerifier>(
-                SEPOLIA_CHAIN_ID,
+                &sepolia(),
                 &update,
                 Some(&data_6553725::SYNC_COMMITTEE),
                 update.next_sync_committee.as_ref(),
@@ -684,7 +686,7 @@ mod tests {
         // if the `update.attested.slot > finalized_slot`, then there must be no next sync committee in the update
         assert!(matches!(
             validate_light_client_update::<Mainnet, AlwaysSuccessBlsVerifier>(
-                SEPOLIA_CHAIN_ID,
+                &sepolia(),
                 &update,
                 Some(&data_6553725::SYNC_COMMITTEE),
                 None,
@@ -700,12 +702,12 @@ mod tests {
     #[test]
     fn finalized_groot_index_correct() {
         assert_eq!(
-            finalized_root_gindex_at_slot::<Mainnet>(SEPOLIA_CHAIN_ID, Slot::new(100)),
+            finalized_root_gindex_at_slot::<Mainnet>(&sepolia(), Slot::new(100)),
             FINALIZED_ROOT_GINDEX
         );
 
         assert_eq!(
-            finalized_root_gindex_at_slot::<Mainnet>(SEPOLIA_CHAIN_ID, Slot::new(222465 * 32)),
+            finalized_root_gindex_at_slot::<Mainnet>(&sepolia(), Slot::new(222465 * 32)),
             FINALIZED_ROOT_GINDEX_ELECTRA
         );
     }
@@ -713,16 +715,76 @@ mod tests {
     #[test]
     fn current_sync_committee_gindex_correct() {
         assert_eq!(
-            current_sync_committee_gindex_at_slot::<Mainnet>(SEPOLIA_CHAIN_ID, Slot::new(100)),
+            current_sync_committee_gindex_at_slot::<Mainnet>(&sepolia(), Slot::new(100)),
             CURRENT_SYNC_COMMITTEE_GINDEX
         );
 
         assert_eq!(
-            current_sync_committee_gindex_at_slot::<Mainnet>(
-                SEPOLIA_CHAIN_ID,
-                Slot::new(222465 * 32)
-            ),
+            current_sync_committee_gindex_at_slot::<Mainnet>(&sepolia(), Slot::new(222465 * 32)),
             CURRENT_SYNC_COMMITTEE_GINDEX_ELECTRA
         );
     }
+
+    #[test]
+    fn gindex_uses_fork_parameters() {
+        // a devnet that starts at deneb and forks to electra at epoch 10
+        let mut fork_parameters = ForkParameters::from(fork_schedules::LATEST);
+        fork_parameters.forks.push(fork_schedules::ForkVersion {
+            version: beacon_api_types::custom_types::Version(hex!("05000001").into()),
+            epoch: beacon_api_types::custom_types::Epoch::new(10),
+        });
+
+        assert_eq!(
+            finalized_root_gindex_at_slot::<Mainnet>(&fork_parameters, Slot::new(9 * 32)),
+            FINALIZED_ROOT_GINDEX
+        );
+        assert_eq!(
+            finalized_root_gindex_at_slot::<Mainnet>(&fork_parameters, Slot::new(10 * 32)),
+            FINALIZED_ROOT_GINDEX_ELECTRA
+        );
+        assert_eq!(
+            next_sync_committee_gindex_at_slot::<Mainnet>(&fork_parameters, Slot::new(10 * 32)),
+            NEXT_SYNC_COMMITTEE_GINDEX_ELECTRA
+        );
+
+        // the well-known fork schedule for devnets has not forked to electra yet
+        assert_eq!(
+            finalized_root_gindex_at_slot::<Mainnet>(
+                &fork_schedules::LATEST.into(),
+                Slot::new(10 * 32)
+            ),
+            FINALIZED_ROOT_GINDEX
+        );
+    }
+
+    #[test]
+    fn fork_version_after_fulu() {
+        let mut fork_parameters = ForkParameters::from(fork_schedules::MAINNET);
+        fork_parameters.forks.push(fork_schedules::ForkVersion {
+            version: beacon_api_types::custom_types::Version(hex!("06000000").into()),
+            epoch: beacon_api_types::custom_types::Epoch::new(411392),
+        });
+
+        let electra = fork_parameters.fork(Forks::Electra).unwrap();
+        let fulu = fork_parameters.fork(Forks::Fulu).unwrap();
+
+        assert_eq!(
+            compute_fork_version(
+                &fork_parameters,
+                beacon_api_types::custom_types::Epoch::new(411391)
+            ),
+            electra.current_version
+        );
+        assert_eq!(
+            compute_fork_version(&fork_parameters, fulu.epoch),
+            fulu.current_version
+        );
+        assert_eq!(
+            compute_fork_version(
+                &fork_parameters,
+                beacon_api_types::custom_types::Epoch::new(500000)
+            ),
+            fulu.current_version
+        );
+    }
 }
@@ -4,7 +4,7 @@ use beacon_api_types::{
     custom_types::{Domain, DomainType, Epoch, Period, Slot, Version},
     phase0::{ForkData, SigningData},
 };
-use fork_schedules::{ForkSchedule, Forks};
+use fork_schedules::{ForkParameters, Forks};
 use sha2::{Digest, Sha256};
 use ssz::Ssz;
 use typenum::Unsigned;
@@ -12,35 +12,23 @@ use unionlabs::primitives::H256;
 
 use crate::{error::InvalidMerkleBranch, GENESIS_SLOT};
 
-/// Returns the fork version based on the `epoch` and `chain_id`.
+/// Returns the fork version based on the `epoch` and `fork_parameters`.
 ///
 /// [See in consensus-spec](https://github.com/ethereum/consensus-specs/blob/dev/specs/electra/fork.md#modified-compute_fork_version)
-pub fn compute_fork_version(chain_id: u64, epoch: Epoch) -> Version {
-    let fs = ForkSchedule::for_chain_id(chain_id);
-
-    if let Some(fork) = fs.fork(Forks::Electra)
-        && epoch >= fork.epoch
-    {
-        fork.current_version
-    } else if let Some(fork) = fs.fork(Forks::Deneb)
-        && epoch >= fork.epoch
-    {
-        fork.current_version
-    } else if let Some(fork) = fs.fork(Forks::Deneb)
-        && epoch >= fork.epoch
-    {
-        fork.current_version
-    } else if let Some(fork) = fs.fork(Forks::Deneb)
-        && epoch >= fork.epoch
-    {
-        fork.current_version
-    } else if let Some(fork) = fs.fork(Forks::Deneb)
-        && epoch >= fork.epoch
-    {
-        fork.current_version
-    } else {
-        fs.genesis().current_version
-    }
+pub fn compute_fork_version(fork_parameters: &ForkParameters, epoch: Epoch) -> Version {
+    [
+        Forks::Fulu,
+        Forks::Electra,
+        Forks::Deneb,
+        Forks::Capella,
+        Forks::Bellatrix,
+        Forks::Altair,
+    ]
+    .into_iter()
+    .filter_map(|fork| fork_parameters.fork(fork))
+    .find(|fork| epoch >= fork.epoch)
+    .unwrap_or_else(|| fork_parameters.genesis())
+    .current_version
 }
 
 /// Returns the sync committee period at a given `slot`.
@@ -12,6 +12,8 @@ use crate::rlp_node_codec::{keccak_256, EthLayout, KeccakHasher};
 
 pub mod error;
 mod rlp_node_codec;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
 
 pub const GENESIS_SLOT: u64 = 0;
 pub const GENESIS_EPOCH: u64 = 0;
@@ -0,0 +1,200 @@
+//! Helpers for building (small) merkle patricia tries in tests, to produce proofs that can be
+//! verified with [`verify_storage_proof`](crate::verify_storage_proof) and
+//! [`verify_account_storage_root`](crate::verify_account_storage_root).
+
+use std::collections::BTreeMap;
+
+use rlp::{Encodable, RlpStream};
+use unionlabs::primitives::{H160, H256, U256};
+
+use crate::rlp_node_codec::keccak_256;
+
+/// A secure merkle patricia trie (i.e. the keys are hashed with keccak256 before insertion), as
+/// used for the ethereum state and storage tries.
+#[derive(Debug, Clone, Default)]
+pub struct SecureTrie {
+    entries: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl SecureTrie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert the rlp encoding of `value` at the storage `slot`.
+    pub fn insert_storage(&mut self, slot: U256, value: &impl Encodable) -> &mut Self {
+        self.insert(slot.to_be_bytes(), rlp::encode(value).to_vec())
+    }
+
+    /// Insert an account with the provided `storage_root` at `address`.
+    pub fn insert_account(&mut self, address: H160, storage_root: H256) -> &mut Self {
+        let mut stream = RlpStream::new_list(4);
+        stream.append(&0_u64);
+        stream.append(&U256::ZERO);
+        stream.append(&storage_root.as_ref());
+        stream.append(&keccak_256(&[]).as_slice());
+
+        self.insert(address, stream.out().to_vec())
+    }
+
+    pub fn insert(&mut self, key: impl AsRef<[u8]>, value: Vec<u8>) -> &mut Self {
+        self.entries
+            .insert(nibbles(&keccak_256(key.as_ref())), value);
+        self
+    }
+
+    /// The root of the trie, and all of the nodes in the trie. The nodes are a valid proof for any
+    /// of the entries in the trie.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the trie is empty.
+    pub fn root_and_proof(&self) -> (H256, Vec<Vec<u8>>) {
+        assert!(!self.entries.is_empty(), "trie must not be empty");
+
+        let mut nodes = vec![];
+        let root = encode_node(
+            &self
+                .entries
+                .iter()
+                .map(|(path, value)| (&path[..], &value[..]))
+                .collect::<Vec<_>>(),
+            &mut nodes,
+        );
+
+        let root_hash = keccak_256(&root).into();
+        nodes.push(root);
+
+        (root_hash, nodes)
+    }
+}
+
+fn nibbles(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect()
+}
+
+/// Hex prefix encoding of a path, see appendix C of the yellow paper.
+fn hex_prefix(path: &[u8], is_leaf: bool) -> Vec<u8> {
+    let flag = if is_leaf { 0x20 } else { 0x00 };
+
+    let (mut out, rest) = if path.len() % 2 == 1 {
+        (vec![flag | 0x10 | path[0]], &path[1..])
+    } else {
+        (vec![flag], path)
+    };
+
+    out.extend(rest.chunks(2).map(|chunk| (chunk[0] << 4) | chunk[1]));
+
+    out
+}
+
+/// Encode the node containing `entries`, pushing all of the hashed child nodes to `nodes`. All
+/// paths must be unique and of the same length.
+fn encode_node(entries: &[(&[u8], &[u8])], nodes: &mut Vec<Vec<u8>>) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+
+    match entries {
+        [(path, value)] => {
+            stream.begin_list(2);
+            stream.append(&hex_prefix(path, true));
+            stream.append(value);
+        }
+        [(first, _), rest @ ..] => {
+            let common_prefix_len = rest
+                .iter()
+                .map(|(path, _)| first.iter().zip(*path).take_while(|(a, b)| a == b).count())
+                .min()
+                .expect("there are at least two entries; qed;");
+
+            if common_prefix_len > 0 {
+                let child = encode_node(
+                    &entries
+                        .iter()
+                        .map(|(path, value)| (&path[common_prefix_len..], *value))
+                        .collect::<Vec<_>>(),
+                    nodes,
+                );
+
+                stream.begin_list(2);
+                stream.append(&hex_prefix(&first[..common_prefix_len], false));
+                append_child(&mut stream, child, nodes);
+            } else {
+                stream.begin_list(17);
+                for nibble in 0..16 {
+                    let children = entries
+                        .iter()
+                        .filter(|(path, _)| path[0] == nibble)
+                        .map(|(path, value)| (&path[1..], *value))
+                        .collect::<Vec<_>>();
+
+                    if children.is_empty() {
+                        stream.append_empty_data();
+                    } else {
+                        let child = encode_node(&children, nodes);
+                        append_child(&mut stream, child, nodes);
+                    }
+                }
+                stream.append_empty_data();
+            }
+        }
+        [] => unreachable!("nodes are never empty"),
+    }
+
+    stream.out().to_vec()
+}
+
+/// Nodes shorter than 32 bytes are inlined into their parent, all others are referenced by hash.
+fn append_child(stream: &mut RlpStream, child: Vec<u8>, nodes: &mut Vec<Vec<u8>>) {
+    if child.len() < 32 {
+        stream.append_raw(&child, 1);
+    } else {
+        stream.append(&keccak_256(&child).as_slice());
+        nodes.push(child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify_account_storage_root, verify_storage_proof};
+
+    #[test]
+    fn storage_proofs_verify() {
+        let entries = (0_u64..20)
+            .map(|i| (U256::from(i), U256::from(i * 1000 + 1)))
+            .collect::<Vec<_>>();
+
+        let mut trie = SecureTrie::new();
+        for (slot, value) in &entries {
+            trie.insert_storage(*slot, value);
+        }
+
+        let (root, proof) = trie.root_and_proof();
+
+        for (slot, value) in entries {
+            verify_storage_proof(root, slot, &rlp::encode(&value), &proof).unwrap();
+        }
+
+        assert!(
+            verify_storage_proof(root, 0_u64.into(), &rlp::encode(&U256::from(2_u64)), &proof)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn account_proof_verifies() {
+        let address = H160::new([0xAA; 20]);
+        let storage_root = H256::new([0xBB; 32]);
+
+        let (root, proof) = SecureTrie::new()
+            .insert_account(address, storage_root)
+            .insert_account(H160::new([0xCC; 20]), H256::new([0xDD; 32]))
+            .root_and_proof();
+
+        verify_account_storage_root(root, &address, &proof, &storage_root).unwrap();
+
+        assert!(
+            verify_account_storage_root(root, &address, &proof, &H256::new([0xDD; 32])).is_err()
+        );
+    }
+}
@@ -1,4 +1,4 @@
-//! Fork schedules for well-known beacon chains.
+//! Fork schedules for well-known beacon chains, and [`ForkParameters`] for chains that are not well-known.
 //!
 //! <https://github.com/ethereum/consensus-specs>
 
@@ -203,6 +203,172 @@ impl ForkSchedule {
     }
 }
 
+/// The epoch used for forks that have not been scheduled yet.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/dev/specs/phase0/beacon-chain.md#constants>
+pub const FAR_FUTURE_EPOCH: Epoch = Epoch::new(u64::MAX);
+
+/// An owned fork schedule for an ethereum beacon chain.
+///
+/// Unlike [`ForkSchedule`], this is not limited to well-known chains; it can be built from the fork
+/// versions and epochs in a beacon node's [`/eth/v1/config/spec`] or provided manually, which allows
+/// devnets and custom beacon chains with arbitrary fork epochs to be supported.
+///
+/// [`/eth/v1/config/spec`]: https://ethereum.github.io/beacon-APIs/?urls.primaryName=v1#/Config/getSpec
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct ForkParameters {
+    /// The version of the genesis (phase0) fork.
+    pub genesis_fork_version: Version,
+    /// The epoch of the genesis (phase0) fork.
+    pub genesis_epoch: Epoch,
+    /// The forks after genesis, in order, starting with [`Forks::Altair`].
+    ///
+    /// Forks that have not been scheduled yet must not be included.
+    pub forks: Vec<ForkVersion>,
+}
+
+/// The version and activation epoch of a single fork in [`ForkParameters`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct ForkVersion {
+    /// The fork version.
+    pub version: Version,
+    /// The epoch at which this fork activates.
+    pub epoch: Epoch,
+}
+
+impl ForkParameters {
+    /// Build the fork parameters from the fork versions and epochs of the forks after genesis, in
+    /// order, starting with [`Forks::Altair`].
+    ///
+    /// The schedule ends at the first fork that is either missing or scheduled at
+    /// [`FAR_FUTURE_EPOCH`], since all subsequent forks cannot have been activated either.
+    ///
+    /// ```rust
+    /// # use fork_schedules::{ForkParameters, Forks, FAR_FUTURE_EPOCH};
+    /// # use hex_literal::hex;
+    /// # use beacon_api_types::custom_types::{Version, Epoch};
+    /// let fork_parameters = ForkParameters::from_spec(
+    ///     Version(hex!("10000038").into()),
+    ///     [
+    ///         Some((Version(hex!("20000038").into()), Epoch::new(0))),
+    ///         Some((Version(hex!("30000038").into()), Epoch::new(0))),
+    ///         Some((Version(hex!("40000038").into()), Epoch::new(10))),
+    ///         Some((Version(hex!("50000038").into()), FAR_FUTURE_EPOCH)),
+    ///         None,
+    ///     ],
+    /// );
+    ///
+    /// assert_eq!(fork_parameters.forks.len(), 3);
+    /// assert_eq!(fork_parameters.fork(Forks::Capella).unwrap().epoch, Epoch::new(10));
+    /// assert_eq!(fork_parameters.fork(Forks::Deneb), None);
+    /// ```
+    #[must_use]
+    pub fn from_spec(
+        genesis_fork_version: Version,
+        forks: impl IntoIterator<Item = Option<(Version, Epoch)>>,
+    ) -> Self {
+        Self {
+            genesis_fork_version,
+            genesis_epoch: Epoch::new(0),
+            forks: forks
+                .into_iter()
+                .map_while(|fork| fork.filter(|(_, epoch)| *epoch != FAR_FUTURE_EPOCH))
+                .map(|(version, epoch)| ForkVersion { version, epoch })
+                .collect(),
+        }
+    }
+
+    /// Get the genesis [`Fork`] for these fork parameters.
+    ///
+    /// See [`ForkSchedule::genesis`].
+    #[must_use]
+    pub const fn genesis(&self) -> Fork {
+        Fork {
+            previous_version: self.genesis_fork_version,
+            current_version: self.genesis_fork_version,
+            epoch: self.genesis_epoch,
+        }
+    }
+
+    /// Get the [`Fork`] associated with the [`Forks`] for these fork parameters, or `None` if the
+    /// fork is not scheduled.
+    ///
+    /// See [`ForkSchedule::fork`].
+    #[must_use]
+    pub fn fork(&self, fork: Forks) -> Option<Fork> {
+        let idx = fork as usize - 1;
+
+        let ForkVersion { version, epoch } = *self.forks.get(idx)?;
+
+        Some(Fork {
+            previous_version: match idx.checked_sub(1) {
+                Some(prev) => self.forks[prev].version,
+                None => self.genesis_fork_version,
+            },
+            current_version: version,
+            epoch,
+        })
+    }
+
+    /// Get the [`Fork`] list for these fork parameters.
+    ///
+    /// See [`ForkSchedule::into_fork_schedule_list`].
+    #[must_use]
+    pub fn into_fork_schedule_list(self) -> Vec<Fork> {
+        let mut previous_version = self.genesis_fork_version;
+
+        [self.genesis()]
+            .into_iter()
+            .chain(
+                self.forks
+                    .into_iter()
+                    .map(|ForkVersion { version, epoch }| {
+                        let fork = Fork {
+                            previous_version,
+                            current_version: version,
+                            epoch,
+                        };
+                        previous_version = version;
+                        fork
+                    }),
+            )
+            .collect()
+    }
+}
+
+impl From<ForkSchedule> for ForkParameters {
+    fn from(fork_schedule: ForkSchedule) -> Self {
+        let mut forks = fork_schedule.into_fork_schedule_list().into_iter();
+
+        let genesis = forks
+            .next()
+            .expect("fork schedule always contains genesis; qed;");
+
+        Self {
+            genesis_fork_version: genesis.current_version,
+            genesis_epoch: genesis.epoch,
+            forks: forks
+                .map(|fork| ForkVersion {
+                    version: fork.current_version,
+                    epoch: fork.epoch,
+                })
+                .collect(),
+        }
+    }
+}
+
 macro_rules! fork_schedule {
     ($(#[$attr:meta])* pub const $name:ident: _ = [($version:literal, $epoch:literal), $($tt:tt)*];) => {
         $(#[$attr])*
@@ -377,4 +543,30 @@ mod tests {
     fn test_len() {
         assert_eq!(MAINNET.len(), 6);
     }
+
+    #[test]
+    fn fork_parameters_from_fork_schedule() {
+        for fork_schedule in [MAINNET, SEPOLIA, HOLESKY, LATEST] {
+            let fork_parameters = ForkParameters::from(ForkSchedule { ..fork_schedule });
+
+            let list = fork_schedule.into_fork_schedule_list();
+
+            assert_eq!(fork_parameters.clone().into_fork_schedule_list(), list);
+        }
+
+        let fork_parameters = ForkParameters::from(MAINNET);
+
+        assert_eq!(fork_parameters.genesis(), MAINNET.genesis());
+
+        for fork in [
+            Forks::Altair,
+            Forks::Bellatrix,
+            Forks::Capella,
+            Forks::Deneb,
+            Forks::Electra,
+            Forks::Fulu,
+        ] {
+            assert_eq!(fork_parameters.fork(fork), MAINNET.fork(fork));
+        }
+    }
 }
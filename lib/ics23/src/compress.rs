@@ -0,0 +1,217 @@
+use std::borrow::Cow;
+
+use unionlabs::{
+    bounded::BoundedI32,
+    cosmos::ics23::{
+        batch_entry::BatchEntry, batch_proof::BatchProof, commitment_proof::CommitmentProof,
+        compressed_batch_entry::CompressedBatchEntry, compressed_batch_proof::CompressedBatchProof,
+        compressed_existence_proof::CompressedExistenceProof,
+        compressed_non_existence_proof::CompressedNonExistenceProof,
+        existence_proof::ExistenceProof, inner_op::InnerOp, non_existence_proof::NonExistenceProof,
+    },
+};
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum DecompressError {
+    #[error("invalid lookup index {index} (lookup length: {lookup_len})")]
+    InvalidLookupIndex { index: usize, lookup_len: usize },
+}
+
+/// Decompresses a [`CommitmentProof::CompressedBatch`] into a [`CommitmentProof::Batch`], leaving
+/// all other proofs as they are.
+pub fn decompress(proof: &CommitmentProof) -> Result<Cow<'_, CommitmentProof>, DecompressError> {
+    match proof {
+        CommitmentProof::CompressedBatch(compressed) => Ok(Cow::Owned(CommitmentProof::Batch(
+            decompress_batch(compressed)?,
+        ))),
+        proof => Ok(Cow::Borrowed(proof)),
+    }
+}
+
+/// Decompresses a [`CompressedBatchProof`] by resolving the path of each entry from the
+/// `lookup_inners`.
+pub fn decompress_batch(proof: &CompressedBatchProof) -> Result<BatchProof, DecompressError> {
+    let lookup = &proof.lookup_inners;
+
+    Ok(BatchProof {
+        entries: proof
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                CompressedBatchEntry::Exist(exist) => {
+                    decompress_exist(exist, lookup).map(BatchEntry::Exist)
+                }
+                CompressedBatchEntry::Nonexist(non_exist) => {
+                    Ok(BatchEntry::Nonexist(NonExistenceProof {
+                        key: non_exist.key.clone(),
+                        left: non_exist
+                            .left
+                            .as_ref()
+                            .map(|left| decompress_exist(left, lookup))
+                            .transpose()?,
+                        right: non_exist
+                            .right
+                            .as_ref()
+                            .map(|right| decompress_exist(right, lookup))
+                            .transpose()?,
+                    }))
+                }
+            })
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn decompress_exist(
+    exist: &CompressedExistenceProof,
+    lookup: &[InnerOp],
+) -> Result<ExistenceProof, DecompressError> {
+    Ok(ExistenceProof {
+        key: exist.key.clone().into(),
+        value: exist.value.clone().into(),
+        leaf: exist.leaf.clone(),
+        path: exist
+            .path
+            .iter()
+            .map(|index| {
+                let index = usize::try_from(index.inner()).expect("index is non-negative; qed;");
+
+                lookup
+                    .get(index)
+                    .cloned()
+                    .ok_or(DecompressError::InvalidLookupIndex {
+                        index,
+                        lookup_len: lookup.len(),
+                    })
+            })
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Compresses a [`BatchProof`] by deduplicating the inner ops of all entries into the
+/// `lookup_inners` of the resulting [`CompressedBatchProof`].
+#[must_use]
+pub fn compress_batch(proof: &BatchProof) -> CompressedBatchProof {
+    let mut lookup = vec![];
+
+    let entries = proof
+        .entries
+        .iter()
+        .map(|entry| match entry {
+            BatchEntry::Exist(exist) => {
+                CompressedBatchEntry::Exist(compress_exist(exist, &mut lookup))
+            }
+            BatchEntry::Nonexist(non_exist) => {
+                CompressedBatchEntry::Nonexist(CompressedNonExistenceProof {
+                    key: non_exist.key.clone(),
+                    left: non_exist
+                        .left
+                        .as_ref()
+                        .map(|left| compress_exist(left, &mut lookup)),
+                    right: non_exist
+                        .right
+                        .as_ref()
+                        .map(|right| compress_exist(right, &mut lookup)),
+                })
+            }
+        })
+        .collect();
+
+    CompressedBatchProof {
+        entries,
+        lookup_inners: lookup,
+    }
+}
+
+fn compress_exist(exist: &ExistenceProof, lookup: &mut Vec<InnerOp>) -> CompressedExistenceProof {
+    CompressedExistenceProof {
+        key: exist.key.to_vec(),
+        value: exist.value.to_vec(),
+        leaf: exist.leaf.clone(),
+        path: exist
+            .path
+            .iter()
+            .map(|inner| {
+                let index = lookup.iter().position(|op| op == inner).unwrap_or_else(|| {
+                    lookup.push(inner.clone());
+                    lookup.len() - 1
+                });
+
+                i32::try_from(index)
+                    .ok()
+                    .and_then(|index| BoundedI32::new(index).ok())
+                    .expect("lookup length is less than i32::MAX; qed;")
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use unionlabs::cosmos::ics23::{hash_op::HashOp, leaf_op::LeafOp, length_op::LengthOp};
+
+    use super::*;
+
+    fn inner(prefix: u8) -> InnerOp {
+        InnerOp {
+            hash: HashOp::Sha256,
+            prefix: vec![prefix].into(),
+            suffix: Default::default(),
+        }
+    }
+
+    fn exist(key: &[u8], path: &[u8]) -> ExistenceProof {
+        ExistenceProof {
+            key: key.to_vec().into(),
+            value: b"value".to_vec().into(),
+            leaf: LeafOp {
+                hash: HashOp::Sha256,
+                prehash_key: HashOp::NoHash,
+                prehash_value: HashOp::Sha256,
+                length: LengthOp::VarProto,
+                prefix: vec![0].into(),
+            },
+            path: path.iter().copied().map(inner).collect(),
+        }
+    }
+
+    #[test]
+    fn compress_roundtrip() {
+        let batch = BatchProof {
+            entries: vec![
+                BatchEntry::Exist(exist(b"a", &[1, 2, 3])),
+                BatchEntry::Exist(exist(b"b", &[4, 2, 3])),
+                BatchEntry::Nonexist(NonExistenceProof {
+                    key: b"c".to_vec(),
+                    left: Some(exist(b"b", &[4, 2, 3])),
+                    right: None,
+                }),
+            ],
+        };
+
+        let compressed = compress_batch(&batch);
+
+        assert_eq!(compressed.lookup_inners, [1, 2, 3, 4].map(inner).to_vec());
+        assert_eq!(decompress_batch(&compressed), Ok(batch.clone()));
+        assert_eq!(
+            decompress(&CommitmentProof::CompressedBatch(compressed)).map(Cow::into_owned),
+            Ok(CommitmentProof::Batch(batch))
+        );
+    }
+
+    #[test]
+    fn decompress_invalid_lookup_index() {
+        let mut compressed = compress_batch(&BatchProof {
+            entries: vec![BatchEntry::Exist(exist(b"a", &[1, 2]))],
+        });
+
+        compressed.lookup_inners.pop();
+
+        assert_eq!(
+            decompress_batch(&compressed),
+            Err(DecompressError::InvalidLookupIndex {
+                index: 1,
+                lookup_len: 1
+            })
+        );
+    }
+}
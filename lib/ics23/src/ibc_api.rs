@@ -1,11 +1,16 @@
+use std::borrow::Cow;
+
 use unionlabs::{
-    cosmos::ics23::{commitment_proof::CommitmentProof, proof_spec::ProofSpec},
+    cosmos::ics23::{
+        batch_entry::BatchEntry, commitment_proof::CommitmentProof, proof_spec::ProofSpec,
+    },
     ibc::core::commitment::{merkle_proof::MerkleProof, merkle_root::MerkleRoot},
     primitives::Bytes,
 };
 
 pub use crate::proof_specs::{IAVL_PROOF_SPEC, TENDERMINT_PROOF_SPEC};
 use crate::{
+    compress::{decompress, DecompressError},
     existence_proof,
     verify::{self},
 };
@@ -32,6 +37,10 @@ pub enum VerifyMembershipError {
     EmptyNonExistenceProof,
     #[error("given proof is empty")]
     EmptyProof,
+    #[error("batch proof decompression failed ({0})")]
+    Decompress(DecompressError),
+    #[error("batch proof has no entries")]
+    EmptyBatchProof,
 }
 
 pub fn verify_membership(
@@ -117,6 +126,133 @@ pub fn verify_non_membership(
     )
 }
 
+/// Verifies that all of the `items` (key, value) are stored under the same `prefix` path, using a
+/// single chained proof where the first proof is a batch (or compressed batch) proof of all of the
+/// items and the remaining proofs prove the root of the batch proof.
+///
+/// `prefix` is the key path of the store the items are in, i.e. all of the path except for the
+/// last key.
+pub fn verify_batch_membership(
+    proof: &MerkleProof,
+    specs: &[ProofSpec],
+    consensus_root: &MerkleRoot,
+    prefix: &[Vec<u8>],
+    items: &[(Vec<u8>, Vec<u8>)],
+) -> Result<(), VerifyMembershipError> {
+    let (batch_proof, subroot) = batch_subroot(proof, specs, prefix)?;
+
+    verify::verify_batch_membership(
+        &specs[0],
+        &subroot,
+        &batch_proof,
+        &items
+            .iter()
+            .map(|(key, value)| (key.as_slice(), value.as_slice()))
+            .collect::<Vec<_>>(),
+    )
+    .map_err(VerifyMembershipError::InnerVerification)?;
+
+    verify_chained_membership_proof(
+        consensus_root.hash.as_ref(),
+        specs,
+        &proof.proofs,
+        &batch_key_path(prefix),
+        subroot,
+        1,
+    )
+}
+
+/// Verifies that none of the `keys` are stored under the same `prefix` path, using a single
+/// chained proof where the first proof is a batch (or compressed batch) proof of all of the keys
+/// and the remaining proofs prove the root of the batch proof.
+///
+/// See [`verify_batch_membership`].
+pub fn verify_batch_non_membership(
+    proof: &MerkleProof,
+    specs: &[ProofSpec],
+    consensus_root: &MerkleRoot,
+    prefix: &[Vec<u8>],
+    keys: &[Vec<u8>],
+) -> Result<(), VerifyMembershipError> {
+    let (batch_proof, subroot) = batch_subroot(proof, specs, prefix)?;
+
+    verify::verify_batch_non_membership(
+        &specs[0],
+        &subroot,
+        &batch_proof,
+        &keys.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+    )
+    .map_err(VerifyMembershipError::InnerVerification)?;
+
+    verify_chained_membership_proof(
+        consensus_root.hash.as_ref(),
+        specs,
+        &proof.proofs,
+        &batch_key_path(prefix),
+        subroot,
+        1,
+    )
+}
+
+/// Decompress the first proof of a chained batch proof, and calculate the root it proves the
+/// entries against.
+fn batch_subroot<'a>(
+    proof: &'a MerkleProof,
+    specs: &[ProofSpec],
+    prefix: &[Vec<u8>],
+) -> Result<(Cow<'a, CommitmentProof>, Vec<u8>), VerifyMembershipError> {
+    if proof.proofs.is_empty() {
+        return Err(VerifyMembershipError::EmptyProof);
+    }
+
+    if proof.proofs.len() != specs.len() {
+        return Err(VerifyMembershipError::InvalidProofsLength {
+            expected: specs.len(),
+            found: proof.proofs.len(),
+        });
+    }
+
+    // the batch proof itself proves the last key of the path
+    if prefix.len() + 1 != specs.len() {
+        return Err(VerifyMembershipError::InvalidKeyPathLength {
+            expected: specs.len() - 1,
+            found: prefix.len(),
+        });
+    }
+
+    let batch_proof = decompress(&proof.proofs[0]).map_err(VerifyMembershipError::Decompress)?;
+
+    // all entries are verified against this root, so any of them can be used to calculate it
+    let existence_proof = match &*batch_proof {
+        CommitmentProof::Exist(existence_proof) => Some(existence_proof),
+        CommitmentProof::Nonexist(non_existence_proof) => non_existence_proof
+            .left
+            .as_ref()
+            .or(non_existence_proof.right.as_ref()),
+        CommitmentProof::Batch(batch) => batch.entries.first().and_then(|entry| match entry {
+            BatchEntry::Exist(existence_proof) => Some(existence_proof),
+            BatchEntry::Nonexist(non_existence_proof) => non_existence_proof
+                .left
+                .as_ref()
+                .or(non_existence_proof.right.as_ref()),
+        }),
+        CommitmentProof::CompressedBatch(_) => unreachable!("proof was decompressed above; qed;"),
+    }
+    .ok_or(VerifyMembershipError::EmptyBatchProof)?;
+
+    let subroot = existence_proof::calculate_root(existence_proof)
+        .map_err(VerifyMembershipError::RootCalculation)?;
+
+    Ok((batch_proof, subroot))
+}
+
+/// The key path used to verify the chained proofs of a batch proof. The last key is proven by the
+/// batch proof itself and is never read when verifying the chain starting at index 1, so an empty
+/// key is used in its place.
+fn batch_key_path(prefix: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    prefix.iter().cloned().chain([vec![]]).collect()
+}
+
 fn verify_chained_membership_proof(
     root: &[u8],
     specs: &[ProofSpec],
@@ -1,6 +1,7 @@
 // #![warn(clippy::pedantic)]
 #![allow(clippy::missing_errors_doc)]
 
+pub mod compress;
 pub mod existence_proof;
 pub mod ibc_api;
 pub mod ops;
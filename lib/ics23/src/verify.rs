@@ -2,6 +2,8 @@ use std::borrow::{Borrow, Cow};
 
 use unionlabs::{
     cosmos::ics23::{
+        batch_entry::BatchEntry,
+        commitment_proof::CommitmentProof,
         existence_proof::ExistenceProof,
         hash_op::HashOp,
         inner_op::InnerOp,
@@ -13,6 +15,7 @@ use unionlabs::{
 };
 
 use crate::{
+    compress::{decompress, DecompressError},
     existence_proof::{self, CalculateRootError, SpecMismatchError},
     ops::hash_op::{do_hash, HashError},
 };
@@ -62,6 +65,10 @@ pub enum VerifyMembershipError {
     ExistenceProofVerify(VerifyError),
     #[error("proof does not exist")]
     ProofDoesNotExist,
+    #[error("proof decompression failed ({0})")]
+    Decompress(DecompressError),
+    #[error(transparent)]
+    Hash(#[from] HashError),
 }
 
 #[derive(Debug, Clone, PartialEq, thiserror::Error)]
@@ -102,6 +109,113 @@ pub fn verify_membership(
         .map_err(VerifyMembershipError::ExistenceProofVerify)
 }
 
+/// Implements ICS-23 batchVerifyMembership: verifies a proof that many paths have been set to
+/// particular values in a commitment.
+///
+/// `proof` can be a single existence proof, a batch proof or a compressed batch proof.
+pub fn verify_batch_membership(
+    spec: &ProofSpec,
+    root: &[u8],
+    proof: &CommitmentProof,
+    items: &[(&[u8], &[u8])],
+) -> Result<(), VerifyMembershipError> {
+    let proof = decompress(proof).map_err(VerifyMembershipError::Decompress)?;
+
+    for (key, value) in items {
+        let existence_proof =
+            get_exist_proof_for_key(&proof, key).ok_or(VerifyMembershipError::ProofDoesNotExist)?;
+
+        verify_membership(spec, root, existence_proof, key, value)?;
+    }
+
+    Ok(())
+}
+
+/// Implements ICS-23 batchVerifyNonMembership: verifies a proof that many paths have not been set
+/// to any value in a commitment.
+///
+/// `proof` can be a single non-existence proof, a batch proof or a compressed batch proof.
+pub fn verify_batch_non_membership(
+    spec: &ProofSpec,
+    root: &[u8],
+    proof: &CommitmentProof,
+    keys: &[&[u8]],
+) -> Result<(), VerifyMembershipError> {
+    let proof = decompress(proof).map_err(VerifyMembershipError::Decompress)?;
+
+    for key in keys {
+        let non_existence_proof = get_non_exist_proof_for_key(spec, &proof, key)?
+            .ok_or(VerifyMembershipError::ProofDoesNotExist)?;
+
+        verify_non_membership(spec, root, non_existence_proof, key)?;
+    }
+
+    Ok(())
+}
+
+/// Find the existence proof for `key` in a (decompressed) commitment proof.
+fn get_exist_proof_for_key<'a>(
+    proof: &'a CommitmentProof,
+    key: &[u8],
+) -> Option<&'a ExistenceProof> {
+    let is_match = |existence_proof: &&ExistenceProof| &existence_proof.key[..] == key;
+
+    match proof {
+        CommitmentProof::Exist(existence_proof) => Some(existence_proof).filter(is_match),
+        CommitmentProof::Batch(batch) => batch
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                BatchEntry::Exist(existence_proof) => Some(existence_proof),
+                BatchEntry::Nonexist(_) => None,
+            })
+            .find(is_match),
+        CommitmentProof::Nonexist(_) | CommitmentProof::CompressedBatch(_) => None,
+    }
+}
+
+/// Find the non-existence proof whose neighbors surround `key` in a (decompressed) commitment
+/// proof.
+fn get_non_exist_proof_for_key<'a>(
+    spec: &ProofSpec,
+    proof: &'a CommitmentProof,
+    key: &[u8],
+) -> Result<Option<&'a NonExistenceProof>, HashError> {
+    let key = key_for_comparison(spec, key)?;
+
+    let surrounds_key = |non_existence_proof: &NonExistenceProof| -> Result<bool, HashError> {
+        let is_left = match &non_existence_proof.left {
+            Some(left) => key_for_comparison(spec, &left.key)? < key,
+            None => true,
+        };
+
+        let is_right = match &non_existence_proof.right {
+            Some(right) => key_for_comparison(spec, &right.key)? > key,
+            None => true,
+        };
+
+        Ok(is_left && is_right)
+    };
+
+    match proof {
+        CommitmentProof::Nonexist(non_existence_proof) => {
+            Ok(surrounds_key(non_existence_proof)?.then_some(non_existence_proof))
+        }
+        CommitmentProof::Batch(batch) => {
+            for entry in &batch.entries {
+                if let BatchEntry::Nonexist(non_existence_proof) = entry {
+                    if surrounds_key(non_existence_proof)? {
+                        return Ok(Some(non_existence_proof));
+                    }
+                }
+            }
+
+            Ok(None)
+        }
+        CommitmentProof::Exist(_) | CommitmentProof::CompressedBatch(_) => Ok(None),
+    }
+}
+
 fn verify_non_existence(
     non_existence_proof: &NonExistenceProof,
     spec: &ProofSpec,
@@ -1,20 +1,27 @@
-use std::{collections::HashMap, fmt::Display, fs, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{bail, Context};
 use ics23::{
+    compress::compress_batch,
     existence_proof::{self, calculate_root},
     ops::{hash_op, inner_op, inner_op::check_against_spec, leaf_op},
     proof_specs::{IAVL_PROOF_SPEC, TENDERMINT_PROOF_SPEC},
     verify::{
-        left_branches_are_empty, right_branches_are_empty, verify_membership, verify_non_membership,
+        left_branches_are_empty, right_branches_are_empty, verify_batch_membership,
+        verify_batch_non_membership, verify_membership, verify_non_membership,
     },
 };
 use protos::cosmos::ics23::v1::InnerSpec;
 use serde::{de::DeserializeOwned, Deserialize};
 use unionlabs::{
     cosmos::ics23::{
-        commitment_proof::CommitmentProof, hash_op::HashOp, inner_op::InnerOp,
-        proof_spec::ProofSpec,
+        batch_entry::BatchEntry, batch_proof::BatchProof, commitment_proof::CommitmentProof,
+        hash_op::HashOp, inner_op::InnerOp, proof_spec::ProofSpec,
     },
     encoding::{DecodeAs, Proto},
 };
@@ -37,7 +44,8 @@ fn suite() -> anyhow::Result<()> {
         "test empty branch data",
     )?;
 
-    run_vector_tests(testdata_dir)?;
+    run_vector_tests(&testdata_dir)?;
+    run_batch_vector_tests(&testdata_dir)?;
 
     Ok(())
 }
@@ -438,8 +446,8 @@ impl SpecType {
     }
 }
 
-fn run_vector_tests(testdata_dir: PathBuf) -> anyhow::Result<()> {
-    let tests: Vec<VectorTest> = SpecType::all()
+fn vector_tests(testdata_dir: &Path) -> Vec<VectorTest> {
+    SpecType::all()
         .iter()
         .flat_map(|spec_type| {
             FILENAMES.iter().map(|file_name| {
@@ -452,9 +460,11 @@ fn run_vector_tests(testdata_dir: PathBuf) -> anyhow::Result<()> {
                 VectorTest { name, data, spec }
             })
         })
-        .collect();
+        .collect()
+}
 
-    for test in tests {
+fn run_vector_tests(testdata_dir: &Path) -> anyhow::Result<()> {
+    for test in vector_tests(testdata_dir) {
         eprint!("test vectors: {}...", &test);
         test.run()?;
         eprintln!("OK");
@@ -463,6 +473,80 @@ fn run_vector_tests(testdata_dir: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Wraps each of the single vectors in a batch proof, and verifies it both as a batch and as a
+/// compressed batch.
+fn run_batch_vector_tests(testdata_dir: &Path) -> anyhow::Result<()> {
+    for test in vector_tests(testdata_dir) {
+        eprint!("batch test vectors: {}...", &test);
+
+        let entry = match CommitmentProof::decode_as::<Proto>(test.data.proof.as_slice())
+            .map_err(|e| anyhow::anyhow!("cannot parse proof - {e:?}"))?
+        {
+            CommitmentProof::Exist(existence_proof) => BatchEntry::Exist(existence_proof),
+            CommitmentProof::Nonexist(non_existence_proof) => {
+                BatchEntry::Nonexist(non_existence_proof)
+            }
+            proof => bail!("unexpected proof: {proof:?}"),
+        };
+
+        let batch = BatchProof {
+            entries: vec![entry],
+        };
+
+        for proof in [
+            CommitmentProof::CompressedBatch(compress_batch(&batch)),
+            CommitmentProof::Batch(batch),
+        ] {
+            if test.data.value.is_empty() {
+                verify_batch_non_membership(
+                    &test.spec,
+                    &test.data.root,
+                    &proof,
+                    &[test.data.key.as_slice()],
+                )
+                .context("verify batch non membership")?;
+
+                // the key is not in the batch as an existence proof
+                verify_batch_membership(
+                    &test.spec,
+                    &test.data.root,
+                    &proof,
+                    &[(test.data.key.as_slice(), b"value".as_slice())],
+                )
+                .expect_err("non existence proof can't prove membership");
+            } else {
+                verify_batch_membership(
+                    &test.spec,
+                    &test.data.root,
+                    &proof,
+                    &[(test.data.key.as_slice(), test.data.value.as_slice())],
+                )
+                .context("verify batch membership")?;
+
+                verify_batch_membership(
+                    &test.spec,
+                    &test.data.root,
+                    &proof,
+                    &[(test.data.key.as_slice(), b"invalid".as_slice())],
+                )
+                .expect_err("batch membership with an invalid value must fail");
+
+                verify_batch_non_membership(
+                    &test.spec,
+                    &test.data.root,
+                    &proof,
+                    &[test.data.key.as_slice()],
+                )
+                .expect_err("existence proof can't prove non membership");
+            }
+        }
+
+        eprintln!("OK");
+    }
+
+    Ok(())
+}
+
 #[derive(Debug, Deserialize)]
 struct VectorTest {
     name: String,
@@ -1,9 +1,11 @@
 pub mod client_state;
 pub mod consensus_state;
 pub mod header;
+pub mod misbehaviour;
 
 pub use crate::{
     client_state::{ClientState, ClientStateV1},
     consensus_state::ConsensusState,
     header::Header,
+    misbehaviour::{AttestedHeader, Misbehaviour},
 };
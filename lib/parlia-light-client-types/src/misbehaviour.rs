@@ -0,0 +1,97 @@
+use parlia_types::ParliaHeader;
+
+/// Two vote attestations, signed by a quorum of the trusted valset, that attest to conflicting blocks
+/// at the same height.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct Misbehaviour {
+    pub header_a: AttestedHeader,
+    pub header_b: AttestedHeader,
+}
+
+/// The same as [`Header`](crate::Header), without the IBC account proof.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct AttestedHeader {
+    /// The valset that signed `attestation`.
+    pub trusted_valset_epoch_number: u64,
+    /// The block that is being attested to as finalized by `attestation`.
+    pub source: ParliaHeader,
+    /// The block that is being attested to as justified by `attestation`.
+    pub target: ParliaHeader,
+    /// The block that contains the attestation.
+    pub attestation: ParliaHeader,
+}
+
+#[cfg(test)]
+mod tests {
+    use unionlabs::{
+        encoding::{Bincode, Json},
+        primitives::{H160, H2048, H256, H64, U256},
+        test_utils::assert_codec_iso,
+    };
+
+    use super::*;
+
+    fn mk_parlia_header(number: u64, parent_hash: u8) -> ParliaHeader {
+        ParliaHeader {
+            parent_hash: H256::new([parent_hash; 32]),
+            sha3_uncles: H256::new([0xAA; 32]),
+            miner: H160::new([0xBB; 20]),
+            state_root: H256::new([0xCC; 32]),
+            transactions_root: H256::new([0xDD; 32]),
+            receipts_root: H256::new([0xEE; 32]),
+            logs_bloom: Box::new(H2048::new([0x01; 256])),
+            difficulty: U256::from(2_u64),
+            number: U256::from(number),
+            gas_limit: 70_000_000,
+            gas_used: 148_160,
+            timestamp: 1_749_250_495,
+            extra_data: vec![1, 2, 3].into(),
+            mix_hash: H256::new([0x02; 32]),
+            nonce: H64::new([0x03; 8]),
+            base_fee_per_gas: U256::from(0_u64),
+            withdrawals_root: H256::new([0x04; 32]),
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+            parent_beacon_block_root: H256::new([0x05; 32]),
+            requests_hash: Default::default(),
+        }
+    }
+
+    fn mk_attested_header(parent_hash: u8) -> AttestedHeader {
+        AttestedHeader {
+            trusted_valset_epoch_number: 53_859,
+            source: mk_parlia_header(100, parent_hash),
+            target: mk_parlia_header(101, parent_hash),
+            attestation: mk_parlia_header(102, parent_hash),
+        }
+    }
+
+    fn mk_misbehaviour() -> Misbehaviour {
+        Misbehaviour {
+            header_a: mk_attested_header(0x10),
+            header_b: mk_attested_header(0x20),
+        }
+    }
+
+    #[test]
+    fn bincode_iso() {
+        assert_codec_iso::<_, Bincode>(&mk_misbehaviour());
+    }
+
+    #[test]
+    fn json_iso() {
+        assert_codec_iso::<_, Json>(&mk_misbehaviour());
+    }
+}
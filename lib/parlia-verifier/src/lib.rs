@@ -129,6 +129,8 @@ pub enum Error<E> {
     InvalidTrustedValsetEpochBlockNumber { expected: u64, found: u64 },
     #[error("the valset is not sorted")]
     ValsetNotSorted,
+    #[error("the attestations do not conflict")]
+    MisbehaviourNotFound,
 }
 
 pub trait VerificationContext {
@@ -144,6 +146,23 @@ pub trait VerificationContext {
     ) -> Result<(), Self::Error>;
 }
 
+impl<C: VerificationContext> VerificationContext for &C {
+    type Error = C::Error;
+
+    fn get_valset(&self, epoch_block_number: u64) -> Result<Valset, Self::Error> {
+        (*self).get_valset(epoch_block_number)
+    }
+
+    fn verify<'pk>(
+        &self,
+        public_keys: impl IntoIterator<Item = &'pk H384>,
+        msg: &[u8],
+        signature: H768,
+    ) -> Result<(), Self::Error> {
+        (*self).verify(public_keys, msg, signature)
+    }
+}
+
 /// Given 3 headers: source `S`, target `T`, and attestation `A`, where `A` contains the vote data for `S` and `T`:
 /// 1. verify that `S ∈ T ∈ A`
 /// 2. validate the signature contained in `A` with the valset that signed it
@@ -239,11 +258,55 @@ pub fn verify_header<C: VerificationContext>(
     }
 }
 
+/// Given two sets of (source `S`, target `T`, attestation `A`) headers, as in [`verify_header`]:
+/// 1. verify both sets of headers, including the signatures of both attestations
+/// 2. verify that both attestations vote for blocks at the same height, but with different hashes
+///
+/// Since both attestations are signed by more than 2/3 of the valset, more than 1/3 of the valset must
+/// have double voted.
+pub fn verify_misbehaviour<C: VerificationContext>(
+    (source_a, target_a, attestation_a): (&ParliaHeader, &ParliaHeader, &ParliaHeader),
+    trusted_valset_epoch_block_number_a: u64,
+    (source_b, target_b, attestation_b): (&ParliaHeader, &ParliaHeader, &ParliaHeader),
+    trusted_valset_epoch_block_number_b: u64,
+    ctx: C,
+) -> Result<(), Error<C::Error>> {
+    // 1.
+    verify_header(
+        source_a,
+        target_a,
+        attestation_a,
+        trusted_valset_epoch_block_number_a,
+        &ctx,
+    )?;
+    verify_header(
+        source_b,
+        target_b,
+        attestation_b,
+        trusted_valset_epoch_block_number_b,
+        &ctx,
+    )?;
+
+    // 2.
+    // the attested target is always the block after the attested source, so comparing the sources is
+    // sufficient to ensure that the votes are for the same height
+    if source_a.number != source_b.number {
+        return Err(Error::MisbehaviourNotFound);
+    }
+
+    if source_a.hash() == source_b.hash() && target_a.hash() == target_b.hash() {
+        return Err(Error::MisbehaviourNotFound);
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
     use hex_literal::hex;
+    use unionlabs_primitives::H256;
 
     use super::*;
 
@@ -493,6 +556,174 @@ mod tests {
     //     dbg!(res);
     // }
 
+    const DST_POP_G2: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+    const SOURCE_NUMBER: u64 = 10248;
+    const TRUSTED_VALSET_EPOCH_BLOCK_NUMBER: u64 = 10000;
+
+    fn mk_keys(seed: u8) -> Vec<(H160, blst::min_pk::SecretKey)> {
+        (1..=3)
+            .map(|i| {
+                (
+                    H160::new([i; 20]),
+                    blst::min_pk::SecretKey::key_gen(&[seed.wrapping_add(i); 32], &[]).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    fn mk_valset(keys: &[(H160, blst::min_pk::SecretKey)]) -> Valset {
+        Valset::new(
+            keys.iter()
+                .map(|(address, sk)| (*address, H384::new(sk.sk_to_pk().compress())))
+                .collect(),
+        )
+    }
+
+    fn mk_header(
+        number: u64,
+        parent_hash: H256,
+        state_root: H256,
+        extra_data: Vec<u8>,
+    ) -> ParliaHeader {
+        ParliaHeader {
+            parent_hash,
+            sha3_uncles: H256::default(),
+            miner: H160::default(),
+            state_root,
+            transactions_root: H256::default(),
+            receipts_root: H256::default(),
+            logs_bloom: Box::default(),
+            difficulty: U256::from(2_u64),
+            number: U256::from(number),
+            gas_limit: 0,
+            gas_used: 0,
+            timestamp: number,
+            extra_data: extra_data.into(),
+            mix_hash: H256::default(),
+            nonce: Default::default(),
+            base_fee_per_gas: U256::ZERO,
+            withdrawals_root: H256::default(),
+            blob_gas_used: 0,
+            excess_blob_gas: 0,
+            parent_beacon_block_root: H256::default(),
+            requests_hash: None.into(),
+        }
+    }
+
+    /// Build a (source, target, attestation) chain where `source` has the provided `state_root`,
+    /// with the attestation signed by all of `keys`.
+    fn mk_attested_chain(
+        keys: &[(H160, blst::min_pk::SecretKey)],
+        state_root: H256,
+    ) -> (ParliaHeader, ParliaHeader, ParliaHeader) {
+        let source = mk_header(SOURCE_NUMBER, H256::default(), state_root, vec![]);
+        let target = mk_header(SOURCE_NUMBER + 1, source.hash(), H256::default(), vec![]);
+
+        let data = VoteData {
+            source_number: SOURCE_NUMBER,
+            source_hash: source.hash(),
+            target_number: SOURCE_NUMBER + 1,
+            target_hash: target.hash(),
+        };
+
+        let signatures = keys
+            .iter()
+            .map(|(_, sk)| sk.sign(data.hash().get(), DST_POP_G2, &[]))
+            .collect::<Vec<_>>();
+
+        let agg_signature = blst::min_pk::AggregateSignature::aggregate(
+            &signatures.iter().collect::<Vec<_>>(),
+            true,
+        )
+        .unwrap()
+        .to_signature();
+
+        let vote_attestation = VoteAttestation {
+            vote_address_set: rlp::decode(&rlp::encode(&((1_u64 << keys.len()) - 1))).unwrap(),
+            agg_signature: H768::new(agg_signature.compress()),
+            data,
+            extra: Default::default(),
+        };
+
+        let attestation = mk_header(
+            SOURCE_NUMBER + 2,
+            target.hash(),
+            H256::default(),
+            [0; EXTRA_VANITY_LEN]
+                .into_iter()
+                .chain(rlp::encode(&vote_attestation))
+                .chain([0; EXTRA_SEAL_LEN])
+                .collect(),
+        );
+
+        (source, target, attestation)
+    }
+
+    fn ctx(keys: &[(H160, blst::min_pk::SecretKey)]) -> BlstContext {
+        BlstContext(
+            [(TRUSTED_VALSET_EPOCH_BLOCK_NUMBER, mk_valset(keys))]
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn verify_misbehaviour_works() {
+        let keys = mk_keys(0);
+
+        let a = mk_attested_chain(&keys, H256::new([0xAA; 32]));
+        let b = mk_attested_chain(&keys, H256::new([0xBB; 32]));
+
+        verify_misbehaviour(
+            (&a.0, &a.1, &a.2),
+            TRUSTED_VALSET_EPOCH_BLOCK_NUMBER,
+            (&b.0, &b.1, &b.2),
+            TRUSTED_VALSET_EPOCH_BLOCK_NUMBER,
+            ctx(&keys),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_misbehaviour_same_attestation() {
+        let keys = mk_keys(0);
+
+        let a = mk_attested_chain(&keys, H256::new([0xAA; 32]));
+
+        assert!(matches!(
+            verify_misbehaviour(
+                (&a.0, &a.1, &a.2),
+                TRUSTED_VALSET_EPOCH_BLOCK_NUMBER,
+                (&a.0, &a.1, &a.2),
+                TRUSTED_VALSET_EPOCH_BLOCK_NUMBER,
+                ctx(&keys),
+            ),
+            Err(Error::MisbehaviourNotFound)
+        ));
+    }
+
+    #[test]
+    fn verify_misbehaviour_invalid_signature() {
+        let keys = mk_keys(0);
+        let other_keys = mk_keys(100);
+
+        let a = mk_attested_chain(&keys, H256::new([0xAA; 32]));
+        // signed by a valset that is not trusted
+        let b = mk_attested_chain(&other_keys, H256::new([0xBB; 32]));
+
+        assert!(matches!(
+            verify_misbehaviour(
+                (&a.0, &a.1, &a.2),
+                TRUSTED_VALSET_EPOCH_BLOCK_NUMBER,
+                (&b.0, &b.1, &b.2),
+                TRUSTED_VALSET_EPOCH_BLOCK_NUMBER,
+                ctx(&keys),
+            ),
+            Err(Error::ContextError(_))
+        ));
+    }
+
     #[test]
     fn calculate_signing_valset_epoch_block_number_works() {
         let cases: &[(u64, u64)] = &[(50932088, 50931500), (50932089, 50932000)];
@@ -0,0 +1,4 @@
+-- no-transaction
+-- used to find the children of an item in the queue history. the done table is large and
+-- continuously written to, so the index is built concurrently to avoid blocking the queue.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS index_done_parents ON done USING GIN (parents);
@@ -0,0 +1,3 @@
+-- no-transaction
+-- used to find the children of an item in the queue history.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS index_failed_parents ON failed USING GIN (parents);
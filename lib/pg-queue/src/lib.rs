@@ -23,13 +23,17 @@ use tracing::{debug, debug_span, error, info, info_span, instrument, trace, warn
 use voyager_vm::{
     filter::{FilterResult, Interest, InterestFilter},
     pass::{Pass, PassResult},
-    BoxDynError, Captures, EnqueueResult, ItemId, Op, QueueError, QueueMessage,
+    schedule::{FairScheduler, Priority, SchedulingConfig},
+    BoxDynError, Captures, EnqueueResult, HistoryItem, ItemId, ItemStatus, Op, QueueError,
+    QueueMessage,
 };
 
 use crate::metrics::Metrics;
 
 pub mod metrics;
 
+pub use voyager_vm::retry::{ErrorClass, RetryPolicy};
+
 /// A fifo queue backed by a postgres table. Not suitable for high-throughput, but enough for ~1k items/sec.
 ///
 /// The queue assumes the following database schema:
@@ -46,6 +50,8 @@ pub struct PgQueue<T> {
     optimize_batch_limit: Option<i64>,
     retryable_error_expo_backoff_max: f64,
     retryable_error_expo_backoff_multiplier: f64,
+    retry_policies: Vec<RetryPolicy>,
+    scheduler: FairScheduler,
 
     metrics: Metrics,
 
@@ -70,6 +76,18 @@ pub struct PgQueueConfig {
     pub retryable_error_expo_backoff_max: f64,
     #[serde(default = "default_retryable_error_expo_backoff_multiplier")]
     pub retryable_error_expo_backoff_multiplier: f64,
+    /// Automatic retry policies, checked in order. The first policy whose error class and message
+    /// filters match an error is applied; errors not matched by any policy are handled as usual
+    /// (retryable errors are retried indefinitely, fatal and unprocessable errors are moved to the
+    /// `failed` table).
+    #[serde(default)]
+    pub retry_policies: Vec<RetryPolicy>,
+    /// Priority and per-chain fairness configuration for processing ready items.
+    ///
+    /// Note that the virtual time used for fair scheduling is tracked per process, so if multiple
+    /// voyager instances share the same database, fairness is only guaranteed within each instance.
+    #[serde(default)]
+    pub scheduling: SchedulingConfig,
 }
 
 pub const fn default_max_connections() -> u32 {
@@ -112,18 +130,27 @@ struct QueueRecord {
     item: String,
     created_at: time::OffsetDateTime,
     attempt: i64,
+    priority: i16,
+    chain: Option<String>,
 }
 
 #[derive(Debug, FromRow)]
 struct OptimizeRecord {
     id: i64,
-    #[allow(dead_code)]
     parents: Vec<i64>,
     item: String,
-    #[allow(dead_code)]
     created_at: time::OffsetDateTime,
 }
 
+#[derive(Debug, FromRow)]
+struct HistoryRecord {
+    id: i64,
+    parents: Vec<i64>,
+    item: String,
+    status: String,
+    message: Option<String>,
+}
+
 #[derive(Debug, FromRow, Serialize)]
 #[serde(bound(serialize = ""))]
 pub struct FailedRecord<T: QueueMessage> {
@@ -134,6 +161,15 @@ pub struct FailedRecord<T: QueueMessage> {
     // pub created_at: sqlx::types::time::OffsetDateTime,
 }
 
+/// The result of [`PgQueue::requeue_failed`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RequeueFailedResult {
+    /// The ids of the items that were moved back into the queue.
+    pub requeued: Vec<i64>,
+    /// The ids of the items that could not be decoded, and as such were left in the failed table.
+    pub undecodable: Vec<i64>,
+}
+
 impl<T: QueueMessage> PgQueue<T> {
     pub async fn query_failed(
         &self,
@@ -205,6 +241,171 @@ impl<T: QueueMessage> PgQueue<T> {
         .await?
         .transpose()
     }
+
+    /// Move all failed items matching the provided filters back into the queue.
+    ///
+    /// The items keep their original id and parents, and their attempt counter is reset. Items
+    /// that can no longer be decoded (for example after a change to the message format) are left
+    /// in the failed table, and their ids are returned separately.
+    pub async fn requeue_failed(
+        &self,
+        mut item_filters: Vec<String>,
+        mut message_filters: Vec<String>,
+    ) -> Result<RequeueFailedResult, sqlx::Error> {
+        // default to all-inclusive filter if none are provided
+        if item_filters.is_empty() {
+            item_filters.push("%".to_owned())
+        }
+
+        if message_filters.is_empty() {
+            message_filters.push("%".to_owned())
+        }
+
+        let mut tx = self.client.begin().await?;
+
+        let records = sqlx::query(
+            r#"
+            SELECT
+                id,
+                item::text,
+                parents,
+                created_at
+            FROM
+                failed
+            WHERE
+                item::TEXT LIKE ANY($1)
+                AND message LIKE ANY($2)
+            FOR UPDATE
+            "#,
+        )
+        .bind(item_filters)
+        .bind(message_filters)
+        .try_map(|row| OptimizeRecord::from_row(&row))
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut undecodable = vec![];
+
+        // the schedule is derived from the op, so it must be decoded to be requeued
+        let (records, ops): (Vec<_>, Vec<_>) = records
+            .into_iter()
+            .filter_map(|record| match de::<Op<T>>(&record.item) {
+                Ok(op) => Some((record, op)),
+                Err(error) => {
+                    warn!(
+                        id = record.id,
+                        %error,
+                        "unable to decode failed item, it will not be requeued"
+                    );
+                    undecodable.push(record.id);
+                    None
+                }
+            })
+            .unzip();
+
+        let (priorities, chains) = schedule_columns(&ops);
+
+        let ids = sqlx::query(
+            r#"
+            INSERT INTO
+            queue (id, item, parents, created_at, priority, chain)
+            SELECT
+                id,
+                item,
+                -- UNNEST can't be used with multidimensional arrays
+                ARRAY(SELECT jsonb_array_elements_text(parents)::BIGINT),
+                created_at,
+                priority,
+                chain
+            FROM
+                UNNEST($1::BIGINT[], $2::JSONB[], $3::JSONB[], $4::TIMESTAMPTZ[], $5::INT2[], $6::TEXT[])
+                AS requeued(id, item, parents, created_at, priority, chain)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(records.iter().map(|record| record.id).collect::<Vec<_>>())
+        .bind(ops.into_iter().map(Json).collect::<Vec<_>>())
+        .bind(
+            records
+                .iter()
+                .map(|record| Json(&record.parents))
+                .collect::<Vec<_>>(),
+        )
+        .bind(
+            records
+                .iter()
+                .map(|record| record.created_at)
+                .collect::<Vec<_>>(),
+        )
+        .bind(priorities)
+        .bind(chains)
+        .try_map(|row| Id::from_row(&row))
+        .fetch_all(tx.as_mut())
+        .await?
+        .into_iter()
+        .map(|id| id.id)
+        .collect::<Vec<_>>();
+
+        sqlx::query("DELETE FROM failed WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(tx.as_mut())
+            .await?;
+
+        tx.commit().await?;
+
+        for id in &ids {
+            debug!(id, "requeued failed item");
+        }
+
+        Ok(RequeueFailedResult {
+            requeued: ids,
+            undecodable,
+        })
+    }
+
+    /// Delete all failed items matching the provided filters, returning the ids of the deleted
+    /// items.
+    pub async fn delete_failed(
+        &self,
+        mut item_filters: Vec<String>,
+        mut message_filters: Vec<String>,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        // default to all-inclusive filter if none are provided
+        if item_filters.is_empty() {
+            item_filters.push("%".to_owned())
+        }
+
+        if message_filters.is_empty() {
+            message_filters.push("%".to_owned())
+        }
+
+        let ids = sqlx::query(
+            r#"
+            DELETE FROM
+                failed
+            WHERE
+                item::TEXT LIKE ANY($1)
+                AND message LIKE ANY($2)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(item_filters)
+        .bind(message_filters)
+        .try_map(|row| Id::from_row(&row))
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|id| id.id)
+        .collect::<Vec<_>>();
+
+        for id in &ids {
+            debug!(id, "deleted failed item");
+        }
+
+        Ok(ids)
+    }
 }
 
 impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
@@ -228,6 +429,8 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
         let retryable_error_expo_backoff_multiplier =
             config.retryable_error_expo_backoff_multiplier;
         let retryable_error_expo_backoff_max = config.retryable_error_expo_backoff_max;
+        let retry_policies = config.retry_policies.clone();
+        let scheduler = FairScheduler::new(config.scheduling.clone());
 
         let pool = config.into_pg_pool().await?;
 
@@ -276,6 +479,12 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
             CREATE INDEX IF NOT EXISTS index_queue_created_at ON queue (created_at ASC) INCLUDE (id);
 
             CREATE INDEX IF NOT EXISTS index_queue_handle_at ON queue(handle_at DESC) INCLUDE (id);
+
+            ALTER TABLE queue ADD COLUMN IF NOT EXISTS priority INT2 NOT NULL DEFAULT 1;
+
+            ALTER TABLE queue ADD COLUMN IF NOT EXISTS chain TEXT;
+
+            CREATE INDEX IF NOT EXISTS index_queue_chain_priority ON queue ((COALESCE(chain, '')), priority, handle_at ASC) INCLUDE (id);
             "#,
         )
         .try_for_each(|result| async move {
@@ -285,11 +494,20 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
         .instrument(info_span!("init"))
         .await?;
 
+        // indexes on the done and failed tables are created concurrently in migrations, as these
+        // tables can be very large and a regular index build would lock them for the duration
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .instrument(info_span!("migrate"))
+            .await?;
+
         Ok(Self {
             client: pool,
             optimize_batch_limit,
             retryable_error_expo_backoff_max,
             retryable_error_expo_backoff_multiplier,
+            retry_policies,
+            scheduler,
             metrics: Metrics::new(),
             __marker: PhantomData,
         })
@@ -312,14 +530,18 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
 
         let mut tx = self.client.begin().await?;
 
+        let (priorities, chains) = schedule_columns(&ready);
+
         let ready_ids = sqlx::query(
             "
-            INSERT INTO queue (item)
-            SELECT * FROM UNNEST($1::JSONB[])
+            INSERT INTO queue (item, priority, chain)
+            SELECT * FROM UNNEST($1::JSONB[], $2::INT2[], $3::TEXT[])
             RETURNING id
             ",
         )
         .bind(ready.into_iter().map(Json).collect::<Vec<_>>())
+        .bind(priorities)
+        .bind(chains)
         .try_map(|x| Id::from_row(&x))
         .fetch_all(tx.as_mut())
         .await?;
@@ -385,47 +607,139 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
 
         let mut tx = self.client.begin().await?;
 
+        let (virtual_times, system_virtual_time) = self.scheduler.virtual_times();
+        let (chains, virtual_times): (Vec<_>, Vec<_>) = virtual_times.into_iter().unzip();
+
+        // items are ordered by priority class (if enabled, raised by one class for every aging
+        // interval that the item has been ready for), then by the virtual time of their chain, and
+        // finally by the time they became ready
+        //
+        // ordering all ready items this way can't be done with an index, so instead only the first
+        // ready item of each (chain, priority) pair is considered, all of which can be found with
+        // `index_queue_chain_priority` (the chains are found with a loose index scan). if all of
+        // these items are already being processed by other workers, the oldest ready item is
+        // processed instead.
         let row = sqlx::query(
             r#"
-            DELETE FROM
-              queue
-            WHERE
-              id = (
+            WITH RECURSIVE chains(chain) AS (
+              (
                 SELECT
-                  id
+                  COALESCE(chain, '')
                 FROM
                   queue
-                WHERE
-                  handle_at < now()
                 ORDER BY
-                  handle_at ASC
-                FOR UPDATE
-                  SKIP LOCKED
+                  COALESCE(chain, '') ASC
                 LIMIT 1)
+              UNION ALL
+              SELECT
+                (
+                  SELECT
+                    COALESCE(queue.chain, '')
+                  FROM
+                    queue
+                  WHERE
+                    COALESCE(queue.chain, '') > chains.chain
+                  ORDER BY
+                    COALESCE(queue.chain, '') ASC
+                  LIMIT 1)
+              FROM
+                chains
+              WHERE
+                chains.chain IS NOT NULL
+            ),
+            candidates AS (
+              SELECT
+                ready.id,
+                ready.priority,
+                ready.handle_at,
+                chains.chain
+              FROM
+                chains
+                CROSS JOIN UNNEST($5::INT2[]) AS priorities(priority)
+                CROSS JOIN LATERAL (
+                  SELECT
+                    queue.id,
+                    queue.priority,
+                    queue.handle_at
+                  FROM
+                    queue
+                  WHERE
+                    COALESCE(queue.chain, '') = chains.chain
+                    AND queue.priority = priorities.priority
+                    AND queue.handle_at < now()
+                  ORDER BY
+                    queue.handle_at ASC
+                  LIMIT 1) AS ready
+              WHERE
+                chains.chain IS NOT NULL
+            )
+            DELETE FROM
+              queue
+            WHERE
+              id = COALESCE(
+                (
+                  SELECT
+                    queue.id
+                  FROM
+                    candidates
+                    JOIN queue ON queue.id = candidates.id
+                    LEFT JOIN UNNEST($1::TEXT[], $2::FLOAT8[]) AS vt(chain, vtime)
+                      ON vt.chain = candidates.chain
+                  ORDER BY
+                    CASE
+                      WHEN $4 THEN LEAST(
+                        $6,
+                        candidates.priority
+                          + FLOOR(EXTRACT(EPOCH FROM now() - candidates.handle_at)::FLOAT8 / $7)
+                      )
+                      ELSE 0
+                    END DESC,
+                    GREATEST(COALESCE(vt.vtime, $3), $3) ASC,
+                    candidates.handle_at ASC,
+                    candidates.id ASC
+                  FOR UPDATE OF queue
+                    SKIP LOCKED
+                  LIMIT 1),
+                (
+                  SELECT
+                    id
+                  FROM
+                    queue
+                  WHERE
+                    handle_at < now()
+                  ORDER BY
+                    handle_at ASC
+                  FOR UPDATE
+                    SKIP LOCKED
+                  LIMIT 1)
+              )
             RETURNING
               id,
               parents,
               item::text,
               attempt,
-              created_at
+              created_at,
+              priority,
+              chain
             "#,
         )
+        .bind(chains)
+        .bind(virtual_times)
+        .bind(system_virtual_time)
+        .bind(self.scheduler.prioritize())
+        .bind(Priority::ALL.map(Priority::as_i16).to_vec())
+        .bind(Priority::Urgent.as_i16())
+        .bind(self.scheduler.priority_aging().as_secs_f64())
         .try_map(|x| QueueRecord::from_row(&x))
         .fetch_optional(tx.as_mut())
         .await?;
 
         let res = match row {
             Some(record) => {
-                process_item(
-                    &self.metrics,
-                    &mut tx,
-                    record,
-                    f,
-                    filter,
-                    self.retryable_error_expo_backoff_max,
-                    self.retryable_error_expo_backoff_multiplier,
-                )
-                .await?
+                self.scheduler
+                    .record(record.chain.as_deref().unwrap_or_default());
+
+                process_item(self, &mut tx, record, f, filter).await?
             }
             None => None,
         };
@@ -604,16 +918,20 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
         }
 
         for (parents, op) in ready_insert_into_queue {
+            let schedule = T::schedule(&op);
+
             let ready_ids = sqlx::query(
                 "
-                INSERT INTO queue (item, parents)
+                INSERT INTO queue (item, parents, priority, chain)
                 VALUES
-                    ($2::JSONB, $1)
+                    ($2::JSONB, $1, $3, $4)
                 RETURNING id
                 ",
             )
             .bind(parents)
             .bind(Json(op))
+            .bind(schedule.priority.as_i16())
+            .bind(schedule.chain)
             .try_map(|x| Id::from_row(&x))
             .fetch_all(tx.as_mut())
             .await
@@ -628,6 +946,106 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
 
         Ok(())
     }
+
+    #[instrument(skip_all, fields(id = id.raw(), %max_depth))]
+    async fn history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> Result<Vec<HistoryItem<T>>, Self::Error> {
+        trace!("history");
+
+        sqlx::query(
+            r#"
+            WITH RECURSIVE
+              ancestors (id, depth) AS (
+                SELECT $1::BIGINT, 0
+                UNION
+                SELECT
+                  parent.id, ancestors.depth + 1
+                FROM
+                  ancestors,
+                  LATERAL (
+                    SELECT parents FROM queue WHERE queue.id = ancestors.id
+                    UNION ALL
+                    SELECT parents FROM optimize WHERE optimize.id = ancestors.id
+                    UNION ALL
+                    SELECT parents FROM done WHERE done.id = ancestors.id
+                    UNION ALL
+                    SELECT parents FROM failed WHERE failed.id = ancestors.id
+                  ) AS items,
+                  UNNEST(items.parents) AS parent(id)
+                WHERE
+                  ancestors.depth < $2
+              ),
+              descendants (id, depth) AS (
+                SELECT $1::BIGINT, 0
+                UNION
+                SELECT
+                  children.id, descendants.depth + 1
+                FROM
+                  descendants,
+                  LATERAL (
+                    SELECT id FROM queue WHERE parents @> ARRAY[descendants.id]
+                    UNION ALL
+                    SELECT id FROM optimize WHERE parents @> ARRAY[descendants.id]
+                    UNION ALL
+                    SELECT id FROM done WHERE parents @> ARRAY[descendants.id]
+                    UNION ALL
+                    SELECT id FROM failed WHERE parents @> ARRAY[descendants.id]
+                  ) AS children
+                WHERE
+                  descendants.depth < $2
+              ),
+              lineage AS (
+                SELECT id FROM ancestors
+                UNION
+                SELECT id FROM descendants
+              )
+            SELECT id, parents, item::text, 'queued' AS status, NULL AS message
+            FROM queue WHERE id IN (SELECT id FROM lineage)
+            UNION ALL
+            SELECT id, parents, item::text, 'optimize' AS status, NULL AS message
+            FROM optimize WHERE id IN (SELECT id FROM lineage)
+            UNION ALL
+            SELECT id, parents, item::text, 'done' AS status, NULL AS message
+            FROM done WHERE id IN (SELECT id FROM lineage)
+            UNION ALL
+            SELECT id, parents, item::text, 'failed' AS status, message
+            FROM failed WHERE id IN (SELECT id FROM lineage)
+            ORDER BY
+              id ASC
+            "#,
+        )
+        .bind(id.raw())
+        .bind(i64::from(max_depth))
+        .try_map(|row| HistoryRecord::from_row(&row))
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|record| {
+            let decode = |e| sqlx::Error::Decode(Box::new(e));
+
+            Ok(HistoryItem {
+                id: ItemId::new(record.id).map_err(decode)?,
+                parents: record
+                    .parents
+                    .into_iter()
+                    .map(|parent| ItemId::new(parent).map_err(decode))
+                    .collect::<Result<_, _>>()?,
+                status: match &*record.status {
+                    "queued" => ItemStatus::Queued,
+                    "optimize" => ItemStatus::Optimize,
+                    "done" => ItemStatus::Done,
+                    "failed" => ItemStatus::Failed,
+                    status => unreachable!("unknown status {status}"),
+                },
+                op: de(&record.item).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                message: record.message,
+            })
+        })
+        .collect()
+    }
 }
 
 #[instrument(
@@ -638,13 +1056,11 @@ impl<T: QueueMessage> voyager_vm::Queue<T> for PgQueue<T> {
     )
 )]
 async fn process_item<'a, T, F, Fut, R, Filter>(
-    metrics: &Metrics,
+    queue: &PgQueue<T>,
     tx: &mut Transaction<'static, Postgres>,
     record: QueueRecord,
     f: F,
     filter: &'a Filter,
-    retryable_error_expo_backoff_max: f64,
-    retryable_error_expo_backoff_multiplier: f64,
 ) -> Result<Option<R>, sqlx::Error>
 where
     T: QueueMessage,
@@ -658,6 +1074,8 @@ where
     // really don't feel like defining a new error type right now
     let op = de::<Op<T>>(&record.item).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
 
+    let metrics = &queue.metrics;
+
     let now = std::time::Instant::now();
     let (r, res) = f(op.clone(), ItemId::new(record.id).unwrap()).await;
     metrics
@@ -665,49 +1083,55 @@ where
         .record(Instant::now().duration_since(now).as_secs_f64(), &[]);
 
     match res {
-        Err(QueueError::Fatal(error)) => {
-            let error = full_error_string(error);
-            error!(%error, "fatal error");
-            insert_error(record, error, tx).await?;
-            metrics.fatal_errors_count.add(1, &[]);
-        }
-        Err(QueueError::Unprocessable(error)) => {
-            let error = full_error_string(error);
-            info!(%error, "unprocessable message");
-            insert_error(record, error, tx).await?;
-            metrics.unprocessable_count.add(1, &[]);
-        }
-        Err(QueueError::Retry(error)) => {
-            warn!(error = %full_error_string(error), "retryable error");
-            sqlx::query(
-                "
-                INSERT INTO
-                queue  (id, item,      parents, attempt, handle_at, created_at)
-                VALUES ($1, $2::JSONB, $3,      $4,      $5,        $6        )
-                ",
-            )
-            .bind(record.id)
-            .bind(record.item)
-            .bind(record.parents)
-            .bind(record.attempt.saturating_add(1))
-            .bind(
-                time::OffsetDateTime::now_utc().saturating_add(
-                    Duration::try_from_secs_f64(
-                        (record.attempt as f64)
-                            .powf(retryable_error_expo_backoff_multiplier)
-                            .clamp(f64::MIN, retryable_error_expo_backoff_max),
-                    )
-                    .unwrap_or(Duration::MAX)
-                    .try_into()
-                    .unwrap_or(time::Duration::MAX),
+        Err(error) => {
+            let (error_class, error) = match error {
+                QueueError::Fatal(error) => (ErrorClass::Fatal, full_error_string(error)),
+                QueueError::Unprocessable(error) => {
+                    (ErrorClass::Unprocessable, full_error_string(error))
+                }
+                QueueError::Retry(error) => (ErrorClass::Retry, full_error_string(error)),
+            };
+
+            let policy = find_retry_policy(&queue.retry_policies, error_class, &error, tx).await?;
+
+            let retry = RetryPolicy::retry_backoff(
+                policy,
+                error_class,
+                record.attempt,
+                (
+                    queue.retryable_error_expo_backoff_max,
+                    queue.retryable_error_expo_backoff_multiplier,
                 ),
-            )
-            .bind(record.created_at)
-            .execute(tx.as_mut())
-            .await?;
+            );
+
+            match (retry, error_class) {
+                (Some((backoff_max, backoff_multiplier)), _) => {
+                    warn!(%error, ?error_class, "retryable error");
+                    requeue_with_backoff(record, backoff_max, backoff_multiplier, tx).await?;
 
-            tokio::time::sleep(Duration::from_millis(500)).await;
-            metrics.retryable_errors_count.add(1, &[]);
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                    metrics.retryable_errors_count.add(1, &[]);
+                }
+                (None, ErrorClass::Fatal) => {
+                    error!(%error, "fatal error");
+                    insert_error(record, error, tx).await?;
+                    metrics.fatal_errors_count.add(1, &[]);
+                }
+                (None, ErrorClass::Unprocessable) => {
+                    info!(%error, "unprocessable message");
+                    insert_error(record, error, tx).await?;
+                    metrics.unprocessable_count.add(1, &[]);
+                }
+                (None, ErrorClass::Retry) => {
+                    error!(
+                        %error,
+                        attempt = record.attempt,
+                        "retryable error, max attempts reached"
+                    );
+                    insert_error(record, error, tx).await?;
+                    metrics.retryable_errors_count.add(1, &[]);
+                }
+            }
         }
         Ok(ops) => {
             'block: {
@@ -738,14 +1162,18 @@ where
                         FilterResult::NoInterest => Either::Right(op),
                     });
 
+                let (priorities, chains) = schedule_columns(&ready);
+
                 sqlx::query(
                     "
-                    INSERT INTO queue (item, parents)
-                    SELECT *, $1 as parents FROM UNNEST($2::JSONB[])
+                    INSERT INTO queue (item, priority, chain, parents)
+                    SELECT *, $1 as parents FROM UNNEST($2::JSONB[], $3::INT2[], $4::TEXT[])
                     ",
                 )
                 .bind(vec![record.id])
                 .bind(ready.into_iter().map(Json).collect::<Vec<_>>())
+                .bind(priorities)
+                .bind(chains)
                 .execute(tx.as_mut())
                 .await?;
 
@@ -782,6 +1210,115 @@ where
     Ok(Some(r))
 }
 
+/// Find the first retry policy that applies to an error of the given class and message.
+async fn find_retry_policy<'a>(
+    retry_policies: &'a [RetryPolicy],
+    error_class: ErrorClass,
+    error: &str,
+    tx: &mut Transaction<'static, Postgres>,
+) -> Result<Option<&'a RetryPolicy>, sqlx::Error> {
+    let candidates = retry_policies
+        .iter()
+        .filter(|policy| policy.error_class == error_class)
+        .collect::<Vec<_>>();
+
+    // a policy without filters always matches, so none of the policies after it are considered
+    let catch_all = candidates
+        .iter()
+        .position(|policy| policy.message_filters.is_empty());
+
+    let filtered = &candidates[..catch_all.unwrap_or(candidates.len())];
+
+    if filtered.is_empty() {
+        return Ok(catch_all.map(|idx| candidates[idx]));
+    }
+
+    let (policies, filters): (Vec<_>, Vec<_>) = filtered
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, policy)| {
+            policy.message_filters.iter().map(move |filter| {
+                (
+                    i32::try_from(idx).expect("too many retry policies"),
+                    filter.clone(),
+                )
+            })
+        })
+        .unzip();
+
+    // use postgres for the matching, so that the filters have the exact same semantics as the
+    // filters used when querying the failed table. all policies are matched in a single query,
+    // returning the first one with a matching filter.
+    let matched = sqlx::query_scalar::<_, Option<i32>>(
+        r#"
+        SELECT
+            MIN(policy)
+        FROM
+            UNNEST($2::INT4[], $3::TEXT[]) AS filters(policy, filter)
+        WHERE
+            $1 LIKE filter
+        "#,
+    )
+    .bind(error)
+    .bind(policies)
+    .bind(filters)
+    .fetch_one(tx.as_mut())
+    .await?;
+
+    Ok(matched
+        .map(|idx| filtered[usize::try_from(idx).expect("index is non-negative")])
+        .or(catch_all.map(|idx| candidates[idx])))
+}
+
+async fn requeue_with_backoff(
+    record: QueueRecord,
+    backoff_max: f64,
+    backoff_multiplier: f64,
+    tx: &mut Transaction<'static, Postgres>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "
+        INSERT INTO
+        queue  (id, item,      parents, attempt, handle_at, created_at, priority, chain)
+        VALUES ($1, $2::JSONB, $3,      $4,      $5,        $6,         $7,       $8   )
+        ",
+    )
+    .bind(record.id)
+    .bind(record.item)
+    .bind(record.parents)
+    .bind(record.attempt.saturating_add(1))
+    .bind(
+        time::OffsetDateTime::now_utc().saturating_add(
+            Duration::try_from_secs_f64(
+                (record.attempt as f64)
+                    .powf(backoff_multiplier)
+                    .clamp(f64::MIN, backoff_max),
+            )
+            .unwrap_or(Duration::MAX)
+            .try_into()
+            .unwrap_or(time::Duration::MAX),
+        ),
+    )
+    .bind(record.created_at)
+    .bind(record.priority)
+    .bind(record.chain)
+    .execute(tx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+/// The `priority` and `chain` columns for the provided ops, as determined by
+/// [`QueueMessage::schedule`].
+fn schedule_columns<T: QueueMessage>(ops: &[Op<T>]) -> (Vec<i16>, Vec<Option<String>>) {
+    ops.iter()
+        .map(|op| {
+            let schedule = T::schedule(op);
+            (schedule.priority.as_i16(), schedule.chain)
+        })
+        .unzip()
+}
+
 async fn insert_error(
     record: QueueRecord,
     error: String,
@@ -0,0 +1,713 @@
+//! Accounting of the fees paid by the relayer to submit IBC union packets, and the per-channel
+//! budgets that packets are checked against before they are submitted.
+//!
+//! The transaction plugins record the gas used and fee paid for every submitted transaction in a
+//! [`FeeLedger`], which is exposed over the [`CHANNEL_SPEND_METHOD`] and [`PACKET_FEE_METHOD`]
+//! custom rpc methods of the plugin. The transaction batch plugin then queries the spend of the
+//! channels to estimate the cost of submitting a packet, and checks it against the configured
+//! [`ChannelBudget`] with [`check_budget`].
+//!
+//! The ledger is only kept in memory, and starts out empty whenever the transaction plugin is
+//! (re)started. Until packets have been submitted on a channel again, there is no estimate for it
+//! and its packets are submitted regardless of the budget.
+//!
+//! The estimate is only updated when packets are submitted, so a channel whose packets are all
+//! deferred would never see it go down again. To avoid this, the estimate decays over time when no
+//! packets are submitted on the channel (see [`FEE_ESTIMATE_HALF_LIFE_SECONDS`]), until a packet
+//! fits the budget again and is submitted, refreshing the estimate with the fee actually paid.
+
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use ibc_union_spec::{datagram::Datagram, ChannelId};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use unionlabs::primitives::H256;
+
+/// The custom rpc method of the transaction plugins returning the [`ChannelSpend`] of all channels
+/// that packets have been submitted on.
+pub const CHANNEL_SPEND_METHOD: &str = "channelSpend";
+
+/// The custom rpc method of the transaction plugins returning the [`PacketFee`] of a packet, by
+/// its hash.
+pub const PACKET_FEE_METHOD: &str = "packetFee";
+
+/// The maximum amount of [`PacketFee`]s kept in the ledger. The oldest records are dropped first,
+/// the per-channel totals are kept for as long as the ledger is.
+pub const MAX_PACKET_FEES: usize = 10_000;
+
+/// The amount of packets that [`ChannelSpend::recent_fee_per_packet`] is averaged over. Each new
+/// packet fee is weighted `1 / RECENT_FEE_WINDOW` against the previous average.
+pub const RECENT_FEE_WINDOW: u128 = 20;
+
+/// The estimated fee of a channel is halved for every this many seconds since the last packet was
+/// submitted on it.
+pub const FEE_ESTIMATE_HALF_LIFE_SECONDS: u64 = 10 * 60;
+
+/// The fee paid to submit a single packet datagram.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PacketFee {
+    pub packet_hash: H256,
+    /// The channel on the chain the packet was submitted to.
+    pub channel_id: ChannelId,
+    /// The channel on the counterparty chain.
+    pub counterparty_channel_id: ChannelId,
+    /// The name of the datagram the packet was submitted in.
+    pub datagram: String,
+    pub tx_hash: H256,
+    pub gas_used: u64,
+    #[serde(with = "::serde_utils::string")]
+    pub fee: u128,
+    pub denom: String,
+}
+
+/// The total fees paid to submit packets on a channel.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelSpend {
+    pub channel_id: ChannelId,
+    pub counterparty_channel_id: ChannelId,
+    pub packets: u64,
+    pub gas_used: u64,
+    #[serde(with = "::serde_utils::string")]
+    pub fee: u128,
+    /// Exponentially weighted moving average of the fee paid per packet, over roughly the last
+    /// [`RECENT_FEE_WINDOW`] packets. Unlike the all time average, this follows changes in the gas
+    /// price.
+    #[serde(with = "::serde_utils::string")]
+    pub recent_fee_per_packet: u128,
+    /// Unix timestamp (in seconds) of when the last packet was recorded on this channel.
+    pub last_packet_at: u64,
+    pub denom: String,
+}
+
+impl ChannelSpend {
+    /// The average fee paid per packet on this channel, or `None` if no packets have been
+    /// submitted yet.
+    pub fn average_fee_per_packet(&self) -> Option<u128> {
+        self.fee.checked_div(self.packets.into())
+    }
+
+    /// The estimated fee of submitting the next packet on this channel at the unix timestamp `now`
+    /// (in seconds), or `None` if no packets have been submitted yet.
+    ///
+    /// This is the [recent fee per packet](Self::recent_fee_per_packet), halved for every
+    /// [`FEE_ESTIMATE_HALF_LIFE_SECONDS`] since the last packet was submitted.
+    pub fn estimated_fee_per_packet(&self, now: u64) -> Option<u128> {
+        let half_lives = now.saturating_sub(self.last_packet_at) / FEE_ESTIMATE_HALF_LIFE_SECONDS;
+
+        (self.packets > 0).then(|| {
+            u32::try_from(half_lives)
+                .ok()
+                .and_then(|half_lives| self.recent_fee_per_packet.checked_shr(half_lives))
+                .unwrap_or_default()
+        })
+    }
+
+    fn record(&mut self, gas_used: u64, fee: u128, now: u64) {
+        self.recent_fee_per_packet = if self.packets == 0 {
+            fee
+        } else {
+            self.recent_fee_per_packet
+                .saturating_mul(RECENT_FEE_WINDOW - 1)
+                .saturating_add(fee)
+                / RECENT_FEE_WINDOW
+        };
+
+        self.packets += 1;
+        self.gas_used = self.gas_used.saturating_add(gas_used);
+        self.fee = self.fee.saturating_add(fee);
+        self.last_packet_at = self.last_packet_at.max(now);
+    }
+}
+
+/// The packets submitted in a datagram, as `(packet hash, channel id, counterparty channel id)`.
+///
+/// The channel is the channel on the chain the datagram is submitted to, i.e. the destination
+/// channel for received packets, and the source channel for acknowledgements and timeouts.
+pub fn datagram_packets(datagram: &Datagram) -> Vec<(H256, ChannelId, ChannelId)> {
+    let (packets, on_source) = match datagram {
+        Datagram::PacketRecv(msg) => (&msg.packets[..], false),
+        Datagram::PacketAcknowledgement(msg) => (&msg.packets[..], true),
+        Datagram::PacketTimeout(msg) => (core::slice::from_ref(&msg.packet), true),
+        Datagram::BatchSend(msg) => (&msg.packets[..], true),
+        Datagram::BatchAcks(msg) => (&msg.packets[..], false),
+        _ => return vec![],
+    };
+
+    packets
+        .iter()
+        .map(|packet| {
+            if on_source {
+                (
+                    packet.hash(),
+                    packet.source_channel_id,
+                    packet.destination_channel_id,
+                )
+            } else {
+                (
+                    packet.hash(),
+                    packet.destination_channel_id,
+                    packet.source_channel_id,
+                )
+            }
+        })
+        .collect()
+}
+
+/// In-memory record of the fees paid to submit packets. See the [crate docs](crate) for how it is
+/// used.
+#[derive(Debug, Default)]
+pub struct FeeLedger {
+    inner: Mutex<FeeLedgerInner>,
+}
+
+#[derive(Debug, Default)]
+struct FeeLedgerInner {
+    packet_fees: VecDeque<PacketFee>,
+    channels: BTreeMap<(ChannelId, ChannelId), ChannelSpend>,
+}
+
+impl FeeLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the gas used and fee paid for a transaction containing `datagrams`.
+    ///
+    /// The cost of a transaction can't be attributed to the individual messages in it, so it is
+    /// split evenly between all of the packets in the transaction, including the cost of any
+    /// non-packet datagrams (i.e. client updates). Any remainder is attributed to the first packet.
+    /// Transactions without any packets are not recorded.
+    ///
+    /// Only the datagrams that were successfully executed should be passed here, otherwise the
+    /// packets of failed datagrams would be counted as submitted.
+    pub fn record_tx<'a>(
+        &self,
+        datagrams: impl IntoIterator<Item = &'a Datagram>,
+        tx_hash: H256,
+        gas_used: u64,
+        fee: u128,
+        denom: &str,
+    ) -> Vec<PacketFee> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("the current timestamp must be greater than the unix epoch")
+            .as_secs();
+
+        self.record_tx_at(datagrams, tx_hash, gas_used, fee, denom, now)
+    }
+
+    /// [`Self::record_tx`], recorded at the unix timestamp `now` (in seconds).
+    fn record_tx_at<'a>(
+        &self,
+        datagrams: impl IntoIterator<Item = &'a Datagram>,
+        tx_hash: H256,
+        gas_used: u64,
+        fee: u128,
+        denom: &str,
+        now: u64,
+    ) -> Vec<PacketFee> {
+        let packets = datagrams
+            .into_iter()
+            .flat_map(|datagram| {
+                datagram_packets(datagram)
+                    .into_iter()
+                    .map(|packet| (datagram.name(), packet))
+            })
+            .collect::<Vec<_>>();
+
+        let Ok(count) = u64::try_from(packets.len()) else {
+            return vec![];
+        };
+
+        if count == 0 {
+            return vec![];
+        }
+
+        let packet_fees = packets
+            .into_iter()
+            .enumerate()
+            .map(
+                |(idx, (datagram, (packet_hash, channel_id, counterparty_channel_id)))| {
+                    let (gas_used, fee) = if idx == 0 {
+                        (
+                            (gas_used / count) + (gas_used % count),
+                            (fee / u128::from(count)) + (fee % u128::from(count)),
+                        )
+                    } else {
+                        (gas_used / count, fee / u128::from(count))
+                    };
+
+                    PacketFee {
+                        packet_hash,
+                        channel_id,
+                        counterparty_channel_id,
+                        datagram: datagram.to_owned(),
+                        tx_hash,
+                        gas_used,
+                        fee,
+                        denom: denom.to_owned(),
+                    }
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let mut inner = self.inner.lock().expect("lock is not poisoned; qed;");
+
+        for packet_fee in &packet_fees {
+            info!(
+                packet_hash = %packet_fee.packet_hash,
+                channel_id = %packet_fee.channel_id,
+                counterparty_channel_id = %packet_fee.counterparty_channel_id,
+                datagram = packet_fee.datagram,
+                %tx_hash,
+                gas_used = packet_fee.gas_used,
+                fee = packet_fee.fee,
+                denom = packet_fee.denom,
+                "packet fee"
+            );
+
+            let spend = inner
+                .channels
+                .entry((packet_fee.channel_id, packet_fee.counterparty_channel_id))
+                .or_insert_with(|| ChannelSpend {
+                    channel_id: packet_fee.channel_id,
+                    counterparty_channel_id: packet_fee.counterparty_channel_id,
+                    packets: 0,
+                    gas_used: 0,
+                    fee: 0,
+                    recent_fee_per_packet: 0,
+                    last_packet_at: 0,
+                    denom: packet_fee.denom.clone(),
+                });
+
+            spend.record(packet_fee.gas_used, packet_fee.fee, now);
+
+            if inner.packet_fees.len() == MAX_PACKET_FEES {
+                inner.packet_fees.pop_front();
+            }
+
+            inner.packet_fees.push_back(packet_fee.clone());
+        }
+
+        packet_fees
+    }
+
+    /// The spend of all channels that packets have been submitted on.
+    pub fn channel_spend(&self) -> Vec<ChannelSpend> {
+        self.inner
+            .lock()
+            .expect("lock is not poisoned; qed;")
+            .channels
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// The fee paid for the packet with the given hash, if it is still in the ledger.
+    pub fn packet_fee(&self, packet_hash: H256) -> Option<PacketFee> {
+        self.inner
+            .lock()
+            .expect("lock is not poisoned; qed;")
+            .packet_fees
+            .iter()
+            .rev()
+            .find(|packet_fee| packet_fee.packet_hash == packet_hash)
+            .cloned()
+    }
+}
+
+/// The budget of a channel, checked before submitting packets on it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChannelBudget {
+    /// The channel on the chain the packets are submitted to.
+    pub channel_id: ChannelId,
+    /// The maximum estimated fee to pay for submitting a single packet on this channel.
+    #[serde(with = "::serde_utils::string")]
+    pub max_fee_per_packet: u128,
+    #[serde(default)]
+    pub on_exceeded: BudgetExceededAction,
+}
+
+/// What to do with packets whose estimated submission cost exceeds the budget of the channel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetExceededAction {
+    /// Hold the packet back, checking it against the budget again later.
+    #[default]
+    Defer,
+    /// Drop the packet, it will not be relayed.
+    Drop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BudgetDecision {
+    Submit,
+    Defer,
+    Drop,
+}
+
+/// Check the estimated cost of submitting a packet on a channel against the budget of the channel.
+///
+/// The estimated cost is the [estimated fee per packet](ChannelSpend::estimated_fee_per_packet) on
+/// the channel at the unix timestamp `now` (in seconds). Packets on channels without a budget, or
+/// without any spend yet, are always submitted.
+pub fn check_budget(
+    budget: Option<&ChannelBudget>,
+    spend: Option<&ChannelSpend>,
+    now: u64,
+) -> BudgetDecision {
+    let (Some(budget), Some(estimate)) = (
+        budget,
+        spend.and_then(|spend| spend.estimated_fee_per_packet(now)),
+    ) else {
+        return BudgetDecision::Submit;
+    };
+
+    if estimate <= budget.max_fee_per_packet {
+        BudgetDecision::Submit
+    } else {
+        match budget.on_exceeded {
+            BudgetExceededAction::Defer => BudgetDecision::Defer,
+            BudgetExceededAction::Drop => BudgetDecision::Drop,
+        }
+    }
+}
+
+/// Index the spend of a set of channels by the channel id on the chain the packets are submitted
+/// to, combining the spend towards all counterparty channels. The recent fees are combined
+/// weighted by the amount of packets submitted towards each counterparty channel, and the combined
+/// spend is considered as recent as the most recent of them.
+pub fn spend_by_channel(
+    spend: impl IntoIterator<Item = ChannelSpend>,
+) -> HashMap<ChannelId, ChannelSpend> {
+    spend.into_iter().fold(HashMap::new(), |mut acc, spend| {
+        acc.entry(spend.channel_id)
+            .and_modify(|total: &mut ChannelSpend| {
+                let packets = total.packets + spend.packets;
+
+                total.recent_fee_per_packet = total
+                    .recent_fee_per_packet
+                    .saturating_mul(total.packets.into())
+                    .saturating_add(
+                        spend
+                            .recent_fee_per_packet
+                            .saturating_mul(spend.packets.into()),
+                    )
+                    .checked_div(packets.into())
+                    .unwrap_or_default();
+                total.packets = packets;
+                total.gas_used = total.gas_used.saturating_add(spend.gas_used);
+                total.fee = total.fee.saturating_add(spend.fee);
+                total.last_packet_at = total.last_packet_at.max(spend.last_packet_at);
+            })
+            .or_insert(spend);
+        acc
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use ibc_union_spec::{
+        datagram::{MsgPacketAcknowledgement, MsgPacketRecv, MsgUpdateClient},
+        ClientId, Packet, Timestamp,
+    };
+    use unionlabs::primitives::Bytes;
+
+    use super::*;
+
+    const NOW: u64 = 1_700_000_000;
+
+    fn channel(raw: u32) -> ChannelId {
+        ChannelId::from_raw(raw).unwrap()
+    }
+
+    fn packet(source: u32, destination: u32, data: &'static [u8]) -> Packet {
+        Packet {
+            source_channel_id: channel(source),
+            destination_channel_id: channel(destination),
+            data: Bytes::new(data),
+            timeout_height: 0,
+            timeout_timestamp: Timestamp::from_nanos(1),
+        }
+    }
+
+    fn recv(packets: Vec<Packet>) -> Datagram {
+        Datagram::PacketRecv(MsgPacketRecv {
+            relayer_msgs: vec![Bytes::default(); packets.len()],
+            packets,
+            proof: Bytes::default(),
+            proof_height: 1,
+        })
+    }
+
+    fn spend(channel_id: u32, packets: u64, fee: u128) -> ChannelSpend {
+        ChannelSpend {
+            channel_id: channel(channel_id),
+            counterparty_channel_id: channel(1),
+            packets,
+            gas_used: 0,
+            fee,
+            recent_fee_per_packet: fee.checked_div(packets.into()).unwrap_or_default(),
+            last_packet_at: NOW,
+            denom: "denom".to_owned(),
+        }
+    }
+
+    #[test]
+    fn packet_channels() {
+        let p = packet(1, 2, b"a");
+
+        assert_eq!(
+            datagram_packets(&recv(vec![p.clone()])),
+            [(p.hash(), channel(2), channel(1))]
+        );
+        assert_eq!(
+            datagram_packets(&Datagram::PacketAcknowledgement(MsgPacketAcknowledgement {
+                packets: vec![p.clone()],
+                acknowledgements: vec![Bytes::default()],
+                proof: Bytes::default(),
+                proof_height: 1,
+            })),
+            [(p.hash(), channel(1), channel(2))]
+        );
+        assert!(datagram_packets(&Datagram::UpdateClient(MsgUpdateClient {
+            client_id: ClientId::from_raw(1).unwrap(),
+            client_message: Bytes::default(),
+        }))
+        .is_empty());
+    }
+
+    #[test]
+    fn splits_tx_fee_between_packets() {
+        let ledger = FeeLedger::new();
+
+        let tx_hash = H256::new([1; 32]);
+
+        let recorded = ledger.record_tx_at(
+            &[
+                Datagram::UpdateClient(MsgUpdateClient {
+                    client_id: ClientId::from_raw(1).unwrap(),
+                    client_message: Bytes::default(),
+                }),
+                recv(vec![packet(1, 2, b"a"), packet(1, 2, b"b")]),
+                recv(vec![packet(3, 4, b"c")]),
+            ],
+            tx_hash,
+            100,
+            1000,
+            "denom",
+            NOW,
+        );
+
+        assert_eq!(
+            recorded
+                .iter()
+                .map(|packet_fee| (packet_fee.gas_used, packet_fee.fee))
+                .collect::<Vec<_>>(),
+            [(34, 334), (33, 333), (33, 333)]
+        );
+
+        assert_eq!(
+            ledger.channel_spend(),
+            [
+                ChannelSpend {
+                    channel_id: channel(2),
+                    counterparty_channel_id: channel(1),
+                    packets: 2,
+                    gas_used: 67,
+                    fee: 667,
+                    recent_fee_per_packet: 333,
+                    last_packet_at: NOW,
+                    denom: "denom".to_owned(),
+                },
+                ChannelSpend {
+                    channel_id: channel(4),
+                    counterparty_channel_id: channel(3),
+                    packets: 1,
+                    gas_used: 33,
+                    fee: 333,
+                    recent_fee_per_packet: 333,
+                    last_packet_at: NOW,
+                    denom: "denom".to_owned(),
+                }
+            ]
+        );
+
+        assert_eq!(
+            ledger.packet_fee(packet(3, 4, b"c").hash()),
+            Some(recorded[2].clone())
+        );
+        assert_eq!(ledger.packet_fee(packet(3, 4, b"d").hash()), None);
+
+        assert!(ledger
+            .record_tx(
+                &[Datagram::UpdateClient(MsgUpdateClient {
+                    client_id: ClientId::from_raw(1).unwrap(),
+                    client_message: Bytes::default(),
+                })],
+                tx_hash,
+                100,
+                1000,
+                "denom",
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn budget() {
+        let budget = ChannelBudget {
+            channel_id: channel(2),
+            max_fee_per_packet: 100,
+            on_exceeded: BudgetExceededAction::Defer,
+        };
+
+        assert_eq!(
+            check_budget(None, Some(&spend(2, 1, 1000)), NOW),
+            BudgetDecision::Submit
+        );
+        assert_eq!(
+            check_budget(Some(&budget), None, NOW),
+            BudgetDecision::Submit
+        );
+        assert_eq!(
+            check_budget(Some(&budget), Some(&spend(2, 0, 0)), NOW),
+            BudgetDecision::Submit
+        );
+        assert_eq!(
+            check_budget(Some(&budget), Some(&spend(2, 10, 1000)), NOW),
+            BudgetDecision::Submit
+        );
+        assert_eq!(
+            check_budget(Some(&budget), Some(&spend(2, 10, 1010)), NOW),
+            BudgetDecision::Defer
+        );
+        assert_eq!(
+            check_budget(
+                Some(&ChannelBudget {
+                    on_exceeded: BudgetExceededAction::Drop,
+                    ..budget
+                }),
+                Some(&spend(2, 10, 1010)),
+                NOW
+            ),
+            BudgetDecision::Drop
+        );
+    }
+
+    #[test]
+    fn combines_spend_by_channel() {
+        let mut other_counterparty = spend(2, 2, 50);
+        other_counterparty.counterparty_channel_id = channel(5);
+
+        let by_channel = spend_by_channel([spend(2, 1, 100), other_counterparty, spend(3, 1, 10)]);
+
+        assert_eq!(by_channel[&channel(2)].packets, 3);
+        assert_eq!(by_channel[&channel(2)].fee, 150);
+        assert_eq!(by_channel[&channel(3)].fee, 10);
+        assert_eq!(by_channel[&channel(2)].recent_fee_per_packet, 50);
+    }
+
+    #[test]
+    fn estimate_follows_recent_fees() {
+        let ledger = FeeLedger::new();
+
+        let record = |data: &'static [u8], fee| {
+            ledger.record_tx_at(
+                &[recv(vec![packet(1, 2, data)])],
+                H256::new([1; 32]),
+                1,
+                fee,
+                "denom",
+                NOW,
+            );
+        };
+
+        for _ in 0..100 {
+            record(b"a", 1000);
+        }
+
+        for _ in 0..100 {
+            record(b"b", 10);
+        }
+
+        let [spend] = &ledger.channel_spend()[..] else {
+            panic!("expected a single channel");
+        };
+
+        assert_eq!(spend.average_fee_per_packet(), Some(505));
+        assert!(spend.estimated_fee_per_packet(NOW).unwrap() < 20);
+
+        let budget = ChannelBudget {
+            channel_id: channel(2),
+            max_fee_per_packet: 100,
+            on_exceeded: BudgetExceededAction::Defer,
+        };
+
+        assert_eq!(
+            check_budget(Some(&budget), Some(spend), NOW),
+            BudgetDecision::Submit
+        );
+    }
+
+    #[test]
+    fn estimate_decays_without_packets() {
+        let spend = spend(2, 10, 10_000);
+
+        assert_eq!(spend.estimated_fee_per_packet(NOW), Some(1000));
+        assert_eq!(spend.estimated_fee_per_packet(NOW - 1), Some(1000));
+        assert_eq!(
+            spend.estimated_fee_per_packet(NOW + FEE_ESTIMATE_HALF_LIFE_SECONDS - 1),
+            Some(1000)
+        );
+        assert_eq!(
+            spend.estimated_fee_per_packet(NOW + FEE_ESTIMATE_HALF_LIFE_SECONDS),
+            Some(500)
+        );
+        assert_eq!(
+            spend.estimated_fee_per_packet(NOW + 3 * FEE_ESTIMATE_HALF_LIFE_SECONDS),
+            Some(125)
+        );
+        assert_eq!(spend.estimated_fee_per_packet(u64::MAX), Some(0));
+
+        let budget = ChannelBudget {
+            channel_id: channel(2),
+            max_fee_per_packet: 100,
+            on_exceeded: BudgetExceededAction::Defer,
+        };
+
+        assert_eq!(
+            check_budget(Some(&budget), Some(&spend), NOW),
+            BudgetDecision::Defer
+        );
+        assert_eq!(
+            check_budget(
+                Some(&budget),
+                Some(&spend),
+                NOW + 4 * FEE_ESTIMATE_HALF_LIFE_SECONDS
+            ),
+            BudgetDecision::Submit
+        );
+    }
+
+    #[test]
+    fn budget_config_parse() {
+        assert_eq!(
+            serde_json::from_str::<ChannelBudget>(
+                r#"{ "channel_id": 2, "max_fee_per_packet": "100" }"#
+            )
+            .unwrap(),
+            ChannelBudget {
+                channel_id: channel(2),
+                max_fee_per_packet: 100,
+                on_exceeded: BudgetExceededAction::Defer,
+            }
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! An alloy transport backed by a [`Pool`], for use with alloy providers:
+//!
+//! ```rust,ignore
+//! let transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
+//!
+//! let provider = ProviderBuilder::new().connect_client(transport.into_client());
+//! ```
+
+use std::task::{Context, Poll};
+
+use alloy::{
+    primitives::U64,
+    rpc::{
+        client::{BuiltInConnectionString, RpcClient},
+        json_rpc::{RequestPacket, ResponsePacket, RpcError},
+    },
+    transports::{BoxTransport, TransportConnect, TransportError, TransportFut},
+};
+use serde::Deserialize;
+use tower::Service;
+
+use crate::{EndpointError, Error, Pool, PoolConfig};
+
+impl EndpointError for TransportError {
+    fn is_endpoint_failure(&self) -> bool {
+        match self {
+            // an error response was returned by a functioning endpoint, unless it is rate limiting
+            // us
+            RpcError::ErrorResp(payload) => payload.is_retry_err(),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolTransport {
+    pool: Pool<BoxTransport>,
+    /// Whether all of the endpoints are local, see [`TransportConnect::is_local`].
+    is_local: bool,
+}
+
+impl PoolTransport {
+    /// Connect to all of the provided URLs (any of `http(s)://`, `ws(s)://` or an IPC path) and
+    /// start the background health checks.
+    pub async fn connect(
+        urls: impl IntoIterator<Item = impl Into<String>>,
+        config: PoolConfig,
+    ) -> Result<Self, Error<TransportError>> {
+        let urls = urls
+            .into_iter()
+            .map(|url| {
+                let url = url.into();
+                let connection_string = url.parse::<BuiltInConnectionString>()?;
+                Ok((url, connection_string))
+            })
+            .collect::<Result<Vec<_>, TransportError>>()
+            .map_err(Error::Endpoint)?;
+
+        let is_local = urls
+            .iter()
+            .all(|(_, connection_string)| connection_string.is_local());
+
+        let pool = Pool::connect(
+            urls.into_iter().map(|(url, _)| url),
+            config,
+            |url| async move {
+                url.parse::<BuiltInConnectionString>()?
+                    .connect_boxed()
+                    .await
+            },
+        )
+        .await?;
+
+        pool.spawn_health_checks(|transport| async move {
+            RpcClient::new(transport, false)
+                .request_noparams::<U64>("eth_blockNumber")
+                .await
+        });
+
+        Ok(Self { pool, is_local })
+    }
+
+    #[must_use]
+    pub fn pool(&self) -> &Pool<BoxTransport> {
+        &self.pool
+    }
+
+    /// Wrap this transport in an [`RpcClient`], for use with
+    /// [`ProviderBuilder::connect_client`](alloy::providers::ProviderBuilder::connect_client).
+    ///
+    /// As with [`ProviderBuilder::connect`](alloy::providers::ProviderBuilder::connect), the
+    /// client uses a shorter poll interval if all of the endpoints are local.
+    #[must_use]
+    pub fn into_client(self) -> RpcClient {
+        let is_local = self.is_local;
+        RpcClient::new(self, is_local)
+    }
+
+    /// Read the latest block number with a quorum across all endpoints. See [`Pool::quorum`].
+    pub async fn block_number(&self) -> Result<u64, Error<TransportError>> {
+        self.pool
+            .quorum(|transport| {
+                let client = RpcClient::new(transport.clone(), false);
+
+                async move {
+                    client
+                        .request_noparams::<U64>("eth_blockNumber")
+                        .await
+                        .map(|number| number.to::<u64>())
+                }
+            })
+            .await
+    }
+
+    /// Read the latest finalized block number with a quorum across all endpoints. See
+    /// [`Pool::quorum`].
+    pub async fn finalized_block_number(&self) -> Result<u64, Error<TransportError>> {
+        #[derive(Debug, Deserialize)]
+        struct Block {
+            number: U64,
+        }
+
+        self.pool
+            .quorum(|transport| {
+                let client = RpcClient::new(transport.clone(), false);
+
+                async move {
+                    client
+                        .request::<_, Option<Block>>("eth_getBlockByNumber", ("finalized", false))
+                        .await?
+                        .map(|block| block.number.to::<u64>())
+                        .ok_or(RpcError::NullResp)
+                }
+            })
+            .await
+    }
+}
+
+impl Service<RequestPacket> for PoolTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        let pool = self.pool.clone();
+
+        Box::pin(async move {
+            pool.request(|transport| {
+                let mut transport = transport.clone();
+                let req = req.clone();
+
+                async move { transport.call(req).await }
+            })
+            .await
+        })
+    }
+}
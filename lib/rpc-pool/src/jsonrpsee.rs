@@ -0,0 +1,10 @@
+use ::jsonrpsee::core::client::Error;
+
+use crate::EndpointError;
+
+impl EndpointError for Error {
+    fn is_endpoint_failure(&self) -> bool {
+        // a json-rpc error response was returned by a functioning endpoint
+        !matches!(self, Error::Call(_))
+    }
+}
@@ -0,0 +1,563 @@
+use std::{
+    fmt::{self, Debug, Display},
+    future::Future,
+    sync::{Arc, Mutex, OnceLock, Weak},
+    time::{Duration, Instant},
+};
+
+use futures::{
+    future::{join_all, BoxFuture},
+    FutureExt,
+};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+#[cfg(feature = "alloy")]
+pub mod eth;
+
+#[cfg(feature = "jsonrpsee")]
+mod jsonrpsee;
+
+#[cfg(test)]
+mod tests;
+
+/// The weight of the most recent latency sample in the moving average used to rank endpoints.
+const LATENCY_SMOOTHING: f64 = 0.3;
+
+/// One or more RPC endpoints for the same chain.
+///
+/// This deserializes from either a single URL or a list of URLs, such that existing single
+/// `rpc_url` configurations continue to work unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Endpoints {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl Endpoints {
+    #[must_use]
+    pub fn urls(&self) -> &[String] {
+        match self {
+            Endpoints::One(url) => std::slice::from_ref(url),
+            Endpoints::Many(urls) => urls,
+        }
+    }
+}
+
+impl From<String> for Endpoints {
+    fn from(url: String) -> Self {
+        Self::One(url)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolConfig {
+    /// The amount of consecutive failures after which an endpoint is considered unhealthy and put
+    /// into a cooldown.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// How long an unhealthy endpoint is deprioritized for before it is tried again.
+    #[serde(default = "default_cooldown_seconds")]
+    pub cooldown_seconds: u64,
+    /// The amount of endpoints that must agree on a value read with [`Pool::quorum`].
+    ///
+    /// A quorum of 1 (the default) reads from a single endpoint, as with any other request. This
+    /// must not be larger than the amount of endpoints.
+    #[serde(default = "default_quorum")]
+    pub quorum: usize,
+    /// The interval between active health checks of all endpoints.
+    #[serde(default = "default_health_check_interval_seconds")]
+    pub health_check_interval_seconds: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: default_failure_threshold(),
+            cooldown_seconds: default_cooldown_seconds(),
+            quorum: default_quorum(),
+            health_check_interval_seconds: default_health_check_interval_seconds(),
+        }
+    }
+}
+
+#[must_use]
+pub const fn default_failure_threshold() -> u32 {
+    2
+}
+
+#[must_use]
+pub const fn default_cooldown_seconds() -> u64 {
+    30
+}
+
+#[must_use]
+pub const fn default_quorum() -> usize {
+    1
+}
+
+#[must_use]
+pub const fn default_health_check_interval_seconds() -> u64 {
+    15
+}
+
+/// Classifies errors returned by an endpoint.
+pub trait EndpointError {
+    /// Whether this error indicates a problem with the endpoint itself (connection errors,
+    /// timeouts, rate limiting, server errors) as opposed to a problem with the request.
+    ///
+    /// Requests that fail with an endpoint failure are retried on the next endpoint in the pool;
+    /// all other errors are returned to the caller immediately.
+    fn is_endpoint_failure(&self) -> bool;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidPool {
+    #[error("no endpoints configured")]
+    NoEndpoints,
+    #[error("quorum of {quorum} can never be reached with {endpoints} endpoints")]
+    QuorumTooLarge { quorum: usize, endpoints: usize },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error<E> {
+    #[error(transparent)]
+    InvalidPool(#[from] InvalidPool),
+    #[error(transparent)]
+    Endpoint(E),
+    #[error("quorum of {quorum} not reached, {responses} of {endpoints} endpoints responded")]
+    NoQuorum {
+        quorum: usize,
+        responses: usize,
+        endpoints: usize,
+    },
+}
+
+/// A pool of clients for the same chain, one per configured endpoint.
+///
+/// This is cheap to clone, and all clones share the same endpoint health.
+pub struct Pool<C> {
+    inner: Arc<Inner<C>>,
+}
+
+impl<C> Clone for Pool<C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C> Debug for Pool<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field(
+                "endpoints",
+                &self
+                    .inner
+                    .endpoints
+                    .iter()
+                    .map(|endpoint| &endpoint.url)
+                    .collect::<Vec<_>>(),
+            )
+            .field("config", &self.inner.config)
+            .finish()
+    }
+}
+
+/// Connects to an endpoint, used by the health checks to connect to endpoints that were
+/// unreachable when the pool was created.
+type Connector<C> = Box<dyn Fn(String) -> BoxFuture<'static, Result<C, String>> + Send + Sync>;
+
+struct Inner<C> {
+    config: PoolConfig,
+    endpoints: Vec<Endpoint<C>>,
+    connect: Option<Connector<C>>,
+}
+
+struct Endpoint<C> {
+    url: String,
+    /// Unset until the endpoint has been connected to. Endpoints without a client are skipped.
+    client: OnceLock<C>,
+    health: Mutex<Health>,
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    /// Moving average of the latency of successful requests, or `None` if no request has
+    /// completed yet.
+    latency: Option<Duration>,
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+}
+
+impl<C> Endpoint<C> {
+    fn new(url: String) -> Self {
+        Self {
+            url,
+            client: OnceLock::new(),
+            health: Mutex::default(),
+        }
+    }
+
+    fn record_success(&self, latency: Duration) {
+        let mut health = self.health.lock().expect("mutex is poisoned");
+
+        if health.unhealthy_until.take().is_some() {
+            info!(url = %self.url, "endpoint recovered");
+        }
+
+        health.consecutive_failures = 0;
+        health.latency = Some(match health.latency {
+            Some(average) => {
+                average.mul_f64(1.0 - LATENCY_SMOOTHING) + latency.mul_f64(LATENCY_SMOOTHING)
+            }
+            None => latency,
+        });
+    }
+
+    fn record_failure(&self, config: &PoolConfig) {
+        let mut health = self.health.lock().expect("mutex is poisoned");
+
+        health.consecutive_failures += 1;
+
+        if health.consecutive_failures >= config.failure_threshold {
+            if health.unhealthy_until.is_none() {
+                warn!(
+                    url = %self.url,
+                    consecutive_failures = health.consecutive_failures,
+                    "endpoint is unhealthy"
+                );
+            }
+
+            health.unhealthy_until =
+                Some(Instant::now() + Duration::from_secs(config.cooldown_seconds));
+        }
+    }
+
+    /// Put an endpoint that could not be connected to into a cooldown straight away.
+    fn record_unreachable(&self, config: &PoolConfig) {
+        let mut health = self.health.lock().expect("mutex is poisoned");
+
+        health.consecutive_failures = config.failure_threshold;
+        health.unhealthy_until =
+            Some(Instant::now() + Duration::from_secs(config.cooldown_seconds));
+    }
+
+    /// Endpoints are ordered first by whether they are currently in a cooldown (and if so, by
+    /// when it ends), and then by latency. Endpoints with no latency samples yet sort first, so
+    /// that they are measured.
+    fn rank(&self, now: Instant) -> (Option<Instant>, Option<Duration>) {
+        let health = self.health.lock().expect("mutex is poisoned");
+
+        (
+            health.unhealthy_until.filter(|until| *until > now),
+            health.latency,
+        )
+    }
+}
+
+impl<C> Pool<C> {
+    /// Create a new pool from already constructed clients, in order of preference.
+    ///
+    /// This fails if there are no clients, or if there are less clients than the configured
+    /// [`quorum`](PoolConfig::quorum).
+    pub fn new(
+        clients: impl IntoIterator<Item = (String, C)>,
+        config: PoolConfig,
+    ) -> Result<Self, InvalidPool> {
+        let endpoints = clients
+            .into_iter()
+            .map(|(url, client)| Endpoint {
+                url,
+                client: OnceLock::from(client),
+                health: Mutex::default(),
+            })
+            .collect();
+
+        Self::from_endpoints(endpoints, config, None)
+    }
+
+    fn from_endpoints(
+        endpoints: Vec<Endpoint<C>>,
+        config: PoolConfig,
+        connect: Option<Connector<C>>,
+    ) -> Result<Self, InvalidPool> {
+        if endpoints.is_empty() {
+            return Err(InvalidPool::NoEndpoints);
+        }
+
+        if config.quorum > endpoints.len() {
+            return Err(InvalidPool::QuorumTooLarge {
+                quorum: config.quorum,
+                endpoints: endpoints.len(),
+            });
+        }
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                config,
+                endpoints,
+                connect,
+            }),
+        })
+    }
+
+    /// Create a new pool by connecting to each of the provided URLs.
+    ///
+    /// Endpoints that cannot be connected to are kept in the pool as unhealthy, and are connected
+    /// to by the [health checks](Self::spawn_health_checks) once they are reachable again. This
+    /// only fails if none of the endpoints could be connected to.
+    pub async fn connect<E, Fut>(
+        urls: impl IntoIterator<Item = impl Into<String>>,
+        config: PoolConfig,
+        connect: impl Fn(String) -> Fut + Send + Sync + 'static,
+    ) -> Result<Self, Error<E>>
+    where
+        C: 'static,
+        E: Display,
+        Fut: Future<Output = Result<C, E>> + Send + 'static,
+    {
+        let mut endpoints = vec![];
+        let mut last_err = None;
+
+        for url in urls {
+            let endpoint = Endpoint::new(url.into());
+
+            match connect(endpoint.url.clone()).await {
+                Ok(client) => {
+                    let _ = endpoint.client.set(client);
+                }
+                Err(err) => {
+                    warn!(
+                        url = %endpoint.url,
+                        %err,
+                        "unable to connect to endpoint, it will be retried by the health checks"
+                    );
+
+                    endpoint.record_unreachable(&config);
+                    last_err = Some(err);
+                }
+            }
+
+            endpoints.push(endpoint);
+        }
+
+        let connected = endpoints
+            .iter()
+            .any(|endpoint| endpoint.client.get().is_some());
+
+        let connector: Connector<C> = Box::new(move |url| {
+            let fut = connect(url);
+            async move { fut.await.map_err(|err| err.to_string()) }.boxed()
+        });
+
+        let pool = Self::from_endpoints(endpoints, config, Some(connector))?;
+
+        match last_err {
+            Some(err) if !connected => Err(Error::Endpoint(err)),
+            _ => Ok(pool),
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &PoolConfig {
+        &self.inner.config
+    }
+
+    /// The configured URLs, in order of preference.
+    pub fn urls(&self) -> impl Iterator<Item = &str> {
+        self.inner
+            .endpoints
+            .iter()
+            .map(|endpoint| endpoint.url.as_str())
+    }
+
+    fn ranked(&self) -> Vec<&Endpoint<C>> {
+        let now = Instant::now();
+
+        let mut endpoints = self.inner.endpoints.iter().collect::<Vec<_>>();
+        // sort_by_cached_key is stable, so endpoints with the same rank stay in configured order
+        endpoints.sort_by_cached_key(|endpoint| endpoint.rank(now));
+
+        endpoints
+    }
+
+    /// Send a request to the best available endpoint, failing over to the next endpoint if the
+    /// request fails with an [endpoint failure](EndpointError::is_endpoint_failure).
+    ///
+    /// If all endpoints fail, the error from the last endpoint tried is returned.
+    pub async fn request<'a, T, E, Fut>(&'a self, f: impl Fn(&'a C) -> Fut) -> Result<T, E>
+    where
+        E: EndpointError + Display,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut last_err = None;
+
+        for endpoint in self.ranked() {
+            let Some(client) = endpoint.client.get() else {
+                continue;
+            };
+
+            let start = Instant::now();
+
+            match f(client).await {
+                Err(err) if err.is_endpoint_failure() => {
+                    debug!(url = %endpoint.url, %err, "endpoint failed, failing over");
+
+                    endpoint.record_failure(&self.inner.config);
+                    last_err = Some(err);
+                }
+                res => {
+                    endpoint.record_success(start.elapsed());
+
+                    return res;
+                }
+            }
+        }
+
+        Err(last_err.expect("pool contains at least one connected endpoint; qed;"))
+    }
+
+    /// Read a monotonically increasing value (such as the latest or finalized height) from all
+    /// endpoints concurrently, returning the highest value that at least
+    /// [`quorum`](PoolConfig::quorum) endpoints have reached.
+    ///
+    /// This prevents a single endpoint that is ahead of (or lying about) the rest of the network
+    /// from being trusted. With a quorum of 1, this is equivalent to [`Self::request`].
+    pub async fn quorum<'a, T, E, Fut>(&'a self, f: impl Fn(&'a C) -> Fut) -> Result<T, Error<E>>
+    where
+        T: Ord,
+        E: EndpointError + Display,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let quorum = self.inner.config.quorum;
+
+        if quorum <= 1 {
+            return self.request(f).await.map_err(Error::Endpoint);
+        }
+
+        // endpoints that have not been connected to yet count as not having responded
+        let mut values = join_all(self.inner.endpoints.iter().filter_map(|endpoint| {
+            let fut = f(endpoint.client.get()?);
+
+            Some(async move {
+                let start = Instant::now();
+
+                match fut.await {
+                    Ok(value) => {
+                        endpoint.record_success(start.elapsed());
+                        Some(value)
+                    }
+                    Err(err) => {
+                        debug!(url = %endpoint.url, %err, "quorum read failed");
+
+                        if err.is_endpoint_failure() {
+                            endpoint.record_failure(&self.inner.config);
+                        }
+
+                        None
+                    }
+                }
+            })
+        }))
+        .await
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+
+        if values.len() < quorum {
+            return Err(Error::NoQuorum {
+                quorum,
+                responses: values.len(),
+                endpoints: self.inner.endpoints.len(),
+            });
+        }
+
+        values.sort_unstable_by(|a, b| b.cmp(a));
+
+        Ok(values.swap_remove(quorum - 1))
+    }
+}
+
+impl<C: Clone + Send + Sync + 'static> Pool<C> {
+    /// Probe every endpoint once, updating its health with the result. Endpoints that have not
+    /// been connected to yet are connected to first.
+    pub async fn health_check<T, E, Fut>(&self, probe: impl Fn(C) -> Fut)
+    where
+        E: Display,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        health_check(&self.inner, &probe).await;
+    }
+
+    /// Spawn a task that periodically [health checks](Self::health_check) all endpoints, such that
+    /// unhealthy endpoints are brought back into rotation as soon as they recover.
+    ///
+    /// The task exits once all clones of this pool have been dropped. This must be called from
+    /// within a tokio runtime.
+    pub fn spawn_health_checks<T, E, Fut>(
+        &self,
+        probe: impl Fn(C) -> Fut + Send + Sync + 'static,
+    ) -> JoinHandle<()>
+    where
+        E: Display,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let inner: Weak<Inner<C>> = Arc::downgrade(&self.inner);
+        let interval = Duration::from_secs(self.inner.config.health_check_interval_seconds);
+
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+
+                let Some(inner) = inner.upgrade() else {
+                    break;
+                };
+
+                health_check(&inner, &probe).await;
+            }
+        })
+    }
+}
+
+async fn health_check<C: Clone, T, E, Fut>(inner: &Inner<C>, probe: &impl Fn(C) -> Fut)
+where
+    E: Display,
+    Fut: Future<Output = Result<T, E>>,
+{
+    join_all(inner.endpoints.iter().map(|endpoint| async {
+        let client = match (endpoint.client.get(), &inner.connect) {
+            (Some(client), _) => client.clone(),
+            (None, Some(connect)) => match connect(endpoint.url.clone()).await {
+                Ok(client) => {
+                    info!(url = %endpoint.url, "connected to endpoint");
+
+                    endpoint.client.get_or_init(|| client).clone()
+                }
+                Err(err) => {
+                    debug!(url = %endpoint.url, %err, "unable to connect to endpoint");
+
+                    endpoint.record_failure(&inner.config);
+                    return;
+                }
+            },
+            (None, None) => return,
+        };
+
+        let start = Instant::now();
+
+        match probe(client).await {
+            Ok(_) => endpoint.record_success(start.elapsed()),
+            Err(err) => {
+                debug!(url = %endpoint.url, %err, "health check failed");
+
+                endpoint.record_failure(&inner.config);
+            }
+        }
+    }))
+    .await;
+}
@@ -0,0 +1,204 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use super::*;
+
+#[derive(Debug, PartialEq)]
+enum TestError {
+    Down,
+    NotFound,
+}
+
+impl Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
+
+impl EndpointError for TestError {
+    fn is_endpoint_failure(&self) -> bool {
+        matches!(self, TestError::Down)
+    }
+}
+
+#[derive(Debug)]
+struct TestClient {
+    name: &'static str,
+    height: Option<u64>,
+    requests: AtomicUsize,
+}
+
+impl TestClient {
+    fn new(name: &'static str, height: Option<u64>) -> (String, Arc<Self>) {
+        (
+            name.to_owned(),
+            Arc::new(Self {
+                name,
+                height,
+                requests: AtomicUsize::new(0),
+            }),
+        )
+    }
+
+    async fn height(&self) -> Result<u64, TestError> {
+        self.requests.fetch_add(1, Ordering::SeqCst);
+        self.height.ok_or(TestError::Down)
+    }
+}
+
+fn config(quorum: usize) -> PoolConfig {
+    PoolConfig {
+        failure_threshold: 1,
+        quorum,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn fails_over_and_deprioritizes_unhealthy_endpoints() {
+    let pool = Pool::new(
+        [TestClient::new("a", None), TestClient::new("b", Some(10))],
+        config(1),
+    )
+    .unwrap();
+
+    assert_eq!(pool.request(|c| c.height()).await, Ok(10));
+    assert_eq!(pool.request(|c| c.height()).await, Ok(10));
+
+    // a failed once and is now in a cooldown, so it was skipped for the second request
+    let requests = pool
+        .inner
+        .endpoints
+        .iter()
+        .map(|endpoint| {
+            let client = endpoint.client.get().unwrap();
+
+            (client.name, client.requests.load(Ordering::SeqCst))
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(requests, [("a", 1), ("b", 2)]);
+}
+
+#[tokio::test]
+async fn only_endpoint_failures_fail_over() {
+    let pool = Pool::new(
+        [TestClient::new("a", Some(1)), TestClient::new("b", Some(1))],
+        config(1),
+    )
+    .unwrap();
+
+    assert_eq!(
+        pool.request(|_| async { Err::<(), _>(TestError::NotFound) })
+            .await,
+        Err(TestError::NotFound)
+    );
+
+    assert_eq!(
+        pool.request(|_| async { Err::<(), _>(TestError::Down) })
+            .await,
+        Err(TestError::Down)
+    );
+}
+
+#[tokio::test]
+async fn quorum() {
+    let clients = [
+        TestClient::new("a", Some(12)),
+        TestClient::new("b", Some(10)),
+        TestClient::new("c", Some(11)),
+        TestClient::new("d", None),
+    ];
+
+    let pool = Pool::new(clients.clone(), config(2)).unwrap();
+    assert_eq!(pool.quorum(|c| c.height()).await.unwrap(), 11);
+
+    let pool = Pool::new(clients.clone(), config(3)).unwrap();
+    assert_eq!(pool.quorum(|c| c.height()).await.unwrap(), 10);
+
+    let pool = Pool::new(clients.clone(), config(4)).unwrap();
+    assert!(matches!(
+        pool.quorum(|c| c.height()).await,
+        Err(Error::NoQuorum {
+            quorum: 4,
+            responses: 3,
+            endpoints: 4
+        })
+    ));
+
+    assert_eq!(
+        Pool::new(clients, config(5)).unwrap_err(),
+        InvalidPool::QuorumTooLarge {
+            quorum: 5,
+            endpoints: 4
+        }
+    );
+}
+
+#[tokio::test]
+async fn unreachable_endpoints_are_connected_by_health_checks() {
+    let attempts = Arc::new(AtomicUsize::new(0));
+
+    // b is unreachable on the first connection attempt only
+    let pool = Pool::connect(["a", "b"], config(2), {
+        let attempts = attempts.clone();
+
+        move |url| {
+            let reachable = url == "a" || attempts.fetch_add(1, Ordering::SeqCst) > 0;
+
+            async move {
+                match (reachable, url.as_str()) {
+                    (true, "a") => Ok(TestClient::new("a", Some(10)).1),
+                    (true, _) => Ok(TestClient::new("b", Some(11)).1),
+                    (false, _) => Err(TestError::Down),
+                }
+            }
+        }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(pool.urls().collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(pool.request(|c| c.height()).await, Ok(10));
+    assert!(matches!(
+        pool.quorum(|c| c.height()).await,
+        Err(Error::NoQuorum {
+            quorum: 2,
+            responses: 1,
+            endpoints: 2
+        })
+    ));
+
+    pool.health_check(|c| async move { c.height().await }).await;
+
+    assert_eq!(pool.quorum(|c| c.height()).await.unwrap(), 10);
+}
+
+#[tokio::test]
+async fn connect_fails_if_no_endpoint_is_reachable() {
+    assert!(matches!(
+        Pool::<Arc<TestClient>>::connect(["a", "b"], config(1), |_| async { Err(TestError::Down) })
+            .await,
+        Err(Error::Endpoint(TestError::Down))
+    ));
+}
+
+#[test]
+fn endpoints_deserialize_from_one_or_many() {
+    assert_eq!(
+        serde_json::from_str::<Endpoints>(r#""http://a""#)
+            .unwrap()
+            .urls(),
+        ["http://a"]
+    );
+    assert_eq!(
+        serde_json::from_str::<Endpoints>(r#"["http://a", "http://b"]"#)
+            .unwrap()
+            .urls(),
+        ["http://a", "http://b"]
+    );
+}
@@ -0,0 +1,1789 @@
+use std::{
+    fmt::Write,
+    future::Future,
+    marker::PhantomData,
+    str::FromStr,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use futures_util::TryStreamExt;
+use itertools::Itertools;
+use opentelemetry::KeyValue;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::{
+    prelude::FromRow,
+    sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
+    types::Json,
+    Either, Executor, Sqlite, SqlitePool, Transaction,
+};
+use tracing::{debug, debug_span, error, info, info_span, instrument, trace, warn, Instrument};
+use voyager_vm::{
+    filter::{FilterResult, Interest, InterestFilter},
+    pass::{Pass, PassResult},
+    retry::{ErrorClass, RetryPolicy},
+    schedule::{FairScheduler, Priority, SchedulingConfig},
+    BoxDynError, Captures, EnqueueResult, HistoryItem, ItemId, ItemStatus, Op, QueueError,
+    QueueMessage,
+};
+
+use crate::metrics::Metrics;
+
+pub mod metrics;
+
+/// A fifo queue backed by an embedded SQLite database. This has the same semantics as `pg-queue`,
+/// but doesn't require a running database server.
+///
+/// Items are claimed while they are being processed, instead of being locked for the duration of
+/// a transaction; any claims left behind by an unclean shutdown are released when the queue is
+/// started again with [`Queue::new`](voyager_vm::Queue::new). As such, a database file must only be
+/// used by a single voyager instance at a time, and other processes accessing the queue (such as
+/// the CLI) must use [`SqliteQueue::open`].
+#[derive(Debug, Clone)]
+pub struct SqliteQueue<T> {
+    client: SqlitePool,
+    optimize_batch_limit: Option<i64>,
+    retryable_error_expo_backoff_max: f64,
+    retryable_error_expo_backoff_multiplier: f64,
+    retry_policies: Vec<RetryPolicy>,
+    scheduler: FairScheduler,
+
+    metrics: Metrics,
+
+    __marker: PhantomData<fn() -> T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SqliteQueueConfig {
+    /// The url of the database, i.e. `sqlite://voyager.db`. The database will be created if it
+    /// doesn't exist.
+    pub database_url: String,
+    #[serde(default = "default_max_connections")]
+    pub max_connections: u32,
+    #[serde(default)]
+    pub busy_timeout: Option<Duration>,
+    #[serde(default)]
+    pub optimize_batch_limit: Option<i64>,
+    #[serde(default = "default_retryable_error_expo_backoff_max")]
+    pub retryable_error_expo_backoff_max: f64,
+    #[serde(default = "default_retryable_error_expo_backoff_multiplier")]
+    pub retryable_error_expo_backoff_multiplier: f64,
+    /// Automatic retry policies, checked in order. The first policy whose error class and message
+    /// filters match an error is applied; errors not matched by any policy are handled as usual
+    /// (retryable errors are retried indefinitely, fatal and unprocessable errors are moved to the
+    /// `failed` table).
+    #[serde(default)]
+    pub retry_policies: Vec<RetryPolicy>,
+    /// Priority and per-chain fairness configuration for processing ready items.
+    #[serde(default)]
+    pub scheduling: SchedulingConfig,
+}
+
+pub const fn default_max_connections() -> u32 {
+    4
+}
+
+pub const fn default_retryable_error_expo_backoff_max() -> f64 {
+    60.0 * 5.0
+}
+
+pub const fn default_retryable_error_expo_backoff_multiplier() -> f64 {
+    2.0
+}
+
+impl SqliteQueueConfig {
+    pub async fn into_sqlite_pool(self) -> sqlx::Result<SqlitePool> {
+        let mut options = SqliteConnectOptions::from_str(&self.database_url)?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal);
+
+        if let Some(busy_timeout) = self.busy_timeout {
+            options = options.busy_timeout(busy_timeout);
+        }
+
+        SqlitePoolOptions::new()
+            .max_connections(self.max_connections)
+            .connect_with(options)
+            .await
+    }
+}
+
+#[derive(FromRow)]
+struct Id {
+    id: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct QueueRecord {
+    id: i64,
+    parents: String,
+    item: String,
+    created_at: i64,
+    attempt: i64,
+    chain: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct FailedRequeueRecord {
+    id: i64,
+    parents: String,
+    item: String,
+    created_at: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct OptimizeRecord {
+    id: i64,
+    item: String,
+}
+
+#[derive(Debug, FromRow)]
+struct HistoryRecord {
+    id: i64,
+    parents: Json<Vec<i64>>,
+    item: String,
+    status: String,
+    message: Option<String>,
+}
+
+#[derive(Debug, FromRow, Serialize)]
+#[serde(bound(serialize = ""))]
+pub struct FailedRecord<T: QueueMessage> {
+    pub id: i64,
+    pub parents: Json<Vec<i64>>,
+    pub item: Json<Op<T>>,
+    pub message: String,
+}
+
+/// The result of [`SqliteQueue::requeue_failed`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RequeueFailedResult {
+    /// The ids of the items that were moved back into the queue.
+    pub requeued: Vec<i64>,
+    /// The ids of the items that could not be decoded, and as such were left in the failed table.
+    pub undecodable: Vec<i64>,
+}
+
+impl<T: QueueMessage> SqliteQueue<T> {
+    /// Query the failed items, filtered by the provided SQL `LIKE` filters.
+    ///
+    /// Note that the item filters are run on the item as it is stored, which is fully compact
+    /// JSON (i.e. `{"a":{"b":"c"}}`).
+    pub async fn query_failed(
+        &self,
+        page: i64,
+        per_page: i64,
+        item_filters: Vec<String>,
+        message_filters: Vec<String>,
+    ) -> Result<Vec<FailedRecord<T>>, sqlx::Error> {
+        sqlx::query(
+            r#"
+            SELECT
+                id,
+                parents,
+                item,
+                message
+            FROM
+                failed
+            WHERE
+                EXISTS (SELECT 1 FROM json_each(?1) WHERE failed.item LIKE json_each.value)
+                AND EXISTS (SELECT 1 FROM json_each(?2) WHERE failed.message LIKE json_each.value)
+            ORDER BY
+                id DESC
+            LIMIT
+                ?3
+            OFFSET
+                ?4
+            "#,
+        )
+        .bind(like_any(item_filters))
+        .bind(like_any(message_filters))
+        .bind(per_page)
+        .bind((page - 1) * per_page)
+        .map(|row| FailedRecord::<T>::from_row(&row))
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .collect()
+    }
+
+    pub async fn query_failed_by_id(
+        &self,
+        id: i64,
+    ) -> Result<Option<FailedRecord<T>>, sqlx::Error> {
+        sqlx::query(
+            r#"
+            SELECT
+               id,
+               parents,
+               item,
+               message
+            FROM
+               failed
+            WHERE
+               id = ?1
+            "#,
+        )
+        .bind(id)
+        .map(|row| FailedRecord::<T>::from_row(&row))
+        .fetch_optional(&self.client)
+        .await?
+        .transpose()
+    }
+
+    /// Move all failed items matching the provided filters back into the queue.
+    ///
+    /// The items keep their original id and parents, and their attempt counter is reset. Items
+    /// that can no longer be decoded (for example after a change to the message format) are left
+    /// in the failed table, and their ids are returned separately.
+    pub async fn requeue_failed(
+        &self,
+        item_filters: Vec<String>,
+        message_filters: Vec<String>,
+    ) -> Result<RequeueFailedResult, sqlx::Error> {
+        let mut tx = self.client.begin().await?;
+
+        let records = sqlx::query(
+            r#"
+            SELECT
+                id,
+                parents,
+                item,
+                created_at
+            FROM
+                failed
+            WHERE
+                EXISTS (SELECT 1 FROM json_each(?1) WHERE failed.item LIKE json_each.value)
+                AND EXISTS (SELECT 1 FROM json_each(?2) WHERE failed.message LIKE json_each.value)
+            ORDER BY
+                id ASC
+            "#,
+        )
+        .bind(like_any(item_filters))
+        .bind(like_any(message_filters))
+        .try_map(|row| FailedRequeueRecord::from_row(&row))
+        .fetch_all(tx.as_mut())
+        .await?;
+
+        let mut ids = vec![];
+        let mut undecodable = vec![];
+
+        for record in records {
+            // the schedule is derived from the op, so it must be decoded to be requeued
+            let schedule = match de::<Op<T>>(&record.item) {
+                Ok(op) => T::schedule(&op),
+                Err(error) => {
+                    warn!(
+                        id = record.id,
+                        %error,
+                        "unable to decode failed item, it will not be requeued"
+                    );
+                    undecodable.push(record.id);
+                    continue;
+                }
+            };
+
+            sqlx::query(
+                "
+                INSERT INTO
+                queue  (id, item, parents, created_at, handle_at, priority, chain)
+                VALUES (?1, ?2,   ?3,      ?4,         ?5,        ?6,       ?7   )
+                ",
+            )
+            .bind(record.id)
+            .bind(record.item)
+            .bind(record.parents)
+            .bind(record.created_at)
+            .bind(now_millis())
+            .bind(schedule.priority.as_i16())
+            .bind(schedule.chain)
+            .execute(tx.as_mut())
+            .await?;
+
+            ids.push(record.id);
+        }
+
+        sqlx::query("DELETE FROM failed WHERE id IN (SELECT value FROM json_each(?1))")
+            .bind(Json(&ids))
+            .execute(tx.as_mut())
+            .await?;
+
+        tx.commit().await?;
+
+        for id in &ids {
+            debug!(id, "requeued failed item");
+        }
+
+        Ok(RequeueFailedResult {
+            requeued: ids,
+            undecodable,
+        })
+    }
+
+    /// Delete all failed items matching the provided filters, returning the ids of the deleted
+    /// items.
+    pub async fn delete_failed(
+        &self,
+        item_filters: Vec<String>,
+        message_filters: Vec<String>,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        let mut tx = self.client.begin().await?;
+
+        let ids = sqlx::query(
+            r#"
+            DELETE FROM
+                failed
+            WHERE
+                EXISTS (SELECT 1 FROM json_each(?1) WHERE failed.item LIKE json_each.value)
+                AND EXISTS (SELECT 1 FROM json_each(?2) WHERE failed.message LIKE json_each.value)
+            RETURNING
+                id
+            "#,
+        )
+        .bind(like_any(item_filters))
+        .bind(like_any(message_filters))
+        .try_map(|row| Id::from_row(&row))
+        .fetch_all(tx.as_mut())
+        .await?
+        .into_iter()
+        .map(|id| id.id)
+        .collect::<Vec<_>>();
+
+        delete_parents(&mut tx, &ids).await?;
+
+        tx.commit().await?;
+
+        for id in &ids {
+            debug!(id, "deleted failed item");
+        }
+
+        Ok(ids)
+    }
+
+    /// Open the queue without releasing any claimed items. This is what should be used by anything
+    /// other than the process that owns the queue (i.e. the CLI), since releasing the claims of a
+    /// running instance would cause it's in-flight items to be processed twice.
+    ///
+    /// [`Queue::new`](voyager_vm::Queue::new) opens the queue and then releases all claims.
+    pub async fn open(config: SqliteQueueConfig) -> Result<Self, sqlx::Error> {
+        let optimize_batch_limit = config.optimize_batch_limit;
+        let retryable_error_expo_backoff_multiplier =
+            config.retryable_error_expo_backoff_multiplier;
+        let retryable_error_expo_backoff_max = config.retryable_error_expo_backoff_max;
+        let retry_policies = config.retry_policies.clone();
+        let scheduler = FairScheduler::new(config.scheduling.clone());
+
+        let pool = config.into_sqlite_pool().await?;
+
+        pool.execute_many(
+            r#"
+            CREATE TABLE IF NOT EXISTS
+              id_seq (
+                id INTEGER NOT NULL
+              );
+
+            INSERT INTO id_seq (id) SELECT 0 WHERE NOT EXISTS (SELECT 1 FROM id_seq);
+
+            CREATE TABLE IF NOT EXISTS
+              queue (
+                id INTEGER PRIMARY KEY,
+                item TEXT NOT NULL,
+                parents TEXT NOT NULL DEFAULT '[]',
+                created_at INTEGER NOT NULL,
+                handle_at INTEGER NOT NULL,
+                attempt INTEGER NOT NULL DEFAULT 0,
+                priority INTEGER NOT NULL DEFAULT 1,
+                chain TEXT,
+                claimed INTEGER NOT NULL DEFAULT FALSE
+              );
+
+            CREATE TABLE IF NOT EXISTS
+              optimize (
+                id INTEGER PRIMARY KEY,
+                item TEXT NOT NULL,
+                tag TEXT NOT NULL,
+                parents TEXT NOT NULL DEFAULT '[]',
+                created_at INTEGER NOT NULL,
+                claimed INTEGER NOT NULL DEFAULT FALSE
+              );
+
+            CREATE TABLE IF NOT EXISTS
+              done (
+                id INTEGER NOT NULL,
+                item TEXT NOT NULL,
+                parents TEXT NOT NULL DEFAULT '[]',
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (id, created_at)
+              );
+
+            CREATE TABLE IF NOT EXISTS
+              failed (
+                id INTEGER PRIMARY KEY,
+                item TEXT NOT NULL,
+                parents TEXT NOT NULL DEFAULT '[]',
+                message TEXT,
+                created_at INTEGER NOT NULL
+              );
+
+            CREATE INDEX IF NOT EXISTS index_queue_handle_at ON queue (claimed, handle_at ASC);
+
+            CREATE INDEX IF NOT EXISTS index_queue_chain_priority ON queue (claimed, COALESCE(chain, ''), priority, handle_at ASC);
+
+            CREATE INDEX IF NOT EXISTS index_optimize_tag ON optimize (tag, claimed, id ASC);
+
+            -- the parents of every item, regardless of which table it is currently in, so that the
+            -- history of an item can be traversed in both directions without scanning the json
+            -- parents of all items
+            CREATE TABLE IF NOT EXISTS
+              item_parents (
+                id INTEGER NOT NULL,
+                parent INTEGER NOT NULL,
+                PRIMARY KEY (id, parent)
+              );
+
+            CREATE INDEX IF NOT EXISTS index_item_parents_parent ON item_parents (parent, id);
+
+            -- backfill the parents of queues created before the item_parents table existed
+            INSERT OR IGNORE INTO
+              item_parents (id, parent)
+            SELECT
+              items.id, parent.value
+            FROM
+              (
+                SELECT id, parents FROM queue
+                UNION ALL
+                SELECT id, parents FROM optimize
+                UNION ALL
+                SELECT id, parents FROM done
+                UNION ALL
+                SELECT id, parents FROM failed
+              ) AS items,
+              json_each(items.parents) AS parent
+            WHERE
+              NOT EXISTS (SELECT 1 FROM item_parents);
+            "#,
+        )
+        .try_for_each(|result| async move {
+            trace!("rows affected: {}", result.rows_affected());
+            Ok(())
+        })
+        .instrument(info_span!("init"))
+        .await?;
+
+        Ok(Self {
+            client: pool,
+            optimize_batch_limit,
+            retryable_error_expo_backoff_max,
+            retryable_error_expo_backoff_multiplier,
+            retry_policies,
+            scheduler,
+            metrics: Metrics::new(),
+            __marker: PhantomData,
+        })
+    }
+}
+
+impl<T: QueueMessage> voyager_vm::Queue<T> for SqliteQueue<T> {
+    type Config = SqliteQueueConfig;
+    type Error = sqlx::Error;
+
+    async fn new(config: Self::Config) -> Result<Self, Self::Error> {
+        let queue = Self::open(config).await?;
+
+        // this is the process that owns the queue, so any claimed items were claimed by a previous
+        // instance that didn't shut down cleanly
+        queue
+            .client
+            .execute_many(
+                "
+                UPDATE queue SET claimed = FALSE WHERE claimed;
+
+                UPDATE optimize SET claimed = FALSE WHERE claimed;
+                ",
+            )
+            .try_for_each(|result| async move {
+                debug!("released {} claimed items", result.rows_affected());
+                Ok(())
+            })
+            .instrument(info_span!("release_claims"))
+            .await?;
+
+        Ok(queue)
+    }
+
+    async fn enqueue<'a, Filter: InterestFilter<T>>(
+        &'a self,
+        op: Op<T>,
+        filter: &'a Filter,
+    ) -> Result<EnqueueResult, Self::Error> {
+        trace!("enqueue");
+
+        let (optimize, ready): (Vec<_>, Vec<_>) =
+            op.normalize()
+                .into_iter()
+                .partition_map(|op| match filter.check_interest(&op) {
+                    FilterResult::Interest(interest) => Either::Left((op, interest)),
+                    FilterResult::NoInterest => Either::Right(op),
+                });
+
+        let mut tx = self.client.begin().await?;
+
+        let mut ready_ids = vec![];
+
+        for op in ready {
+            let id = insert_queue(&mut tx, &[], &op).await?;
+            debug!(id, "enqueued ready item");
+            ready_ids.push(id);
+        }
+
+        let mut optimize_further_ids = vec![];
+
+        for (op, interest) in optimize {
+            for tag in interest.tags {
+                let id = insert_optimize(&mut tx, &[], &op, tag).await?;
+                debug!(id, "enqueued optimize item");
+                optimize_further_ids.push(id);
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(EnqueueResult {
+            queue: ready_ids
+                .into_iter()
+                .map(|id| ItemId::new(id).expect("invalid id returned from database"))
+                .collect(),
+            optimize: optimize_further_ids
+                .into_iter()
+                .map(|id| ItemId::new(id).expect("invalid id returned from database"))
+                .collect(),
+        })
+    }
+
+    #[instrument(skip_all)]
+    async fn process<'a, F, Fut, R, Filter>(
+        &'a self,
+        filter: &'a Filter,
+        f: F,
+    ) -> Result<Option<R>, Self::Error>
+    where
+        F: (FnOnce(Op<T>, ItemId) -> Fut) + Send + Captures<'a>,
+        Fut: Future<Output = (R, Result<Vec<Op<T>>, QueueError>)> + Send + Captures<'a>,
+        R: Send + Sync + 'static,
+        Filter: InterestFilter<T>,
+    {
+        trace!("process");
+
+        let (virtual_times, system_virtual_time) = self.scheduler.virtual_times();
+
+        // claim the item in it's own transaction, sqlite only allows for a single writer at a time
+        // so the item can't be locked for the entire duration of processing it
+        //
+        // items are ordered by priority class (if enabled, raised by one class for every aging
+        // interval that the item has been ready for), then by the virtual time of their chain, and
+        // finally by the time they became ready
+        //
+        // ordering all ready items this way can't be done with an index, so instead only the first
+        // ready item of each (chain, priority) pair is considered, all of which can be found with
+        // `index_queue_chain_priority` (the chains are found with a loose index scan)
+        let row = sqlx::query(
+            r#"
+            WITH RECURSIVE chains(chain) AS (
+              SELECT
+                (
+                  SELECT
+                    COALESCE(chain, '')
+                  FROM
+                    queue
+                  WHERE
+                    claimed = FALSE
+                  ORDER BY
+                    COALESCE(chain, '') ASC
+                  LIMIT 1)
+              UNION ALL
+              SELECT
+                (
+                  SELECT
+                    COALESCE(queue.chain, '')
+                  FROM
+                    queue
+                  WHERE
+                    claimed = FALSE
+                    AND COALESCE(queue.chain, '') > chains.chain
+                  ORDER BY
+                    COALESCE(queue.chain, '') ASC
+                  LIMIT 1)
+              FROM
+                chains
+              WHERE
+                chains.chain IS NOT NULL
+            ),
+            candidates(id) AS (
+              SELECT
+                (
+                  SELECT
+                    queue.id
+                  FROM
+                    queue
+                  WHERE
+                    claimed = FALSE
+                    AND COALESCE(queue.chain, '') = chains.chain
+                    AND queue.priority = priorities.value
+                    AND queue.handle_at <= ?1
+                  ORDER BY
+                    queue.handle_at ASC
+                  LIMIT 1)
+              FROM
+                chains,
+                json_each(?5) AS priorities
+              WHERE
+                chains.chain IS NOT NULL
+            )
+            UPDATE
+              queue
+            SET
+              claimed = TRUE
+            WHERE
+              id = (
+                SELECT
+                  queue.id
+                FROM
+                  candidates
+                  JOIN queue ON queue.id = candidates.id
+                  LEFT JOIN json_each(?2) AS vt ON vt.key = COALESCE(queue.chain, '')
+                ORDER BY
+                  CASE WHEN ?4 THEN MIN(?6, queue.priority + (?1 - queue.handle_at) / ?7) ELSE 0 END DESC,
+                  MAX(COALESCE(vt.value, ?3), ?3) ASC,
+                  queue.handle_at ASC,
+                  queue.id ASC
+                LIMIT 1)
+            RETURNING
+              id,
+              parents,
+              item,
+              attempt,
+              created_at,
+              chain
+            "#,
+        )
+        .bind(now_millis())
+        .bind(Json(virtual_times))
+        .bind(system_virtual_time)
+        .bind(self.scheduler.prioritize())
+        .bind(Json(Priority::ALL.map(Priority::as_i16)))
+        .bind(Priority::Urgent.as_i16())
+        .bind(i64::try_from(self.scheduler.priority_aging().as_millis()).unwrap_or(i64::MAX))
+        .try_map(|x| QueueRecord::from_row(&x))
+        .fetch_optional(&self.client)
+        .await?;
+
+        match row {
+            Some(record) => {
+                self.scheduler
+                    .record(record.chain.as_deref().unwrap_or_default());
+
+                process_item(self, record, f, filter).await
+            }
+            None => Ok(None),
+        }
+    }
+
+    #[instrument(skip_all, fields(%tag))]
+    async fn optimize<'a, O, Filter>(
+        &'a self,
+        tag: &'a str,
+        filter: &'a Filter,
+        optimizer: &'a O,
+    ) -> Result<(), Either<Self::Error, O::Error>>
+    where
+        O: Pass<T>,
+        Filter: InterestFilter<T>,
+    {
+        trace!(%tag, "optimize");
+
+        let mut msgs = sqlx::query(
+            r#"
+            UPDATE
+              optimize
+            SET
+              claimed = TRUE
+            WHERE
+              id IN (
+                SELECT
+                  id
+                FROM
+                  optimize
+                WHERE
+                  tag = ?1
+                  AND NOT claimed
+                ORDER BY
+                  id ASC
+                LIMIT ?2)
+            RETURNING
+              id,
+              item
+            "#,
+        )
+        .bind(tag)
+        // a negative limit is no limit in sqlite
+        .bind(self.optimize_batch_limit.unwrap_or(-1))
+        .try_map(|x| OptimizeRecord::from_row(&x))
+        .fetch_all(&self.client)
+        .await
+        .map_err(Either::Left)?;
+
+        if msgs.is_empty() {
+            trace!("optimizer queue is empty");
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            return Ok(());
+        }
+
+        // the order of RETURNING is unspecified in sqlite
+        msgs.sort_unstable_by_key(|r| r.id);
+
+        let (ids, msgs) = msgs
+            .into_iter()
+            .map(|r| {
+                Ok((
+                    r.id,
+                    de(&r.item).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                ))
+            })
+            .collect::<Result<(Vec<_>, Vec<_>), sqlx::Error>>()
+            .map_err(Either::Left)?;
+
+        self.metrics.optimize_item_count.record(
+            msgs.len() as u64,
+            &[KeyValue::new("tag".to_owned(), tag.to_owned())],
+        );
+
+        let now = std::time::Instant::now();
+
+        let res = optimizer
+            .run_pass(msgs.clone())
+            .instrument(debug_span!(
+                "optimizing items",
+                ids = ids
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ))
+            .await;
+
+        let PassResult {
+            optimize_further,
+            ready,
+        } = match res {
+            Ok(res) => res,
+            Err(error) => {
+                sqlx::query(
+                    "UPDATE optimize SET claimed = FALSE WHERE id IN (SELECT value FROM json_each(?1))",
+                )
+                .bind(Json(&ids))
+                .execute(&self.client)
+                .await
+                .map_err(Either::Left)?;
+
+                return Err(Either::Right(error));
+            }
+        };
+
+        self.metrics.optimize_processing_duration.record(
+            Instant::now().duration_since(now).as_secs_f64(),
+            &[KeyValue::new("tag".to_owned(), tag.to_owned())],
+        );
+
+        trace!(
+            ready = ready.len(),
+            optimize_further = optimize_further.len(),
+            "optimized items"
+        );
+
+        let get_parent_ids = |parent_idxs: &[usize]| {
+            ids.iter()
+                .enumerate()
+                .filter_map(|(idx, id)| parent_idxs.contains(&idx).then_some(*id))
+                .collect::<Vec<_>>()
+        };
+
+        let mut tx = self.client.begin().await.map_err(Either::Left)?;
+
+        sqlx::query("DELETE FROM optimize WHERE id IN (SELECT value FROM json_each(?1))")
+            .bind(Json(&ids))
+            .execute(tx.as_mut())
+            .await
+            .map_err(Either::Left)?;
+
+        delete_parents(&mut tx, &ids).await.map_err(Either::Left)?;
+
+        for (parent_idxs, new_msg, tag) in optimize_further {
+            let parents = get_parent_ids(&parent_idxs);
+            trace!(parent_idxs = ?&parent_idxs, parents = ?&parents);
+
+            let id = insert_optimize(&mut tx, &parents, &new_msg, &tag)
+                .await
+                .map_err(Either::Left)?;
+
+            debug!(id, "inserted new optimizer message");
+        }
+
+        for (parent_idxs, op) in ready {
+            let parents = get_parent_ids(&parent_idxs);
+            trace!(parent_idxs = ?&parent_idxs, parents = ?&parents);
+
+            'block: for op in op.normalize() {
+                match filter.check_interest(&op) {
+                    FilterResult::Interest(Interest { tags, remove }) => {
+                        for tag in tags {
+                            let id = insert_optimize(&mut tx, &parents, &op, tag)
+                                .await
+                                .map_err(Either::Left)?;
+
+                            debug!(id, "inserted new optimizer message");
+                        }
+
+                        if remove {
+                            break 'block;
+                        }
+                    }
+                    FilterResult::NoInterest => {}
+                }
+
+                let id = insert_queue(&mut tx, &parents, &op)
+                    .await
+                    .map_err(Either::Left)?;
+
+                debug!(id, "enqueued ready item");
+            }
+        }
+
+        tx.commit().await.map_err(Either::Left)?;
+
+        Ok(())
+    }
+
+    #[instrument(skip_all, fields(id = id.raw(), %max_depth))]
+    async fn history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> Result<Vec<HistoryItem<T>>, Self::Error> {
+        trace!("history");
+
+        sqlx::query(
+            r#"
+            WITH RECURSIVE
+              ancestors (id, depth) AS (
+                SELECT ?1, 0
+                UNION
+                SELECT
+                  item_parents.parent, ancestors.depth + 1
+                FROM
+                  ancestors
+                  JOIN item_parents ON item_parents.id = ancestors.id
+                WHERE
+                  ancestors.depth < ?2
+              ),
+              descendants (id, depth) AS (
+                SELECT ?1, 0
+                UNION
+                SELECT
+                  item_parents.id, descendants.depth + 1
+                FROM
+                  descendants
+                  JOIN item_parents ON item_parents.parent = descendants.id
+                WHERE
+                  descendants.depth < ?2
+              ),
+              related AS (
+                SELECT id FROM ancestors UNION SELECT id FROM descendants
+              )
+            SELECT id, parents, item, 'queued' AS status, NULL AS message
+            FROM queue WHERE id IN (SELECT id FROM related)
+            UNION ALL
+            SELECT id, parents, item, 'optimize' AS status, NULL AS message
+            FROM optimize WHERE id IN (SELECT id FROM related)
+            UNION ALL
+            SELECT id, parents, item, 'done' AS status, NULL AS message
+            FROM done WHERE id IN (SELECT id FROM related)
+            UNION ALL
+            SELECT id, parents, item, 'failed' AS status, message
+            FROM failed WHERE id IN (SELECT id FROM related)
+            ORDER BY
+              id ASC
+            "#,
+        )
+        .bind(id.raw())
+        .bind(i64::from(max_depth))
+        .try_map(|row| HistoryRecord::from_row(&row))
+        .fetch_all(&self.client)
+        .await?
+        .into_iter()
+        .map(|record| {
+            let decode = |e| sqlx::Error::Decode(Box::new(e));
+
+            Ok(HistoryItem {
+                id: ItemId::new(record.id).map_err(decode)?,
+                parents: record
+                    .parents
+                    .0
+                    .into_iter()
+                    .map(|parent| ItemId::new(parent).map_err(decode))
+                    .collect::<Result<_, _>>()?,
+                status: match &*record.status {
+                    "queued" => ItemStatus::Queued,
+                    "optimize" => ItemStatus::Optimize,
+                    "done" => ItemStatus::Done,
+                    "failed" => ItemStatus::Failed,
+                    status => unreachable!("unknown status {status}"),
+                },
+                op: de(&record.item).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                message: record.message,
+            })
+        })
+        .collect()
+    }
+}
+
+#[instrument(
+    skip_all,
+    fields(
+        item_id = record.id,
+        attempt = record.attempt
+    )
+)]
+async fn process_item<'a, T, F, Fut, R, Filter>(
+    queue: &SqliteQueue<T>,
+    record: QueueRecord,
+    f: F,
+    filter: &'a Filter,
+) -> Result<Option<R>, sqlx::Error>
+where
+    T: QueueMessage,
+    F: (FnOnce(Op<T>, ItemId) -> Fut) + Send + Captures<'a>,
+    Fut: Future<Output = (R, Result<Vec<Op<T>>, QueueError>)> + Send + Captures<'a>,
+    R: Send + Sync + 'static,
+    Filter: InterestFilter<T>,
+{
+    trace!(%record.item);
+
+    let metrics = &queue.metrics;
+
+    let op = de::<Op<T>>(&record.item).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+    let now = std::time::Instant::now();
+    let (r, res) = f(op.clone(), ItemId::new(record.id).unwrap()).await;
+    metrics
+        .item_processing_duration
+        .record(Instant::now().duration_since(now).as_secs_f64(), &[]);
+
+    let mut tx = queue.client.begin().await?;
+
+    match res {
+        Err(error) => {
+            let (error_class, error) = match error {
+                QueueError::Fatal(error) => (ErrorClass::Fatal, full_error_string(error)),
+                QueueError::Unprocessable(error) => {
+                    (ErrorClass::Unprocessable, full_error_string(error))
+                }
+                QueueError::Retry(error) => (ErrorClass::Retry, full_error_string(error)),
+            };
+
+            let policy =
+                find_retry_policy(&queue.retry_policies, error_class, &error, &mut tx).await?;
+
+            let retry = RetryPolicy::retry_backoff(
+                policy,
+                error_class,
+                record.attempt,
+                (
+                    queue.retryable_error_expo_backoff_max,
+                    queue.retryable_error_expo_backoff_multiplier,
+                ),
+            );
+
+            match (retry, error_class) {
+                (Some((backoff_max, backoff_multiplier)), _) => {
+                    warn!(%error, ?error_class, "retryable error");
+
+                    let backoff = Duration::try_from_secs_f64(
+                        (record.attempt as f64)
+                            .powf(backoff_multiplier)
+                            .clamp(f64::MIN, backoff_max),
+                    )
+                    .unwrap_or(Duration::MAX);
+
+                    sqlx::query(
+                        "
+                        UPDATE
+                          queue
+                        SET
+                          claimed = FALSE,
+                          attempt = ?2,
+                          handle_at = ?3
+                        WHERE
+                          id = ?1
+                        ",
+                    )
+                    .bind(record.id)
+                    .bind(record.attempt.saturating_add(1))
+                    .bind(
+                        now_millis()
+                            .saturating_add(backoff.as_millis().try_into().unwrap_or(i64::MAX)),
+                    )
+                    .execute(tx.as_mut())
+                    .await?;
+
+                    metrics.retryable_errors_count.add(1, &[]);
+                }
+                (None, ErrorClass::Fatal) => {
+                    error!(%error, "fatal error");
+                    insert_error(record, error, &mut tx).await?;
+                    metrics.fatal_errors_count.add(1, &[]);
+                }
+                (None, ErrorClass::Unprocessable) => {
+                    info!(%error, "unprocessable message");
+                    insert_error(record, error, &mut tx).await?;
+                    metrics.unprocessable_count.add(1, &[]);
+                }
+                (None, ErrorClass::Retry) => {
+                    error!(
+                        %error,
+                        attempt = record.attempt,
+                        "retryable error, max attempts reached"
+                    );
+                    insert_error(record, error, &mut tx).await?;
+                    metrics.retryable_errors_count.add(1, &[]);
+                }
+            }
+        }
+        Ok(ops) => {
+            sqlx::query("DELETE FROM queue WHERE id = ?1")
+                .bind(record.id)
+                .execute(tx.as_mut())
+                .await?;
+
+            // insert the op we just processed into done
+            sqlx::query(
+                "
+                INSERT INTO
+                done   (id, parents, item, created_at)
+                VALUES (?1, ?2,      ?3,   ?4        )
+                ",
+            )
+            .bind(record.id)
+            .bind(record.parents)
+            .bind(record.item)
+            .bind(record.created_at)
+            .execute(tx.as_mut())
+            .await?;
+
+            for op in ops.into_iter().flat_map(Op::normalize) {
+                match filter.check_interest(&op) {
+                    FilterResult::Interest(interest) => {
+                        for tag in interest.tags {
+                            insert_optimize(&mut tx, &[record.id], &op, tag).await?;
+                        }
+                    }
+                    FilterResult::NoInterest => {
+                        insert_queue(&mut tx, &[record.id], &op).await?;
+                    }
+                }
+            }
+
+            metrics.processed_item_count.add(1, &[]);
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(Some(r))
+}
+
+/// Find the first retry policy that applies to an error of the given class and message.
+async fn find_retry_policy<'a>(
+    retry_policies: &'a [RetryPolicy],
+    error_class: ErrorClass,
+    error: &str,
+    tx: &mut Transaction<'static, Sqlite>,
+) -> Result<Option<&'a RetryPolicy>, sqlx::Error> {
+    for policy in retry_policies {
+        if policy.error_class != error_class {
+            continue;
+        }
+
+        if policy.message_filters.is_empty() {
+            return Ok(Some(policy));
+        }
+
+        // use sqlite for the matching, so that the filters have the exact same semantics as the
+        // filters used when querying the failed table
+        let matches = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS (SELECT 1 FROM json_each(?2) WHERE ?1 LIKE json_each.value)",
+        )
+        .bind(error)
+        .bind(Json(&policy.message_filters))
+        .fetch_one(tx.as_mut())
+        .await?;
+
+        if matches {
+            return Ok(Some(policy));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Allocate a new id, shared between the `queue` and `optimize` tables.
+async fn next_id(tx: &mut Transaction<'static, Sqlite>) -> Result<i64, sqlx::Error> {
+    sqlx::query("UPDATE id_seq SET id = id + 1 RETURNING id")
+        .try_map(|row| Id::from_row(&row))
+        .fetch_one(tx.as_mut())
+        .await
+        .map(|id| id.id)
+}
+
+async fn insert_queue<T: QueueMessage>(
+    tx: &mut Transaction<'static, Sqlite>,
+    parents: &[i64],
+    op: &Op<T>,
+) -> Result<i64, sqlx::Error> {
+    let id = next_id(tx).await?;
+    let now = now_millis();
+    let schedule = T::schedule(op);
+
+    sqlx::query(
+        "
+        INSERT INTO
+        queue  (id, item, parents, created_at, handle_at, priority, chain)
+        VALUES (?1, ?2,   ?3,      ?4,         ?4,        ?5,       ?6   )
+        ",
+    )
+    .bind(id)
+    .bind(Json(op))
+    .bind(Json(parents))
+    .bind(now)
+    .bind(schedule.priority.as_i16())
+    .bind(schedule.chain)
+    .execute(tx.as_mut())
+    .await?;
+
+    insert_parents(tx, id, parents).await?;
+
+    Ok(id)
+}
+
+async fn insert_optimize<T: QueueMessage>(
+    tx: &mut Transaction<'static, Sqlite>,
+    parents: &[i64],
+    op: &Op<T>,
+    tag: &str,
+) -> Result<i64, sqlx::Error> {
+    let id = next_id(tx).await?;
+
+    sqlx::query(
+        "
+        INSERT INTO
+        optimize (id, item, tag, parents, created_at)
+        VALUES   (?1, ?2,   ?3,  ?4,      ?5        )
+        ",
+    )
+    .bind(id)
+    .bind(Json(op))
+    .bind(tag)
+    .bind(Json(parents))
+    .bind(now_millis())
+    .execute(tx.as_mut())
+    .await?;
+
+    insert_parents(tx, id, parents).await?;
+
+    Ok(id)
+}
+
+async fn insert_parents(
+    tx: &mut Transaction<'static, Sqlite>,
+    id: i64,
+    parents: &[i64],
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "
+        INSERT OR IGNORE INTO
+        item_parents (id, parent)
+        SELECT ?1, value FROM json_each(?2)
+        ",
+    )
+    .bind(id)
+    .bind(Json(parents))
+    .execute(tx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+/// Delete the parents of items that have been removed from the queue entirely, so that the history
+/// of their descendants doesn't traverse through them.
+async fn delete_parents(
+    tx: &mut Transaction<'static, Sqlite>,
+    ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM item_parents WHERE id IN (SELECT value FROM json_each(?1))")
+        .bind(Json(ids))
+        .execute(tx.as_mut())
+        .await?;
+
+    Ok(())
+}
+
+async fn insert_error(
+    record: QueueRecord,
+    error: String,
+    tx: &mut Transaction<'static, Sqlite>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM queue WHERE id = ?1")
+        .bind(record.id)
+        .execute(tx.as_mut())
+        .await?;
+
+    // insert error message and the op into failed
+    sqlx::query(
+        r#"
+        INSERT INTO
+        failed (id, parents, item, created_at, message)
+        VALUES (?1, ?2,      ?3,   ?4,         ?5     )
+        "#,
+    )
+    .bind(record.id)
+    .bind(record.parents)
+    .bind(record.item)
+    .bind(record.created_at)
+    .bind(error)
+    .execute(tx.as_mut())
+    .await?;
+
+    Ok(())
+}
+
+/// Encode a list of `LIKE` filters as a JSON array, to be used with `json_each`. This defaults to
+/// an all-inclusive filter if none are provided.
+fn like_any(mut filters: Vec<String>) -> Json<Vec<String>> {
+    if filters.is_empty() {
+        filters.push("%".to_owned());
+    }
+
+    Json(filters)
+}
+
+fn now_millis() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time is before the unix epoch")
+        .as_millis()
+        .try_into()
+        .expect("system time is too far in the future")
+}
+
+// copied from unionlabs::ErrorReporter
+fn full_error_string(error: BoxDynError) -> String {
+    let mut s = String::new();
+
+    write!(s, "{}", error).unwrap();
+
+    for e in core::iter::successors(error.source(), |e| (*e).source()) {
+        write!(s, ": {e}").unwrap();
+    }
+
+    s
+}
+
+fn de<T: DeserializeOwned>(s: &str) -> Result<T, serde_json::Error> {
+    let mut deserializer = serde_json::Deserializer::from_str(s);
+    deserializer.disable_recursion_limit();
+    let json = T::deserialize(&mut deserializer)?;
+    Ok(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use voyager_vm::{call, data, Queue};
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum TestMessage {}
+
+    impl QueueMessage for TestMessage {
+        type Data = u64;
+        type Call = u64;
+        type Callback = u64;
+    }
+
+    fn config(database_url: &str) -> SqliteQueueConfig {
+        SqliteQueueConfig {
+            database_url: database_url.to_owned(),
+            // the in-memory database is not shared between connections
+            max_connections: 1,
+            busy_timeout: None,
+            optimize_batch_limit: None,
+            retryable_error_expo_backoff_max: default_retryable_error_expo_backoff_max(),
+            retryable_error_expo_backoff_multiplier:
+                default_retryable_error_expo_backoff_multiplier(),
+            retry_policies: vec![],
+            scheduling: Default::default(),
+        }
+    }
+
+    async fn queue() -> SqliteQueue<TestMessage> {
+        SqliteQueue::new(config("sqlite::memory:")).await.unwrap()
+    }
+
+    async fn claimed(queue: &SqliteQueue<TestMessage>) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM queue WHERE claimed")
+            .fetch_one(&queue.client)
+            .await
+            .unwrap()
+    }
+
+    fn id(id: i64) -> ItemId {
+        ItemId::new(id).unwrap()
+    }
+
+    #[tokio::test]
+    async fn process_ok() {
+        let queue = queue().await;
+
+        let res = queue.enqueue(call(1), &()).await.unwrap();
+        assert_eq!(res.queue, vec![id(1)]);
+
+        let processed = queue
+            .process(&(), async |op, item_id| {
+                assert_eq!(op, call(1));
+                assert_eq!(item_id, id(1));
+
+                ((), Ok(vec![call(2), data(3)]))
+            })
+            .await
+            .unwrap();
+        assert_eq!(processed, Some(()));
+
+        assert_eq!(
+            queue.history(id(1), 10).await.unwrap(),
+            vec![
+                HistoryItem {
+                    id: id(1),
+                    parents: vec![],
+                    status: ItemStatus::Done,
+                    op: call(1),
+                    message: None,
+                },
+                HistoryItem {
+                    id: id(2),
+                    parents: vec![id(1)],
+                    status: ItemStatus::Queued,
+                    op: call(2),
+                    message: None,
+                },
+                HistoryItem {
+                    id: id(3),
+                    parents: vec![id(1)],
+                    status: ItemStatus::Queued,
+                    op: data(3),
+                    message: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn history_depth() {
+        let queue = queue().await;
+
+        queue.enqueue(call(1), &()).await.unwrap();
+
+        for next in 2..=4 {
+            queue
+                .process(&(), async |_, _| ((), Ok(vec![call(next)])))
+                .await
+                .unwrap();
+        }
+
+        let ids = async |item_id, max_depth| {
+            queue
+                .history(id(item_id), max_depth)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>()
+        };
+
+        // both ancestors and descendants are traversed
+        assert_eq!(ids(2, 1).await, vec![id(1), id(2), id(3)]);
+        assert_eq!(ids(2, 10).await, vec![id(1), id(2), id(3), id(4)]);
+        assert_eq!(ids(4, 2).await, vec![id(2), id(3), id(4)]);
+        assert_eq!(ids(1, 0).await, vec![id(1)]);
+    }
+
+    #[tokio::test]
+    async fn process_empty() {
+        let queue = queue().await;
+
+        let processed = queue
+            .process(&(), async |_, _| -> ((), Result<_, QueueError>) {
+                panic!("queue is empty")
+            })
+            .await
+            .unwrap();
+        assert_eq!(processed, None);
+    }
+
+    #[tokio::test]
+    async fn process_retry() {
+        let queue = queue().await;
+
+        queue.enqueue(call(1), &()).await.unwrap();
+
+        queue
+            .process(&(), async |_, _| {
+                ((), Err(QueueError::Retry("transient error".into())))
+            })
+            .await
+            .unwrap();
+
+        // first retry is not delayed
+        let processed = queue
+            .process(&(), async |_, _| {
+                ((), Err(QueueError::Retry("transient error".into())))
+            })
+            .await
+            .unwrap();
+        assert_eq!(processed, Some(()));
+
+        // second retry is delayed by 1 second
+        let processed = queue
+            .process(&(), async |_, _| -> ((), Result<_, QueueError>) {
+                panic!("item should not be ready yet")
+            })
+            .await
+            .unwrap();
+        assert_eq!(processed, None);
+    }
+
+    #[tokio::test]
+    async fn failed() {
+        let queue = queue().await;
+
+        queue.enqueue(call(1), &()).await.unwrap();
+        queue.enqueue(call(2), &()).await.unwrap();
+
+        for _ in 0..2 {
+            queue
+                .process(&(), async |op, _| {
+                    (
+                        (),
+                        Err(QueueError::Fatal(
+                            format!("bad op {}", serde_json::to_string(&op).unwrap()).into(),
+                        )),
+                    )
+                })
+                .await
+                .unwrap();
+        }
+
+        let failed = queue
+            .query_failed(1, 10, vec![], vec!["bad op%".to_owned()])
+            .await
+            .unwrap();
+        assert_eq!(failed.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 1]);
+
+        let failed = queue
+            .query_failed(1, 10, vec![r#"%"@value":2%"#.to_owned()], vec![])
+            .await
+            .unwrap();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].item.0, call(2));
+
+        assert_eq!(
+            queue.query_failed_by_id(1).await.unwrap().map(|r| r.item.0),
+            Some(call(1))
+        );
+
+        assert_eq!(
+            queue
+                .requeue_failed(vec![r#"%"@value":1%"#.to_owned()], vec![])
+                .await
+                .unwrap(),
+            RequeueFailedResult {
+                requeued: vec![1],
+                undecodable: vec![],
+            }
+        );
+
+        let processed = queue
+            .process(&(), async |op, item_id| {
+                assert_eq!(op, call(1));
+                assert_eq!(item_id, id(1));
+
+                ((), Ok(vec![]))
+            })
+            .await
+            .unwrap();
+        assert_eq!(processed, Some(()));
+
+        assert_eq!(queue.delete_failed(vec![], vec![]).await.unwrap(), vec![2]);
+        assert!(queue
+            .query_failed(1, 10, vec![], vec![])
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn requeue_failed_skips_undecodable_items() {
+        let queue = queue().await;
+
+        queue.enqueue(call(1), &()).await.unwrap();
+
+        queue
+            .process(&(), async |_, _| {
+                ((), Err(QueueError::Fatal("bad op".into())))
+            })
+            .await
+            .unwrap();
+
+        // an item in a format that is no longer supported
+        sqlx::query(
+            "INSERT INTO failed (id, item, message, created_at) VALUES (2, '{\"old\":2}', 'bad op', 0)",
+        )
+        .execute(&queue.client)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            queue.requeue_failed(vec![], vec![]).await.unwrap(),
+            RequeueFailedResult {
+                requeued: vec![1],
+                undecodable: vec![2],
+            }
+        );
+
+        // the undecodable item is left in the failed table
+        assert_eq!(
+            sqlx::query_scalar::<_, i64>("SELECT id FROM failed")
+                .fetch_all(&queue.client)
+                .await
+                .unwrap(),
+            vec![2]
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_policy() {
+        let queue = SqliteQueue::new(SqliteQueueConfig {
+            retry_policies: vec![RetryPolicy {
+                error_class: ErrorClass::Fatal,
+                message_filters: vec!["%connection reset%".to_owned()],
+                max_attempts: 2,
+                backoff_max: 0.0,
+                backoff_multiplier: 1.0,
+            }],
+            ..config("sqlite::memory:")
+        })
+        .await
+        .unwrap();
+
+        queue.enqueue(call(1), &()).await.unwrap();
+        queue.enqueue(call(2), &()).await.unwrap();
+
+        for _ in 0..2 {
+            queue
+                .process(&(), async |op, _| {
+                    let error = if op == call(1) {
+                        "rpc error: connection reset"
+                    } else {
+                        "invalid proof"
+                    };
+
+                    ((), Err(QueueError::Fatal(error.into())))
+                })
+                .await
+                .unwrap();
+        }
+
+        // the matching error is retried, the other one is not
+        let failed = queue.query_failed(1, 10, vec![], vec![]).await.unwrap();
+        assert_eq!(failed.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2]);
+
+        // the second attempt is the last one
+        let processed = queue
+            .process(&(), async |op, _| {
+                assert_eq!(op, call(1));
+
+                (
+                    (),
+                    Err(QueueError::Fatal("rpc error: connection reset".into())),
+                )
+            })
+            .await
+            .unwrap();
+        assert_eq!(processed, Some(()));
+
+        let failed = queue.query_failed(1, 10, vec![], vec![]).await.unwrap();
+        assert_eq!(failed.iter().map(|r| r.id).collect::<Vec<_>>(), vec![2, 1]);
+    }
+
+    #[tokio::test]
+    async fn only_new_releases_claims() {
+        let path = std::env::temp_dir().join(format!(
+            "sqlite-queue-claims-{}-{}.db",
+            std::process::id(),
+            now_millis()
+        ));
+        let config = config(&format!("sqlite://{}", path.display()));
+
+        let queue = SqliteQueue::new(config.clone()).await.unwrap();
+
+        queue.enqueue(call(1), &()).await.unwrap();
+
+        queue
+            .process(&(), async |_, _| {
+                assert_eq!(claimed(&queue).await, 1);
+
+                // i.e. the cli
+                let other = SqliteQueue::open(config.clone()).await.unwrap();
+                assert_eq!(claimed(&other).await, 1);
+
+                // i.e. a new instance, after the previous one crashed
+                let other = SqliteQueue::new(config.clone()).await.unwrap();
+                assert_eq!(claimed(&other).await, 0);
+
+                ((), Ok(vec![]))
+            })
+            .await
+            .unwrap();
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{suffix}", path.display()));
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum ScheduledMessage {}
+
+    impl QueueMessage for ScheduledMessage {
+        type Data = ();
+        type Call = (String, Priority);
+        type Callback = ();
+
+        fn schedule(op: &Op<Self>) -> voyager_vm::schedule::Schedule {
+            match op {
+                Op::Call((chain, priority)) => voyager_vm::schedule::Schedule {
+                    priority: *priority,
+                    chain: Some(chain.clone()),
+                },
+                _ => Default::default(),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn process_priority_and_fairness() {
+        let queue = SqliteQueue::<ScheduledMessage>::new(SqliteQueueConfig {
+            scheduling: SchedulingConfig {
+                prioritize: true,
+                ..Default::default()
+            },
+            ..config("sqlite::memory:")
+        })
+        .await
+        .unwrap();
+
+        let op = |chain: &str, priority| call((chain.to_owned(), priority));
+
+        for _ in 0..4 {
+            queue.enqueue(op("a", Priority::Normal), &()).await.unwrap();
+        }
+        for _ in 0..2 {
+            queue.enqueue(op("b", Priority::Normal), &()).await.unwrap();
+        }
+        queue
+            .enqueue(op("a", Priority::Backfill), &())
+            .await
+            .unwrap();
+        queue.enqueue(op("b", Priority::Urgent), &()).await.unwrap();
+        queue.enqueue(data(()), &()).await.unwrap();
+
+        let mut processed = vec![];
+
+        while let Some(op) = queue
+            .process(&(), async |op, _| (op, Ok(vec![])))
+            .await
+            .unwrap()
+        {
+            processed.push(op);
+        }
+
+        assert_eq!(
+            processed,
+            vec![
+                // urgent items are processed first, regardless of when they were queued
+                op("b", Priority::Urgent),
+                // chain b has already been scheduled once, so a gets to catch up
+                op("a", Priority::Normal),
+                // ops without a chain are scheduled as if they belonged to the same chain
+                data(()),
+                op("a", Priority::Normal),
+                op("b", Priority::Normal),
+                op("a", Priority::Normal),
+                op("b", Priority::Normal),
+                op("a", Priority::Normal),
+                op("a", Priority::Backfill),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn process_priority_aging() {
+        let queue = SqliteQueue::<ScheduledMessage>::new(config("sqlite::memory:"))
+            .await
+            .unwrap();
+
+        let op = |chain: &str, priority| call((chain.to_owned(), priority));
+
+        queue
+            .enqueue(op("a", Priority::Backfill), &())
+            .await
+            .unwrap();
+        for _ in 0..2 {
+            queue.enqueue(op("b", Priority::Urgent), &()).await.unwrap();
+        }
+
+        // the backfill item has been ready for two aging intervals, and is now scheduled as urgent
+        sqlx::query("UPDATE queue SET handle_at = handle_at - ?1 WHERE id = 1")
+            .bind(2 * 60 * 1000)
+            .execute(&queue.client)
+            .await
+            .unwrap();
+
+        let mut processed = vec![];
+
+        while let Some(op) = queue
+            .process(&(), async |op, _| (op, Ok(vec![])))
+            .await
+            .unwrap()
+        {
+            processed.push(op);
+        }
+
+        assert_eq!(
+            processed,
+            vec![
+                op("a", Priority::Backfill),
+                op("b", Priority::Urgent),
+                op("b", Priority::Urgent),
+            ]
+        );
+    }
+}
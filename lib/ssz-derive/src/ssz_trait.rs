@@ -142,6 +142,7 @@ fn container(
         .unzip::<_, _, Vec<_>, Vec<_>>();
 
     let num_leaves = ident.len();
+    let leaf_index = 0..num_leaves;
 
     let output = quote! {
         impl #impl_generics ::ssz::Ssz for #name #ty_generics #where_clause {
@@ -173,6 +174,18 @@ fn container(
                 hasher.finish().expect("tree hash derive should not have a remaining buffer")
             }
 
+            fn tree_hash_node(&self, gindex: ::ssz::tree_hash::GeneralizedIndex) -> Option<::ssz::H256> {
+                ::ssz::tree_hash::merkle_tree_node(
+                    &[#(self.#ident.tree_hash_root()),*],
+                    #num_leaves,
+                    gindex,
+                    |leaf, gindex| match leaf {
+                        #(#leaf_index => self.#ident.tree_hash_node(gindex),)*
+                        _ => None,
+                    },
+                )
+            }
+
             fn ssz_bytes_len(&self) -> ::core::num::NonZeroUsize {
                 match <Self as ::ssz::Ssz>::SSZ_FIXED_LEN {
                     Some(len) => len,
@@ -340,6 +353,10 @@ fn wrapper(item: &DeriveInput, struct_data: &DataStruct) -> Result<TokenStream,
                 self.#ident.tree_hash_root()
             }
 
+            fn tree_hash_node(&self, gindex: ::ssz::tree_hash::GeneralizedIndex) -> Option<::ssz::H256> {
+                self.#ident.tree_hash_node(gindex)
+            }
+
             fn ssz_bytes_len(&self) -> ::core::num::NonZeroUsize {
                 self.#ident.ssz_bytes_len()
             }
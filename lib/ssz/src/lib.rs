@@ -43,7 +43,11 @@ extern crate ssz_derive;
 pub use ssz_derive::*;
 pub use unionlabs_primitives::H256;
 
-use crate::{decode::DecodeError, tree_hash::TreeHashType, types::tree_hash::vec_tree_hash_root};
+use crate::{
+    decode::DecodeError,
+    tree_hash::{GeneralizedIndex, TreeHashType},
+    types::tree_hash::{vec_tree_hash_node, vec_tree_hash_root},
+};
 
 pub mod types;
 
@@ -70,6 +74,16 @@ pub trait Ssz: Sized {
 
     fn tree_hash_root(&self) -> H256;
 
+    /// Returns the node at `gindex` in the merkle tree of `self`, or `None` if there is no such
+    /// node.
+    ///
+    /// The default implementation only provides the root (`gindex == 1`). Types whose merkle tree
+    /// contains other objects (containers, vectors and lists) override this to descend into them,
+    /// which is what allows [generating proofs](crate::tree_hash::generate_proof) of their fields.
+    fn tree_hash_node(&self, gindex: GeneralizedIndex) -> Option<H256> {
+        (gindex == 1).then(|| self.tree_hash_root())
+    }
+
     /// Append the encoding `self` to `buf`.
     ///
     /// Note, variable length objects need only to append their "variable length" portion, they do
@@ -239,6 +253,10 @@ where
         vec_tree_hash_root::<T, U<N>>(self)
     }
 
+    fn tree_hash_node(&self, gindex: GeneralizedIndex) -> Option<H256> {
+        vec_tree_hash_node::<T, U<N>>(self, gindex)
+    }
+
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         sequence_ssz_append::<_, T>(self, buf);
     }
@@ -452,6 +470,10 @@ where
         <[u8; BYTES] as Ssz>::tree_hash_root(self.get())
     }
 
+    fn tree_hash_node(&self, gindex: GeneralizedIndex) -> Option<H256> {
+        <[u8; BYTES] as Ssz>::tree_hash_node(self.get(), gindex)
+    }
+
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         <[u8; BYTES] as Ssz>::ssz_append(self.get(), buf);
     }
@@ -1,8 +1,16 @@
 mod merkle_hasher;
+mod merkle_proof;
 mod merkleize_padded;
 mod merkleize_standard;
 
 pub use merkle_hasher::{Error, MerkleHasher};
+pub use merkle_proof::{
+    calculate_merkle_root, calculate_multi_merkle_root, concat_generalized_indices,
+    generate_multiproof, generate_proof, get_branch_indices, get_generalized_index_bit,
+    get_generalized_index_length, get_helper_indices, get_path_indices, merkle_tree_node,
+    verify_merkle_multiproof, verify_merkle_proof, GeneralizedIndex, MerkleMultiproof, MerkleProof,
+    ProofError,
+};
 pub use merkleize_padded::merkleize_padded;
 pub use merkleize_standard::merkleize_standard;
 use sha2::{digest::FixedOutput, Digest, Sha256};
@@ -0,0 +1,447 @@
+//! Merkle proofs and multiproofs over the merkle tree of an [`Ssz`] object.
+//!
+//! See <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md>.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use unionlabs_primitives::H256;
+
+use crate::{
+    tree_hash::{hash_concat, merkle_root},
+    Ssz,
+};
+
+/// The index of a node in a binary merkle tree, where the root is `1` and the children of a node
+/// `n` are `2n` and `2n + 1`.
+pub type GeneralizedIndex = u64;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProofError {
+    #[error("invalid generalized index {0}")]
+    InvalidGeneralizedIndex(GeneralizedIndex),
+    #[error("no node exists at generalized index {0}")]
+    NodeNotFound(GeneralizedIndex),
+    #[error("invalid proof length, expected {expected} but found {found}")]
+    InvalidProofLength { expected: usize, found: usize },
+    #[error("invalid leaves length, expected {expected} but found {found}")]
+    InvalidLeavesLength { expected: usize, found: usize },
+    #[error("the proof does not prove the root")]
+    MissingRoot,
+}
+
+/// A merkle proof of a single node in a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    pub leaf: H256,
+    /// The sibling nodes of the path from the leaf to the root, ordered from the bottom of the tree
+    /// to the top.
+    pub branch: Vec<H256>,
+}
+
+/// A merkle proof of multiple nodes in a tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleMultiproof {
+    /// The leaves, in the same order as the generalized indices they were requested for.
+    pub leaves: Vec<H256>,
+    /// The nodes at the [helper indices](get_helper_indices) of the leaves.
+    pub proof: Vec<H256>,
+}
+
+/// Get the node at `gindex` in the merkle tree of `leaves` padded with zero chunks to `limit`
+/// leaves.
+///
+/// If `gindex` is below one of the leaves, `descend` is called with the index of the leaf and
+/// `gindex` relative to the subtree of that leaf.
+pub fn merkle_tree_node(
+    leaves: &[H256],
+    limit: usize,
+    gindex: GeneralizedIndex,
+    descend: impl FnOnce(usize, GeneralizedIndex) -> Option<H256>,
+) -> Option<H256> {
+    let depth = limit.next_power_of_two().trailing_zeros();
+    let gindex_depth = gindex.checked_ilog2()?;
+
+    if gindex_depth <= depth {
+        // the node is in this tree, merkleize the leaves of the subtree it's the root of
+        let width = 1_usize << (depth - gindex_depth);
+        let start = usize::try_from(gindex - (1 << gindex_depth)).ok()? * width;
+
+        let bytes = leaves
+            .get(start..leaves.len().min(start + width))
+            .unwrap_or_default()
+            .iter()
+            .flat_map(|leaf| *leaf.get())
+            .collect::<Vec<_>>();
+
+        Some(merkle_root(&bytes, width))
+    } else {
+        let sub_depth = gindex_depth - depth;
+
+        let leaf = usize::try_from((gindex >> sub_depth) - (1 << depth)).ok()?;
+
+        // padding leaves have no children
+        if leaf < leaves.len() {
+            descend(leaf, (1 << sub_depth) | (gindex & ((1 << sub_depth) - 1)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Generate a proof of the node at `gindex` in the merkle tree of `value`.
+pub fn generate_proof<T: Ssz>(
+    value: &T,
+    gindex: GeneralizedIndex,
+) -> Result<MerkleProof, ProofError> {
+    if gindex == 0 {
+        return Err(ProofError::InvalidGeneralizedIndex(gindex));
+    }
+
+    Ok(MerkleProof {
+        leaf: node(value, gindex)?,
+        branch: get_branch_indices(gindex)
+            .into_iter()
+            .map(|index| node(value, index))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Generate a proof of the nodes at `indices` in the merkle tree of `value`.
+pub fn generate_multiproof<T: Ssz>(
+    value: &T,
+    indices: &[GeneralizedIndex],
+) -> Result<MerkleMultiproof, ProofError> {
+    if let Some(gindex) = indices.iter().find(|gindex| **gindex == 0) {
+        return Err(ProofError::InvalidGeneralizedIndex(*gindex));
+    }
+
+    Ok(MerkleMultiproof {
+        leaves: indices
+            .iter()
+            .map(|index| node(value, *index))
+            .collect::<Result<_, _>>()?,
+        proof: get_helper_indices(indices)
+            .into_iter()
+            .map(|index| node(value, index))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+fn node<T: Ssz>(value: &T, gindex: GeneralizedIndex) -> Result<H256, ProofError> {
+    value
+        .tree_hash_node(gindex)
+        .ok_or(ProofError::NodeNotFound(gindex))
+}
+
+/// Concatenate a sequence of generalized indices, where each index is relative to the node at
+/// the previous index.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#concat_generalized_indices>
+#[must_use]
+pub fn concat_generalized_indices(indices: &[GeneralizedIndex]) -> GeneralizedIndex {
+    indices.iter().fold(1, |o, index| {
+        let length = get_generalized_index_length(*index);
+        (o << length) | (index ^ (1 << length))
+    })
+}
+
+/// The depth of `index` in the tree.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#get_generalized_index_length>
+///
+/// # Panics
+///
+/// Panics if `index` is `0`.
+#[must_use]
+pub fn get_generalized_index_length(index: GeneralizedIndex) -> u32 {
+    index.ilog2()
+}
+
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#get_generalized_index_bit>
+#[must_use]
+pub fn get_generalized_index_bit(index: GeneralizedIndex, position: u32) -> bool {
+    (index & (1 << position)) > 0
+}
+
+/// The generalized indices of the sister chunks along the path from `tree_index` to the root.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#get_branch_indices>
+#[must_use]
+pub fn get_branch_indices(tree_index: GeneralizedIndex) -> Vec<GeneralizedIndex> {
+    get_path_indices(tree_index)
+        .into_iter()
+        .map(|index| index ^ 1)
+        .collect()
+}
+
+/// The generalized indices of the chunks along the path from `tree_index` to the root.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#get_path_indices>
+#[must_use]
+pub fn get_path_indices(tree_index: GeneralizedIndex) -> Vec<GeneralizedIndex> {
+    std::iter::successors(Some(tree_index), |index| Some(index / 2))
+        .take_while(|index| *index > 1)
+        .collect()
+}
+
+/// The generalized indices of all the nodes required to prove the nodes at `indices`, sorted in
+/// descending order.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#get_helper_indices>
+#[must_use]
+pub fn get_helper_indices(indices: &[GeneralizedIndex]) -> Vec<GeneralizedIndex> {
+    let mut all_helper_indices = BTreeSet::new();
+    let mut all_path_indices = BTreeSet::new();
+
+    for index in indices {
+        all_helper_indices.extend(get_branch_indices(*index));
+        all_path_indices.extend(get_path_indices(*index));
+    }
+
+    all_helper_indices.retain(|index| !all_path_indices.contains(index));
+
+    all_helper_indices.into_iter().rev().collect()
+}
+
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#merkle-multiproofs>
+pub fn calculate_merkle_root(
+    leaf: H256,
+    proof: &[H256],
+    index: GeneralizedIndex,
+) -> Result<H256, ProofError> {
+    if index == 0 {
+        return Err(ProofError::InvalidGeneralizedIndex(index));
+    }
+
+    let expected = get_generalized_index_length(index) as usize;
+    if proof.len() != expected {
+        return Err(ProofError::InvalidProofLength {
+            expected,
+            found: proof.len(),
+        });
+    }
+
+    Ok(proof.iter().zip(0..).fold(leaf, |node, (h, i)| {
+        if get_generalized_index_bit(index, i) {
+            hash_concat(h.get(), node.get())
+        } else {
+            hash_concat(node.get(), h.get())
+        }
+    }))
+}
+
+/// Verify that `proof` proves `leaf` at `index` in the tree with the root `root`.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#merkle-multiproofs>
+#[must_use]
+pub fn verify_merkle_proof(
+    leaf: H256,
+    proof: &[H256],
+    index: GeneralizedIndex,
+    root: H256,
+) -> bool {
+    calculate_merkle_root(leaf, proof, index).is_ok_and(|calculated| calculated == root)
+}
+
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#merkle-multiproofs>
+pub fn calculate_multi_merkle_root(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[GeneralizedIndex],
+) -> Result<H256, ProofError> {
+    if leaves.len() != indices.len() {
+        return Err(ProofError::InvalidLeavesLength {
+            expected: indices.len(),
+            found: leaves.len(),
+        });
+    }
+
+    if let Some(index) = indices.iter().find(|index| **index == 0) {
+        return Err(ProofError::InvalidGeneralizedIndex(*index));
+    }
+
+    let helper_indices = get_helper_indices(indices);
+    if proof.len() != helper_indices.len() {
+        return Err(ProofError::InvalidProofLength {
+            expected: helper_indices.len(),
+            found: proof.len(),
+        });
+    }
+
+    let mut objects = indices
+        .iter()
+        .zip(leaves)
+        .chain(helper_indices.iter().zip(proof))
+        .map(|(index, node)| (*index, *node))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut keys = objects.keys().rev().copied().collect::<Vec<_>>();
+
+    let mut pos = 0;
+    while pos < keys.len() {
+        let k = keys[pos];
+
+        if objects.contains_key(&(k ^ 1)) && !objects.contains_key(&(k / 2)) {
+            let left = objects[&((k | 1) ^ 1)];
+            let right = objects[&(k | 1)];
+
+            objects.insert(k / 2, hash_concat(left.get(), right.get()));
+            keys.push(k / 2);
+        }
+
+        pos += 1;
+    }
+
+    objects.get(&1).copied().ok_or(ProofError::MissingRoot)
+}
+
+/// Verify that `proof` proves `leaves` at `indices` in the tree with the root `root`.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/v1.4.0/ssz/merkle-proofs.md#merkle-multiproofs>
+#[must_use]
+pub fn verify_merkle_multiproof(
+    leaves: &[H256],
+    proof: &[H256],
+    indices: &[GeneralizedIndex],
+    root: H256,
+) -> bool {
+    calculate_multi_merkle_root(leaves, proof, indices).is_ok_and(|calculated| calculated == root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generalized_index_helpers() {
+        assert_eq!(get_generalized_index_length(1), 0);
+        assert_eq!(get_generalized_index_length(105), 6);
+
+        assert_eq!(concat_generalized_indices(&[]), 1);
+        assert_eq!(concat_generalized_indices(&[2, 3]), 5);
+        // BeaconState.finalized_checkpoint.root
+        assert_eq!(concat_generalized_indices(&[52, 3]), 105);
+
+        assert_eq!(get_path_indices(1), Vec::<u64>::new());
+        assert_eq!(get_path_indices(9), [9, 4, 2]);
+
+        assert_eq!(get_branch_indices(1), Vec::<u64>::new());
+        assert_eq!(get_branch_indices(9), [8, 5, 3]);
+    }
+
+    #[test]
+    fn helper_indices() {
+        // example from the spec
+        assert_eq!(get_helper_indices(&[8, 9, 14]), [15, 6, 5]);
+        assert_eq!(get_helper_indices(&[9]), [8, 5, 3]);
+        assert_eq!(get_helper_indices(&[2, 3]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn merkle_tree_node() {
+        let leaves = [1, 2, 3].map(|i| H256::new([i; 32]));
+
+        let node = |gindex| super::merkle_tree_node(&leaves, 5, gindex, |_, _| None);
+
+        let zero = H256::default();
+        let h = |a: H256, b: H256| hash_concat(a.get(), b.get());
+
+        assert_eq!(node(0), None);
+        assert_eq!(node(8), Some(leaves[0]));
+        assert_eq!(node(10), Some(leaves[2]));
+        assert_eq!(node(11), Some(zero));
+        assert_eq!(node(5), Some(h(leaves[2], zero)));
+        assert_eq!(node(3), Some(h(h(zero, zero), h(zero, zero))));
+        assert_eq!(
+            node(1),
+            Some(merkle_root(
+                &leaves
+                    .iter()
+                    .flat_map(|leaf| *leaf.get())
+                    .collect::<Vec<_>>(),
+                5
+            ))
+        );
+        // below a leaf
+        assert_eq!(node(16), None);
+        assert_eq!(
+            super::merkle_tree_node(&leaves, 5, 21, |leaf, gindex| {
+                assert_eq!((leaf, gindex), (2, 3));
+                Some(zero)
+            }),
+            Some(zero)
+        );
+    }
+
+    #[test]
+    fn single_proof() {
+        let leaves = [1, 2, 3, 4, 5].map(|i| H256::new([i; 32]));
+        let root = merkle_root(
+            &leaves
+                .iter()
+                .flat_map(|leaf| *leaf.get())
+                .collect::<Vec<_>>(),
+            8,
+        );
+
+        for (index, leaf) in (8..).zip(leaves) {
+            let proof = get_branch_indices(index)
+                .into_iter()
+                .map(|index| super::merkle_tree_node(&leaves, 8, index, |_, _| None).unwrap())
+                .collect::<Vec<_>>();
+
+            assert!(verify_merkle_proof(leaf, &proof, index, root));
+            assert!(!verify_merkle_proof(leaf, &proof, index ^ 1, root));
+            assert!(!verify_merkle_proof(leaf, &proof[1..], index, root));
+        }
+    }
+
+    #[test]
+    fn multiproof() {
+        let leaves = [1, 2, 3, 4, 5].map(|i| H256::new([i; 32]));
+        let root = merkle_root(
+            &leaves
+                .iter()
+                .flat_map(|leaf| *leaf.get())
+                .collect::<Vec<_>>(),
+            8,
+        );
+
+        let node = |index| super::merkle_tree_node(&leaves, 8, index, |_, _| None).unwrap();
+
+        for indices in [&[8, 9, 14][..], &[9], &[2, 12], &[1], &[8, 9, 10, 11, 12]] {
+            let proof = get_helper_indices(indices)
+                .into_iter()
+                .map(node)
+                .collect::<Vec<_>>();
+            let proven = indices.iter().copied().map(node).collect::<Vec<_>>();
+
+            assert!(verify_merkle_multiproof(&proven, &proof, indices, root));
+            assert!(!verify_merkle_multiproof(
+                &proven,
+                &proof,
+                indices,
+                H256::default()
+            ));
+        }
+
+        assert_eq!(
+            calculate_multi_merkle_root(&[], &[], &[8]),
+            Err(ProofError::InvalidLeavesLength {
+                expected: 1,
+                found: 0
+            })
+        );
+        assert_eq!(
+            calculate_multi_merkle_root(&[node(8)], &[], &[8]),
+            Err(ProofError::InvalidProofLength {
+                expected: 3,
+                found: 0
+            })
+        );
+        assert_eq!(
+            calculate_multi_merkle_root(&[], &[], &[]),
+            Err(ProofError::MissingRoot)
+        );
+    }
+}
@@ -13,8 +13,11 @@ use typenum::{NonZero, Unsigned};
 use unionlabs_primitives::H256;
 
 use crate::{
-    decode::TryFromIter, decode_list_of_variable_length_items, sequence_ssz_append,
-    sequence_ssz_bytes_len, tree_hash::TreeHashType, types::tree_hash::vec_tree_hash_root, Ssz,
+    decode::TryFromIter,
+    decode_list_of_variable_length_items, sequence_ssz_append, sequence_ssz_bytes_len,
+    tree_hash::{merkle_tree_node, GeneralizedIndex, TreeHashType},
+    types::tree_hash::{vec_tree_hash_node, vec_tree_hash_root},
+    Ssz,
 };
 
 /// Emulates a SSZ `List`.
@@ -221,6 +224,23 @@ where
         crate::tree_hash::mix_in_length(&root, self.len())
     }
 
+    fn tree_hash_node(&self, gindex: GeneralizedIndex) -> Option<H256> {
+        // the list data is the left subtree, and the length is the right leaf
+        merkle_tree_node(
+            &[
+                vec_tree_hash_root::<T, N>(&self.vec),
+                self.len().tree_hash_root(),
+            ],
+            2,
+            gindex,
+            |leaf, gindex| {
+                (leaf == 0)
+                    .then(|| vec_tree_hash_node::<T, N>(&self.vec, gindex))
+                    .flatten()
+            },
+        )
+    }
+
     fn ssz_append(&self, buf: &mut Vec<u8>) {
         sequence_ssz_append(self.iter(), buf);
     }
@@ -2,7 +2,7 @@ use typenum::Unsigned;
 use unionlabs_primitives::H256;
 
 use crate::{
-    tree_hash::{MerkleHasher, TreeHashType, BYTES_PER_CHUNK},
+    tree_hash::{merkle_tree_node, GeneralizedIndex, MerkleHasher, TreeHashType, BYTES_PER_CHUNK},
     Ssz,
 };
 
@@ -43,6 +43,47 @@ where
     }
 }
 
+/// A helper function providing common functionality between the `tree_hash_node` implementations
+/// for `Vector` and `List`. For lists, `gindex` is relative to the root of the list data (i.e.
+/// without the length mixed in).
+pub fn vec_tree_hash_node<T, N>(vec: &[T], gindex: GeneralizedIndex) -> Option<H256>
+where
+    T: Ssz,
+    N: Unsigned,
+{
+    match T::TREE_HASH_TYPE {
+        TreeHashType::Basic { size } => {
+            // basic items are packed into chunks, so there is nothing to descend into
+            let bytes = vec
+                .iter()
+                .flat_map(|item| item.tree_hash_root().get()[..(size as usize)].to_vec())
+                .collect::<Vec<_>>();
+
+            let chunks = bytes
+                .chunks(BYTES_PER_CHUNK)
+                .map(|chunk| {
+                    let mut leaf = [0; BYTES_PER_CHUNK];
+                    leaf[..chunk.len()].copy_from_slice(chunk);
+                    H256::new(leaf)
+                })
+                .collect::<Vec<_>>();
+
+            merkle_tree_node(
+                &chunks,
+                chunk_count_basic_list_or_vector::<N>(size),
+                gindex,
+                |_, _| None,
+            )
+        }
+        TreeHashType::Container | TreeHashType::List | TreeHashType::Vector => merkle_tree_node(
+            &vec.iter().map(Ssz::tree_hash_root).collect::<Vec<_>>(),
+            N::USIZE,
+            gindex,
+            |leaf, gindex| vec[leaf].tree_hash_node(gindex),
+        ),
+    }
+}
+
 /// Corresponds to `chunk_count(type)` definition for `List[B, N]` and `Vector[B, N]` from [the spec](https://github.com/ethereum/consensus-specs/blob/dev/ssz/simple-serialize.md#merkleization).
 #[inline]
 fn chunk_count_basic_list_or_vector<N>(size: u8) -> usize
@@ -69,3 +110,43 @@ pub fn bitfield_bytes_tree_hash_root<N: Unsigned>(bytes: &[u8]) -> H256 {
         .finish()
         .expect("bitfield tree hash buffer should not exceed leaf limit")
 }
+
+#[cfg(test)]
+mod tests {
+    use typenum::{U100, U1000, U13};
+
+    use super::*;
+
+    fn assert_root_node_matches_root<T: Ssz, N: Unsigned>(vec: &[T]) {
+        assert_eq!(
+            vec_tree_hash_node::<T, N>(vec, 1),
+            Some(vec_tree_hash_root::<T, N>(vec)),
+            "len = {}",
+            vec.len()
+        );
+    }
+
+    #[test]
+    fn packed_u8_root_node() {
+        for len in [0_u8, 1, 5, 31, 33, 63, 99, 100] {
+            let vec = (0..len).collect::<Vec<_>>();
+
+            assert_root_node_matches_root::<u8, U100>(&vec);
+        }
+    }
+
+    #[test]
+    fn packed_u64_root_node() {
+        for len in [0, 1, 3, 5, 7, 11, 13] {
+            let vec = (0..len).map(|i| i * 0x0101_0101).collect::<Vec<u64>>();
+
+            assert_root_node_matches_root::<u64, U13>(&vec);
+        }
+
+        for len in [0, 3, 5, 17, 999, 1000] {
+            let vec = (0..len).map(|i| i * 0x0101_0101).collect::<Vec<u64>>();
+
+            assert_root_node_matches_root::<u64, U1000>(&vec);
+        }
+    }
+}
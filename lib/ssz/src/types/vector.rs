@@ -13,8 +13,10 @@ use typenum::{Const, NonZero, ToUInt, Unsigned, U};
 use unionlabs_primitives::H256;
 
 use crate::{
-    decode::TryFromIter, decode_list_of_variable_length_items, sequence_ssz_append,
-    sequence_ssz_bytes_len, tree_hash::TreeHashType, types::tree_hash::vec_tree_hash_root,
+    decode::TryFromIter,
+    decode_list_of_variable_length_items, sequence_ssz_append, sequence_ssz_bytes_len,
+    tree_hash::{GeneralizedIndex, TreeHashType},
+    types::tree_hash::{vec_tree_hash_node, vec_tree_hash_root},
     DecodeError, Ssz,
 };
 
@@ -200,6 +202,10 @@ where
         vec_tree_hash_root::<T, N>(&self.vec)
     }
 
+    fn tree_hash_node(&self, gindex: GeneralizedIndex) -> Option<H256> {
+        vec_tree_hash_node::<T, N>(&self.vec, gindex)
+    }
+
     fn ssz_bytes_len(&self) -> NonZeroUsize {
         sequence_ssz_bytes_len(&self.vec)
     }
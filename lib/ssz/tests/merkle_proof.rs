@@ -0,0 +1,196 @@
+use beacon_api_types::{
+    chain_spec::Mainnet, consts::EXECUTION_PAYLOAD_GINDEX, deneb::LightClientHeaderSsz,
+};
+use ssz::{
+    tree_hash::{
+        concat_generalized_indices, generate_multiproof, generate_proof, verify_merkle_multiproof,
+        verify_merkle_proof, ProofError,
+    },
+    types::{
+        typenum::{U4, U8},
+        List,
+    },
+    Ssz,
+};
+use unionlabs::primitives::H256;
+
+#[derive(Debug, Clone, PartialEq, Ssz)]
+struct Inner {
+    a: u64,
+    b: H256,
+    c: List<u16, U8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Ssz)]
+struct Outer {
+    x: u8,
+    inner: Inner,
+    items: List<Inner, U4>,
+}
+
+fn inner(n: u8) -> Inner {
+    Inner {
+        a: n.into(),
+        b: H256::new([n; 32]),
+        c: vec![n.into(); n.into()].try_into().unwrap(),
+    }
+}
+
+fn outer() -> Outer {
+    Outer {
+        x: 1,
+        inner: inner(2),
+        items: vec![inner(3), inner(4)].try_into().unwrap(),
+    }
+}
+
+#[test]
+fn nodes() {
+    let outer = outer();
+
+    assert_eq!(outer.tree_hash_node(1), Some(outer.tree_hash_root()));
+    assert_eq!(outer.tree_hash_node(0), None);
+
+    // outer.inner.b
+    assert_eq!(
+        outer.tree_hash_node(concat_generalized_indices(&[5, 5])),
+        Some(outer.inner.b.tree_hash_root())
+    );
+
+    // outer.items[1].a
+    assert_eq!(
+        outer.tree_hash_node(concat_generalized_indices(&[6, 2, 5, 4])),
+        Some(outer.items[1].a.tree_hash_root())
+    );
+
+    // outer.items.length
+    assert_eq!(
+        outer.tree_hash_node(concat_generalized_indices(&[6, 3])),
+        Some(2_usize.tree_hash_root())
+    );
+
+    // padding leaves are zero, and have no children
+    assert_eq!(outer.tree_hash_node(7), Some(H256::default()));
+    assert_eq!(outer.tree_hash_node(14), None);
+    assert_eq!(
+        outer.tree_hash_node(concat_generalized_indices(&[6, 2, 7])),
+        Some(H256::default())
+    );
+    assert_eq!(
+        outer.tree_hash_node(concat_generalized_indices(&[6, 2, 7, 2])),
+        None
+    );
+
+    // basic types have no children
+    assert_eq!(
+        outer.tree_hash_node(concat_generalized_indices(&[4, 2])),
+        None
+    );
+}
+
+#[test]
+fn single_proofs() {
+    let outer = outer();
+    let root = outer.tree_hash_root();
+
+    for gindex in [
+        1,
+        4,
+        concat_generalized_indices(&[5, 5]),
+        concat_generalized_indices(&[5, 6, 3]),
+        concat_generalized_indices(&[6, 2, 5, 4]),
+        concat_generalized_indices(&[6, 3]),
+    ] {
+        let proof = generate_proof(&outer, gindex).unwrap();
+
+        assert_eq!(Some(proof.leaf), outer.tree_hash_node(gindex));
+        assert!(verify_merkle_proof(proof.leaf, &proof.branch, gindex, root));
+        assert!(!verify_merkle_proof(
+            H256::new([0xff; 32]),
+            &proof.branch,
+            gindex,
+            root
+        ));
+    }
+
+    assert_eq!(
+        generate_proof(&outer, 14),
+        Err(ProofError::NodeNotFound(14))
+    );
+    assert_eq!(
+        generate_proof(&outer, 0),
+        Err(ProofError::InvalidGeneralizedIndex(0))
+    );
+}
+
+#[test]
+fn multiproofs() {
+    let outer = outer();
+    let root = outer.tree_hash_root();
+
+    let indices = [
+        4,
+        concat_generalized_indices(&[5, 5]),
+        concat_generalized_indices(&[6, 2, 4, 4]),
+        concat_generalized_indices(&[6, 2, 5, 4]),
+        concat_generalized_indices(&[6, 3]),
+    ];
+
+    let proof = generate_multiproof(&outer, &indices).unwrap();
+
+    assert_eq!(
+        proof.leaves,
+        [
+            outer.x.tree_hash_root(),
+            outer.inner.b.tree_hash_root(),
+            outer.items[0].a.tree_hash_root(),
+            outer.items[1].a.tree_hash_root(),
+            2_usize.tree_hash_root(),
+        ]
+    );
+    assert!(verify_merkle_multiproof(
+        &proof.leaves,
+        &proof.proof,
+        &indices,
+        root
+    ));
+
+    let mut leaves = proof.leaves.clone();
+    leaves.swap(2, 3);
+    assert!(!verify_merkle_multiproof(
+        &leaves,
+        &proof.proof,
+        &indices,
+        root
+    ));
+}
+
+#[test]
+fn light_client_header_branches() {
+    let header = serde_json::from_value::<LightClientHeaderSsz<Mainnet>>(
+        serde_json::from_str::<serde_json::Value>(include_str!(
+            "../../ethereum-sync-protocol/src/test/light_client_update_6553725.json"
+        ))
+        .unwrap()["attested_header"]
+            .clone(),
+    )
+    .unwrap();
+
+    // verify the branch supplied by the beacon node
+    assert!(verify_merkle_proof(
+        header.execution.tree_hash_root(),
+        &header.execution_branch,
+        EXECUTION_PAYLOAD_GINDEX,
+        header.beacon.body_root,
+    ));
+
+    // BeaconBlockHeader.body_root
+    let proof = generate_proof(&header.beacon, 12).unwrap();
+    assert_eq!(proof.leaf, header.beacon.body_root);
+    assert!(verify_merkle_proof(
+        proof.leaf,
+        &proof.branch,
+        12,
+        header.beacon.tree_hash_root()
+    ));
+}
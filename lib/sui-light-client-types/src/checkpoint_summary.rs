@@ -48,6 +48,18 @@ pub struct CheckpointSummary {
     pub version_specific_data: Vec<u8>,
 }
 
+impl CheckpointSummary {
+    /// The digest of the bcs encoding of this checkpoint summary, which is what identifies a
+    /// checkpoint.
+    #[cfg(feature = "serde")]
+    pub fn digest(&self) -> Result<Digest, bcs::Error> {
+        let mut hasher = Blake2b::<typenum::U32>::new();
+        hasher.update("CheckpointSummary::");
+        bcs::serialize_into(&mut hasher, self)?;
+        Ok(Digest(FixedBytes::new(hasher.finalize().into())))
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 #[cfg_attr(
     feature = "serde",
@@ -104,11 +116,11 @@ impl CheckpointContents {
     }
 
     #[cfg(feature = "serde")]
-    pub fn digest(&self) -> Digest {
+    pub fn digest(&self) -> Result<Digest, bcs::Error> {
         let mut hasher = Blake2b::<typenum::U32>::new();
         hasher.update("CheckpointContents::");
-        bcs::serialize_into(&mut hasher, self).unwrap();
-        Digest(FixedBytes::new(hasher.finalize().into()))
+        bcs::serialize_into(&mut hasher, self)?;
+        Ok(Digest(FixedBytes::new(hasher.finalize().into())))
     }
 }
 
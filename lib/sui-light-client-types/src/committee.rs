@@ -7,3 +7,15 @@ pub struct Committee {
     pub epoch: U64,
     pub voting_rights: Vec<(AuthorityPublicKeyBytes, U64)>,
 }
+
+impl Committee {
+    /// The sum of the voting power of all authorities in this committee.
+    pub fn total_votes(&self) -> u64 {
+        self.voting_rights.iter().map(|(_, U64(votes))| votes).sum()
+    }
+
+    /// The voting power required for a certificate to be valid (2f + 1).
+    pub fn quorum_threshold(&self) -> u64 {
+        self.total_votes() * 2 / 3 + 1
+    }
+}
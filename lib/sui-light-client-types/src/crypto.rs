@@ -26,7 +26,7 @@ impl<const N: usize> Display for CryptoBytes<N> {
 pub type AuthorityStrongQuorumSignInfo = AuthorityQuorumSignInfo<true>;
 pub type AggregateAuthoritySignature = CryptoBytes<BLS_G1_SIZE>;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
 pub struct AuthorityQuorumSignInfo<const STRONG_THRESHOLD: bool> {
@@ -35,7 +35,7 @@ pub struct AuthorityQuorumSignInfo<const STRONG_THRESHOLD: bool> {
     pub signers_map: SuiBitmap,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct SuiBitmap(pub RoaringBitmap);
 
 #[cfg(feature = "serde")]
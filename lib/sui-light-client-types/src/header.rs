@@ -1,6 +1,6 @@
 use crate::{checkpoint_summary::CheckpointSummary, crypto::AuthorityStrongQuorumSignInfo};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
 pub struct Header {
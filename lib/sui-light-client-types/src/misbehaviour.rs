@@ -0,0 +1,83 @@
+use crate::header::Header;
+
+/// Two certified checkpoints with the same sequence number but different digests.
+///
+/// Each checkpoint is verified against the committee of the epoch that it was signed in, which must
+/// already be known to the client.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct Misbehaviour {
+    pub header_a: Header,
+    pub header_b: Header,
+}
+
+#[cfg(test)]
+mod tests {
+    use roaring::RoaringBitmap;
+    use unionlabs::{
+        encoding::{Bincode, Json},
+        test_utils::assert_codec_iso,
+    };
+    use unionlabs_primitives::FixedBytes;
+
+    use super::*;
+    use crate::{
+        checkpoint_summary::{CheckpointSummary, EndOfEpochData, GasCostSummary},
+        crypto::{AuthorityQuorumSignInfo, CryptoBytes, SuiBitmap},
+        digest::Digest,
+        U64,
+    };
+
+    fn mk_header(content_digest: u8) -> Header {
+        Header {
+            trusted_height: 10,
+            checkpoint_summary: CheckpointSummary {
+                epoch: 2,
+                sequence_number: 123,
+                network_total_transactions: 4567,
+                content_digest: Digest(FixedBytes::new([content_digest; 32])),
+                previous_digest: Some(Digest(FixedBytes::new([0xAA; 32]))),
+                epoch_rolling_gas_cost_summary: GasCostSummary {
+                    computation_cost: U64(1),
+                    storage_cost: U64(2),
+                    storage_rebate: U64(3),
+                    non_refundable_storage_fee: U64(4),
+                },
+                timestamp_ms: 1_700_000_000_000,
+                checkpoint_commitments: vec![],
+                end_of_epoch_data: Some(EndOfEpochData {
+                    next_epoch_committee: vec![(
+                        CryptoBytes(FixedBytes::new([0xBB; 96])),
+                        U64(100),
+                    )],
+                    next_epoch_protocol_version: U64(70),
+                    epoch_commitments: vec![],
+                }),
+                version_specific_data: vec![1, 2, 3],
+            },
+            sign_info: AuthorityQuorumSignInfo {
+                epoch: 2,
+                signature: CryptoBytes(FixedBytes::new([0xCC; 48])),
+                signers_map: SuiBitmap(RoaringBitmap::from_iter([0, 2, 5])),
+            },
+        }
+    }
+
+    fn mk_misbehaviour() -> Misbehaviour {
+        Misbehaviour {
+            header_a: mk_header(1),
+            header_b: mk_header(2),
+        }
+    }
+
+    #[test]
+    fn bincode_iso() {
+        assert_codec_iso::<_, Bincode>(&mk_misbehaviour());
+    }
+
+    #[test]
+    fn json_iso() {
+        assert_codec_iso::<_, Json>(&mk_misbehaviour());
+    }
+}
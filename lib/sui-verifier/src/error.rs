@@ -27,4 +27,16 @@ pub enum Error {
         "proven contents digest ({proven}) does not match the given contents digest ({given})"
     )]
     ContentsDigestMismatch { given: Digest, proven: Digest },
+
+    #[error("authority index {0} is not in the committee")]
+    UnknownAuthority(u32),
+
+    #[error("insufficient voting power: signed {signed_votes}, required {threshold}")]
+    InsufficientVotingPower { signed_votes: u64, threshold: u64 },
+
+    #[error("the checkpoints do not prove a misbehaviour")]
+    MisbehaviourNotFound,
+
+    #[error("unable to compute digest")]
+    Digest(#[from] bcs::Error),
 }
@@ -9,7 +9,7 @@ use sui_light_client_types::{
     digest::Digest,
     object::{Data, ObjectInner, TypeTag},
     transaction_effects::{ObjectOut, TransactionEffects},
-    AppId, Intent, IntentMessage, IntentScope, IntentVersion, ObjectID,
+    AppId, Intent, IntentMessage, IntentScope, IntentVersion, ObjectID, U64,
 };
 
 mod error;
@@ -60,6 +60,81 @@ pub fn verify_checkpoint<V: SignatureVerification>(
         selected_public_keys.push(committee.voting_rights[authority_index as usize].0.clone());
     }
 
+    signature_verifier
+        .verify_signature(
+            &selected_public_keys,
+            &checkpoint_signing_message(checkpoint),
+            &sign_info.signature,
+        )
+        .map_err(Into::into)
+}
+
+/// Verify that the authorities in `sign_info.signers_map` hold a quorum of the voting power of
+/// `committee`.
+pub fn verify_quorum(
+    committee: &Committee,
+    sign_info: &AuthorityStrongQuorumSignInfo,
+) -> Result<(), Error> {
+    let signed_votes = sign_info
+        .signers_map
+        .0
+        .iter()
+        .map(|authority_index| {
+            committee
+                .voting_rights
+                .get(authority_index as usize)
+                .map(|(_, U64(votes))| *votes)
+                .ok_or(Error::UnknownAuthority(authority_index))
+        })
+        .sum::<Result<u64, _>>()?;
+
+    let threshold = committee.quorum_threshold();
+
+    if signed_votes < threshold {
+        return Err(Error::InsufficientVotingPower {
+            signed_votes,
+            threshold,
+        });
+    }
+
+    Ok(())
+}
+
+/// Verify that the two checkpoints are an equivocation by the committee: they must have the same
+/// sequence number but different digests, and both must be certified by a quorum of the committee
+/// of their respective epoch.
+pub fn verify_misbehaviour<V: SignatureVerification>(
+    (committee_a, checkpoint_a, sign_info_a): (
+        &Committee,
+        &CheckpointSummary,
+        &AuthorityStrongQuorumSignInfo,
+    ),
+    (committee_b, checkpoint_b, sign_info_b): (
+        &Committee,
+        &CheckpointSummary,
+        &AuthorityStrongQuorumSignInfo,
+    ),
+    signature_verifier: &V,
+) -> Result<(), Error> {
+    if checkpoint_a.sequence_number != checkpoint_b.sequence_number {
+        return Err(Error::MisbehaviourNotFound);
+    }
+
+    if checkpoint_a.digest()? == checkpoint_b.digest()? {
+        return Err(Error::MisbehaviourNotFound);
+    }
+
+    verify_quorum(committee_a, sign_info_a)?;
+    verify_checkpoint(committee_a, checkpoint_a, sign_info_a, signature_verifier)?;
+
+    verify_quorum(committee_b, sign_info_b)?;
+    verify_checkpoint(committee_b, checkpoint_b, sign_info_b, signature_verifier)?;
+
+    Ok(())
+}
+
+/// The message that is signed by the committee to certify `checkpoint`.
+pub fn checkpoint_signing_message(checkpoint: &CheckpointSummary) -> Vec<u8> {
     let intent_msg = IntentMessage {
         intent: Intent {
             scope: IntentScope::CheckpointSummary,
@@ -73,13 +148,7 @@ pub fn verify_checkpoint<V: SignatureVerification>(
     bcs::serialize_into(&mut intent_msg_bytes, &checkpoint.epoch)
         .expect("Message serialization should not fail");
 
-    signature_verifier
-        .verify_signature(
-            &selected_public_keys,
-            &intent_msg_bytes,
-            &sign_info.signature,
-        )
-        .map_err(Into::into)
+    intent_msg_bytes
 }
 
 pub fn verify_membership(
@@ -144,7 +213,7 @@ pub fn verify_membership(
         .expect("execution digests do not contain the given effect");
 
     // STEP 5: compare the digest of `checkpoint_contents` with the `contents_digest` which is verified previously by the client
-    let calc_contents_digest = CheckpointContents::V1(checkpoint_contents.clone()).digest();
+    let calc_contents_digest = CheckpointContents::V1(checkpoint_contents.clone()).digest()?;
     if contents_digest != calc_contents_digest {
         return Err(Error::ContentsDigestMismatch {
             given: contents_digest,
@@ -196,3 +265,244 @@ pub fn calculate_dynamic_field_object_id(parent: [u8; 32], key_bytes: &[u8]) ->
 
     ObjectID::new(hash.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use blst::{min_sig, BLST_ERROR};
+    use roaring::RoaringBitmap;
+    use sui_light_client_types::crypto::{CryptoBytes, SuiBitmap};
+    use unionlabs_primitives::FixedBytes;
+
+    use super::*;
+
+    static CHECKPOINT: LazyLock<CheckpointSummary> = LazyLock::new(|| {
+        serde_json::from_str(include_str!("./test/checkpoint_summary.json")).unwrap()
+    });
+
+    struct BlstVerifier;
+
+    impl SignatureVerification for BlstVerifier {
+        type Error = Error;
+
+        fn verify_signature(
+            &self,
+            public_keys: &[AuthorityPublicKeyBytes],
+            msg: &[u8],
+            signature: &AggregateAuthoritySignature,
+        ) -> Result<(), Self::Error> {
+            let signature = min_sig::Signature::uncompress(signature.0.get())
+                .map_err(|e| Error::Client(format!("{e:?}").into()))?;
+
+            let public_keys = public_keys
+                .iter()
+                .map(|pk| min_sig::PublicKey::uncompress(pk.0.get()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| Error::Client(format!("{e:?}").into()))?;
+
+            match signature.fast_aggregate_verify(
+                true,
+                msg,
+                Self::BLS_DST,
+                &public_keys.iter().collect::<Vec<_>>(),
+            ) {
+                BLST_ERROR::BLST_SUCCESS => Ok(()),
+                err => Err(Error::Client(format!("{err:?}").into())),
+            }
+        }
+    }
+
+    fn mk_keys() -> Vec<min_sig::SecretKey> {
+        (1..=4)
+            .map(|i| min_sig::SecretKey::key_gen(&[i; 32], &[]).unwrap())
+            .collect()
+    }
+
+    fn mk_committee(keys: &[min_sig::SecretKey]) -> Committee {
+        Committee {
+            epoch: U64(CHECKPOINT.epoch),
+            voting_rights: keys
+                .iter()
+                .map(|sk| {
+                    (
+                        CryptoBytes(FixedBytes::new(sk.sk_to_pk().compress())),
+                        U64(2500),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    fn sign(
+        keys: &[min_sig::SecretKey],
+        signers: &[u32],
+        checkpoint: &CheckpointSummary,
+    ) -> AuthorityStrongQuorumSignInfo {
+        let msg = checkpoint_signing_message(checkpoint);
+
+        let signatures = signers
+            .iter()
+            .map(|i| keys[*i as usize].sign(&msg, BLS_DST, &[]))
+            .collect::<Vec<_>>();
+
+        let signature =
+            min_sig::AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+                .unwrap()
+                .to_signature();
+
+        AuthorityStrongQuorumSignInfo {
+            epoch: checkpoint.epoch,
+            signature: CryptoBytes(FixedBytes::new(signature.compress())),
+            signers_map: SuiBitmap(RoaringBitmap::from_iter(signers.iter().copied())),
+        }
+    }
+
+    fn conflicting_checkpoint() -> CheckpointSummary {
+        CheckpointSummary {
+            content_digest: Digest(FixedBytes::new([0xAA; 32])),
+            ..CHECKPOINT.clone()
+        }
+    }
+
+    #[test]
+    fn verify_checkpoint_works() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let sign_info = sign(&keys, &[0, 1, 2], &CHECKPOINT);
+
+        verify_quorum(&committee, &sign_info).unwrap();
+        verify_checkpoint(&committee, &CHECKPOINT, &sign_info, &BlstVerifier).unwrap();
+    }
+
+    #[test]
+    fn verify_quorum_insufficient_voting_power() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let sign_info = sign(&keys, &[0, 1], &CHECKPOINT);
+
+        assert!(matches!(
+            verify_quorum(&committee, &sign_info),
+            Err(Error::InsufficientVotingPower {
+                signed_votes: 5000,
+                threshold: 6667
+            })
+        ));
+    }
+
+    #[test]
+    fn verify_quorum_unknown_authority() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys[..3]);
+
+        let sign_info = sign(&keys, &[0, 1, 3], &CHECKPOINT);
+
+        assert!(matches!(
+            verify_quorum(&committee, &sign_info),
+            Err(Error::UnknownAuthority(3))
+        ));
+    }
+
+    #[test]
+    fn verify_misbehaviour_works() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let checkpoint_b = conflicting_checkpoint();
+
+        let sign_info_a = sign(&keys, &[0, 1, 2], &CHECKPOINT);
+        let sign_info_b = sign(&keys, &[1, 2, 3], &checkpoint_b);
+
+        verify_misbehaviour(
+            (&committee, &CHECKPOINT, &sign_info_a),
+            (&committee, &checkpoint_b, &sign_info_b),
+            &BlstVerifier,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn verify_misbehaviour_same_checkpoint() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let sign_info_a = sign(&keys, &[0, 1, 2], &CHECKPOINT);
+        let sign_info_b = sign(&keys, &[1, 2, 3], &CHECKPOINT);
+
+        assert!(matches!(
+            verify_misbehaviour(
+                (&committee, &CHECKPOINT, &sign_info_a),
+                (&committee, &CHECKPOINT, &sign_info_b),
+                &BlstVerifier,
+            ),
+            Err(Error::MisbehaviourNotFound)
+        ));
+    }
+
+    #[test]
+    fn verify_misbehaviour_different_sequence_numbers() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let checkpoint_b = CheckpointSummary {
+            sequence_number: CHECKPOINT.sequence_number + 1,
+            ..conflicting_checkpoint()
+        };
+
+        let sign_info_a = sign(&keys, &[0, 1, 2], &CHECKPOINT);
+        let sign_info_b = sign(&keys, &[0, 1, 2], &checkpoint_b);
+
+        assert!(matches!(
+            verify_misbehaviour(
+                (&committee, &CHECKPOINT, &sign_info_a),
+                (&committee, &checkpoint_b, &sign_info_b),
+                &BlstVerifier,
+            ),
+            Err(Error::MisbehaviourNotFound)
+        ));
+    }
+
+    #[test]
+    fn verify_misbehaviour_no_quorum() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let checkpoint_b = conflicting_checkpoint();
+
+        let sign_info_a = sign(&keys, &[0, 1, 2], &CHECKPOINT);
+        // a single authority equivocating is not enough to prove misbehaviour of the committee
+        let sign_info_b = sign(&keys, &[3], &checkpoint_b);
+
+        assert!(matches!(
+            verify_misbehaviour(
+                (&committee, &CHECKPOINT, &sign_info_a),
+                (&committee, &checkpoint_b, &sign_info_b),
+                &BlstVerifier,
+            ),
+            Err(Error::InsufficientVotingPower { .. })
+        ));
+    }
+
+    #[test]
+    fn verify_misbehaviour_invalid_signature() {
+        let keys = mk_keys();
+        let committee = mk_committee(&keys);
+
+        let checkpoint_b = conflicting_checkpoint();
+
+        let sign_info_a = sign(&keys, &[0, 1, 2], &CHECKPOINT);
+        // signature over the original checkpoint, not the conflicting one
+        let sign_info_b = sign(&keys, &[1, 2, 3], &CHECKPOINT);
+
+        assert!(matches!(
+            verify_misbehaviour(
+                (&committee, &CHECKPOINT, &sign_info_a),
+                (&committee, &checkpoint_b, &sign_info_b),
+                &BlstVerifier,
+            ),
+            Err(Error::Client(_))
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::header::Header;
+
+/// Two conflicting headers, both of which are valid updates from a trusted consensus state.
+///
+/// This is either a duplicate vote (both headers are at the same height but commit to different
+/// blocks) or a violation of BFT time monotonicity (`header_a` is at a greater height than
+/// `header_b`, but is not strictly after it in time).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(deny_unknown_fields)
+)]
+#[cfg_attr(feature = "bincode", derive(bincode::Encode, bincode::Decode))]
+pub struct Misbehaviour {
+    pub header_a: Header,
+    pub header_b: Header,
+}
+
+#[cfg(feature = "proto")]
+pub mod proto {
+    use unionlabs::{errors::MissingField, impl_proto_via_try_from_into, required};
+
+    use crate::{header, Misbehaviour};
+
+    impl_proto_via_try_from_into!(Misbehaviour => protos::ibc::lightclients::tendermint::v1::Misbehaviour);
+
+    impl From<Misbehaviour> for protos::ibc::lightclients::tendermint::v1::Misbehaviour {
+        fn from(value: Misbehaviour) -> Self {
+            #[allow(deprecated)]
+            Self {
+                client_id: String::new(),
+                header_1: Some(value.header_a.into()),
+                header_2: Some(value.header_b.into()),
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, Clone, thiserror::Error)]
+    pub enum Error {
+        #[error(transparent)]
+        MissingField(#[from] MissingField),
+        #[error("invalid header_1")]
+        HeaderA(#[source] header::proto::Error),
+        #[error("invalid header_2")]
+        HeaderB(#[source] header::proto::Error),
+    }
+
+    impl TryFrom<protos::ibc::lightclients::tendermint::v1::Misbehaviour> for Misbehaviour {
+        type Error = Error;
+
+        fn try_from(
+            value: protos::ibc::lightclients::tendermint::v1::Misbehaviour,
+        ) -> Result<Self, Self::Error> {
+            Ok(Self {
+                header_a: required!(value.header_1)?
+                    .try_into()
+                    .map_err(Error::HeaderA)?,
+                header_b: required!(value.header_2)?
+                    .try_into()
+                    .map_err(Error::HeaderB)?,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cometbft_types::{
+        crypto::public_key::PublicKey,
+        types::{
+            block_id::BlockId, commit::Commit, commit_sig::CommitSig,
+            part_set_header::PartSetHeader, signed_header::SignedHeader, validator::Validator,
+            validator_set::ValidatorSet,
+        },
+        version::consensus::Consensus,
+    };
+    use unionlabs::{
+        encoding::{Bincode, Json, Proto},
+        google::protobuf::timestamp::Timestamp,
+        ibc::core::client::height::Height,
+        primitives::{H160, H256},
+        test_utils::assert_codec_iso,
+    };
+
+    use super::*;
+
+    fn mk_header(app_hash: u8) -> Header {
+        let validator = Validator {
+            address: H160::new([0xAA; 20]),
+            pub_key: PublicKey::Ed25519([1, 2, 3].into()),
+            voting_power: 1.try_into().unwrap(),
+            proposer_priority: -1,
+        };
+
+        Header {
+            signed_header: SignedHeader {
+                header: cometbft_types::types::header::Header {
+                    version: Consensus { block: 11, app: 0 },
+                    chain_id: "oogabooga-1".to_owned(),
+                    height: 321.try_into().unwrap(),
+                    time: Timestamp {
+                        seconds: 654.try_into().unwrap(),
+                        nanos: 321.try_into().unwrap(),
+                    },
+                    last_block_id: BlockId {
+                        hash: Some(H256::new([0xAA; 32])),
+                        part_set_header: PartSetHeader {
+                            total: 1,
+                            hash: Some(H256::new([0xAA; 32])),
+                        },
+                    },
+                    last_commit_hash: H256::new([0xAA; 32]),
+                    data_hash: H256::new([0xAA; 32]),
+                    validators_hash: H256::new([0xAA; 32]),
+                    next_validators_hash: H256::new([0xAA; 32]),
+                    consensus_hash: H256::new([0xAA; 32]),
+                    app_hash: H256::new([app_hash; 32]),
+                    last_results_hash: H256::new([0xAA; 32]),
+                    evidence_hash: H256::new([0xAA; 32]),
+                    proposer_address: H160::new([0xAA; 20]),
+                },
+                commit: Commit {
+                    height: 321.try_into().unwrap(),
+                    round: 0.try_into().unwrap(),
+                    block_id: BlockId {
+                        hash: Some(H256::new([app_hash; 32])),
+                        part_set_header: PartSetHeader {
+                            total: 1,
+                            hash: Some(H256::new([0xAA; 32])),
+                        },
+                    },
+                    signatures: [CommitSig::Commit {
+                        validator_address: H160::new([0xAA; 20]),
+                        timestamp: Timestamp {
+                            seconds: 102030.try_into().unwrap(),
+                            nanos: 405060.try_into().unwrap(),
+                        },
+                        signature: [1, 2, 3].into(),
+                    }]
+                    .to_vec(),
+                },
+            },
+            validator_set: ValidatorSet {
+                validators: [validator.clone()].to_vec(),
+                proposer: validator.clone(),
+                total_voting_power: 1,
+            },
+            trusted_height: Height::new_with_revision(1, 300),
+            trusted_validators: ValidatorSet {
+                validators: [validator.clone()].to_vec(),
+                proposer: validator,
+                total_voting_power: 1,
+            },
+        }
+    }
+
+    fn mk_misbehaviour() -> Misbehaviour {
+        Misbehaviour {
+            header_a: mk_header(0xAA),
+            header_b: mk_header(0xBB),
+        }
+    }
+
+    #[test]
+    fn bincode_iso() {
+        assert_codec_iso::<_, Bincode>(&mk_misbehaviour());
+    }
+
+    #[test]
+    fn json_iso() {
+        assert_codec_iso::<_, Json>(&mk_misbehaviour());
+    }
+
+    #[test]
+    fn proto_iso() {
+        assert_codec_iso::<_, Proto>(&mk_misbehaviour());
+    }
+}
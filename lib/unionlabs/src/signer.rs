@@ -59,17 +59,9 @@ impl CosmosSigner {
     #[must_use]
     pub fn address(&self) -> Bech32<H160> {
         // TODO: benchmark this, and consider caching it in the struct
-        // bech32(prefix, ripemd(sha256(pubkey)))
-        Bech32::new(
+        cosmos_address(
+            &self.signing_key.public_key().to_bytes(),
             self.prefix.clone(),
-            ripemd::Ripemd160::new()
-                .chain_update(
-                    sha2::Sha256::new()
-                        .chain_update(self.signing_key.public_key().to_bytes())
-                        .finalize(),
-                )
-                .finalize()
-                .into(),
         )
     }
 
@@ -79,6 +71,19 @@ impl CosmosSigner {
     }
 }
 
+/// The cosmos address of a compressed secp256k1 public key:
+/// `bech32(prefix, ripemd(sha256(pubkey)))`.
+#[must_use]
+pub fn cosmos_address(public_key: &[u8], prefix: String) -> Bech32<H160> {
+    Bech32::new(
+        prefix,
+        ripemd::Ripemd160::new()
+            .chain_update(sha2::Sha256::new().chain_update(public_key).finalize())
+            .finalize()
+            .into(),
+    )
+}
+
 impl Display for CosmosSigner {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.address())
@@ -107,4 +112,14 @@ mod tests {
             "prefix14sarpj4p7l68eze5shfx4xtxr7vl92ge20mdc5"
         );
     }
+
+    #[test]
+    fn cosmos_address_from_public_key() {
+        let signer = CosmosSigner::new(signing_key(), "prefix".to_string());
+
+        assert_eq!(
+            cosmos_address(signer.public_key().get(), "prefix".to_string()),
+            signer.address()
+        );
+    }
 }
@@ -542,4 +542,26 @@ impl<C: VoyagerRpcClient> VoyagerClient<C> {
             .await
             .map_err(json_rpc_error_to_error_object)
     }
+
+    /// Call a custom rpc method of a plugin, deserializing the response into `T`.
+    pub async fn plugin_custom<T: DeserializeOwned>(
+        &self,
+        plugin: String,
+        method: String,
+        params: Vec<Value>,
+    ) -> RpcResult<T> {
+        let value = self
+            .0
+            .plugin_custom(plugin, method, params)
+            .await
+            .map_err(json_rpc_error_to_error_object)?;
+
+        serde_json::from_value(value).map_err(|e| {
+            ErrorObject::owned(
+                FATAL_JSONRPC_ERROR_CODE,
+                ErrorReporter(e).with_message("error decoding plugin response from json value"),
+                None::<()>,
+            )
+        })
+    }
 }
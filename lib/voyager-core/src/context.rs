@@ -1,5 +1,10 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, RwLock},
+};
 
+use anyhow::anyhow;
 use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -15,7 +20,10 @@ use voyager_rpc::{
 };
 use voyager_vm::QueueError;
 
-use crate::{equivalent_chain_ids::EquivalentChainIds, ibc_spec_handlers::IbcSpecHandlers};
+use crate::{
+    equivalent_chain_ids::EquivalentChainIds, filter::InterestFilters,
+    ibc_spec_handlers::IbcSpecHandlers, supervisor::Supervisor,
+};
 
 pub struct Context {
     pub(crate) state_modules: HashMap<(ChainId, IbcSpecId), WorkerClient>,
@@ -34,6 +42,12 @@ pub struct Context {
 
     pub(crate) plugins: HashMap<String, WorkerClient>,
 
+    /// The interest filters of [`Self::plugins`]. These are part of the context so that both are
+    /// always replaced together on reload.
+    pub(crate) interest_filters: Arc<InterestFilters>,
+
+    pub(crate) supervisor: Supervisor,
+
     pub(crate) equivalent_chain_ids: EquivalentChainIds,
 
     // ibc version id => handler
@@ -78,7 +92,237 @@ const fn default_enabled() -> bool {
     true
 }
 
+/// A shared handle to the current [`Context`].
+///
+/// The context is not available until all plugins and modules have been registered, and is
+/// replaced as a whole when the plugin and module configuration is reloaded. Consumers should
+/// fetch the context once per request, so that they see a consistent view of the registered
+/// plugins and modules.
+#[derive(Clone, Default)]
+pub struct ContextHandle(Arc<RwLock<Option<Arc<Context>>>>);
+
+impl ContextHandle {
+    /// Returns the current context, if it has been loaded.
+    pub fn get(&self) -> Option<Arc<Context>> {
+        self.0.read().expect("lock is poisoned").clone()
+    }
+
+    pub(crate) fn set(&self, context: Context) {
+        *self.0.write().expect("lock is poisoned") = Some(Arc::new(context));
+    }
+}
+
 impl Context {
+    pub(crate) fn new(
+        equivalent_chain_ids: EquivalentChainIds,
+        ibc_spec_handlers: IbcSpecHandlers,
+        supervisor: Supervisor,
+        interest_filters: Arc<InterestFilters>,
+    ) -> Self {
+        Self {
+            state_modules: Default::default(),
+            proof_modules: Default::default(),
+            client_modules: Default::default(),
+            client_bootstrap_modules: Default::default(),
+            finality_modules: Default::default(),
+            chain_consensus_types: Default::default(),
+            client_consensus_types: Default::default(),
+            plugins: Default::default(),
+            interest_filters,
+            supervisor,
+            equivalent_chain_ids,
+            ibc_spec_handlers,
+        }
+    }
+
+    pub(crate) fn register_plugin(
+        &mut self,
+        name: &str,
+        client: WorkerClient,
+    ) -> anyhow::Result<()> {
+        let prev = self.plugins.insert(name.to_owned(), client);
+
+        if prev.is_some() {
+            return Err(anyhow!("multiple plugins configured with name `{name}`"));
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn register_state_module(
+        &mut self,
+        StateModuleInfo {
+            chain_id,
+            ibc_spec_id,
+        }: &StateModuleInfo,
+        client: WorkerClient,
+    ) -> anyhow::Result<()> {
+        for equivalent_chain_id in self
+            .equivalent_chain_ids
+            .equivalents(chain_id)
+            .chain([chain_id])
+        {
+            let prev = self.state_modules.insert(
+                (equivalent_chain_id.clone(), ibc_spec_id.clone()),
+                client.clone(),
+            );
+
+            if prev.is_some() {
+                return Err(anyhow!(
+                    "multiple state modules configured for chain id \
+                    `{equivalent_chain_id}` and IBC version `{ibc_spec_id}`",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn register_proof_module(
+        &mut self,
+        ProofModuleInfo {
+            chain_id,
+            ibc_spec_id,
+        }: &ProofModuleInfo,
+        client: WorkerClient,
+    ) -> anyhow::Result<()> {
+        for equivalent_chain_id in self
+            .equivalent_chain_ids
+            .equivalents(chain_id)
+            .chain([chain_id])
+        {
+            let prev = self.proof_modules.insert(
+                (equivalent_chain_id.clone(), ibc_spec_id.clone()),
+                client.clone(),
+            );
+
+            if prev.is_some() {
+                return Err(anyhow!(
+                    "multiple proof modules configured for chain id \
+                    `{equivalent_chain_id}` and IBC version `{ibc_spec_id}`",
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn register_finality_module(
+        &mut self,
+        FinalityModuleInfo {
+            chain_id,
+            consensus_type,
+        }: &FinalityModuleInfo,
+        client: WorkerClient,
+    ) -> anyhow::Result<()> {
+        for equivalent_chain_id in self
+            .equivalent_chain_ids
+            .equivalents(chain_id)
+            .chain([chain_id])
+        {
+            let prev = self
+                .finality_modules
+                .insert(equivalent_chain_id.clone(), client.clone());
+
+            if prev.is_some() {
+                return Err(anyhow!(
+                    "multiple consensus modules configured for chain id `{}`",
+                    equivalent_chain_id
+                ));
+            }
+
+            let None = self
+                .chain_consensus_types
+                .insert(equivalent_chain_id.clone(), consensus_type.clone())
+            else {
+                unreachable!()
+            };
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn register_client_module(
+        &mut self,
+        ClientModuleInfo {
+            client_type,
+            consensus_type,
+            ibc_interface,
+            ibc_spec_id,
+        }: &ClientModuleInfo,
+        client: WorkerClient,
+    ) -> anyhow::Result<()> {
+        if !self.ibc_spec_handlers.handlers.contains_key(ibc_spec_id) {
+            return Err(anyhow!(
+                "IBC version `{ibc_spec_id}` is not supported in this build of voyager"
+            ));
+        }
+
+        let prev = self.client_modules.insert(
+            (
+                client_type.clone(),
+                ibc_interface.clone(),
+                ibc_spec_id.clone(),
+            ),
+            client,
+        );
+
+        if prev.is_some() {
+            return Err(anyhow!(
+                "multiple client modules configured for client \
+                type `{client_type}`, IBC interface `{ibc_interface}`, \
+                and IBC version `{ibc_spec_id}`",
+            ));
+        }
+
+        if let Some(previous_consensus_type) = self
+            .client_consensus_types
+            .insert(client_type.clone(), consensus_type.clone())
+        {
+            if &previous_consensus_type != consensus_type {
+                return Err(anyhow!(
+                    "inconsistency in client consensus types: \
+                    client type `{client_type}` is registered \
+                    as tracking both `{previous_consensus_type}` \
+                    and `{consensus_type}`"
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn register_client_bootstrap_module(
+        &mut self,
+        ClientBootstrapModuleInfo {
+            client_type,
+            chain_id,
+        }: &ClientBootstrapModuleInfo,
+        client: WorkerClient,
+    ) -> anyhow::Result<()> {
+        for equivalent_chain_id in self
+            .equivalent_chain_ids
+            .equivalents(chain_id)
+            .chain([chain_id])
+        {
+            let prev = self.client_bootstrap_modules.insert(
+                (equivalent_chain_id.clone(), client_type.clone()),
+                client.clone(),
+            );
+
+            if prev.is_some() {
+                return Err(anyhow!(
+                    "multiple client bootstrap modules configured for client \
+                    type `{client_type}` and chain id `{equivalent_chain_id}`",
+                ));
+            }
+
+            // TODO: Check consistency with client_consensus_types and chain_id?
+        }
+
+        Ok(())
+    }
+
     pub fn info(&self) -> InfoResponse {
         let state = self
             .state_modules
@@ -138,6 +382,7 @@ impl Context {
             consensus,
             client,
             client_bootstrap,
+            workers: self.supervisor.health(),
         }
     }
 
@@ -147,6 +392,15 @@ impl Context {
         })
     }
 
+    /// The interest filters of the plugins registered in this context.
+    pub fn interest_filters(&self) -> &Arc<InterestFilters> {
+        &self.interest_filters
+    }
+
+    pub fn supervisor(&self) -> &Supervisor {
+        &self.supervisor
+    }
+
     pub fn equivalent_chain_ids(&self) -> &EquivalentChainIds {
         &self.equivalent_chain_ids
     }
@@ -2,6 +2,7 @@ use std::rc::Rc;
 
 use anyhow::anyhow;
 use jaq_interpret::{Ctx, Filter, FilterT, ParseCtx, RcIter, Val};
+use serde::{Deserialize, Serialize};
 use tracing::{error, instrument, trace};
 use unionlabs::ErrorReporter;
 use voyager_rpc::types::PluginInfo;
@@ -26,6 +27,88 @@ impl InterestFilters {
                 .collect::<anyhow::Result<_>>()?,
         })
     }
+
+    /// Run every filter against `op`, without short-circuiting on the first plugin that takes it.
+    ///
+    /// The returned tags and `remove` flag are exactly what [`InterestFilter::check_interest`] would
+    /// produce for the same op, while [`Simulation::plugins`] additionally reports the result of the
+    /// filters that the engine would never have reached.
+    pub fn simulate(&self, op: &Op<VoyagerMessage>) -> Simulation {
+        let msg_json = Val::from(serde_json::to_value(op.clone()).unwrap());
+
+        let mut simulation = Simulation {
+            plugins: vec![],
+            tags: vec![],
+            remove: false,
+        };
+
+        for (filter, plugin_name) in &self.filters {
+            let result = match run_filter(filter, plugin_name, msg_json.clone()) {
+                Ok(JaqFilterResult::Take(_)) => SimulatedInterest::Take,
+                Ok(JaqFilterResult::Copy(_)) => SimulatedInterest::Copy,
+                Ok(JaqFilterResult::NoInterest) => SimulatedInterest::NoInterest,
+                Err(()) => SimulatedInterest::Failed,
+            };
+
+            let shadowed = simulation.remove
+                && matches!(result, SimulatedInterest::Take | SimulatedInterest::Copy);
+
+            if !simulation.remove {
+                match result {
+                    SimulatedInterest::Take => {
+                        simulation.tags.push(plugin_name.clone());
+                        simulation.remove = true;
+                    }
+                    SimulatedInterest::Copy => simulation.tags.push(plugin_name.clone()),
+                    SimulatedInterest::NoInterest | SimulatedInterest::Failed => {}
+                }
+            }
+
+            simulation.plugins.push(PluginSimulation {
+                plugin: plugin_name.clone(),
+                result,
+                shadowed,
+            });
+        }
+
+        simulation
+    }
+}
+
+/// The outcome of [`InterestFilters::simulate`] for a single op.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Simulation {
+    /// The result of every plugin's filter, in the order the filters are checked in.
+    pub plugins: Vec<PluginSimulation>,
+    /// The tags the op would be assigned. If this is empty, no plugin is interested in the op.
+    pub tags: Vec<String>,
+    /// Whether the op would be removed from the queue.
+    pub remove: bool,
+}
+
+impl Simulation {
+    #[must_use]
+    pub fn is_interesting(&self) -> bool {
+        !self.tags.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginSimulation {
+    pub plugin: String,
+    pub result: SimulatedInterest,
+    /// The filter matched, but a plugin earlier in the list already took the op, so this plugin
+    /// will never see it.
+    pub shadowed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SimulatedInterest {
+    Take,
+    Copy,
+    NoInterest,
+    Failed,
 }
 
 pub fn make_filter(
@@ -185,3 +268,72 @@ pub enum JaqFilterResult<'a> {
     Copy(&'a str),
     Take(&'a str),
 }
+
+#[cfg(test)]
+mod tests {
+    use voyager_vm::{defer, noop};
+
+    use super::*;
+
+    fn filters() -> InterestFilters {
+        InterestFilters::new(
+            [
+                ("a", r#"if ."@type" == "defer" then false else null end"#),
+                ("b", r#"if ."@type" == "defer" then true else null end"#),
+                ("c", "true"),
+                ("d", r#""not a bool""#),
+            ]
+            .into_iter()
+            .map(|(name, interest_filter)| PluginInfo {
+                name: name.to_owned(),
+                interest_filter: interest_filter.to_owned(),
+            })
+            .collect(),
+        )
+        .unwrap()
+    }
+
+    fn check_interest(filters: &InterestFilters, op: &Op<VoyagerMessage>) -> (Vec<String>, bool) {
+        match filters.check_interest(op) {
+            FilterResult::Interest(Interest { tags, remove }) => {
+                (tags.into_iter().map(ToOwned::to_owned).collect(), remove)
+            }
+            FilterResult::NoInterest => (vec![], false),
+        }
+    }
+
+    #[test]
+    fn simulate_matches_check_interest() {
+        let filters = filters();
+
+        let op = defer(1);
+        let simulation = filters.simulate(&op);
+
+        assert_eq!(
+            (simulation.tags.clone(), simulation.remove),
+            check_interest(&filters, &op)
+        );
+        assert_eq!(
+            simulation
+                .plugins
+                .iter()
+                .map(|p| (p.plugin.as_str(), p.result, p.shadowed))
+                .collect::<Vec<_>>(),
+            [
+                ("a", SimulatedInterest::Copy, false),
+                ("b", SimulatedInterest::Take, false),
+                ("c", SimulatedInterest::Take, true),
+                ("d", SimulatedInterest::Failed, false),
+            ]
+        );
+
+        let op = noop();
+        let simulation = filters.simulate(&op);
+
+        assert_eq!(
+            (simulation.tags.clone(), simulation.remove),
+            check_interest(&filters, &op)
+        );
+        assert_eq!(simulation.tags, ["c"]);
+    }
+}
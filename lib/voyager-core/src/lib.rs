@@ -1,10 +1,11 @@
 #![feature(trait_alias, slice_partition_dedup)]
 
 use std::{
-    collections::{HashMap, VecDeque},
+    any::Any,
+    collections::VecDeque,
     net::{IpAddr, Ipv4Addr, SocketAddr},
     panic::AssertUnwindSafe,
-    sync::{Arc, OnceLock},
+    sync::Arc,
     time::Duration,
 };
 
@@ -15,12 +16,9 @@ use futures::{
     Future, FutureExt, StreamExt, TryFutureExt, TryStreamExt,
 };
 use itertools::Itertools;
-use serde::Serialize;
 use serde_json::Value;
 use tokio_util::sync::CancellationToken;
-use tracing::{
-    debug, debug_span, error, info, info_span, instrument, trace, trace_span, warn, Instrument,
-};
+use tracing::{debug, error, info, info_span, instrument, trace, trace_span, Instrument};
 use unionlabs::{traits::Member, ErrorReporter};
 use voyager_message::{
     call::{
@@ -29,34 +27,29 @@ use voyager_message::{
     },
     callback::{AggregateSubmitTxFromOrderedHeaders, Callback},
     data::{Data, IbcDatagram, OrderedHeaders},
-    PluginMessage, VoyagerMessage,
-};
-use voyager_plugin_protocol::{
-    coordinator_server, worker_child_process, WithId, WorkerClient, INVALID_CONFIG_EXIT_CODE,
+    VoyagerMessage,
 };
+use voyager_plugin_protocol::{WithId, INVALID_CONFIG_EXIT_CODE};
 use voyager_primitives::{ClientInfo, IbcSpec, QueryHeight};
 use voyager_rpc::{
-    error_object_to_queue_error, json_rpc_error_to_queue_error, missing_state,
-    types::{
-        ClientBootstrapModuleInfo, ClientModuleInfo, FinalityModuleInfo, PluginInfo,
-        ProofModuleInfo, StateModuleInfo,
-    },
-    ClientModuleClient, PluginClient, VoyagerRpcServer,
+    error_object_to_queue_error, json_rpc_error_to_queue_error, missing_state, types::PluginInfo,
+    ClientModuleClient, PluginClient, VoyagerQueueRpcServer, VoyagerRpcServer,
 };
 use voyager_vm::{
     defer,
     in_memory::InMemoryQueue,
     noop, now,
     pass::{Pass, PassResult},
-    seq, BoxDynError, HandlerFactory, ItemId, Op, Queue, QueueError,
+    promise, seq, BoxDynError, HandlerFactory, ItemId, Op, Queue, QueueError,
 };
 
 use crate::{
-    context::{Context, ModuleConfig, ModulesConfig, PluginConfig},
+    context::{Context, ModulesConfig, PluginConfig},
     equivalent_chain_ids::EquivalentChainIds,
-    filter::InterestFilters,
     ibc_spec_handlers::IbcSpecHandlers,
-    server::Server,
+    registry::{ConfigLoader, Registry},
+    server::{QueueServer, Server},
+    supervisor::{Supervisor, SupervisorConfig},
 };
 
 pub mod cache;
@@ -64,14 +57,16 @@ pub mod context;
 pub mod equivalent_chain_ids;
 pub mod filter;
 pub mod ibc_spec_handlers;
+pub mod registry;
 pub mod server;
+pub mod supervisor;
 
 pub struct Engine<Q: Queue<VoyagerMessage>> {
-    context: Arc<OnceLock<Context>>,
-    interest_filters: InterestFilters,
+    registry: Arc<Registry>,
     cache: cache::Cache,
     queue: Q,
     cancellation_token: CancellationToken,
+    drain_token: CancellationToken,
     // NOTE: non-zero
     num_workers: usize,
     rest_laddr: SocketAddr,
@@ -89,12 +84,14 @@ impl Engine<InMemoryQueue<VoyagerMessage>> {
             equivalent_chain_ids: Default::default(),
             ipc_client_request_timeout: Default::default(),
             cache_config: Default::default(),
+            supervisor_config: Default::default(),
+            config_loader: None,
             metrics_endpoint: Default::default(),
             num_workers: 1,
             rest_laddr: default_rest_laddr(),
             rpc_laddr: default_rpc_laddr(),
             optimizer_delay_milliseconds: default_optimizer_delay_milliseconds(),
-            queue_config: (),
+            queue_config: Default::default(),
         }
     }
 }
@@ -114,8 +111,65 @@ impl<Q: Queue<VoyagerMessage>> Engine<Q> {
         self.cancellation_token.cancel();
     }
 
+    /// Start draining the engine: no new items will be pulled from the queue, and once all
+    /// in-flight items have been processed, the optimizer buffers are flushed and the queue is
+    /// checkpointed, after which [`Self::run`] returns.
+    pub fn drain(&self) {
+        self.drain_token.cancel();
+    }
+
+    /// The registry of all plugins and modules run by this engine.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    fn context(&self) -> Arc<Context> {
+        self.registry
+            .context()
+            .get()
+            .expect("context is set once the engine is built")
+    }
+
+    /// Run a final optimization pass for every plugin and checkpoint the queue. This must only be
+    /// called once all workers and optimizers have stopped.
+    async fn flush(&self) -> Result<(), BoxDynError> {
+        let context = self.context();
+        let interest_filters = context.interest_filters();
+
+        for (_filter, plugin_name) in &interest_filters.filters {
+            info!(%plugin_name, "flushing optimizer");
+
+            let res = self
+                .queue
+                .optimize(
+                    plugin_name,
+                    &**interest_filters,
+                    &plugin_opt_pass(&context, plugin_name),
+                )
+                .await;
+
+            if let Err(error) = res {
+                let error = error
+                    .map_either::<_, _, BoxDynError, BoxDynError>(|x| Box::new(x), |x| Box::new(x))
+                    .into_inner();
+
+                error!(
+                    %plugin_name,
+                    error = %ErrorReporter(&*error),
+                    "error flushing optimizer"
+                );
+            }
+        }
+
+        info!("checkpointing queue");
+
+        self.queue.checkpoint().await?;
+
+        Ok(())
+    }
+
     pub fn server(&self) -> Server {
-        Server::new(self.cache.clone(), self.context.clone())
+        Server::new(self.cache.clone(), self.registry.context().clone())
     }
 
     #[allow(clippy::too_many_lines)]
@@ -137,7 +191,17 @@ impl<Q: Queue<VoyagerMessage>> Engine<Q> {
 
                     let addr = server.local_addr()?;
 
-                    let handle = server.start(self.server().into_rpc());
+                    let mut rpc = self.server().into_rpc();
+                    rpc.merge(
+                        QueueServer::new(
+                            self.queue.clone(),
+                            self.drain_token.clone(),
+                            self.registry.clone(),
+                        )
+                        .into_rpc(),
+                    )?;
+
+                    let handle = server.start(rpc);
 
                     info!("rpc listening on {addr}");
 
@@ -163,121 +227,166 @@ impl<Q: Queue<VoyagerMessage>> Engine<Q> {
                             serde_json::to_value(&op).unwrap()
                         );
 
-                        self.queue.enqueue(op, &self.interest_filters).await?;
+                        self.queue
+                            .enqueue(op, &*self.registry.interest_filters())
+                            .await?;
                     }
 
                     Ok(())
                 })
                 .catch_unwind(),
             ));
+        }
 
-            info!("spawning {} workers", self.num_workers);
+        let drained = async move {
+            let res = loop {
+                let context = self.context();
+                let interest_filters = context.interest_filters().clone();
 
-            for id in 0..self.num_workers {
-                debug!("spawning worker {id}");
+                // workers and optimizers exit once the engine is drained, or once the interest
+                // filters change, in which case they are restarted with the new filters
+                let is_interrupted = || {
+                    self.drain_token.is_cancelled()
+                        || !Arc::ptr_eq(&self.registry.interest_filters(), &interest_filters)
+                };
 
-                tasks.push(Box::pin(
-                    AssertUnwindSafe(
-                        voyager_vm::engine::Engine::new(
-                            self.server(),
-                            &self.queue,
-                            &self.interest_filters,
-                        )
-                        .run()
-                        .for_each(async |res| match res {
-                            Ok(data) => {
-                                debug!(
-                                    data = %serde_json::to_value(&data).unwrap(),
-                                    "received data outside of an aggregation",
-                                );
-                            }
-                            Err(error) => {
-                                error!(
-                                    error = %ErrorReporter(&*error),
-                                    "error processing message"
-                                );
-                            }
-                        })
-                        .map(Ok)
-                        .instrument(trace_span!("engine task", %id)),
-                    )
-                    .catch_unwind(),
-                ));
-            }
+                let mut processors = FuturesUnordered::<Processor>::new();
 
-            for (_filter, plugin_name) in &self.interest_filters.filters {
-                info!(%plugin_name, "spawning optimizer");
-
-                tasks.push(Box::pin(
-                    AssertUnwindSafe(
-                        async {
-                            let plugin_name = plugin_name.clone();
-
-                            let pass = PluginOptPass::new(
-                                self.context
-                                    .get()
-                                    .unwrap()
-                                    .plugin(&plugin_name)
-                                    .expect("plugin exists")
-                                    .client(),
-                            );
+                info!("spawning {} workers", self.num_workers);
 
-                            loop {
-                                trace!("optimizing");
-
-                                let res = self
-                                    .queue
-                                    .optimize(&plugin_name, &self.interest_filters, &pass)
-                                    .await
-                                    .map_err(|e| {
-                                        e.map_either::<_, _, BoxDynError, BoxDynError>(
-                                            |x| Box::new(x),
-                                            |x| Box::new(x),
-                                        )
-                                        .into_inner()
-                                    });
+                for id in 0..self.num_workers {
+                    debug!("spawning worker {id}");
 
-                                if let Err(error) = res {
+                    processors.push(Box::pin(
+                        AssertUnwindSafe(
+                            voyager_vm::engine::Engine::new(
+                                self.server(),
+                                &self.queue,
+                                &*interest_filters,
+                            )
+                            .run_until(&is_interrupted)
+                            .for_each(async |res| match res {
+                                Ok(data) => {
+                                    debug!(
+                                        data = %serde_json::to_value(&data).unwrap(),
+                                        "received data outside of an aggregation",
+                                    );
+                                }
+                                Err(error) => {
                                     error!(
                                         error = %ErrorReporter(&*error),
-                                        "optimization pass returned with error"
+                                        "error processing message"
                                     );
                                 }
+                            })
+                            .map(Ok)
+                            .instrument(trace_span!("engine task", %id)),
+                        )
+                        .catch_unwind(),
+                    ));
+                }
+
+                for (_filter, plugin_name) in &interest_filters.filters {
+                    info!(%plugin_name, "spawning optimizer");
+
+                    let interest_filters = &*interest_filters;
+                    let context = &*context;
+                    let is_interrupted = &is_interrupted;
 
-                                tokio::time::sleep(std::time::Duration::from_millis(
-                                    self.optimizer_delay_milliseconds,
-                                ))
-                                .await;
+                    processors.push(Box::pin(
+                        AssertUnwindSafe(
+                            async move {
+                                let pass = plugin_opt_pass(context, plugin_name);
+
+                                while !is_interrupted() {
+                                    trace!("optimizing");
+
+                                    let res = self
+                                        .queue
+                                        .optimize(plugin_name, interest_filters, &pass)
+                                        .await
+                                        .map_err(|e| {
+                                            e.map_either::<_, _, BoxDynError, BoxDynError>(
+                                                |x| Box::new(x),
+                                                |x| Box::new(x),
+                                            )
+                                            .into_inner()
+                                        });
+
+                                    if let Err(error) = res {
+                                        error!(
+                                            error = %ErrorReporter(&*error),
+                                            "optimization pass returned with error"
+                                        );
+                                    }
+
+                                    self.drain_token
+                                        .run_until_cancelled(tokio::time::sleep(
+                                            std::time::Duration::from_millis(
+                                                self.optimizer_delay_milliseconds,
+                                            ),
+                                        ))
+                                        .await;
+                                }
+
+                                Ok(())
                             }
-                        }
-                        .instrument(info_span!("optimize", %plugin_name)), // .instrument(trace_span!("optimize_verbose", filter = %filter.to_string())),
-                    )
-                    .catch_unwind(),
-                ));
+                            .instrument(info_span!("optimize", %plugin_name)), // .instrument(trace_span!("optimize_verbose", filter = %filter.to_string())),
+                        )
+                        .catch_unwind(),
+                    ));
+                }
+
+                // if a processor fails, the remaining processors are stopped by dropping them
+                if let Err(error) = join_processors(processors).await {
+                    break Err(error);
+                }
+
+                if self.drain_token.is_cancelled() {
+                    break Ok(());
+                }
+
+                info!("interest filters changed, restarting workers and optimizers");
+            };
+
+            // the optimizers are flushed and the queue is checkpointed even if a processor failed,
+            // so that no buffered state is lost
+            match res {
+                Ok(()) => info!("all in-flight items processed"),
+                Err(ProcessorFailed) => error!("processor failed, stopping"),
             }
-        }
 
-        self.cancellation_token
-            .run_until_cancelled(async move {
-                while let Some(res) = tasks.next().await {
-                    match res {
-                        Ok(Ok(())) => {
-                            info!("task exited gracefully");
-                        }
-                        Ok(Err(error)) => {
-                            error!(
-                                error = %ErrorReporter(&*error),
-                                "task returned with an error"
-                            );
-                            break;
-                        }
-                        Err(_err) => {
-                            // can't do anything with dyn Any
-                            error!("task panicked");
-                            break;
-                        }
+            match self.flush().await {
+                Ok(()) => info!("drained"),
+                Err(error) => error!(error = %ErrorReporter(&*error), "error draining"),
+            }
+        };
+
+        let tasks = async move {
+            while let Some(res) = tasks.next().await {
+                match res {
+                    Ok(Ok(())) => {
+                        info!("task exited gracefully");
+                    }
+                    Ok(Err(error)) => {
+                        error!(
+                            error = %ErrorReporter(&*error),
+                            "task returned with an error"
+                        );
+                        break;
+                    }
+                    Err(_err) => {
+                        // can't do anything with dyn Any
+                        error!("task panicked");
+                        break;
                     }
                 }
+            }
+        };
+
+        self.cancellation_token
+            .run_until_cancelled(async move {
+                future::select(Box::pin(drained), Box::pin(tasks)).await;
             })
             .map(|_| ())
     }
@@ -290,6 +399,8 @@ pub struct EngineBuilder<Q: Queue<VoyagerMessage> = InMemoryQueue<VoyagerMessage
     equivalent_chain_ids: EquivalentChainIds,
     ipc_client_request_timeout: Duration,
     cache_config: cache::Config,
+    supervisor_config: SupervisorConfig,
+    config_loader: Option<ConfigLoader>,
     metrics_endpoint: Option<String>,
     ibc_spec_handlers: IbcSpecHandlers,
     num_workers: usize,
@@ -334,6 +445,28 @@ impl<Q: Queue<VoyagerMessage>> EngineBuilder<Q> {
         }
     }
 
+    pub fn with_supervisor_config(self, supervisor_config: SupervisorConfig) -> Self {
+        Self {
+            supervisor_config,
+            ..self
+        }
+    }
+
+    /// Set the loader used to re-read the plugin and module configuration when reloading. If this
+    /// is not set, reloading is not supported.
+    pub fn with_config_loader(
+        self,
+        config_loader: impl Fn() -> anyhow::Result<(Vec<PluginConfig>, ModulesConfig)>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self {
+            config_loader: Some(Arc::new(config_loader)),
+            ..self
+        }
+    }
+
     pub fn with_metrics_endpoint(self, metrics_endpoint: String) -> Self {
         Self {
             metrics_endpoint: Some(metrics_endpoint),
@@ -379,6 +512,8 @@ impl<Q: Queue<VoyagerMessage>> EngineBuilder<Q> {
             equivalent_chain_ids: self.equivalent_chain_ids,
             ipc_client_request_timeout: self.ipc_client_request_timeout,
             cache_config: self.cache_config,
+            supervisor_config: self.supervisor_config,
+            config_loader: self.config_loader,
             metrics_endpoint: self.metrics_endpoint,
             ibc_spec_handlers: self.ibc_spec_handlers,
             num_workers: self.num_workers,
@@ -395,369 +530,31 @@ impl<Q: Queue<VoyagerMessage>> EngineBuilder<Q> {
 
         let queue = Q::new(self.queue_config).await?;
 
-        let mut context_inner = Context {
-            state_modules: Default::default(),
-            proof_modules: Default::default(),
-            client_modules: Default::default(),
-            client_bootstrap_modules: Default::default(),
-            finality_modules: Default::default(),
-            chain_consensus_types: Default::default(),
-            client_consensus_types: Default::default(),
-            plugins: Default::default(),
-            equivalent_chain_ids: self.equivalent_chain_ids,
-            ibc_spec_handlers: self.ibc_spec_handlers,
-        };
-
-        let context = Arc::new(OnceLock::new());
-
-        let mut interest_filters = HashMap::new();
-
         let cache = cache::Cache::new(self.cache_config);
 
-        info!("spawning {} plugins", self.plugin_configs.len());
-
-        stream::iter(self.plugin_configs.into_iter().enumerate())
-            .filter(|(_, plugin_config)| {
-                future::ready(if !plugin_config.enabled {
-                    info!(
-                        plugin_path = %plugin_config.path.to_string_lossy(),
-                        "plugin is not enabled, skipping"
-                    );
-                    false
-                } else {
-                    true
-                })
-            })
-            .zip(stream::repeat(Server::new(cache.clone(), context.clone())))
-            .then(async |((idx, plugin_config), server)| {
-                let plugin_info = info_span!("get_plugin_info", %idx)
-                    .in_scope(|| get_plugin_info(&plugin_config))?;
-
-                debug!("starting rpc server for plugin {}", plugin_info.name);
-
-                tokio::spawn(coordinator_server(&plugin_info.name, server).await?);
-
-                debug!("started rpc server for plugin {}", plugin_info.name);
-
-                Ok((idx, plugin_config, plugin_info))
-            })
-            .try_for_each_concurrent(
-                None,
-                |(
-                    idx,
-                    plugin_config,
-                    PluginInfo {
-                        name,
-                        interest_filter,
-                    },
-                )| {
-                    debug!("registering plugin {}", name);
-
-                    tokio::spawn(worker_child_process(
-                        name.clone(),
-                        plugin_config.path,
-                        cancellation_token.clone(),
-                        [plugin_config.config.to_string()]
-                            .into_iter()
-                            .chain(self.metrics_endpoint.clone()),
-                    ));
-
-                    let rpc_client = WorkerClient::new(&name, self.ipc_client_request_timeout);
-
-                    let prev = context_inner
-                        .plugins
-                        .insert(name.clone(), rpc_client.clone());
-
-                    if prev.is_some() {
-                        return future::ready(Err(anyhow!(
-                            "multiple plugins configured with name `{name}`"
-                        )));
-                    }
-
-                    info!("registered plugin {name}");
-
-                    interest_filters.insert((idx, name), interest_filter);
-
-                    future::ready(Ok(()))
-                },
-            )
-            .await?;
-
-        modules_startup(
-            self.module_configs.state,
-            cancellation_token.clone(),
-            Server::new(cache.clone(), context.clone()),
+        let registry = Arc::new(Registry::new(
+            Supervisor::new(self.supervisor_config),
+            cache.clone(),
+            self.equivalent_chain_ids,
+            self.ibc_spec_handlers,
             self.ipc_client_request_timeout,
-            |info| info.id(),
-            |StateModuleInfo {
-                 chain_id,
-                 ibc_spec_id,
-             },
-             rpc_client| {
-                for equivalent_chain_id in context_inner
-                    .equivalent_chain_ids
-                    .equivalents(chain_id)
-                    .chain([chain_id])
-                {
-                    let prev = context_inner.state_modules.insert(
-                        (equivalent_chain_id.clone(), ibc_spec_id.clone()),
-                        rpc_client.clone(),
-                    );
-
-                    if prev.is_some() {
-                        return Err(anyhow!(
-                            "multiple state modules configured for chain id \
-                            `{equivalent_chain_id}` and IBC version `{ibc_spec_id}`",
-                        ));
-                    }
-                }
-
-                Ok(())
-            },
-            self.metrics_endpoint.clone(),
-        )
-        .await?;
-
-        modules_startup(
-            self.module_configs.proof,
+            self.metrics_endpoint,
+            self.config_loader,
             cancellation_token.clone(),
-            Server::new(cache.clone(), context.clone()),
-            self.ipc_client_request_timeout,
-            |info| info.id(),
-            |ProofModuleInfo {
-                 chain_id,
-                 ibc_spec_id,
-             },
-             rpc_client| {
-                for equivalent_chain_id in context_inner
-                    .equivalent_chain_ids
-                    .equivalents(chain_id)
-                    .chain([chain_id])
-                {
-                    let prev = context_inner.proof_modules.insert(
-                        (equivalent_chain_id.clone(), ibc_spec_id.clone()),
-                        rpc_client.clone(),
-                    );
-
-                    if prev.is_some() {
-                        return Err(anyhow!(
-                            "multiple proof modules configured for chain id \
-                            `{equivalent_chain_id}` and IBC version `{ibc_spec_id}`",
-                        ));
-                    }
-                }
-
-                Ok(())
-            },
-            self.metrics_endpoint.clone(),
-        )
-        .await?;
-
-        modules_startup(
-            self.module_configs.consensus,
-            cancellation_token.clone(),
-            Server::new(cache.clone(), context.clone()),
-            self.ipc_client_request_timeout,
-            |info| info.id(),
-            |FinalityModuleInfo {
-                 chain_id,
-                 consensus_type,
-             },
-             rpc_client| {
-                for equivalent_chain_id in context_inner
-                    .equivalent_chain_ids
-                    .equivalents(chain_id)
-                    .chain([chain_id])
-                {
-                    let prev = context_inner
-                        .finality_modules
-                        .insert(equivalent_chain_id.clone(), rpc_client.clone());
-
-                    if prev.is_some() {
-                        return Err(anyhow!(
-                            "multiple consensus modules configured for chain id `{}`",
-                            equivalent_chain_id
-                        ));
-                    }
-
-                    let None = context_inner
-                        .chain_consensus_types
-                        .insert(equivalent_chain_id.clone(), consensus_type.clone())
-                    else {
-                        unreachable!()
-                    };
-                }
-
-                Ok(())
-            },
-            self.metrics_endpoint.clone(),
-        )
-        .await?;
+        ));
 
-        modules_startup(
-            self.module_configs.client,
-            cancellation_token.clone(),
-            Server::new(cache.clone(), context.clone()),
-            self.ipc_client_request_timeout,
-            |info| info.id(),
-            |ClientModuleInfo {
-                 client_type,
-                 consensus_type,
-                 ibc_interface,
-                 ibc_spec_id,
-             },
-             rpc_client| {
-                if !context_inner
-                    .ibc_spec_handlers
-                    .handlers
-                    .contains_key(ibc_spec_id)
-                {
-                    return Err(anyhow!(
-                        "IBC version `{ibc_spec_id}` is not supported in this build of voyager"
-                    ));
-                }
-
-                let prev = context_inner.client_modules.insert(
-                    (
-                        client_type.clone(),
-                        ibc_interface.clone(),
-                        ibc_spec_id.clone(),
-                    ),
-                    rpc_client.clone(),
-                );
-
-                if prev.is_some() {
-                    return Err(anyhow!(
-                        "multiple client modules configured for client \
-                        type `{client_type}`, IBC interface `{ibc_interface}`, \
-                        and IBC version `{ibc_spec_id}`",
-                    ));
-                }
-
-                if let Some(previous_consensus_type) = context_inner
-                    .client_consensus_types
-                    .insert(client_type.clone(), consensus_type.clone())
-                {
-                    if previous_consensus_type != consensus_type {
-                        return Err(anyhow!(
-                            "inconsistency in client consensus types: \
-                            client type `{client_type}` is registered \
-                            as tracking both `{previous_consensus_type}` \
-                            and `{consensus_type}`"
-                        ));
-                    }
-                }
-
-                Ok(())
-            },
-            self.metrics_endpoint.clone(),
-        )
-        .await?;
-
-        modules_startup(
-            self.module_configs.client_bootstrap,
-            cancellation_token.clone(),
-            Server::new(cache.clone(), context.clone()),
-            self.ipc_client_request_timeout,
-            |info| info.id(),
-            |ClientBootstrapModuleInfo {
-                 client_type,
-                 chain_id,
-             },
-             rpc_client| {
-                for equivalent_chain_id in context_inner
-                    .equivalent_chain_ids
-                    .equivalents(chain_id)
-                    .chain([chain_id])
-                {
-                    let prev = context_inner.client_bootstrap_modules.insert(
-                        (equivalent_chain_id.clone(), client_type.clone()),
-                        rpc_client.clone(),
-                    );
-
-                    if prev.is_some() {
-                        return Err(anyhow!(
-                            "multiple client bootstrap modules configured for client \
-                            type `{client_type}` and chain id `{equivalent_chain_id}`",
-                        ));
-                    }
-
-                    // TODO: Check consistency with client_consensus_types and chain_id?
-
-                    // if let Some(previous_consensus_type) = modules
-                    //     .client_consensus_types
-                    //     .insert(client_type.clone(), consensus_type.clone())
-                    // {
-                    //     if previous_consensus_type != consensus_type {
-                    //         return Err(anyhow!(
-                    //             "inconsistency in client consensus types: \
-                    //             client type `{client_type}` is registered \
-                    //             as tracking both `{previous_consensus_type}` \
-                    //             and `{consensus_type}`"
-                    //         ));
-                    //     }
-                    // }
-                }
-
-                Ok(())
-            },
-            self.metrics_endpoint.clone(),
-        )
-        .await?;
-
-        info!("checking for plugin health...");
-
-        let futures = context_inner
-            .plugins
-            .iter()
-            .map(|(name, client)| async move {
-                match client
-                    .inner()
-                    .wait_until_connected(Duration::from_secs(10))
-                    .instrument(debug_span!("health check", %name))
-                    .await
-                {
-                    Ok(()) => {
-                        info!("plugin {name} connected")
-                    }
-                    Err(_) => {
-                        warn!("plugin {name} failed to connect after 10 seconds")
-                    }
-                }
-            })
-            .collect::<FuturesUnordered<_>>();
-
-        match cancellation_token
-            .run_until_cancelled(futures.collect::<Vec<_>>())
-            .await
-        {
-            Some(_) => {}
-            None => return Err(anyhow!("startup error")),
-        }
+        info!("spawning {} plugins", self.plugin_configs.len());
 
-        context
-            .set(context_inner)
-            .ok()
-            .expect("context is only set once");
+        registry
+            .apply(self.plugin_configs, self.module_configs)
+            .await?;
 
         info!("started");
 
-        let interest_filters = InterestFilters::new(
-            interest_filters
-                .into_iter()
-                .sorted_unstable_by(|((a, _), _), ((b, _), _)| a.cmp(b))
-                .map(|((_, k), v)| (k, v))
-                .map(|(name, interest_filter)| PluginInfo {
-                    name,
-                    interest_filter,
-                })
-                .collect(),
-        )?;
-
         Ok(Engine {
-            interest_filters,
+            registry,
             cancellation_token,
-            context,
+            drain_token: CancellationToken::new(),
             cache,
             queue,
             num_workers: self.num_workers,
@@ -1081,15 +878,24 @@ impl voyager_vm::Handler<VoyagerMessage> for Handler {
                 }
             }
 
-            Call::Plugin(PluginMessage { plugin, message }) => {
+            Call::Plugin(plugin_message) => {
+                let ctx = self.server.context().map_err(error_object_to_queue_error)?;
+
+                if let Some(defer_seconds) =
+                    ctx.supervisor().defer_while_down(&plugin_message.plugin)
+                {
+                    debug!(plugin = %plugin_message.plugin, "plugin is down, deferring call");
+
+                    return Ok(seq([
+                        defer(now() + defer_seconds),
+                        voyager_vm::call(Call::Plugin(plugin_message)),
+                    ]));
+                }
+
                 Ok(PluginClient::<Value, Value>::call(
-                    &self
-                        .server
-                        .context()
-                        .map_err(error_object_to_queue_error)?
-                        .plugin(&plugin)?
+                    &ctx.plugin(&plugin_message.plugin)?
                         .with_id(self.server.id()),
-                    message,
+                    plugin_message.message,
                 )
                 .await
                 .map_err(json_rpc_error_to_queue_error)?)
@@ -1143,17 +949,13 @@ impl voyager_vm::Handler<VoyagerMessage> for Handler {
                     .ok_or_else(missing_state("client not found", None))
                     .map_err(error_object_to_queue_error)?;
 
-                let client_module = self
-                    .server
-                    .context()
-                    .map_err(error_object_to_queue_error)?
+                let ctx = self.server.context().map_err(error_object_to_queue_error)?;
+
+                let client_module = ctx
                     .client_module(&client_type, &ibc_interface, &ibc_spec_id)?
                     .with_id(self.server.id());
 
-                let ibc_spec_handler = self
-                    .server
-                    .context()
-                    .map_err(error_object_to_queue_error)?
+                let ibc_spec_handler = ctx
                     .ibc_spec_handlers
                     .get(&ibc_spec_id)
                     .map_err(error_object_to_queue_error)?;
@@ -1190,15 +992,24 @@ impl voyager_vm::Handler<VoyagerMessage> for Handler {
                         .await?,
                 }))
             }
-            Callback::Plugin(PluginMessage { plugin, message }) => {
+            Callback::Plugin(plugin_message) => {
+                let ctx = self.server.context().map_err(error_object_to_queue_error)?;
+
+                if let Some(defer_seconds) =
+                    ctx.supervisor().defer_while_down(&plugin_message.plugin)
+                {
+                    debug!(plugin = %plugin_message.plugin, "plugin is down, deferring callback");
+
+                    return Ok(seq([
+                        defer(now() + defer_seconds),
+                        promise([], data, Callback::Plugin(plugin_message)),
+                    ]));
+                }
+
                 Ok(PluginClient::<Value, Value>::callback(
-                    &self
-                        .server
-                        .context()
-                        .map_err(error_object_to_queue_error)?
-                        .plugin(&plugin)?
+                    &ctx.plugin(&plugin_message.plugin)?
                         .with_id(self.server.id()),
-                    message,
+                    plugin_message.message,
                     data,
                 )
                 .await
@@ -1248,76 +1059,6 @@ pub fn get_plugin_info(plugin_config: &PluginConfig) -> anyhow::Result<PluginInf
     Ok(serde_json::from_slice(&output.stdout).unwrap())
 }
 
-async fn modules_startup<Info: Serialize + Clone + Unpin + Send + 'static>(
-    configs: Vec<ModuleConfig<Info>>,
-    cancellation_token: CancellationToken,
-    server: Server,
-    ipc_client_request_timeout: Duration,
-    id_f: fn(&Info) -> String,
-    mut push_f: impl FnMut(&Info, WorkerClient) -> anyhow::Result<()>,
-    metrics_endpoint: Option<String>,
-) -> anyhow::Result<()> {
-    stream::iter(configs)
-        .filter(|module_config| {
-            future::ready(if !module_config.enabled {
-                info!(
-                    module_path = %module_config.path.to_string_lossy(),
-                    "module is not enabled, skipping"
-                );
-                false
-            } else {
-                true
-            })
-        })
-        .zip(stream::repeat(server.clone()))
-        .map::<anyhow::Result<_>, _>(anyhow::Result::Ok)
-        .try_filter_map(|(module_config, server)| async move {
-            if !module_config.enabled {
-                info!(
-                    module_path = %module_config.path.to_string_lossy(),
-                    "module is not enabled, skipping"
-                );
-                anyhow::Result::Ok(None)
-            } else {
-                debug!(
-                    "starting rpc server for module {}",
-                    id_f(&module_config.info)
-                );
-                tokio::spawn(coordinator_server(&id_f(&module_config.info), server).await?);
-
-                anyhow::Result::Ok(Some(module_config))
-            }
-        })
-        .try_collect::<FuturesUnordered<_>>()
-        .await?
-        .into_iter()
-        .try_for_each(|module_config| {
-            let id = id_f(&module_config.info);
-
-            debug!("registering module {}", id);
-
-            tokio::spawn(worker_child_process(
-                id.clone(),
-                module_config.path,
-                cancellation_token.clone(),
-                [
-                    module_config.config.to_string(),
-                    serde_json::to_string(&module_config.info).unwrap(),
-                ]
-                .into_iter()
-                .chain(metrics_endpoint.clone()),
-            ));
-
-            let rpc_client = WorkerClient::new(&id, ipc_client_request_timeout);
-
-            push_f(&module_config.info, rpc_client)?;
-
-            info!("registered module {id}");
-
-            Ok(())
-        })
-}
-
 pub mod api {
     use std::net::SocketAddr;
 
@@ -1358,6 +1099,15 @@ pub mod api {
     }
 }
 
+fn plugin_opt_pass<'a>(context: &'a Context, plugin_name: &str) -> impl Pass<VoyagerMessage> + 'a {
+    PluginOptPass::new(
+        context
+            .plugin(plugin_name)
+            .expect("the interest filters and plugins of a context are consistent")
+            .client(),
+    )
+}
+
 pub struct PluginOptPass<T> {
     client: T,
 }
@@ -1379,6 +1129,39 @@ impl<T: PluginClient<Value, Value> + Send + Sync> Pass<VoyagerMessage> for Plugi
     }
 }
 
+/// A worker or optimizer spawned by [`Engine::run`].
+type Processor<'a> = BoxFuture<'a, Result<Result<(), BoxDynError>, Box<dyn Any + Send>>>;
+
+#[derive(Debug, PartialEq)]
+struct ProcessorFailed;
+
+/// Wait for all `processors` to exit, returning early if any of them returns an error or panics.
+async fn join_processors(
+    mut processors: FuturesUnordered<Processor<'_>>,
+) -> Result<(), ProcessorFailed> {
+    while let Some(res) = processors.next().await {
+        match res {
+            Ok(Ok(())) => {
+                debug!("processor exited");
+            }
+            Ok(Err(error)) => {
+                error!(
+                    error = %ErrorReporter(&*error),
+                    "processor returned with an error"
+                );
+                return Err(ProcessorFailed);
+            }
+            Err(_err) => {
+                // can't do anything with dyn Any
+                error!("processor panicked");
+                return Err(ProcessorFailed);
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[must_use]
 #[inline]
 pub const fn default_rest_laddr() -> SocketAddr {
@@ -1407,3 +1190,69 @@ pub const fn default_optimizer_delay_milliseconds() -> u64 {
 pub const fn default_ipc_client_request_timeout() -> Duration {
     Duration::new(60, 0)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::pending,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+
+    /// A processor that never exits, and records whether it has been dropped.
+    fn pending_processor(dropped: &AtomicBool) -> Processor<'_> {
+        struct OnDrop<'a>(&'a AtomicBool);
+
+        impl Drop for OnDrop<'_> {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let on_drop = OnDrop(dropped);
+
+        Box::pin(async move {
+            let _on_drop = on_drop;
+            pending().await
+        })
+    }
+
+    #[tokio::test]
+    async fn join_processors_stops_on_error() {
+        let dropped = AtomicBool::new(false);
+
+        let processors = FuturesUnordered::<Processor>::new();
+        processors.push(pending_processor(&dropped));
+        processors.push(Box::pin(async { Ok(Err("error".into())) }));
+
+        assert_eq!(join_processors(processors).await, Err(ProcessorFailed));
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn join_processors_stops_on_panic() {
+        let dropped = AtomicBool::new(false);
+
+        let processors = FuturesUnordered::<Processor>::new();
+        processors.push(pending_processor(&dropped));
+        processors.push(Box::pin(
+            AssertUnwindSafe(async { panic!("processor panicked") }).catch_unwind(),
+        ));
+
+        assert_eq!(join_processors(processors).await, Err(ProcessorFailed));
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn join_processors_waits_for_all_processors() {
+        let processors = FuturesUnordered::<Processor>::new();
+        processors.push(Box::pin(async { Ok(Ok(())) }));
+        processors.push(Box::pin(async {
+            tokio::task::yield_now().await;
+            Ok(Ok(()))
+        }));
+
+        assert_eq!(join_processors(processors).await, Ok(()));
+    }
+}
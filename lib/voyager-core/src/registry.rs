@@ -0,0 +1,501 @@
+//! The set of plugins and modules run by an [`Engine`](crate::Engine).
+//!
+//! The [`Registry`] is responsible for resolving the plugin and module configuration into the
+//! workers to run, registering them in the [`Context`], and reloading this configuration at
+//! runtime. On reload, only the workers whose configuration changed are touched; everything else
+//! (including the queue and the cache) is left as-is.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use futures::{future, stream::FuturesUnordered, StreamExt};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, debug_span, info, info_span, warn, Instrument};
+use voyager_plugin_protocol::{coordinator_server, WorkerClient};
+use voyager_rpc::types::{
+    ClientBootstrapModuleInfo, ClientModuleInfo, FinalityModuleInfo, PluginInfo, ProofModuleInfo,
+    ReloadResponse, StateModuleInfo, WorkerKind,
+};
+
+use crate::{
+    cache::Cache,
+    context::{Context, ContextHandle, ModuleConfig, ModulesConfig, PluginConfig},
+    equivalent_chain_ids::EquivalentChainIds,
+    filter::InterestFilters,
+    get_plugin_info,
+    ibc_spec_handlers::IbcSpecHandlers,
+    server::Server,
+    supervisor::Supervisor,
+};
+
+/// Loads the current plugin and module configuration, used when reloading.
+pub type ConfigLoader =
+    Arc<dyn Fn() -> anyhow::Result<(Vec<PluginConfig>, ModulesConfig)> + Send + Sync>;
+
+pub struct Registry {
+    supervisor: Supervisor,
+    context: ContextHandle,
+    cache: Cache,
+    equivalent_chain_ids: EquivalentChainIds,
+    ibc_spec_handlers: IbcSpecHandlers,
+    ipc_client_request_timeout: Duration,
+    metrics_endpoint: Option<String>,
+    config_loader: Option<ConfigLoader>,
+    cancellation_token: CancellationToken,
+    /// Held for the entire duration of a reload, ensuring that reloads are never interleaved.
+    state: Mutex<RegistryState>,
+}
+
+#[derive(Default)]
+struct RegistryState {
+    workers: BTreeMap<String, WorkerSpec>,
+    plugins: Vec<PluginInfo>,
+    clients: HashMap<String, WorkerClient>,
+    /// The workers that a coordinator server has been started for. These servers are never
+    /// stopped, and are reused if a worker with the same name is started again.
+    coordinator_servers: HashSet<String>,
+}
+
+/// A worker process, and how it is registered in the [`Context`].
+#[derive(Debug, Clone, PartialEq)]
+struct WorkerSpec {
+    path: PathBuf,
+    args: Vec<String>,
+    registration: Registration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Registration {
+    Plugin,
+    State(StateModuleInfo),
+    Proof(ProofModuleInfo),
+    Finality(FinalityModuleInfo),
+    Client(ClientModuleInfo),
+    ClientBootstrap(ClientBootstrapModuleInfo),
+}
+
+impl Registration {
+    fn kind(&self) -> WorkerKind {
+        match self {
+            Registration::Plugin => WorkerKind::Plugin,
+            _ => WorkerKind::Module,
+        }
+    }
+}
+
+/// The resolved workers for a plugin and module configuration.
+#[derive(Default)]
+struct Workers {
+    workers: BTreeMap<String, WorkerSpec>,
+    /// The info of all enabled plugins, in the order they were configured in.
+    plugins: Vec<PluginInfo>,
+}
+
+impl Workers {
+    fn resolve(
+        plugin_configs: Vec<PluginConfig>,
+        module_configs: ModulesConfig,
+        metrics_endpoint: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let mut this = Self::default();
+
+        for (idx, plugin_config) in plugin_configs.into_iter().enumerate() {
+            if !plugin_config.enabled {
+                info!(
+                    plugin_path = %plugin_config.path.to_string_lossy(),
+                    "plugin is not enabled, skipping"
+                );
+                continue;
+            }
+
+            let plugin_info =
+                info_span!("get_plugin_info", %idx).in_scope(|| get_plugin_info(&plugin_config))?;
+
+            this.insert(
+                plugin_info.name.clone(),
+                WorkerSpec {
+                    path: plugin_config.path,
+                    args: [plugin_config.config.to_string()]
+                        .into_iter()
+                        .chain(metrics_endpoint.map(ToOwned::to_owned))
+                        .collect(),
+                    registration: Registration::Plugin,
+                },
+            )?;
+
+            this.plugins.push(plugin_info);
+        }
+
+        this.insert_modules(
+            module_configs.state,
+            StateModuleInfo::id,
+            Registration::State,
+            metrics_endpoint,
+        )?;
+        this.insert_modules(
+            module_configs.proof,
+            ProofModuleInfo::id,
+            Registration::Proof,
+            metrics_endpoint,
+        )?;
+        this.insert_modules(
+            module_configs.consensus,
+            FinalityModuleInfo::id,
+            Registration::Finality,
+            metrics_endpoint,
+        )?;
+        this.insert_modules(
+            module_configs.client,
+            ClientModuleInfo::id,
+            Registration::Client,
+            metrics_endpoint,
+        )?;
+        this.insert_modules(
+            module_configs.client_bootstrap,
+            ClientBootstrapModuleInfo::id,
+            Registration::ClientBootstrap,
+            metrics_endpoint,
+        )?;
+
+        Ok(this)
+    }
+
+    fn insert_modules<Info: Serialize>(
+        &mut self,
+        configs: Vec<ModuleConfig<Info>>,
+        id_f: fn(&Info) -> String,
+        registration_f: fn(Info) -> Registration,
+        metrics_endpoint: Option<&str>,
+    ) -> anyhow::Result<()> {
+        for module_config in configs {
+            if !module_config.enabled {
+                info!(
+                    module_path = %module_config.path.to_string_lossy(),
+                    "module is not enabled, skipping"
+                );
+                continue;
+            }
+
+            let args = [
+                module_config.config.to_string(),
+                serde_json::to_string(&module_config.info).unwrap(),
+            ]
+            .into_iter()
+            .chain(metrics_endpoint.map(ToOwned::to_owned))
+            .collect();
+
+            self.insert(
+                id_f(&module_config.info),
+                WorkerSpec {
+                    path: module_config.path,
+                    args,
+                    registration: registration_f(module_config.info),
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn insert(&mut self, name: String, spec: WorkerSpec) -> anyhow::Result<()> {
+        if self.workers.contains_key(&name) {
+            return Err(anyhow!(
+                "multiple plugins or modules configured with name `{name}`"
+            ));
+        }
+
+        self.workers.insert(name, spec);
+
+        Ok(())
+    }
+}
+
+impl Registry {
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        supervisor: Supervisor,
+        cache: Cache,
+        equivalent_chain_ids: EquivalentChainIds,
+        ibc_spec_handlers: IbcSpecHandlers,
+        ipc_client_request_timeout: Duration,
+        metrics_endpoint: Option<String>,
+        config_loader: Option<ConfigLoader>,
+        cancellation_token: CancellationToken,
+    ) -> Self {
+        Self {
+            supervisor,
+            context: ContextHandle::default(),
+            cache,
+            equivalent_chain_ids,
+            ibc_spec_handlers,
+            ipc_client_request_timeout,
+            metrics_endpoint,
+            config_loader,
+            cancellation_token,
+            state: Mutex::default(),
+        }
+    }
+
+    pub fn context(&self) -> &ContextHandle {
+        &self.context
+    }
+
+    /// The interest filters of the currently registered plugins.
+    ///
+    /// These are only replaced when the set of registered plugins changes, so
+    /// [`Arc::ptr_eq`] can be used to check whether they have changed.
+    pub fn interest_filters(&self) -> Arc<InterestFilters> {
+        self.context.get().map_or_else(
+            || Arc::new(InterestFilters { filters: vec![] }),
+            |context| context.interest_filters().clone(),
+        )
+    }
+
+    /// Reload the plugin and module configuration with the configured [`ConfigLoader`].
+    pub async fn reload(&self) -> anyhow::Result<ReloadResponse> {
+        let config_loader = self
+            .config_loader
+            .as_ref()
+            .ok_or_else(|| anyhow!("no config loader is configured, unable to reload"))?;
+
+        let (plugin_configs, module_configs) = config_loader()?;
+
+        self.apply(plugin_configs, module_configs).await
+    }
+
+    fn build_context(
+        &self,
+        workers: &BTreeMap<String, WorkerSpec>,
+        clients: &HashMap<String, WorkerClient>,
+        interest_filters: Arc<InterestFilters>,
+    ) -> anyhow::Result<Context> {
+        let mut context = Context::new(
+            self.equivalent_chain_ids.clone(),
+            self.ibc_spec_handlers.clone(),
+            self.supervisor.clone(),
+            interest_filters,
+        );
+
+        for (name, spec) in workers {
+            let client = clients[name].clone();
+
+            match &spec.registration {
+                Registration::Plugin => context.register_plugin(name, client),
+                Registration::State(info) => context.register_state_module(info, client),
+                Registration::Proof(info) => context.register_proof_module(info, client),
+                Registration::Finality(info) => context.register_finality_module(info, client),
+                Registration::Client(info) => context.register_client_module(info, client),
+                Registration::ClientBootstrap(info) => {
+                    context.register_client_bootstrap_module(info, client)
+                }
+            }?;
+        }
+
+        Ok(context)
+    }
+
+    /// Supervise the worker `name`, returning the token that is cancelled if it exits with an
+    /// invalid config. Every worker gets it's own token, so that this only stops the worker
+    /// itself, not the entire relayer.
+    fn supervise(&self, name: &str, spec: &WorkerSpec, client: &WorkerClient) -> CancellationToken {
+        let token = self.cancellation_token.child_token();
+
+        self.supervisor.supervise(
+            name.to_owned(),
+            spec.registration.kind(),
+            spec.path.clone(),
+            spec.args.clone(),
+            client.clone(),
+            &token,
+        );
+
+        token
+    }
+
+    /// Apply the provided plugin and module configuration, starting, stopping, and restarting
+    /// workers as required.
+    ///
+    /// The new configuration is fully validated before any workers are touched; if it is invalid,
+    /// the currently running workers are left as-is. If any started or restarted worker exits with
+    /// an invalid config, the previous workers are restored and an error is returned.
+    pub(crate) async fn apply(
+        &self,
+        plugin_configs: Vec<PluginConfig>,
+        module_configs: ModulesConfig,
+    ) -> anyhow::Result<ReloadResponse> {
+        let mut state = self.state.lock().await;
+
+        let Workers { workers, plugins } = Workers::resolve(
+            plugin_configs,
+            module_configs,
+            self.metrics_endpoint.as_deref(),
+        )?;
+
+        // the interest filters are only rebuilt if the plugins changed, see
+        // `Registry::interest_filters`
+        let interest_filters = if state.plugins == plugins {
+            self.interest_filters()
+        } else {
+            Arc::new(InterestFilters::new(plugins.clone())?)
+        };
+
+        let mut started = vec![];
+        let mut stopped = vec![];
+        let mut restarted = vec![];
+
+        for name in state.workers.keys() {
+            if !workers.contains_key(name) {
+                stopped.push(name.clone());
+            }
+        }
+
+        for (name, spec) in &workers {
+            match state.workers.get(name) {
+                None => started.push(name.clone()),
+                Some(prev) if prev != spec => restarted.push(name.clone()),
+                Some(_) => {}
+            }
+        }
+
+        let clients = workers
+            .keys()
+            .map(|name| {
+                let client =
+                    state.clients.get(name).cloned().unwrap_or_else(|| {
+                        WorkerClient::new(name, self.ipc_client_request_timeout)
+                    });
+
+                (name.clone(), client)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let context = match self.build_context(&workers, &clients, interest_filters) {
+            Ok(context) => context,
+            Err(err) => {
+                for name in &started {
+                    clients[name].inner().shutdown();
+                }
+
+                return Err(err);
+            }
+        };
+
+        for name in &started {
+            if !state.coordinator_servers.contains(name) {
+                debug!("starting rpc server for {name}");
+
+                tokio::spawn(
+                    coordinator_server(name, Server::new(self.cache.clone(), self.context.clone()))
+                        .await?,
+                );
+
+                state.coordinator_servers.insert(name.clone());
+            }
+        }
+
+        for name in stopped.iter().chain(&restarted) {
+            info!("stopping {name}");
+
+            self.supervisor.stop(name).await;
+        }
+
+        let mut tokens = vec![];
+
+        for name in started.iter().chain(&restarted) {
+            info!("starting {name}");
+
+            tokens.push((name, self.supervise(name, &workers[name], &clients[name])));
+        }
+
+        info!("checking for worker health...");
+
+        let invalid_config = tokens
+            .iter()
+            .map(|&(name, ref token)| {
+                let client = &clients[name];
+
+                async move {
+                    let res = token
+                        .run_until_cancelled(
+                            client
+                                .inner()
+                                .wait_until_connected(Duration::from_secs(10))
+                                .instrument(debug_span!("health check", %name)),
+                        )
+                        .await;
+
+                    match res {
+                        Some(Ok(())) => {
+                            info!("{name} connected");
+                            None
+                        }
+                        Some(Err(_)) => {
+                            warn!("{name} failed to connect after 10 seconds");
+                            None
+                        }
+                        None => Some(name.as_str()),
+                    }
+                }
+            })
+            .collect::<FuturesUnordered<_>>()
+            .filter_map(future::ready)
+            .collect::<Vec<_>>()
+            .await;
+
+        if !invalid_config.is_empty() || self.cancellation_token.is_cancelled() {
+            let err = if self.cancellation_token.is_cancelled() {
+                anyhow!("startup error")
+            } else {
+                anyhow!("invalid config for {}", invalid_config.join(", "))
+            };
+
+            for (name, _) in &tokens {
+                self.supervisor.stop(name).await;
+            }
+
+            for name in &started {
+                clients[name].inner().shutdown();
+            }
+
+            for name in stopped.iter().chain(&restarted) {
+                info!("restoring {name}");
+
+                self.supervise(name, &state.workers[name], &state.clients[name]);
+            }
+
+            return Err(err);
+        }
+
+        self.context.set(context);
+
+        for name in &stopped {
+            if let Some(client) = state.clients.get(name) {
+                client.inner().shutdown();
+            }
+        }
+
+        state.workers = workers;
+        state.plugins = plugins;
+        state.clients = clients;
+
+        info!(
+            started = started.len(),
+            stopped = stopped.len(),
+            restarted = restarted.len(),
+            "applied plugin and module configuration"
+        );
+
+        Ok(ReloadResponse {
+            started,
+            stopped,
+            restarted,
+        })
+    }
+}
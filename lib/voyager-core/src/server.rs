@@ -1,6 +1,6 @@
 // #![warn(clippy::unwrap_used)]
 
-use std::sync::{Arc, OnceLock};
+use std::sync::Arc;
 
 use anyhow::{anyhow, Context as _};
 use futures::TryFutureExt;
@@ -10,8 +10,10 @@ use jsonrpsee::{
     Extensions,
 };
 use serde_json::Value;
-use tracing::{debug, info_span, instrument, trace};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, info_span, instrument, trace};
 use unionlabs::{ibc::core::client::height::Height, primitives::Bytes, ErrorReporter};
+use voyager_message::VoyagerMessage;
 use voyager_plugin_protocol::WithId;
 use voyager_primitives::{
     ChainId, ClientInfo, ClientStateMeta, ClientType, ConsensusStateMeta, IbcInterface, IbcSpec,
@@ -20,23 +22,25 @@ use voyager_primitives::{
 use voyager_rpc::{
     json_rpc_error_to_error_object,
     types::{
-        IbcProofResponse, IbcStateResponse, InfoResponse, SelfClientStateResponse,
+        IbcProofResponse, IbcStateResponse, InfoResponse, ReloadResponse, SelfClientStateResponse,
         SelfConsensusStateResponse,
     },
     ClientBootstrapModuleClient, ClientModuleClient, FinalityModuleClient, PluginClient,
-    RawProofModuleClient, RawStateModuleClient, VoyagerRpcServer, FATAL_JSONRPC_ERROR_CODE,
+    RawProofModuleClient, RawStateModuleClient, VoyagerQueueRpcServer, VoyagerRpcServer,
+    FATAL_JSONRPC_ERROR_CODE,
 };
 use voyager_types::{IbcProof, RawClientId};
-use voyager_vm::ItemId;
+use voyager_vm::{HistoryItem, ItemId, Queue};
 
 use crate::{
     cache::{ClientInfoRequest, StateRequest},
-    context::Context,
+    context::{Context, ContextHandle},
+    registry::Registry,
 };
 
 #[derive(Clone)]
 pub struct Server {
-    context: Arc<OnceLock<Context>>,
+    context: ContextHandle,
     cache: crate::cache::Cache,
     item_id: Option<ItemId>,
 }
@@ -45,7 +49,7 @@ pub struct Server {
 pub struct ServerInner {}
 
 impl Server {
-    pub fn new(cache: crate::cache::Cache, context: Arc<OnceLock<Context>>) -> Self {
+    pub fn new(cache: crate::cache::Cache, context: ContextHandle) -> Self {
         Server {
             context,
             cache,
@@ -73,8 +77,8 @@ impl Server {
         }
     }
 
-    /// Returns the contained context, if it has been loaded.
-    pub fn context(&self) -> RpcResult<&Context> {
+    /// Returns the current context, if it has been loaded.
+    pub fn context(&self) -> RpcResult<Arc<Context>> {
         self.context
             .get()
             .ok_or_else(|| ErrorObject::owned(-2, "server has not started", None::<()>))
@@ -245,8 +249,7 @@ impl Server {
                     return Ok(None);
                 };
 
-                let ibc_spec_handler = self
-                    .context()?
+                let ibc_spec_handler = context
                     .ibc_spec_handlers
                     .handlers
                     .get(ibc_spec_id)
@@ -339,8 +342,7 @@ impl Server {
                     return Ok(None);
                 };
 
-                let ibc_spec_handler = self
-                    .context()?
+                let ibc_spec_handler = context
                     .ibc_spec_handlers
                     .handlers
                     .get(ibc_spec_id)
@@ -427,8 +429,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("query");
 
-                let state_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let state_module = context
                     .state_module(&chain_id, &ibc_spec_id)?
                     .with_id(self.item_id);
 
@@ -459,8 +462,9 @@ impl Server {
 
                 let height = self.query_height(&chain_id, height).await?;
 
-                let state_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let state_module = context
                     .state_module(&chain_id, &ibc_spec_id)?
                     .with_id(self.item_id);
 
@@ -511,8 +515,9 @@ impl Server {
 
                 debug!("fetching ibc proof");
 
-                let proof_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let proof_module = context
                     .proof_module(&chain_id, &ibc_spec_id)?
                     .with_id(self.item_id);
 
@@ -548,8 +553,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("fetching ibc state");
 
-                let state_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let state_module = context
                     .state_module(chain_id, &P::Spec::ID)?
                     .with_id(self.item_id);
 
@@ -596,8 +602,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("fetching ibc state");
 
-                let proof_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let proof_module = context
                     .proof_module(chain_id, &P::Spec::ID)?
                     .with_id(self.item_id);
 
@@ -634,8 +641,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("querying self client state");
 
-                let client_bootstrap_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let client_bootstrap_module = context
                     .client_bootstrap_module(&chain_id, &client_type)?
                     .with_id(self.item_id);
 
@@ -664,8 +672,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("querying self consensus state");
 
-                let client_bootstrap_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let client_bootstrap_module = context
                     .client_bootstrap_module(&chain_id, &client_type)?
                     .with_id(self.item_id);
 
@@ -697,8 +706,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("encoding proof");
 
-                let client_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let client_module = context
                     .client_module(client_type, ibc_interface, ibc_spec_id)?
                     .with_id(self.item_id);
 
@@ -727,8 +737,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("encoding header");
 
-                let client_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let client_module = context
                     .client_module(client_type, ibc_interface, ibc_spec_id)?
                     .with_id(self.item_id);
 
@@ -757,8 +768,9 @@ impl Server {
             .in_scope(|| async {
                 trace!("decoding client state meta");
 
-                let client_module = self
-                    .context()?
+                let context = self.context()?;
+
+                let client_module = context
                     .client_module(client_type, ibc_interface, ibc_spec_id)?
                     .with_id(self.item_id);
 
@@ -1154,6 +1166,58 @@ impl VoyagerRpcServer for Server {
     }
 }
 
+/// Serves the [`VoyagerQueueRpcServer`] methods for the queue of an [`Engine`](crate::Engine).
+#[derive(Clone)]
+pub struct QueueServer<Q: Queue<VoyagerMessage>> {
+    queue: Q,
+    drain_token: CancellationToken,
+    registry: Arc<Registry>,
+}
+
+impl<Q: Queue<VoyagerMessage>> QueueServer<Q> {
+    pub fn new(queue: Q, drain_token: CancellationToken, registry: Arc<Registry>) -> Self {
+        Self {
+            queue,
+            drain_token,
+            registry,
+        }
+    }
+}
+
+#[async_trait]
+impl<Q: Queue<VoyagerMessage>> VoyagerQueueRpcServer for QueueServer<Q> {
+    #[instrument(skip_all, fields(id = id.raw(), %max_depth))]
+    async fn queue_history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> RpcResult<Vec<HistoryItem<VoyagerMessage>>> {
+        self.queue
+            .history(id, max_depth)
+            .await
+            .map_err(|e| ErrorObject::owned(-1, ErrorReporter(e).to_string(), None::<()>))
+    }
+
+    #[instrument(skip_all)]
+    async fn drain(&self) -> RpcResult<()> {
+        info!("drain requested");
+
+        self.drain_token.cancel();
+
+        Ok(())
+    }
+
+    #[instrument(skip_all)]
+    async fn reload(&self) -> RpcResult<ReloadResponse> {
+        info!("reload requested");
+
+        self.registry
+            .reload()
+            .await
+            .map_err(|e| ErrorObject::owned(-1, format!("{e:#}"), None::<()>))
+    }
+}
+
 pub(crate) fn fatal_error(t: impl core::error::Error) -> ErrorObjectOwned {
     ErrorObject::owned(
         FATAL_JSONRPC_ERROR_CODE,
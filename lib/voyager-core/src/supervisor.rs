@@ -0,0 +1,472 @@
+//! Supervision of the plugin and module worker processes.
+//!
+//! Every worker is spawned and watched by a [`Supervisor`]. If a worker exits for any reason other
+//! than an invalid config, it is restarted with exponential backoff, and the IPC connection to it
+//! is re-established by it's [`WorkerClient`]. The health of all workers is exposed via
+//! [`Supervisor::health`] (and as such `voyager rpc info`) and the `worker.*` metrics.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::PathBuf,
+    pin::pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use opentelemetry::KeyValue;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{process::Child, task::JoinHandle, time::sleep};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, instrument, warn};
+use unionlabs::ErrorReporter;
+use voyager_plugin_protocol::{worker_args, WorkerClient, INVALID_CONFIG_EXIT_CODE};
+use voyager_rpc::types::{WorkerHealth, WorkerKind, WorkerStatus};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SupervisorConfig {
+    /// The delay before the first restart of a worker, in milliseconds.
+    #[serde(default = "default_restart_backoff_initial_milliseconds")]
+    pub restart_backoff_initial_milliseconds: u64,
+    /// The maximum delay between restarts of a worker, in milliseconds.
+    #[serde(default = "default_restart_backoff_max_milliseconds")]
+    pub restart_backoff_max_milliseconds: u64,
+    #[serde(default = "default_restart_backoff_multiplier")]
+    pub restart_backoff_multiplier: f64,
+    /// A worker that stays up for at least this many seconds is considered to have recovered, and
+    /// it's restart backoff is reset.
+    #[serde(default = "default_healthy_after_seconds")]
+    pub healthy_after_seconds: u64,
+    /// If set, calls and callbacks for a plugin that is currently down are deferred until it is
+    /// back up, instead of being attempted (and eventually failing) against the unavailable
+    /// plugin.
+    #[serde(default)]
+    pub defer_while_down: bool,
+    /// How long to defer ops for by when [`Self::defer_while_down`] is set, in seconds.
+    #[serde(default = "default_defer_seconds")]
+    pub defer_seconds: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            restart_backoff_initial_milliseconds: default_restart_backoff_initial_milliseconds(),
+            restart_backoff_max_milliseconds: default_restart_backoff_max_milliseconds(),
+            restart_backoff_multiplier: default_restart_backoff_multiplier(),
+            healthy_after_seconds: default_healthy_after_seconds(),
+            defer_while_down: false,
+            defer_seconds: default_defer_seconds(),
+        }
+    }
+}
+
+#[must_use]
+pub const fn default_restart_backoff_initial_milliseconds() -> u64 {
+    1000
+}
+
+#[must_use]
+pub const fn default_restart_backoff_max_milliseconds() -> u64 {
+    60 * 1000
+}
+
+#[must_use]
+pub const fn default_restart_backoff_multiplier() -> f64 {
+    2.0
+}
+
+#[must_use]
+pub const fn default_healthy_after_seconds() -> u64 {
+    60
+}
+
+#[must_use]
+pub const fn default_defer_seconds() -> u64 {
+    5
+}
+
+impl SupervisorConfig {
+    /// The delay before restarting a worker that has failed `attempt` times in a row.
+    ///
+    /// This never exceeds [`Self::restart_backoff_max_milliseconds`], even if it is configured to
+    /// be lower than [`Self::restart_backoff_initial_milliseconds`].
+    #[must_use]
+    pub fn restart_backoff(&self, attempt: u32) -> Duration {
+        let backoff = (self.restart_backoff_initial_milliseconds as f64)
+            * self
+                .restart_backoff_multiplier
+                .powi(attempt.try_into().unwrap_or(i32::MAX));
+
+        let max = self.restart_backoff_max_milliseconds;
+
+        Duration::from_millis(
+            (backoff as u64)
+                .min(max)
+                .max(self.restart_backoff_initial_milliseconds.min(max)),
+        )
+    }
+}
+
+/// Spawns and restarts worker processes, tracking their health.
+///
+/// This is cheap to clone, and all clones share the same state.
+#[derive(Debug, Clone)]
+pub struct Supervisor {
+    config: SupervisorConfig,
+    workers: Arc<Mutex<BTreeMap<String, WorkerHealth>>>,
+    handles: Arc<Mutex<HashMap<String, WorkerHandle>>>,
+
+    restart_counter_metric: opentelemetry::metrics::Counter<u64>,
+    up_gauge_metric: opentelemetry::metrics::Gauge<u64>,
+}
+
+impl Supervisor {
+    #[must_use]
+    pub fn new(config: SupervisorConfig) -> Self {
+        Self {
+            config,
+            workers: Arc::default(),
+            handles: Arc::default(),
+            restart_counter_metric: opentelemetry::global::meter("voyager")
+                .u64_counter("worker.restarts")
+                .build(),
+            up_gauge_metric: opentelemetry::global::meter("voyager")
+                .u64_gauge("worker.up")
+                .build(),
+        }
+    }
+
+    #[must_use]
+    pub fn config(&self) -> &SupervisorConfig {
+        &self.config
+    }
+
+    /// The health of all supervised workers, sorted by name.
+    #[must_use]
+    pub fn health(&self) -> Vec<WorkerHealth> {
+        self.workers
+            .lock()
+            .expect("mutex is poisoned")
+            .values()
+            .cloned()
+            .collect()
+    }
+
+    /// Whether the worker with the provided name is currently running and connected. Workers that
+    /// are not supervised are assumed to be up.
+    #[must_use]
+    pub fn is_up(&self, name: &str) -> bool {
+        self.workers
+            .lock()
+            .expect("mutex is poisoned")
+            .get(name)
+            .is_none_or(|worker| worker.status == WorkerStatus::Running)
+    }
+
+    /// If [`SupervisorConfig::defer_while_down`] is set and the worker with the provided name is
+    /// not up, returns the amount of seconds that ops for it should be deferred by.
+    #[must_use]
+    pub fn defer_while_down(&self, name: &str) -> Option<u64> {
+        (self.config.defer_while_down && !self.is_up(name)).then_some(self.config.defer_seconds)
+    }
+
+    fn update(&self, name: &str, f: impl FnOnce(&mut WorkerHealth)) {
+        let mut workers = self.workers.lock().expect("mutex is poisoned");
+
+        let Some(worker) = workers.get_mut(name) else {
+            return;
+        };
+
+        f(worker);
+
+        self.up_gauge_metric.record(
+            (worker.status == WorkerStatus::Running).into(),
+            &[KeyValue::new("worker", name.to_owned())],
+        );
+    }
+
+    /// Spawn the worker `name` and keep it running until either it is [stopped](Self::stop) or
+    /// `cancellation_token` is cancelled, restarting it whenever it exits.
+    ///
+    /// If the worker exits with [`INVALID_CONFIG_EXIT_CODE`], it will not be restarted and
+    /// `cancellation_token` is cancelled, shutting down all other workers supervised with the same
+    /// token (or a child of it) as well.
+    pub fn supervise(
+        &self,
+        name: String,
+        kind: WorkerKind,
+        path: PathBuf,
+        args: Vec<String>,
+        client: WorkerClient,
+        cancellation_token: &CancellationToken,
+    ) {
+        self.workers.lock().expect("mutex is poisoned").insert(
+            name.clone(),
+            WorkerHealth {
+                name: name.clone(),
+                kind,
+                status: WorkerStatus::Starting,
+                restarts: 0,
+                last_exit: None,
+            },
+        );
+
+        let stop = cancellation_token.child_token();
+
+        let task = tokio::spawn(self.clone().run(
+            name.clone(),
+            path,
+            args,
+            client,
+            stop.clone(),
+            cancellation_token.clone(),
+        ));
+
+        let prev = self
+            .handles
+            .lock()
+            .expect("mutex is poisoned")
+            .insert(name, WorkerHandle { stop, task });
+
+        assert!(prev.is_none(), "worker is already supervised");
+    }
+
+    /// Stop the worker `name`, waiting for it's process to exit. The worker will no longer be
+    /// included in [`Self::health`].
+    pub async fn stop(&self, name: &str) {
+        let handle = self.handles.lock().expect("mutex is poisoned").remove(name);
+
+        if let Some(WorkerHandle { stop, task }) = handle {
+            stop.cancel();
+
+            if let Err(err) = task.await {
+                error!(%name, err = %ErrorReporter(err), "worker supervisor panicked");
+            }
+        }
+
+        self.workers.lock().expect("mutex is poisoned").remove(name);
+    }
+
+    #[instrument(skip_all, fields(%name))]
+    async fn run(
+        self,
+        name: String,
+        path: PathBuf,
+        args: Vec<String>,
+        client: WorkerClient,
+        stop: CancellationToken,
+        cancellation_token: CancellationToken,
+    ) {
+        let args = worker_args(&name, args);
+
+        // the amount of consecutive failed starts, used to calculate the restart backoff
+        let mut attempt = 0;
+
+        loop {
+            self.update(&name, |worker| worker.status = WorkerStatus::Starting);
+
+            let mut cmd = tokio::process::Command::new(&path);
+            cmd.args(&args);
+
+            debug!(%attempt, "spawning worker child process");
+
+            let exit = match cmd.spawn() {
+                Ok(child) => {
+                    debug!(id = child.id(), "spawned worker");
+
+                    match self.watch(&name, child, &client, &stop).await {
+                        Some(exit) => exit,
+                        None => {
+                            self.update(&name, |worker| worker.status = WorkerStatus::Stopped);
+                            break;
+                        }
+                    }
+                }
+                Err(err) => {
+                    error!(err = %ErrorReporter(&err), "unable to spawn worker");
+
+                    WorkerExit {
+                        description: format!("unable to spawn: {}", ErrorReporter(err)),
+                        uptime: Duration::ZERO,
+                        invalid_config: false,
+                    }
+                }
+            };
+
+            if exit.invalid_config {
+                error!("invalid config for plugin or module");
+                self.update(&name, |worker| {
+                    worker.status = WorkerStatus::Stopped;
+                    worker.last_exit = Some(exit.description);
+                });
+                cancellation_token.cancel();
+                break;
+            }
+
+            if exit.uptime >= Duration::from_secs(self.config.healthy_after_seconds) {
+                attempt = 0;
+            }
+
+            let backoff = self.config.restart_backoff(attempt);
+            attempt = attempt.saturating_add(1);
+
+            warn!(
+                exit = %exit.description,
+                backoff_ms = backoff.as_millis(),
+                "worker exited, restarting"
+            );
+
+            self.update(&name, |worker| {
+                worker.status = WorkerStatus::Restarting;
+                worker.restarts += 1;
+                worker.last_exit = Some(exit.description);
+            });
+            self.restart_counter_metric
+                .add(1, &[KeyValue::new("worker", name.clone())]);
+
+            if stop.run_until_cancelled(sleep(backoff)).await.is_none() {
+                self.update(&name, |worker| worker.status = WorkerStatus::Stopped);
+                break;
+            }
+        }
+    }
+
+    /// Watch a running worker until it exits, marking it as running once the IPC connection to it
+    /// has been (re-)established.
+    ///
+    /// Returns `None` if `cancellation_token` was cancelled, in which case the worker is killed.
+    async fn watch(
+        &self,
+        name: &str,
+        mut child: Child,
+        client: &WorkerClient,
+        cancellation_token: &CancellationToken,
+    ) -> Option<WorkerExit> {
+        let started_at = Instant::now();
+
+        let connected = async {
+            while !client.inner().is_healthy() {
+                sleep(Duration::from_millis(100)).await;
+            }
+        };
+
+        let res = cancellation_token
+            .run_until_cancelled(async {
+                let mut exit = pin!(child.wait());
+
+                tokio::select! {
+                    res = &mut exit => return res,
+                    () = connected => {
+                        info!("worker connected");
+                        self.update(name, |worker| worker.status = WorkerStatus::Running);
+                    }
+                }
+
+                exit.await
+            })
+            .await;
+
+        let Some(res) = res else {
+            kill(child).await;
+            return None;
+        };
+
+        let (description, invalid_config) = match res {
+            Ok(exit_status) => {
+                info!(%exit_status, "worker exited");
+
+                (
+                    exit_status.to_string(),
+                    exit_status
+                        .code()
+                        .is_some_and(|c| c == INVALID_CONFIG_EXIT_CODE as i32),
+                )
+            }
+            Err(err) => {
+                error!(err = %ErrorReporter(&err), "worker exited");
+
+                (ErrorReporter(err).to_string(), false)
+            }
+        };
+
+        Some(WorkerExit {
+            description,
+            uptime: started_at.elapsed(),
+            invalid_config,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct WorkerHandle {
+    stop: CancellationToken,
+    task: JoinHandle<()>,
+}
+
+struct WorkerExit {
+    description: String,
+    uptime: Duration,
+    invalid_config: bool,
+}
+
+async fn kill(mut child: Child) {
+    let id = child.id();
+
+    debug!(?id, "killing worker");
+
+    match child.kill().await {
+        Ok(()) => {
+            debug!(?id, "worker received kill signal");
+            match child.wait().await {
+                Ok(exit_status) => {
+                    debug!(?id, %exit_status, "child exited successfully")
+                }
+                Err(err) => {
+                    error!(?id, err = %ErrorReporter(err), "child exited unsuccessfully")
+                }
+            }
+        }
+        Err(err) => {
+            error!(?id, err = %ErrorReporter(err), "unable to kill worker")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_backoff() {
+        let config = SupervisorConfig {
+            restart_backoff_initial_milliseconds: 500,
+            restart_backoff_max_milliseconds: 10_000,
+            restart_backoff_multiplier: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(config.restart_backoff(0), Duration::from_millis(500));
+        assert_eq!(config.restart_backoff(1), Duration::from_millis(1000));
+        assert_eq!(config.restart_backoff(4), Duration::from_millis(8000));
+        assert_eq!(config.restart_backoff(5), Duration::from_millis(10_000));
+        assert_eq!(
+            config.restart_backoff(u32::MAX),
+            Duration::from_millis(10_000)
+        );
+    }
+
+    #[test]
+    fn restart_backoff_initial_above_max() {
+        let config = SupervisorConfig {
+            restart_backoff_initial_milliseconds: 10_000,
+            restart_backoff_max_milliseconds: 500,
+            restart_backoff_multiplier: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(config.restart_backoff(0), Duration::from_millis(500));
+        assert_eq!(config.restart_backoff(3), Duration::from_millis(500));
+        assert_eq!(config.restart_backoff(u32::MAX), Duration::from_millis(500));
+    }
+}
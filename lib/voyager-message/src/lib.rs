@@ -5,9 +5,20 @@ use std::fmt::Debug;
 use macros::model;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
-use voyager_vm::QueueMessage;
+use voyager_primitives::ChainId;
+use voyager_vm::{
+    schedule::{Priority, Schedule},
+    Op, QueueMessage,
+};
 
-use crate::{call::Call, callback::Callback, data::Data};
+use crate::{
+    call::{
+        Call, FetchBlocks, FetchUpdateHeaders, SubmitTx, WaitForClientUpdate, WaitForHeight,
+        WaitForHeightRelative, WaitForTimestamp, WaitForTrustedHeight, WaitForTrustedTimestamp,
+    },
+    callback::{AggregateSubmitTxFromOrderedHeaders, Callback},
+    data::Data,
+};
 
 pub mod call;
 pub mod callback;
@@ -20,6 +31,76 @@ impl QueueMessage for VoyagerMessage {
     type Call = Call;
     type Data = Data;
     type Callback = Callback;
+
+    /// Transaction submission is prioritised over everything else, since it is the final step of
+    /// all relaying, and fetching blocks is treated as backfill. Ops are scheduled fairly across
+    /// the chains they target; plugin messages are opaque to voyager, and as such are assigned the
+    /// chain of the plugin they are for and the priority declared by the plugin (see
+    /// [`PluginMessage::with_priority`]).
+    fn schedule(op: &Op<Self>) -> Schedule {
+        let schedule = |priority, chain_id: &ChainId| Schedule {
+            priority,
+            chain: Some(chain_id.to_string()),
+        };
+
+        let plugin_schedule = |message: &PluginMessage| Schedule {
+            priority: message.priority.unwrap_or_default(),
+            chain: Some(plugin_chain(&message.plugin).to_owned()),
+        };
+
+        match op {
+            Op::Call(call) => match call {
+                Call::SubmitTx(SubmitTx { chain_id, .. }) => schedule(Priority::Urgent, chain_id),
+                Call::FetchBlocks(FetchBlocks { chain_id, .. }) => {
+                    schedule(Priority::Backfill, chain_id)
+                }
+                Call::FetchUpdateHeaders(FetchUpdateHeaders { chain_id, .. })
+                | Call::WaitForHeight(WaitForHeight { chain_id, .. })
+                | Call::WaitForTimestamp(WaitForTimestamp { chain_id, .. })
+                | Call::WaitForHeightRelative(WaitForHeightRelative { chain_id, .. })
+                | Call::WaitForTrustedHeight(WaitForTrustedHeight { chain_id, .. })
+                | Call::WaitForTrustedTimestamp(WaitForTrustedTimestamp { chain_id, .. })
+                | Call::WaitForClientUpdate(WaitForClientUpdate { chain_id, .. }) => {
+                    schedule(Priority::Normal, chain_id)
+                }
+                Call::Plugin(message) => plugin_schedule(message),
+            },
+            // the first op is the one that will be handled next, however ops that don't do any
+            // work on their own (such as the `defer` in `seq([defer(..), call(..)])`) are
+            // classified by the op following them
+            Op::Seq(ops) | Op::Conc(ops) => ops
+                .iter()
+                .find(|op| !is_inert(op))
+                .map(Self::schedule)
+                .unwrap_or_default(),
+            Op::Void(op) => Self::schedule(op),
+            Op::Promise(promise) => match promise.queue.iter().find(|op| !is_inert(op)) {
+                Some(op) => Self::schedule(op),
+                None => match &promise.receiver {
+                    Callback::AggregateSubmitTxFromOrderedHeaders(
+                        AggregateSubmitTxFromOrderedHeaders { chain_id, .. },
+                    ) => schedule(Priority::Urgent, chain_id),
+                    Callback::Plugin(message) => plugin_schedule(message),
+                },
+            },
+            Op::Data(_) | Op::Defer { .. } | Op::Noop => Schedule::default(),
+        }
+    }
+}
+
+/// The chain that the plugin `plugin` is for, used as it's fair scheduling key.
+///
+/// Plugins are named `{plugin}/{chain_id}` by convention (optionally followed by further
+/// segments), so this is the second segment of the name. Plugins that aren't specific to a chain
+/// are scheduled under their full name.
+fn plugin_chain(plugin: &str) -> &str {
+    plugin.split('/').nth(1).unwrap_or(plugin)
+}
+
+/// Whether the op doesn't do any work on it's own, and as such shouldn't be used to classify the
+/// op containing it.
+fn is_inert(op: &Op<VoyagerMessage>) -> bool {
+    matches!(op, Op::Data(_) | Op::Defer { .. } | Op::Noop)
 }
 
 /// A message specific to a plugin.
@@ -29,6 +110,10 @@ impl QueueMessage for VoyagerMessage {
 pub struct PluginMessage {
     pub plugin: String,
     pub message: Value,
+    /// The priority class to schedule this message with, if it is a [`Call`] or [`Callback`].
+    /// Messages without a priority are scheduled as [`Priority::Normal`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
 }
 
 impl PluginMessage {
@@ -38,9 +123,19 @@ impl PluginMessage {
             message: serde_json::to_value(message).expect(
                 "serialization must be infallible, this is a bug in the plugin implementation",
             ),
+            priority: None,
         }
     }
 
+    /// Schedule this message with `priority`. Plugins should use [`Priority::Urgent`] for
+    /// transaction submission and other time sensitive messages (such as packet timeouts), and
+    /// [`Priority::Backfill`] for fetching historical data.
+    #[must_use]
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
     pub fn downcast<T: DeserializeOwned>(self, plugin_name: impl AsRef<str>) -> Result<T, Self> {
         if self.plugin == plugin_name.as_ref() {
             if let Ok(t) = serde_json::from_value(self.message.clone()) {
@@ -53,3 +148,103 @@ impl PluginMessage {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use voyager_vm::{call, in_memory::InMemoryQueue, promise, seq, Queue};
+
+    use super::*;
+
+    #[test]
+    fn plugin_messages_are_scheduled_by_the_chain_of_their_plugin() {
+        let plugin_call = |plugin: &str| {
+            call::<VoyagerMessage>(Call::Plugin(PluginMessage::new(plugin, json!(null))))
+        };
+
+        let chain = |op| VoyagerMessage::schedule(&op).chain;
+
+        assert_eq!(
+            chain(plugin_call("voyager-transaction-plugin-ethereum/1")),
+            Some("1".to_owned())
+        );
+        assert_eq!(
+            chain(plugin_call("voyager-plugin-packet-batch/union-1/channel-0")),
+            Some("union-1".to_owned())
+        );
+        assert_eq!(
+            chain(plugin_call("voyager-plugin-packet-index")),
+            Some("voyager-plugin-packet-index".to_owned())
+        );
+        assert_eq!(
+            chain(seq([
+                plugin_call("voyager-plugin-a/1"),
+                plugin_call("voyager-plugin-b/2")
+            ])),
+            Some("1".to_owned())
+        );
+        assert_eq!(
+            chain(promise::<VoyagerMessage>(
+                [],
+                [],
+                Callback::Plugin(PluginMessage::new("voyager-plugin-a/2", json!(null)))
+            )),
+            Some("2".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn submissions_and_timeouts_are_processed_before_event_source_backfill() {
+        let queue = InMemoryQueue::<VoyagerMessage>::new(Default::default())
+            .await
+            .unwrap();
+
+        let plugin_call = |plugin: &str, message, priority| {
+            call::<VoyagerMessage>(Call::Plugin(
+                PluginMessage::new(plugin, json!(message)).with_priority(priority),
+            ))
+        };
+
+        let fetch_logs = |from_block: u64| {
+            plugin_call(
+                "voyager-event-source-plugin-ethereum/1",
+                json!({ "fetch_get_logs_range": { "from_block": from_block } }),
+                Priority::Backfill,
+            )
+        };
+        let submit = plugin_call(
+            "voyager-transaction-plugin-ethereum/1",
+            json!({ "submit_multicall": [] }),
+            Priority::Urgent,
+        );
+        let timeout = plugin_call(
+            "voyager-plugin-packet-timeout/1",
+            json!({ "make_msg_timeout": {} }),
+            Priority::Urgent,
+        );
+
+        for from_block in 0..5 {
+            queue.enqueue(fetch_logs(from_block), &()).await.unwrap();
+        }
+        queue.enqueue(submit.clone(), &()).await.unwrap();
+        queue.enqueue(timeout.clone(), &()).await.unwrap();
+
+        let mut processed = vec![];
+
+        while let Some(op) = queue
+            .process(&(), async |op, _| (op, Ok(vec![])))
+            .await
+            .unwrap()
+        {
+            processed.push(op);
+        }
+
+        assert_eq!(
+            processed,
+            [submit, timeout]
+                .into_iter()
+                .chain((0..5).map(fetch_logs))
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -18,14 +18,7 @@
 //! 4. Worker creates a client connecting to [`coordinator_socket_path`].
 //! 5. Coordinator client now connects to the booted worker.
 
-use std::{
-    borrow::Cow,
-    fmt::Debug,
-    future::Future,
-    path::{Path, PathBuf},
-    sync::Arc,
-    time::Duration,
-};
+use std::{borrow::Cow, fmt::Debug, future::Future, sync::Arc, time::Duration};
 
 use futures::FutureExt;
 use jsonrpsee::{
@@ -44,9 +37,7 @@ use reth_ipc::{
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::value::RawValue;
-use tokio::time::sleep;
-use tokio_util::sync::CancellationToken;
-use tracing::{debug, debug_span, error, info, info_span, instrument, trace, Instrument};
+use tracing::{debug, debug_span, error, info_span, instrument, trace, Instrument};
 use unionlabs::{ethereum::slot::keccak256, primitives::encoding::HexUnprefixed, ErrorReporter};
 use voyager_client::VoyagerClient;
 use voyager_rpc::VoyagerRpcServer;
@@ -528,114 +519,24 @@ impl<Inner: ClientT + Send + Sync> ClientT for IdThreadClient<Inner> {
     }
 }
 
-#[instrument(skip_all, fields(%name))]
-pub async fn worker_child_process(
-    name: String,
-    path: PathBuf,
-    cancellation_token: CancellationToken,
-    args: impl IntoIterator<Item: Into<String>>,
-) {
-    let coordinator_to_worker_socket = worker_socket_path(&name);
-    let worker_to_coordinator_socket = coordinator_socket_path(&name);
+/// The arguments to spawn the worker `name` with, connecting it to the coordinator over the IPC
+/// sockets derived from it's name.
+///
+/// The worker process itself is spawned and supervised by the coordinator.
+pub fn worker_args(name: &str, args: impl IntoIterator<Item: Into<String>>) -> Vec<String> {
+    let coordinator_to_worker_socket = worker_socket_path(name);
+    let worker_to_coordinator_socket = coordinator_socket_path(name);
 
     debug!(%coordinator_to_worker_socket, %worker_to_coordinator_socket);
 
-    lazarus_pit(
-        &path,
-        [
-            "run".to_owned(),
-            coordinator_to_worker_socket,
-            worker_to_coordinator_socket,
-        ]
-        .into_iter()
-        .chain(args.into_iter().map(Into::into))
-        .collect(),
-        cancellation_token,
-    )
-    .await
-}
-
-/// Spawn a worker process with the given args, re-spawning it indefinitely unless it exits with [`INVALID_CONFIG_EXIT_CODE`] or the passed in cancellation token is cancelled.
-#[instrument(skip_all)]
-async fn lazarus_pit(cmd: &Path, args: Vec<String>, cancellation_token: CancellationToken) {
-    let mut attempt = 0;
-
-    loop {
-        let mut cmd = tokio::process::Command::new(cmd);
-        cmd.args(&args);
-
-        debug!(%attempt, "spawning plugin child process");
-
-        let mut child = loop {
-            match cmd.spawn() {
-                Ok(child) => {
-                    let id = child.id().unwrap();
-
-                    debug!(%id, "spawned plugin");
-
-                    break child;
-                }
-                Err(err) => {
-                    error!(
-                        err = %ErrorReporter(err),
-                        "unable to spawn plugin"
-                    );
-
-                    sleep(Duration::from_secs(1)).await;
-                }
-            }
-        };
-
-        let id = child.id().unwrap();
-
-        tokio::select! {
-            _ = cancellation_token.cancelled() => {
-                debug!(%id, "killing plugin");
-                match child.kill().await {
-                    Ok(()) => {
-                        debug!(%id, "plugin received kill signal");
-                        match child.wait().await {
-                            Ok(exit_status) => {
-                                debug!(%id, %exit_status, "child exited successfully")
-                            }
-                            Err(err) => {
-                                error!(%id, err = %ErrorReporter(err), "child exited unsuccessfully")
-                            }
-                        }
-                    }
-                    Err(err) => {
-                        error!(%id, err = %ErrorReporter(err), "unable to kill plugin")
-                    }
-                }
-
-                break
-            }
-            res = child.wait() => {
-                match res {
-                    Ok(exit_status) => {
-                        info!(%id, %exit_status, "child exited");
-
-                        if exit_status
-                            .code()
-                            .is_some_and(|c| c == INVALID_CONFIG_EXIT_CODE as i32)
-                        {
-                            error!(%id, "invalid config for plugin or module");
-                            cancellation_token.cancel();
-                            break;
-                        }
-                    }
-                    Err(err) => {
-                        error!(%id, err = %ErrorReporter(err), "child exited");
-                    }
-                }
-
-                // TODO: Exponential backoff
-                sleep(Duration::from_secs(1)).await;
-            }
-        }
-
-        attempt += 1;
-    }
+    [
+        "run".to_owned(),
+        coordinator_to_worker_socket,
+        worker_to_coordinator_socket,
+    ]
+    .into_iter()
+    .chain(args.into_iter().map(Into::into))
+    .collect()
 }
 
 // https://github.com/paritytech/jsonrpsee/issues/1578
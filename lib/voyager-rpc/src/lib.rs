@@ -20,10 +20,10 @@ use voyager_primitives::{
     IbcSpecId, QueryHeight, Timestamp,
 };
 use voyager_types::{ProofType, RawClientId};
-use voyager_vm::{pass::PassResult, Op, QueueError};
+use voyager_vm::{pass::PassResult, HistoryItem, ItemId, Op, QueueError};
 
 use crate::types::{
-    IbcProofResponse, IbcStateResponse, InfoResponse, SelfClientStateResponse,
+    IbcProofResponse, IbcStateResponse, InfoResponse, ReloadResponse, SelfClientStateResponse,
     SelfConsensusStateResponse,
 };
 
@@ -307,6 +307,37 @@ pub trait VoyagerRpc {
     ) -> RpcResult<Value>;
 }
 
+/// Methods for inspecting the queue of a running voyager instance.
+#[rpc(
+    client,
+    server,
+    client_bounds(Self: Send + Sync),
+    server_bounds(Self:),
+    namespace = "voyager",
+)]
+pub trait VoyagerQueueRpc {
+    /// Fetch the lineage of an op in the queue, walking up to `max_depth` levels of both its
+    /// ancestors and its descendants.
+    #[method(name = "queueHistory")]
+    async fn queue_history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> RpcResult<Vec<HistoryItem<VoyagerMessage>>>;
+
+    /// Start draining the running voyager instance. No new items will be pulled from the queue,
+    /// and once all in-flight items have been processed, the optimizer buffers are flushed and the
+    /// queue is checkpointed before voyager exits.
+    #[method(name = "drain")]
+    async fn drain(&self) -> RpcResult<()>;
+
+    /// Re-read the plugin and module configuration of the running voyager instance, and start,
+    /// stop, or restart only the workers whose configuration changed. The queue and cache are left
+    /// untouched.
+    #[method(name = "reload")]
+    async fn reload(&self) -> RpcResult<ReloadResponse>;
+}
+
 #[rpc(client, server, namespace = "plugin")]
 pub trait Plugin<C: Member, Cb: Member> {
     #[method(name = "runPass", with_extensions)]
@@ -16,6 +16,55 @@ pub struct InfoResponse {
     pub consensus: Vec<FinalityModuleInfo>,
     pub client: Vec<ClientModuleInfo>,
     pub client_bootstrap: Vec<ClientBootstrapModuleInfo>,
+    /// The health of all plugin and module worker processes.
+    #[serde(default)]
+    pub workers: Vec<WorkerHealth>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct WorkerHealth {
+    /// The name of the plugin, or the id of the module.
+    pub name: String,
+    pub kind: WorkerKind,
+    pub status: WorkerStatus,
+    /// The amount of times this worker has been restarted.
+    pub restarts: u64,
+    /// A description of how the worker last exited, if it has exited at all.
+    pub last_exit: Option<String>,
+}
+
+/// The workers affected by a reload, by name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReloadResponse {
+    /// Workers that were added to the configuration.
+    pub started: Vec<String>,
+    /// Workers that were removed from (or disabled in) the configuration.
+    pub stopped: Vec<String>,
+    /// Workers whose configuration changed.
+    pub restarted: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerKind {
+    Plugin,
+    Module,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkerStatus {
+    /// The worker process has been spawned, but the IPC connection to it has not yet been
+    /// established.
+    Starting,
+    /// The worker is running and connected.
+    Running,
+    /// The worker exited and is waiting to be restarted.
+    Restarting,
+    /// The worker has been stopped, and will not be restarted.
+    Stopped,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
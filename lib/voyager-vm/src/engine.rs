@@ -31,8 +31,28 @@ where
     pub fn run(
         self,
     ) -> impl Stream<Item = Result<T::Data, BoxDynError>> + Send + use<'a, T, Q, H, F> {
-        try_unfold(self, async |this| {
-            this.step().await.map(move |x| Some((x, this)))
+        self.run_until(|| false)
+    }
+
+    /// Run the engine until `is_draining` returns `true`, at which point the stream ends.
+    ///
+    /// `is_draining` is checked before every item is pulled from the queue, such that an item that
+    /// is being processed is always processed to completion.
+    pub fn run_until<D>(
+        self,
+        is_draining: D,
+    ) -> impl Stream<Item = Result<T::Data, BoxDynError>> + Send + use<'a, T, Q, H, F, D>
+    where
+        D: Fn() -> bool + Send + Sync + 'a,
+    {
+        try_unfold((self, is_draining), async |(this, is_draining)| {
+            if is_draining() {
+                return Ok(None);
+            }
+
+            this.step()
+                .await
+                .map(move |x| Some((x, (this, is_draining))))
         })
         .try_filter_map(async |e| Ok(e))
     }
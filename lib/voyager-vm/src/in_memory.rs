@@ -1,20 +1,27 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
+    fs,
     future::Future,
+    io,
+    path::PathBuf,
     sync::{
         atomic::{AtomicU32, Ordering},
         Arc, Mutex,
     },
+    time::Instant,
 };
 
 use either::Either;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, info_span, trace, Instrument};
 use unionlabs::ErrorReporter;
 
 use crate::{
     filter::{FilterResult, Interest, InterestFilter},
     pass::Pass,
-    Captures, EnqueueResult, ItemId, Op, Queue, QueueError, QueueMessage,
+    schedule::{FairScheduler, Schedule, SchedulingConfig},
+    Captures, EnqueueResult, HistoryItem, ItemId, ItemStatus, Op, Queue, QueueError, QueueMessage,
 };
 
 #[derive(Debug, Clone)]
@@ -24,25 +31,196 @@ pub struct InMemoryQueue<T: QueueMessage> {
     done: Arc<Mutex<BTreeMap<u32, Item<T>>>>,
     #[allow(clippy::type_complexity)]
     optimizer_queue: Arc<Mutex<BTreeMap<String, BTreeMap<u32, Item<T>>>>>,
+    scheduler: FairScheduler,
+    snapshot_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InMemoryQueueConfig {
+    #[serde(default)]
+    pub scheduling: SchedulingConfig,
+    /// If set, all items remaining in the queue are written to this file on
+    /// [`checkpoint`](Queue::checkpoint), and reloaded from it when the queue is created. The file
+    /// is removed once it has been loaded.
+    #[serde(default)]
+    pub snapshot_path: Option<PathBuf>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InMemoryQueueError {
+    #[error("unable to read snapshot file {path}")]
+    ReadSnapshot {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+    #[error("unable to write snapshot file {path}")]
+    WriteSnapshot {
+        path: PathBuf,
+        #[source]
+        error: io::Error,
+    },
+    #[error("invalid snapshot file {path}")]
+    InvalidSnapshot {
+        path: PathBuf,
+        #[source]
+        error: serde_json::Error,
+    },
+}
+
+/// The items remaining in an [`InMemoryQueue`], as written to
+/// [`InMemoryQueueConfig::snapshot_path`]. Done items are not included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    deny_unknown_fields,
+    rename_all = "snake_case",
+    bound(serialize = "", deserialize = "")
+)]
+struct Snapshot<T: QueueMessage> {
+    ready: BTreeMap<u32, SnapshotItem<T>>,
+    optimize: BTreeMap<String, BTreeMap<u32, SnapshotItem<T>>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(
+    deny_unknown_fields,
+    rename_all = "snake_case",
+    bound(serialize = "", deserialize = "")
+)]
+struct SnapshotItem<T: QueueMessage> {
+    parents: Vec<u32>,
+    op: Op<T>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct Item<T: QueueMessage> {
-    #[allow(dead_code)] // used in debug
     parents: Vec<u32>,
     op: Op<T>,
+    schedule: Schedule,
+    /// When this item was queued, used for aging it's priority.
+    queued_at: Instant,
+}
+
+impl<T: QueueMessage> Item<T> {
+    fn new(parents: Vec<u32>, op: Op<T>) -> Self {
+        Self {
+            parents,
+            schedule: T::schedule(&op),
+            op,
+            queued_at: Instant::now(),
+        }
+    }
+}
+
+impl<T: QueueMessage> InMemoryQueue<T> {
+    fn load_snapshot(&self, path: PathBuf) -> Result<(), InMemoryQueueError> {
+        let bytes = match fs::read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(error) => return Err(InMemoryQueueError::ReadSnapshot { path, error }),
+        };
+
+        let snapshot = serde_json::from_slice::<Snapshot<T>>(&bytes).map_err(|error| {
+            InMemoryQueueError::InvalidSnapshot {
+                path: path.clone(),
+                error,
+            }
+        })?;
+
+        let mut next_idx = 0;
+        let mut into_items = |items: BTreeMap<u32, SnapshotItem<T>>| {
+            items
+                .into_iter()
+                .map(|(id, item)| {
+                    next_idx = next_idx.max(id + 1);
+                    (id, Item::new(item.parents, item.op))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let ready = into_items(snapshot.ready);
+        let optimize = snapshot
+            .optimize
+            .into_iter()
+            .map(|(tag, items)| (tag, into_items(items)))
+            .collect::<BTreeMap<_, _>>();
+
+        info!(
+            ready = ready.len(),
+            optimize = optimize.values().map(BTreeMap::len).sum::<usize>(),
+            path = %path.display(),
+            "loaded snapshot"
+        );
+
+        *self.ready.lock().expect("mutex is poisoned") = ready;
+        *self.optimizer_queue.lock().expect("mutex is poisoned") = optimize;
+        self.idx.store(next_idx, Ordering::SeqCst);
+
+        // the snapshot has been consumed, don't load it again on the next start
+        fs::remove_file(&path).map_err(|error| InMemoryQueueError::WriteSnapshot { path, error })
+    }
+
+    /// Remove the next item to be processed from the ready queue, as determined by the scheduler.
+    fn pop_next(&self) -> Option<(u32, Item<T>)> {
+        let mut ready = self.ready.lock().expect("mutex is poisoned");
+
+        let (virtual_times, system_virtual_time) = self.scheduler.virtual_times();
+
+        let now = Instant::now();
+
+        let priority = |item: &Item<T>| {
+            self.scheduler.prioritize().then(|| {
+                self.scheduler.aged_priority(
+                    item.schedule.priority,
+                    now.saturating_duration_since(item.queued_at),
+                )
+            })
+        };
+
+        let virtual_time = |item: &Item<T>| {
+            virtual_times
+                .get(item.schedule.chain_key())
+                .copied()
+                .unwrap_or_default()
+                .max(system_virtual_time)
+        };
+
+        let item_id = ready
+            .iter()
+            .min_by(|(a_id, a), (b_id, b)| {
+                priority(b)
+                    .cmp(&priority(a))
+                    .then(virtual_time(a).total_cmp(&virtual_time(b)))
+                    .then(a_id.cmp(b_id))
+            })
+            .map(|(item_id, _)| *item_id)?;
+
+        let item = ready.remove(&item_id).expect("item exists");
+
+        self.scheduler.record(item.schedule.chain_key());
+
+        Some((item_id, item))
+    }
 }
 
 impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
-    type Error = std::convert::Infallible;
-    type Config = ();
+    type Error = InMemoryQueueError;
+    type Config = InMemoryQueueConfig;
 
-    fn new(_cfg: Self::Config) -> impl Future<Output = Result<Self, Self::Error>> {
-        futures::future::ok(Self {
+    fn new(cfg: Self::Config) -> impl Future<Output = Result<Self, Self::Error>> {
+        let queue = Self {
             idx: Arc::new(AtomicU32::default()),
             done: Arc::new(Mutex::new(BTreeMap::default())),
             ready: Arc::new(Mutex::new(BTreeMap::default())),
             optimizer_queue: Arc::new(Mutex::new(BTreeMap::default())),
+            scheduler: FairScheduler::new(cfg.scheduling),
+            snapshot_path: cfg.snapshot_path.clone(),
+        };
+
+        futures::future::ready(match cfg.snapshot_path {
+            Some(path) => queue.load_snapshot(path).map(|()| queue),
+            None => Ok(queue),
         })
     }
 
@@ -65,30 +243,21 @@ impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
                     for tag in tags {
                         optimizer_queue.entry(tag.to_owned()).or_default().insert(
                             self.idx.fetch_add(1, Ordering::SeqCst),
-                            Item {
-                                parents: vec![],
-                                op: op.clone(),
-                            },
+                            Item::new(vec![], op.clone()),
                         );
                     }
 
                     if !remove {
                         ready.insert(
                             self.idx.fetch_add(1, Ordering::SeqCst),
-                            Item {
-                                parents: vec![],
-                                op,
-                            },
+                            Item::new(vec![], op),
                         );
                     }
                 }
                 FilterResult::NoInterest => {
                     ready.insert(
                         self.idx.fetch_add(1, Ordering::SeqCst),
-                        Item {
-                            parents: vec![],
-                            op,
-                        },
+                        Item::new(vec![], op),
                     );
                 }
             }
@@ -113,16 +282,7 @@ impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
         R: Send + Sync + 'static,
         Filter: InterestFilter<T>,
     {
-        let op = {
-            let mut queue = self.ready.lock().expect("mutex is poisoned");
-            let op = queue.pop_first();
-
-            drop(queue);
-
-            op
-        };
-
-        match op {
+        match self.pop_next() {
             Some((item_id, item)) => {
                 let span = info_span!("processing item", %item_id);
 
@@ -149,30 +309,21 @@ impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
                                     for tag in tags {
                                         optimizer_queue.entry(tag.to_owned()).or_default().insert(
                                             self.idx.fetch_add(1, Ordering::SeqCst),
-                                            Item {
-                                                parents: vec![],
-                                                op: op.clone(),
-                                            },
+                                            Item::new(vec![item_id], op.clone()),
                                         );
                                     }
 
                                     if !remove {
                                         ready.insert(
                                             self.idx.fetch_add(1, Ordering::SeqCst),
-                                            Item {
-                                                parents: vec![],
-                                                op,
-                                            },
+                                            Item::new(vec![item_id], op),
                                         );
                                     }
                                 }
                                 FilterResult::NoInterest => {
                                     ready.insert(
                                         self.idx.fetch_add(1, Ordering::SeqCst),
-                                        Item {
-                                            parents: vec![],
-                                            op,
-                                        },
+                                        Item::new(vec![item_id], op),
                                     );
                                 }
                             }
@@ -253,14 +404,10 @@ impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
                             for tag in tags {
                                 optimizer_queue.entry(tag.to_owned()).or_default().insert(
                                     self.idx.fetch_add(1, Ordering::SeqCst),
-                                    Item {
-                                        parents: parents_idxs
-                                            .iter()
-                                            .map(|&i| &ids[i])
-                                            .copied()
-                                            .collect(),
-                                        op: op.clone(),
-                                    },
+                                    Item::new(
+                                        parents_idxs.iter().map(|&i| &ids[i]).copied().collect(),
+                                        op.clone(),
+                                    ),
                                 );
                             }
 
@@ -273,10 +420,7 @@ impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
 
                     ready.insert(
                         self.idx.fetch_add(1, Ordering::SeqCst),
-                        Item {
-                            parents: parents_idxs.iter().map(|&i| &ids[i]).copied().collect(),
-                            op,
-                        },
+                        Item::new(parents_idxs.iter().map(|&i| &ids[i]).copied().collect(), op),
                     );
                 }
             }
@@ -284,14 +428,142 @@ impl<T: QueueMessage> Queue<T> for InMemoryQueue<T> {
             for (parents_idxs, op, tag) in res.optimize_further {
                 optimizer_queue.entry(tag.clone()).or_default().insert(
                     self.idx.fetch_add(1, Ordering::SeqCst),
-                    Item {
-                        parents: parents_idxs.iter().map(|&i| &ids[i]).copied().collect(),
-                        op,
-                    },
+                    Item::new(parents_idxs.iter().map(|&i| &ids[i]).copied().collect(), op),
                 );
             }
 
             Ok(())
         }
     }
+
+    fn history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> impl Future<Output = Result<Vec<HistoryItem<T>>, Self::Error>> + Send + '_ {
+        let mut items = BTreeMap::<u32, (Item<T>, ItemStatus)>::new();
+
+        // items that are retried are present in both done and ready, prefer the latter
+        for (status, queue) in [
+            (
+                ItemStatus::Done,
+                self.done.lock().expect("mutex is poisoned").clone(),
+            ),
+            (
+                ItemStatus::Optimize,
+                self.optimizer_queue
+                    .lock()
+                    .expect("mutex is poisoned")
+                    .values()
+                    .flatten()
+                    .map(|(id, item)| (*id, item.clone()))
+                    .collect(),
+            ),
+            (
+                ItemStatus::Queued,
+                self.ready.lock().expect("mutex is poisoned").clone(),
+            ),
+        ] {
+            items.extend(queue.into_iter().map(|(id, item)| (id, (item, status))));
+        }
+
+        let Ok(id) = u32::try_from(id.raw()) else {
+            return futures::future::ok(vec![]);
+        };
+
+        let mut lineage = BTreeSet::from([id]);
+
+        let mut ancestors = vec![id];
+        for _ in 0..max_depth {
+            ancestors = ancestors
+                .iter()
+                .filter_map(|id| items.get(id))
+                .flat_map(|(item, _)| item.parents.iter().copied())
+                .filter(|parent| lineage.insert(*parent))
+                .collect();
+        }
+
+        let mut descendants = vec![id];
+        for _ in 0..max_depth {
+            descendants = items
+                .iter()
+                .filter(|(_, (item, _))| item.parents.iter().any(|p| descendants.contains(p)))
+                .map(|(id, _)| *id)
+                .filter(|id| lineage.insert(*id))
+                .collect();
+        }
+
+        futures::future::ok(
+            lineage
+                .into_iter()
+                .filter_map(|id| {
+                    let (item, status) = items.remove(&id)?;
+
+                    Some(HistoryItem {
+                        id: ItemId::new(i64::from(id)).expect("infallible"),
+                        parents: item
+                            .parents
+                            .into_iter()
+                            .map(|parent| ItemId::new(i64::from(parent)).expect("infallible"))
+                            .collect(),
+                        status,
+                        op: item.op,
+                        message: None,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    fn checkpoint(&self) -> impl Future<Output = Result<(), Self::Error>> + Send + '_ {
+        let Some(path) = self.snapshot_path.clone() else {
+            return futures::future::ok(());
+        };
+
+        let to_snapshot_items = |items: &BTreeMap<u32, Item<T>>| {
+            items
+                .iter()
+                .map(|(id, item)| {
+                    (
+                        *id,
+                        SnapshotItem {
+                            parents: item.parents.clone(),
+                            op: item.op.clone(),
+                        },
+                    )
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        let snapshot = Snapshot::<T> {
+            ready: to_snapshot_items(&self.ready.lock().expect("mutex is poisoned")),
+            optimize: self
+                .optimizer_queue
+                .lock()
+                .expect("mutex is poisoned")
+                .iter()
+                .filter(|(_, items)| !items.is_empty())
+                .map(|(tag, items)| (tag.clone(), to_snapshot_items(items)))
+                .collect(),
+        };
+
+        info!(
+            ready = snapshot.ready.len(),
+            optimize = snapshot.optimize.values().map(BTreeMap::len).sum::<usize>(),
+            path = %path.display(),
+            "writing snapshot"
+        );
+
+        // write to a temporary file first so that a partially written snapshot is never loaded
+        let tmp_path = path.with_extension("tmp");
+
+        futures::future::ready(
+            fs::write(
+                &tmp_path,
+                serde_json::to_vec(&snapshot).expect("serialization is infallible; qed;"),
+            )
+            .and_then(|()| fs::rename(&tmp_path, &path))
+            .map_err(|error| InMemoryQueueError::WriteSnapshot { path, error }),
+        )
+    }
 }
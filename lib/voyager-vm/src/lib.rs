@@ -20,12 +20,14 @@ use tokio::time::sleep;
 use tracing::{debug, error, info, trace, warn};
 use unionlabs::bounded::{BoundedI64, BoundedIntError};
 
-use crate::{filter::InterestFilter, pass::Pass};
+use crate::{filter::InterestFilter, pass::Pass, schedule::Schedule};
 
 pub mod engine;
 pub mod filter;
 pub mod in_memory;
 pub mod pass;
+pub mod retry;
+pub mod schedule;
 
 #[cfg(test)]
 mod tests;
@@ -73,6 +75,55 @@ pub trait Queue<T: QueueMessage>: Debug + Clone + Send + Sync + Sized + 'static
     where
         O: Pass<T>,
         Filter: InterestFilter<T>;
+
+    /// Fetch the lineage of the item with the provided id, walking up to `max_depth` levels of both
+    /// its ancestors and its descendants.
+    ///
+    /// The returned items (including the item itself, if it exists) are sorted by their id.
+    fn history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> impl Future<Output = Result<Vec<HistoryItem<T>>, Self::Error>> + Send + '_;
+
+    /// Persist any state that would otherwise be lost when the queue is dropped. This is called
+    /// during a graceful shutdown, once all processing has stopped.
+    ///
+    /// The default implementation does nothing, which is correct for queues that are already
+    /// durable.
+    fn checkpoint(&self) -> impl Future<Output = Result<(), Self::Error>> + Send + '_ {
+        futures::future::ok(())
+    }
+}
+
+/// An item in the lineage of an op, as returned by [`Queue::history`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    deny_unknown_fields,
+    rename_all = "snake_case",
+    bound(serialize = "", deserialize = "")
+)]
+pub struct HistoryItem<T: QueueMessage> {
+    pub id: ItemId,
+    pub parents: Vec<ItemId>,
+    pub status: ItemStatus,
+    pub op: Op<T>,
+    /// The error message, if this item failed.
+    pub message: Option<String>,
+}
+
+/// The location of an item in the queue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ItemStatus {
+    /// The item is ready to be processed.
+    Queued,
+    /// The item is waiting to be picked up by an optimization pass.
+    Optimize,
+    /// The item has been processed.
+    Done,
+    /// The item failed to be processed.
+    Failed,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -196,6 +247,16 @@ pub trait QueueMessage: Debug + Clone + PartialEq + Sized + 'static {
     type Data: OpT;
     type Call: OpT;
     type Callback: OpT;
+
+    /// Classify an op for scheduling, see [`schedule`](crate::schedule) for more information.
+    ///
+    /// The default implementation places all ops in the [`Normal`](schedule::Priority::Normal)
+    /// priority class, without a chain.
+    fn schedule(op: &Op<Self>) -> Schedule {
+        let _ = op;
+
+        Schedule::default()
+    }
 }
 
 pub trait HandlerFactory<T: QueueMessage>: Send + Sync {
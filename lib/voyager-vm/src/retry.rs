@@ -0,0 +1,94 @@
+//! Configurable retry behaviour for failed items, shared between the persistent
+//! [`Queue`](crate::Queue) implementations.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// The class of error this policy applies to.
+    pub error_class: ErrorClass,
+    /// SQL `LIKE` filters for the full error message. The policy matches if any of the filters
+    /// match, or if no filters are provided.
+    #[serde(default)]
+    pub message_filters: Vec<String>,
+    /// The maximum amount of times an item will be attempted before it is moved to the `failed`
+    /// table. This includes the initial attempt.
+    pub max_attempts: i64,
+    #[serde(default = "default_backoff_max")]
+    pub backoff_max: f64,
+    #[serde(default = "default_backoff_multiplier")]
+    pub backoff_multiplier: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    Retry,
+    Fatal,
+    Unprocessable,
+}
+
+pub const fn default_backoff_max() -> f64 {
+    60.0 * 5.0
+}
+
+pub const fn default_backoff_multiplier() -> f64 {
+    2.0
+}
+
+impl RetryPolicy {
+    /// Decide whether an item that failed on it's `attempt`th attempt (starting at 0) with an error
+    /// of `error_class` should be retried, returning the `(backoff_max, backoff_multiplier)` to
+    /// requeue it with if so.
+    ///
+    /// `policy` is the first policy matching the error, if any. Without a matching policy, only
+    /// [`ErrorClass::Retry`] errors are retried, with the queue's `default_backoff`.
+    pub fn retry_backoff(
+        policy: Option<&Self>,
+        error_class: ErrorClass,
+        attempt: i64,
+        default_backoff: (f64, f64),
+    ) -> Option<(f64, f64)> {
+        match (error_class, policy) {
+            (_, Some(policy)) => (attempt.saturating_add(1) < policy.max_attempts)
+                .then_some((policy.backoff_max, policy.backoff_multiplier)),
+            (ErrorClass::Retry, None) => Some(default_backoff),
+            (ErrorClass::Fatal | ErrorClass::Unprocessable, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff() {
+        let policy = RetryPolicy {
+            error_class: ErrorClass::Fatal,
+            message_filters: vec![],
+            max_attempts: 3,
+            backoff_max: 10.0,
+            backoff_multiplier: 1.5,
+        };
+
+        assert_eq!(
+            RetryPolicy::retry_backoff(Some(&policy), ErrorClass::Fatal, 0, (1.0, 1.0)),
+            Some((10.0, 1.5))
+        );
+        assert_eq!(
+            RetryPolicy::retry_backoff(Some(&policy), ErrorClass::Fatal, 2, (1.0, 1.0)),
+            None
+        );
+        assert_eq!(
+            RetryPolicy::retry_backoff(None, ErrorClass::Retry, 100, (1.0, 1.0)),
+            Some((1.0, 1.0))
+        );
+        assert_eq!(
+            RetryPolicy::retry_backoff(None, ErrorClass::Unprocessable, 0, (1.0, 1.0)),
+            None
+        );
+    }
+}
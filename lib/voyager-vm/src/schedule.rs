@@ -0,0 +1,196 @@
+//! Priority classes and per-chain fair scheduling, shared between the [`Queue`](crate::Queue)
+//! implementations.
+//!
+//! Items are first ordered by their [`Priority`] (if enabled), raised by one class for every
+//! [`SchedulingConfig::priority_aging_seconds`] that they have been ready for, and then by the
+//! virtual time of the chain they belong to. Each time an item is processed, the virtual time of
+//! it's chain is advanced by `1 / weight`, such that chains with a higher weight are scheduled
+//! proportionally more often, and a single busy chain cannot starve the others. This is start-time
+//! fair queueing: chains that were idle are caught up to the current virtual time when they become
+//! active again, instead of being able to claim all of the time they were idle for.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    num::{NonZeroU32, NonZeroU64},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use unionlabs::option_unwrap;
+
+/// The priority class of an op.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Priority {
+    /// Work that is not time sensitive, such as fetching historical blocks.
+    Backfill,
+    #[default]
+    Normal,
+    /// Time sensitive work, such as submitting transactions.
+    Urgent,
+}
+
+impl Priority {
+    /// All priority classes, from lowest to highest.
+    pub const ALL: [Self; 3] = [Self::Backfill, Self::Normal, Self::Urgent];
+
+    /// The representation of this priority class in a database column.
+    #[must_use]
+    pub const fn as_i16(self) -> i16 {
+        match self {
+            Self::Backfill => 0,
+            Self::Normal => 1,
+            Self::Urgent => 2,
+        }
+    }
+
+    /// This priority class raised by `classes`, saturating at [`Priority::Urgent`].
+    #[must_use]
+    pub fn raised(self, classes: u64) -> Self {
+        let classes = usize::try_from(classes).unwrap_or(usize::MAX);
+
+        Self::ALL[(self as usize)
+            .saturating_add(classes)
+            .min(Self::ALL.len() - 1)]
+    }
+}
+
+/// Scheduling metadata for an op, as returned by [`QueueMessage::schedule`].
+///
+/// [`QueueMessage::schedule`]: crate::QueueMessage::schedule
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Schedule {
+    pub priority: Priority,
+    /// The key used for fair scheduling, usually a chain id. All ops without a key are scheduled
+    /// as if they belonged to the same chain.
+    pub chain: Option<String>,
+}
+
+impl Schedule {
+    /// The key to use for fair scheduling of this item.
+    #[must_use]
+    pub fn chain_key(&self) -> &str {
+        self.chain.as_deref().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SchedulingConfig {
+    /// Process all ready items in a higher priority class before any items in a lower priority
+    /// class (the default). If this is disabled, only per-chain fairness is applied.
+    #[serde(default = "default_prioritize")]
+    pub prioritize: bool,
+    /// How long an item can be ready for before it is raised by one priority class. This bounds
+    /// how long lower priority items are held back while higher priority items are constantly
+    /// ready, as a backfill item is scheduled as urgent once it has been ready for twice this long.
+    #[serde(default = "default_priority_aging_seconds")]
+    pub priority_aging_seconds: NonZeroU64,
+    /// Weights for the per-chain fair scheduling. If they both have items ready, a chain with a
+    /// weight of 2 will be scheduled twice as often as a chain with a weight of 1. Chains that are
+    /// not listed have a weight of 1.
+    #[serde(default)]
+    pub chain_weights: BTreeMap<String, NonZeroU32>,
+}
+
+impl Default for SchedulingConfig {
+    fn default() -> Self {
+        Self {
+            prioritize: default_prioritize(),
+            priority_aging_seconds: default_priority_aging_seconds(),
+            chain_weights: BTreeMap::default(),
+        }
+    }
+}
+
+#[must_use]
+pub const fn default_prioritize() -> bool {
+    true
+}
+
+#[must_use]
+pub const fn default_priority_aging_seconds() -> NonZeroU64 {
+    option_unwrap!(NonZeroU64::new(60))
+}
+
+/// Tracks the virtual time of each chain, see the [module level docs](self) for more information.
+///
+/// This is cheap to clone, and all clones share the same state.
+#[derive(Debug, Clone)]
+pub struct FairScheduler {
+    config: SchedulingConfig,
+    state: Arc<Mutex<FairSchedulerState>>,
+}
+
+#[derive(Debug, Default)]
+struct FairSchedulerState {
+    system_virtual_time: f64,
+    virtual_times: HashMap<String, f64>,
+}
+
+impl FairScheduler {
+    #[must_use]
+    pub fn new(config: SchedulingConfig) -> Self {
+        Self {
+            config,
+            state: Arc::default(),
+        }
+    }
+
+    /// Whether items should be ordered by their [`Priority`] first.
+    #[must_use]
+    pub fn prioritize(&self) -> bool {
+        self.config.prioritize
+    }
+
+    /// How long an item can be ready for before it is raised by one priority class.
+    #[must_use]
+    pub fn priority_aging(&self) -> Duration {
+        Duration::from_secs(self.config.priority_aging_seconds.get())
+    }
+
+    /// The priority to schedule an item of `priority` with, given that it has been ready for
+    /// `ready_for`.
+    #[must_use]
+    pub fn aged_priority(&self, priority: Priority, ready_for: Duration) -> Priority {
+        priority.raised(ready_for.as_secs() / self.config.priority_aging_seconds.get())
+    }
+
+    /// The virtual time of all chains that have been scheduled so far, along with the current
+    /// system virtual time. The virtual time of a chain is the maximum of it's own virtual time
+    /// and the system virtual time; chains that are not present in the returned map are at the
+    /// system virtual time.
+    #[must_use]
+    pub fn virtual_times(&self) -> (HashMap<String, f64>, f64) {
+        let state = self.state.lock().expect("mutex is poisoned");
+
+        (state.virtual_times.clone(), state.system_virtual_time)
+    }
+
+    /// Record that an item of `chain` has been scheduled, advancing it's virtual time.
+    pub fn record(&self, chain: &str) {
+        let weight = self
+            .config
+            .chain_weights
+            .get(chain)
+            .map_or(1, |weight| weight.get());
+
+        let mut state = self.state.lock().expect("mutex is poisoned");
+
+        let start = state
+            .virtual_times
+            .get(chain)
+            .copied()
+            .unwrap_or_default()
+            .max(state.system_virtual_time);
+
+        state.system_virtual_time = start;
+        state
+            .virtual_times
+            .insert(chain.to_owned(), start + 1.0 / f64::from(weight));
+    }
+}
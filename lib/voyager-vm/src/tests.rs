@@ -1,9 +1,19 @@
+use std::{
+    collections::BTreeMap,
+    num::{NonZeroU32, NonZeroU64},
+    time::Duration,
+};
+
 use macros::model;
 
 use crate::{
-    call, conc, data, defer, noop, now, promise, seq,
+    call, conc, data, defer,
+    in_memory::{InMemoryQueue, InMemoryQueueConfig},
+    noop, now, promise,
+    schedule::{FairScheduler, Priority, Schedule, SchedulingConfig},
+    seq,
     tests::utils::{BuildPrintAbc, DataA, DataB, DataC, FetchA, FetchB, PrintAbc, SimpleMessage},
-    QueueMessage,
+    HistoryItem, ItemId, ItemStatus, Op, Queue, QueueMessage,
 };
 
 pub mod utils;
@@ -268,3 +278,266 @@ fn seq_conc_conc() {
 
     assert_eq!(op.normalize(), expected_output);
 }
+
+#[tokio::test]
+async fn in_memory_history() {
+    let queue = InMemoryQueue::<SimpleMessage>::new(Default::default())
+        .await
+        .unwrap();
+
+    queue.enqueue(call(FetchA {}), &()).await.unwrap();
+
+    queue
+        .process(&(), async |_, _| {
+            ((), Ok(vec![call(FetchB {}), data(DataA {})]))
+        })
+        .await
+        .unwrap();
+
+    let id = |id| ItemId::new(id).unwrap();
+
+    let expected = vec![
+        HistoryItem {
+            id: id(0),
+            parents: vec![],
+            status: ItemStatus::Done,
+            op: call(FetchA {}),
+            message: None,
+        },
+        HistoryItem {
+            id: id(1),
+            parents: vec![id(0)],
+            status: ItemStatus::Queued,
+            op: call(FetchB {}),
+            message: None,
+        },
+        HistoryItem {
+            id: id(2),
+            parents: vec![id(0)],
+            status: ItemStatus::Queued,
+            op: data(DataA {}),
+            message: None,
+        },
+    ];
+
+    assert_eq!(queue.history(id(0), 10).await.unwrap(), expected);
+
+    // walking up from a child only includes it's ancestors
+    assert_eq!(
+        queue.history(id(1), 10).await.unwrap(),
+        expected[..2].to_vec()
+    );
+
+    assert_eq!(
+        queue.history(id(1), 0).await.unwrap(),
+        expected[1..2].to_vec()
+    );
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum ScheduledMessage {}
+
+impl QueueMessage for ScheduledMessage {
+    type Data = ();
+    type Call = ScheduledCall;
+    type Callback = ();
+
+    fn schedule(op: &Op<Self>) -> Schedule {
+        match op {
+            Op::Call(ScheduledCall { chain, priority }) => Schedule {
+                priority: *priority,
+                chain: Some(chain.clone()),
+            },
+            _ => Schedule::default(),
+        }
+    }
+}
+
+#[model]
+pub struct ScheduledCall {
+    chain: String,
+    priority: Priority,
+}
+
+#[test]
+fn fair_scheduler_weights() {
+    let scheduler = FairScheduler::new(SchedulingConfig {
+        prioritize: true,
+        chain_weights: BTreeMap::from([("a".to_owned(), NonZeroU32::new(2).unwrap())]),
+        ..Default::default()
+    });
+
+    let mut picked = BTreeMap::<&str, u32>::new();
+
+    for _ in 0..30 {
+        let (virtual_times, system_virtual_time) = scheduler.virtual_times();
+
+        let virtual_time = |chain: &str| {
+            virtual_times
+                .get(chain)
+                .copied()
+                .unwrap_or_default()
+                .max(system_virtual_time)
+        };
+
+        // both chains always have items ready
+        let chain = ["a", "b"]
+            .into_iter()
+            .min_by(|a, b| virtual_time(a).total_cmp(&virtual_time(b)))
+            .unwrap();
+
+        scheduler.record(chain);
+
+        *picked.entry(chain).or_default() += 1;
+    }
+
+    assert_eq!(picked, BTreeMap::from([("a", 20), ("b", 10)]));
+}
+
+#[test]
+fn fair_scheduler_priority_aging() {
+    let scheduler = FairScheduler::new(SchedulingConfig {
+        priority_aging_seconds: NonZeroU64::new(10).unwrap(),
+        ..Default::default()
+    });
+
+    let aged = |priority, secs| scheduler.aged_priority(priority, Duration::from_secs(secs));
+
+    assert_eq!(aged(Priority::Backfill, 0), Priority::Backfill);
+    assert_eq!(aged(Priority::Backfill, 9), Priority::Backfill);
+    assert_eq!(aged(Priority::Backfill, 10), Priority::Normal);
+    assert_eq!(aged(Priority::Backfill, 20), Priority::Urgent);
+    assert_eq!(aged(Priority::Normal, 15), Priority::Urgent);
+    // saturates at the highest priority class
+    assert_eq!(aged(Priority::Urgent, 1000), Priority::Urgent);
+    assert_eq!(aged(Priority::Backfill, u64::MAX), Priority::Urgent);
+}
+
+#[tokio::test]
+async fn in_memory_priority_and_fairness() {
+    let queue = InMemoryQueue::<ScheduledMessage>::new(InMemoryQueueConfig {
+        scheduling: SchedulingConfig {
+            prioritize: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let op = |chain: &str, priority| {
+        call(ScheduledCall {
+            chain: chain.to_owned(),
+            priority,
+        })
+    };
+
+    for _ in 0..4 {
+        queue.enqueue(op("a", Priority::Normal), &()).await.unwrap();
+    }
+    for _ in 0..2 {
+        queue.enqueue(op("b", Priority::Normal), &()).await.unwrap();
+    }
+    queue
+        .enqueue(op("a", Priority::Backfill), &())
+        .await
+        .unwrap();
+    queue.enqueue(op("b", Priority::Urgent), &()).await.unwrap();
+
+    let mut processed = vec![];
+
+    while let Some(op) = queue
+        .process(&(), async |op, _| (op, Ok(vec![])))
+        .await
+        .unwrap()
+    {
+        processed.push(op);
+    }
+
+    assert_eq!(
+        processed,
+        vec![
+            // urgent items are processed first, regardless of when they were queued
+            op("b", Priority::Urgent),
+            // chain b has already been scheduled once, so a gets to catch up
+            op("a", Priority::Normal),
+            op("a", Priority::Normal),
+            op("b", Priority::Normal),
+            op("a", Priority::Normal),
+            op("b", Priority::Normal),
+            op("a", Priority::Normal),
+            op("a", Priority::Backfill),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn in_memory_snapshot() {
+    let snapshot_path = std::env::temp_dir().join(format!(
+        "voyager-vm-in-memory-snapshot-{}.json",
+        std::process::id()
+    ));
+
+    let config = InMemoryQueueConfig {
+        snapshot_path: Some(snapshot_path.clone()),
+        ..Default::default()
+    };
+
+    let queue = InMemoryQueue::<SimpleMessage>::new(config.clone())
+        .await
+        .unwrap();
+
+    queue.enqueue(call(FetchA {}), &()).await.unwrap();
+
+    queue
+        .process(&(), async |_, _| {
+            ((), Ok(vec![call(FetchB {}), data(DataA {})]))
+        })
+        .await
+        .unwrap();
+
+    queue.checkpoint().await.unwrap();
+
+    let queue = InMemoryQueue::<SimpleMessage>::new(config).await.unwrap();
+
+    // the snapshot is consumed when it is loaded
+    assert!(!snapshot_path.exists());
+
+    let id = |id| ItemId::new(id).unwrap();
+
+    // done items are not included in the snapshot, but the ids and parents of the remaining items
+    // are preserved
+    assert_eq!(
+        queue.history(id(0), 10).await.unwrap(),
+        vec![
+            HistoryItem {
+                id: id(1),
+                parents: vec![id(0)],
+                status: ItemStatus::Queued,
+                op: call(FetchB {}),
+                message: None,
+            },
+            HistoryItem {
+                id: id(2),
+                parents: vec![id(0)],
+                status: ItemStatus::Queued,
+                op: data(DataA {}),
+                message: None,
+            },
+        ]
+    );
+
+    // new items continue from the highest id in the snapshot
+    queue.enqueue(call(FetchA {}), &()).await.unwrap();
+
+    assert_eq!(
+        queue.history(id(3), 0).await.unwrap(),
+        vec![HistoryItem {
+            id: id(3),
+            parents: vec![],
+            status: ItemStatus::Queued,
+            op: call(FetchA {}),
+            message: None,
+        }]
+    );
+}
@@ -1,11 +1,12 @@
 use std::ops::Div;
 
 use alloy::providers::{layers::CacheLayer, DynProvider, Provider, ProviderBuilder};
-use beacon_api::client::BeaconApiClient;
+use beacon_api::client::{BeaconApiClient, Encoding};
 use beacon_api_types::{altair::SyncCommittee, chain_spec::PresetBaseKind, custom_types::Slot};
 use ethereum_light_client_types::{
     client_state::InitialSyncCommittee, ClientState, ClientStateV1, ConsensusState,
 };
+use fork_schedules::ForkParameters;
 use jsonrpsee::{
     core::{async_trait, RpcResult},
     types::ErrorObject,
@@ -43,6 +44,10 @@ pub struct Module {
 
     pub provider: DynProvider,
     pub beacon_api_client: BeaconApiClient,
+
+    /// The fork parameters to store in a [`ClientState::V2`], or `None` to create a
+    /// [`ClientState::V1`] that uses the well-known fork schedule for the chain id.
+    pub fork_parameters: Option<ForkParameters>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,9 +62,30 @@ pub struct Config {
     pub rpc_url: String,
     /// The RPC endpoint for the beacon chain.
     pub beacon_rpc_url: String,
+    /// The preferred encoding of the beacon chain RPC responses. SSZ is only used for the routes
+    /// that support it, and falls back to JSON.
+    #[serde(default)]
+    pub beacon_rpc_encoding: Encoding,
 
     #[serde(default)]
     pub max_cache_size: u32,
+
+    /// Where to source the fork schedule of the beacon chain from.
+    #[serde(default)]
+    pub fork_schedule: ForkScheduleSource,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForkScheduleSource {
+    /// Use the fork schedule of the well-known chain with this chain id. The fork schedule is not
+    /// stored in the client state, and a [`ClientState::V1`] is created.
+    #[default]
+    WellKnown,
+    /// Load the fork schedule from the beacon node's `/eth/v1/config/spec` endpoint.
+    Spec,
+    /// Use the provided fork schedule.
+    Custom(ForkParameters),
 }
 
 impl Module {
@@ -133,7 +159,8 @@ impl ClientBootstrapModule for Module {
         info.ensure_chain_id(chain_id.to_string())?;
         info.ensure_client_type(ClientType::ETHEREUM)?;
 
-        let beacon_api_client = BeaconApiClient::new(config.beacon_rpc_url);
+        let beacon_api_client =
+            BeaconApiClient::new(config.beacon_rpc_url).with_encoding(config.beacon_rpc_encoding);
 
         let spec = beacon_api_client.spec().await.unwrap();
 
@@ -145,12 +172,21 @@ impl ClientBootstrapModule for Module {
             );
         }
 
+        let fork_parameters = match config.fork_schedule {
+            ForkScheduleSource::WellKnown => None,
+            ForkScheduleSource::Spec => Some(spec.fork_parameters()),
+            ForkScheduleSource::Custom(fork_parameters) => Some(fork_parameters),
+        };
+
+        debug!(?fork_parameters);
+
         Ok(Self {
             chain_id,
             chain_spec: spec.preset_base,
             ibc_handler_address: config.ibc_handler_address,
             provider,
             beacon_api_client,
+            fork_parameters,
         })
     }
 }
@@ -247,23 +283,26 @@ impl ClientBootstrapModuleServer for Module {
                 |l| l.current_sync_committee,
             );
 
-        Ok(serde_json::to_value(ClientState::V1(ClientStateV1 {
-            chain_id: self
-                .chain_id
-                .as_str()
-                .parse()
-                .expect("self.chain_id is a valid u256"),
-            chain_spec: spec.preset_base,
-            genesis_validators_root: genesis.genesis_validators_root,
-            genesis_time: genesis.genesis_time,
-            latest_height: height.height(),
-            frozen_height: Height::new(0),
-            ibc_contract_address: self.ibc_handler_address,
-            initial_sync_committee: Some(InitialSyncCommittee {
-                current_sync_committee,
-                next_sync_committee: light_client_update.next_sync_committee.unwrap(),
-            }),
-        }))
+        Ok(serde_json::to_value(ClientState::from_parts(
+            ClientStateV1 {
+                chain_id: self
+                    .chain_id
+                    .as_str()
+                    .parse()
+                    .expect("self.chain_id is a valid u256"),
+                chain_spec: spec.preset_base,
+                genesis_validators_root: genesis.genesis_validators_root,
+                genesis_time: genesis.genesis_time,
+                latest_height: height.height(),
+                frozen_height: Height::new(0),
+                ibc_contract_address: self.ibc_handler_address,
+                initial_sync_committee: Some(InitialSyncCommittee {
+                    current_sync_committee,
+                    next_sync_committee: light_client_update.next_sync_committee.unwrap(),
+                }),
+            },
+            self.fork_parameters.clone(),
+        ))
         .expect("infallible"))
     }
 
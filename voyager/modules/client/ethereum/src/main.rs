@@ -81,12 +81,12 @@ impl ClientModuleServer for Module {
         _: &Extensions,
         client_state: Bytes,
     ) -> RpcResult<ClientStateMeta> {
-        match Module::decode_client_state(&client_state)? {
-            ClientState::V1(v1) => Ok(ClientStateMeta {
-                counterparty_chain_id: ChainId::new(v1.chain_id.to_string()),
-                counterparty_height: Module::make_height(v1.latest_height),
-            }),
-        }
+        let client_state = Module::decode_client_state(&client_state)?;
+
+        Ok(ClientStateMeta {
+            counterparty_chain_id: ChainId::new(client_state.chain_id().to_string()),
+            counterparty_height: Module::make_height(client_state.latest_height()),
+        })
     }
 
     #[instrument]
@@ -4,8 +4,10 @@ use alloy::providers::{DynProvider, Provider, ProviderBuilder};
 use beacon_api_types::{chain_spec::Mainnet, deneb};
 use jsonrpsee::{
     core::{async_trait, RpcResult},
+    types::ErrorObject,
     Extensions,
 };
+use rpc_pool::{eth::PoolTransport, Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
 use unionlabs::{
@@ -13,6 +15,7 @@ use unionlabs::{
     encoding::{DecodeAs, Ssz},
     ibc::core::client::height::Height,
     primitives::H160,
+    ErrorReporter,
 };
 use voyager_sdk::{
     anyhow,
@@ -34,6 +37,7 @@ pub struct Module {
     pub l2_chain_id: ChainId,
     pub ibc_handler_address: H160,
     pub eth_provider: DynProvider,
+    pub eth_transport: PoolTransport,
     pub tm_client: cometbft_rpc::Client,
 }
 
@@ -43,17 +47,27 @@ pub struct Config {
     pub l1_client_id: u32,
     pub l1_chain_id: ChainId,
     pub ibc_handler_address: H160,
-    pub rpc_url: String,
-    pub comet_ws_url: String,
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
+    pub comet_ws_url: Endpoints,
+    #[serde(default)]
+    pub comet_rpc_pool: PoolConfig,
 }
 
 impl FinalityModule for Module {
     type Config = Config;
 
     async fn new(config: Self::Config, info: FinalityModuleInfo) -> anyhow::Result<Self> {
-        let tm_client = cometbft_rpc::Client::new(config.comet_ws_url).await?;
+        let tm_client =
+            cometbft_rpc::Client::new_pool(config.comet_ws_url.urls(), config.comet_rpc_pool)
+                .await?;
+
+        let eth_transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
 
-        let eth_provider = DynProvider::new(ProviderBuilder::new().connect(&config.rpc_url).await?);
+        let eth_provider = DynProvider::new(
+            ProviderBuilder::new().connect_client(eth_transport.clone().into_client()),
+        );
 
         let l2_chain_id = ChainId::new(eth_provider.get_chain_id().await?.to_string());
 
@@ -66,6 +80,7 @@ impl FinalityModule for Module {
             l2_chain_id,
             ibc_handler_address: config.ibc_handler_address,
             eth_provider,
+            eth_transport,
             tm_client,
         })
     }
@@ -106,12 +121,11 @@ impl FinalityModuleServer for Module {
 
             Ok(Height::new(execution_header.block_number))
         } else {
-            Ok(Height::new(
-                self.eth_provider
-                    .get_block_number()
-                    .await
-                    .expect("big trouble"),
-            ))
+            self.eth_transport
+                .block_number()
+                .await
+                .map(Height::new)
+                .map_err(|err| ErrorObject::owned(-1, ErrorReporter(err).to_string(), None::<()>))
         }
     }
 
@@ -1,9 +1,11 @@
-use std::num::ParseIntError;
+use std::num::{NonZeroU64, ParseIntError};
 
+use cometbft_rpc::rpc_types::CommitResponse;
 use jsonrpsee::{
     core::{async_trait, RpcResult},
     Extensions,
 };
+use rpc_pool::{Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{error, instrument, trace};
 use unionlabs::{
@@ -34,7 +36,9 @@ pub struct Module {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
-    pub rpc_url: String,
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ibc_host_contract_address: Option<Bech32<H256>>,
 }
@@ -43,7 +47,8 @@ impl FinalityModule for Module {
     type Config = Config;
 
     async fn new(config: Self::Config, info: FinalityModuleInfo) -> anyhow::Result<Self> {
-        let tm_client = cometbft_rpc::Client::new(config.rpc_url).await?;
+        let tm_client =
+            cometbft_rpc::Client::new_pool(config.rpc_url.urls(), config.rpc_pool).await?;
 
         let chain_id = tm_client.status().await?.node_info.network.to_string();
 
@@ -89,9 +94,22 @@ impl Module {
         Height::new_with_revision(self.chain_revision, height)
     }
 
+    /// Fetch the commit for the latest height. The height is read with a quorum across all of the
+    /// configured endpoints, such that a single endpoint that is ahead of the rest of the network
+    /// isn't trusted.
+    async fn latest_commit(&self) -> Result<CommitResponse, cometbft_rpc::JsonRpcError> {
+        let latest_height = self.cometbft_client.latest_height().await?;
+
+        self.cometbft_client
+            .commit(Some(
+                NonZeroU64::new(latest_height).expect("block height is never zero; qed;"),
+            ))
+            .await
+    }
+
     #[instrument(skip_all, fields(%finalized))]
     async fn latest_height(&self, finalized: bool) -> Result<Height, cometbft_rpc::JsonRpcError> {
-        let commit_response = self.cometbft_client.commit(None).await?;
+        let commit_response = self.latest_commit().await?;
 
         let mut height = commit_response
             .signed_header
@@ -134,8 +152,7 @@ impl FinalityModuleServer for Module {
         finalized: bool,
     ) -> RpcResult<Timestamp> {
         let mut commit_response = self
-            .cometbft_client
-            .commit(None)
+            .latest_commit()
             .await
             .map_err(json_rpc_error_to_error_object)?;
 
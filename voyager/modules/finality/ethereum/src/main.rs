@@ -2,12 +2,9 @@
 
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use alloy::{
-    eips::BlockNumberOrTag,
-    providers::{layers::CacheLayer, DynProvider, Provider, ProviderBuilder},
-};
+use alloy::providers::{layers::CacheLayer, DynProvider, Provider, ProviderBuilder};
 use beacon_api::{
-    client::{BeaconApiClient, VersionedResponse},
+    client::{BeaconApiClient, Encoding, VersionedResponse},
     routes::light_client_finality_update::LightClientFinalityUpdateResponseTypes,
 };
 use beacon_api_types::{chain_spec::PresetBaseKind, custom_types::Slot};
@@ -16,6 +13,7 @@ use jsonrpsee::{
     types::ErrorObject,
     Extensions,
 };
+use rpc_pool::{eth::PoolTransport, Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, instrument, trace};
 use unionlabs::{ibc::core::client::height::Height, primitives::H256, ErrorReporter};
@@ -36,6 +34,7 @@ pub struct Module {
     chain_id: ChainId,
 
     provider: DynProvider,
+    transport: PoolTransport,
     beacon_api_client: BeaconApiClient,
 
     finality_update:
@@ -47,10 +46,18 @@ pub struct Module {
 pub struct Config {
     pub chain_spec: PresetBaseKind,
 
-    /// The RPC endpoint for the execution chain.
-    pub rpc_url: String,
-    /// The RPC endpoint for the beacon chain.
-    pub beacon_rpc_url: String,
+    /// The RPC endpoint(s) for the execution chain.
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
+    /// The RPC endpoint(s) for the beacon chain.
+    pub beacon_rpc_url: Endpoints,
+    #[serde(default)]
+    pub beacon_rpc_pool: PoolConfig,
+    /// The preferred encoding of the beacon chain RPC responses. SSZ is only used for the routes
+    /// that support it, and falls back to JSON.
+    #[serde(default)]
+    pub beacon_rpc_encoding: Encoding,
 
     #[serde(default)]
     pub max_cache_size: u32,
@@ -123,7 +130,7 @@ impl Module {
 
     /// Returns (block_number, timestamp)
     async fn query_latest_execution_meta(&self) -> RpcResult<(u64, u64)> {
-        Ok(self
+        let (slot, block_number, timestamp) = self
             .finality_update_cached()
             .await
             .map_err(|err| ErrorObject::owned(-1, ErrorReporter(err).to_string(), None::<()>))?
@@ -133,23 +140,53 @@ impl Module {
                 |_| todo!(),
                 |f| {
                     (
+                        f.finalized_header.beacon.slot,
                         f.finalized_header.execution.block_number,
                         f.finalized_header.execution.timestamp,
                     )
                 },
                 |f| {
                     (
+                        f.finalized_header.beacon.slot,
                         f.finalized_header.execution.block_number,
                         f.finalized_header.execution.timestamp,
                     )
                 },
                 |f| {
                     (
+                        f.finalized_header.beacon.slot,
                         f.finalized_header.execution.block_number,
                         f.finalized_header.execution.timestamp,
                     )
                 },
-            ))
+            );
+
+        // the finality update is served by a single beacon node, ensure that it is not ahead of
+        // what a quorum of the configured beacon nodes consider to be finalized
+        let finalized_slot = self
+            .beacon_api_client
+            .finalized_slot()
+            .await
+            .map_err(|err| {
+                ErrorObject::owned(
+                    -1,
+                    ErrorReporter(err).with_message("error fetching finalized slot"),
+                    None::<()>,
+                )
+            })?;
+
+        if slot > finalized_slot {
+            return Err(ErrorObject::owned(
+                -1,
+                format!(
+                    "finality update is for slot {slot}, but the finalized slot agreed on by the \
+                    beacon nodes is {finalized_slot}"
+                ),
+                None::<()>,
+            ));
+        }
+
+        Ok((block_number, timestamp))
     }
 
     // TODO: Deduplicate this from ethereum client-update plugin
@@ -210,11 +247,12 @@ impl FinalityModule for Module {
     type Config = Config;
 
     async fn new(config: Self::Config, info: FinalityModuleInfo) -> anyhow::Result<Self> {
+        let transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
+
         let provider = DynProvider::new(
             ProviderBuilder::new()
                 .layer(CacheLayer::new(config.max_cache_size))
-                .connect(&config.rpc_url)
-                .await?,
+                .connect_client(transport.clone().into_client()),
         );
 
         let chain_id = ChainId::new(provider.get_chain_id().await?.to_string());
@@ -222,7 +260,9 @@ impl FinalityModule for Module {
         info.ensure_chain_id(chain_id.to_string())?;
         info.ensure_consensus_type(ConsensusType::ETHEREUM)?;
 
-        let beacon_api_client = BeaconApiClient::new(config.beacon_rpc_url);
+        let beacon_api_client =
+            BeaconApiClient::new_pool(config.beacon_rpc_url.urls(), config.beacon_rpc_pool)?
+                .with_encoding(config.beacon_rpc_encoding);
 
         let spec = beacon_api_client
             .spec()
@@ -240,6 +280,7 @@ impl FinalityModule for Module {
         Ok(Self {
             chain_id,
             provider,
+            transport,
             beacon_api_client,
             finality_update: moka::future::CacheBuilder::new(1)
                 .name("finality_update")
@@ -262,8 +303,8 @@ impl FinalityModuleServer for Module {
                 .map(|meta| Height::new(meta.0))
                 .map_err(|err| ErrorObject::owned(-1, ErrorReporter(err).to_string(), None::<()>))
         } else {
-            self.provider
-                .get_block_number()
+            self.transport
+                .block_number()
                 .await
                 .map(Height::new)
                 .map_err(|err| ErrorObject::owned(-1, ErrorReporter(err).to_string(), None::<()>))
@@ -280,8 +321,12 @@ impl FinalityModuleServer for Module {
         let latest_timestamp = if finalized {
             self.query_latest_execution_meta().await?.1
         } else {
+            let latest_height = self.transport.block_number().await.map_err(|err| {
+                ErrorObject::owned(-1, ErrorReporter(err).to_string(), None::<()>)
+            })?;
+
             self.provider
-                .get_block(BlockNumberOrTag::Latest.into())
+                .get_block(latest_height.into())
                 .hashes()
                 .await
                 .map_err(|err| ErrorObject::owned(-1, ErrorReporter(err).to_string(), None::<()>))?
@@ -1,10 +1,15 @@
-use std::{fmt::Debug, num::ParseIntError};
+use std::{
+    fmt::Debug,
+    num::{NonZeroU64, ParseIntError},
+};
 
+use cometbft_rpc::rpc_types::CommitResponse;
 use jsonrpsee::{
     core::{async_trait, RpcResult},
     types::ErrorObject,
     Extensions,
 };
+use rpc_pool::{Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{error, instrument, trace};
 use unionlabs::{ibc::core::client::height::Height, ErrorReporter};
@@ -31,14 +36,17 @@ pub struct Module {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
-    pub rpc_url: String,
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
 }
 
 impl FinalityModule for Module {
     type Config = Config;
 
     async fn new(config: Self::Config, info: FinalityModuleInfo) -> anyhow::Result<Self> {
-        let tm_client = cometbft_rpc::Client::new(config.rpc_url).await?;
+        let tm_client =
+            cometbft_rpc::Client::new_pool(config.rpc_url.urls(), config.rpc_pool).await?;
 
         let chain_id = tm_client.status().await?.node_info.network.to_string();
 
@@ -80,9 +88,22 @@ impl Module {
         Height::new_with_revision(self.chain_revision, height)
     }
 
+    /// Fetch the commit for the latest height. The height is read with a quorum across all of the
+    /// configured endpoints, such that a single endpoint that is ahead of the rest of the network
+    /// isn't trusted.
+    async fn latest_commit(&self) -> Result<CommitResponse, cometbft_rpc::JsonRpcError> {
+        let latest_height = self.cometbft_client.latest_height().await?;
+
+        self.cometbft_client
+            .commit(Some(
+                NonZeroU64::new(latest_height).expect("block height is never zero; qed;"),
+            ))
+            .await
+    }
+
     #[instrument(skip_all, fields(%finalized))]
     async fn latest_height(&self, finalized: bool) -> Result<Height, cometbft_rpc::JsonRpcError> {
-        let commit_response = self.cometbft_client.commit(None).await?;
+        let commit_response = self.latest_commit().await?;
 
         let mut height = commit_response
             .signed_header
@@ -125,8 +146,7 @@ impl FinalityModuleServer for Module {
         finalized: bool,
     ) -> RpcResult<Timestamp> {
         let mut commit_response = self
-            .cometbft_client
-            .commit(None)
+            .latest_commit()
             .await
             .map_err(json_rpc_error_to_error_object)?;
 
@@ -17,6 +17,7 @@ use jsonrpsee::{
     types::{ErrorObject, ErrorObjectOwned},
     Extensions,
 };
+use rpc_pool::{Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::{error, instrument};
@@ -62,7 +63,9 @@ pub struct Module {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
-    pub rpc_url: String,
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
     #[serde(default = "default_max_drift")]
     pub max_drift: u64,
 }
@@ -75,7 +78,8 @@ impl StateModule<IbcClassic> for Module {
     type Config = Config;
 
     async fn new(config: Self::Config, info: StateModuleInfo) -> anyhow::Result<Self> {
-        let tm_client = cometbft_rpc::Client::new(config.rpc_url).await?;
+        let tm_client =
+            cometbft_rpc::Client::new_pool(config.rpc_url.urls(), config.rpc_pool).await?;
 
         let chain_id = tm_client.status().await?.node_info.network;
 
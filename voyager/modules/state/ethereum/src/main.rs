@@ -26,6 +26,7 @@ use jsonrpsee::{
     types::ErrorObject,
     Extensions,
 };
+use rpc_pool::{eth::PoolTransport, Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::{debug, info, instrument, trace};
@@ -66,8 +67,11 @@ pub struct Config {
     /// The address of the `IBCHandler` smart contract.
     pub ibc_handler_address: H160,
 
-    /// The RPC endpoint for the execution chain.
-    pub rpc_url: String,
+    /// The RPC endpoint(s) for the execution chain.
+    pub rpc_url: Endpoints,
+
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
 
     #[serde(default)]
     pub max_query_window: Option<u64>,
@@ -80,12 +84,13 @@ impl StateModule<IbcUnion> for Module {
     type Config = Config;
 
     async fn new(config: Self::Config, info: StateModuleInfo) -> anyhow::Result<Self> {
+        let transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
+
         let provider = DynProvider::new(
             ProviderBuilder::new()
                 .layer(CacheLayer::new(config.max_cache_size))
                 .network::<AnyNetwork>()
-                .connect(&config.rpc_url)
-                .await?,
+                .connect_client(transport.into_client()),
         );
 
         let chain_id = provider.get_chain_id().await?;
@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+
+use ethereum_light_client_types::{LightClientUpdateData, SyncCommitteePeriodChangeUpdate};
+use ethereum_sync_protocol_types::LightClientUpdate;
+
+/// The maximum amount of light client updates that can be requested from the beacon node in a
+/// single `light_client_updates` request.
+///
+/// <https://github.com/ethereum/consensus-specs/blob/dev/specs/altair/light-client/p2p-interface.md#configuration>
+pub const MAX_REQUEST_LIGHT_CLIENT_UPDATES: u64 = 128;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CatchUpError {
+    #[error("missing light client update for sync committee period {0}")]
+    MissingPeriod(u64),
+    #[error("received multiple light client updates for sync committee period {0}")]
+    DuplicatePeriod(u64),
+    #[error(
+        "received light client update for sync committee period {period}, \
+        which is outside of the requested range {from_period}..={to_period}"
+    )]
+    UnexpectedPeriod {
+        period: u64,
+        from_period: u64,
+        to_period: u64,
+    },
+    #[error(
+        "light client update for sync committee period {0} does not contain the next sync \
+        committee"
+    )]
+    MissingNextSyncCommittee(u64),
+    #[error(
+        "light client update for sync committee period {period} finalizes block \
+        {block_number}, which is not after the trusted block {trusted_block_number}"
+    )]
+    NotAdvancing {
+        period: u64,
+        block_number: u64,
+        trusted_block_number: u64,
+    },
+}
+
+/// The `(start_period, count)` arguments of the `light_client_updates` requests required to fetch
+/// the updates for all of the sync committee periods in `from_period..=to_period`.
+pub fn light_client_update_ranges(from_period: u64, to_period: u64) -> Vec<(u64, u64)> {
+    (from_period..=to_period)
+        .step_by(MAX_REQUEST_LIGHT_CLIENT_UPDATES as usize)
+        .map(|start_period| {
+            (
+                start_period,
+                (to_period - start_period + 1).min(MAX_REQUEST_LIGHT_CLIENT_UPDATES),
+            )
+        })
+        .collect()
+}
+
+/// Order the light client updates fetched for the sync committee periods in
+/// `from_period..=to_period` into a sequence of period-rotating updates, such that each update
+/// can be applied to the client after the previous one.
+///
+/// The period of an update is the period of it's attested header, since that is the period the
+/// `next_sync_committee` of the update is proven against. Exactly one update is required for
+/// every period in the range, and the finalized execution block number must strictly increase
+/// from `trusted_block_number` through the whole sequence.
+///
+/// `period` is the amount of slots in a sync committee period.
+pub fn order_period_updates(
+    updates: impl IntoIterator<Item = LightClientUpdate>,
+    from_period: u64,
+    to_period: u64,
+    period: u64,
+    trusted_block_number: u64,
+) -> Result<Vec<SyncCommitteePeriodChangeUpdate>, CatchUpError> {
+    let mut by_period = BTreeMap::new();
+
+    for update in updates {
+        let update_period = update.attested_header.beacon.slot.get() / period;
+
+        if !(from_period..=to_period).contains(&update_period) {
+            return Err(CatchUpError::UnexpectedPeriod {
+                period: update_period,
+                from_period,
+                to_period,
+            });
+        }
+
+        if by_period.insert(update_period, update).is_some() {
+            return Err(CatchUpError::DuplicatePeriod(update_period));
+        }
+    }
+
+    let mut trusted_block_number = trusted_block_number;
+
+    (from_period..=to_period)
+        .map(|update_period| {
+            let update = by_period
+                .remove(&update_period)
+                .ok_or(CatchUpError::MissingPeriod(update_period))?;
+
+            let (Some(next_sync_committee), Some(next_sync_committee_branch)) = (
+                update.next_sync_committee,
+                update.next_sync_committee_branch,
+            ) else {
+                return Err(CatchUpError::MissingNextSyncCommittee(update_period));
+            };
+
+            let block_number = update.finalized_header.execution.block_number;
+
+            if block_number <= trusted_block_number {
+                return Err(CatchUpError::NotAdvancing {
+                    period: update_period,
+                    block_number,
+                    trusted_block_number,
+                });
+            }
+
+            trusted_block_number = block_number;
+
+            Ok(SyncCommitteePeriodChangeUpdate {
+                next_sync_committee,
+                next_sync_committee_branch,
+                update_data: LightClientUpdateData {
+                    attested_header: update.attested_header,
+                    finalized_header: update.finalized_header,
+                    finality_branch: update.finality_branch,
+                    sync_aggregate: update.sync_aggregate,
+                    signature_slot: update.signature_slot,
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use beacon_api_types::{
+        altair::SyncCommittee,
+        chain_spec::{ChainSpec, Mainnet},
+        custom_types::Slot,
+    };
+    use ethereum_sync_protocol::{validate_light_client_update, BlsVerify};
+    use hex_literal::hex;
+    use typenum::Unsigned;
+    use unionlabs::primitives::{H256, H384, H768};
+
+    use super::*;
+
+    // the recorded update is from sepolia, which uses the mainnet preset
+    const PERIOD: u64 = <Mainnet as ChainSpec>::PERIOD::U64;
+
+    const GENESIS_VALIDATORS_ROOT: H256 = H256::new(hex!(
+        "d8ea171f3c94aea21ebc42a1ed61052acf3f9209c00e4efbaaddac09ed9b8078"
+    ));
+
+    struct AlwaysSuccessBlsVerifier;
+
+    impl BlsVerify for AlwaysSuccessBlsVerifier {
+        const INVERSE: bool = false;
+
+        fn aggregate_verify_signature<'pk>(
+            &self,
+            _aggregate_public_key: &'pk H384,
+            _public_keys: impl IntoIterator<Item = &'pk H384>,
+            _msg: Vec<u8>,
+            _signature: H768,
+        ) -> Result<(), ethereum_sync_protocol::error::Error> {
+            Ok(())
+        }
+    }
+
+    /// The recorded update attested at slot 6553725, in period 800.
+    fn recorded_update() -> LightClientUpdate {
+        serde_json::from_str(include_str!(
+            "../../../../../lib/ethereum-sync-protocol/src/test/light_client_update_6553725.json"
+        ))
+        .unwrap()
+    }
+
+    fn recorded_sync_committee() -> SyncCommittee {
+        serde_json::from_str(include_str!(
+            "../../../../../lib/ethereum-sync-protocol/src/test/sync_committee_6553725.json"
+        ))
+        .unwrap()
+    }
+
+    /// The recorded update, moved `periods` sync committee periods forward.
+    fn shifted_update(periods: u64) -> LightClientUpdate {
+        let mut update = recorded_update();
+
+        for header in [&mut update.attested_header, &mut update.finalized_header] {
+            header.beacon.slot = Slot::new(header.beacon.slot.get() + (periods * PERIOD));
+            header.execution.block_number += periods * PERIOD;
+        }
+        update.signature_slot = Slot::new(update.signature_slot.get() + (periods * PERIOD));
+
+        update
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(light_client_update_ranges(801, 801), [(801, 1)]);
+        assert_eq!(light_client_update_ranges(801, 928), [(801, 128)]);
+        assert_eq!(
+            light_client_update_ranges(801, 1100),
+            [(801, 128), (929, 128), (1057, 44)]
+        );
+        assert!(light_client_update_ranges(801, 800).is_empty());
+    }
+
+    #[test]
+    fn orders_updates() {
+        let trusted_block_number = 7_000_000;
+
+        let ordered = order_period_updates(
+            [shifted_update(2), shifted_update(0), shifted_update(1)],
+            800,
+            802,
+            PERIOD,
+            trusted_block_number,
+        )
+        .unwrap();
+
+        assert_eq!(
+            ordered
+                .iter()
+                .map(|update| update.update_data.attested_header.beacon.slot.get() / PERIOD)
+                .collect::<Vec<_>>(),
+            [800, 801, 802]
+        );
+        assert_eq!(
+            ordered[0].next_sync_committee,
+            recorded_update().next_sync_committee.unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_ranges() {
+        assert_eq!(
+            order_period_updates([shifted_update(0), shifted_update(2)], 800, 802, PERIOD, 0),
+            Err(CatchUpError::MissingPeriod(801))
+        );
+        assert_eq!(
+            order_period_updates([shifted_update(0), shifted_update(0)], 800, 800, PERIOD, 0),
+            Err(CatchUpError::DuplicatePeriod(800))
+        );
+        assert_eq!(
+            order_period_updates([shifted_update(3)], 800, 802, PERIOD, 0),
+            Err(CatchUpError::UnexpectedPeriod {
+                period: 803,
+                from_period: 800,
+                to_period: 802
+            })
+        );
+
+        let mut update = recorded_update();
+        update.next_sync_committee_branch = None;
+        assert_eq!(
+            order_period_updates([update], 800, 800, PERIOD, 0),
+            Err(CatchUpError::MissingNextSyncCommittee(800))
+        );
+
+        let block_number = recorded_update().finalized_header.execution.block_number;
+        assert_eq!(
+            order_period_updates([recorded_update()], 800, 800, PERIOD, block_number),
+            Err(CatchUpError::NotAdvancing {
+                period: 800,
+                block_number,
+                trusted_block_number: block_number
+            })
+        );
+    }
+
+    #[test]
+    fn ordered_update_steps_client_forward() {
+        let [update] = order_period_updates([recorded_update()], 800, 800, PERIOD, 0)
+            .unwrap()
+            .try_into()
+            .unwrap();
+
+        let update = ethereum_light_client_types::LightClientUpdate::SyncCommitteePeriodChange(
+            Box::new(update),
+        )
+        .into_light_client_update();
+
+        assert_eq!(update, recorded_update());
+
+        // a client trusting the last slot of period 799 knows the sync committee of period 800 as
+        // its next sync committee, which signed the update
+        assert_eq!(
+            validate_light_client_update::<Mainnet, _>(
+                &fork_schedules::SEPOLIA.into(),
+                &update,
+                None,
+                Some(&recorded_sync_committee()),
+                Slot::new(6553726),
+                Slot::new((800 * PERIOD) - 1),
+                GENESIS_VALIDATORS_ROOT,
+                AlwaysSuccessBlsVerifier,
+            ),
+            Ok(())
+        );
+    }
+}
@@ -3,7 +3,7 @@
 use std::{collections::VecDeque, ops::Div};
 
 use alloy::providers::{DynProvider, Provider, ProviderBuilder};
-use beacon_api::client::BeaconApiClient;
+use beacon_api::client::{BeaconApiClient, Encoding};
 use beacon_api_types::{altair::SyncCommittee, chain_spec::PresetBaseKind, custom_types::Slot};
 use bitvec::{order::Msb0, vec::BitVec};
 use ethereum_light_client_types::{
@@ -16,6 +16,7 @@ use jsonrpsee::{
     types::ErrorObject,
     Extensions,
 };
+use rpc_pool::{eth::PoolTransport, Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, instrument, trace};
 use unionlabs::{
@@ -41,9 +42,13 @@ use voyager_sdk::{
     DefaultCmd,
 };
 
-use crate::call::{FetchUpdate, ModuleCall};
+use crate::{
+    call::{FetchUpdate, ModuleCall},
+    catch_up::{light_client_update_ranges, order_period_updates},
+};
 
 pub mod call;
+pub mod catch_up;
 
 #[tokio::main]
 async fn main() {
@@ -73,10 +78,18 @@ pub struct Config {
     /// The address of the `IBCHandler` smart contract.
     pub ibc_handler_address: H160,
 
-    /// The RPC endpoint for the execution chain.
-    pub rpc_url: String,
-    /// The RPC endpoint for the beacon chain.
-    pub beacon_rpc_url: String,
+    /// The RPC endpoint(s) for the execution chain.
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
+    /// The RPC endpoint(s) for the beacon chain.
+    pub beacon_rpc_url: Endpoints,
+    #[serde(default)]
+    pub beacon_rpc_pool: PoolConfig,
+    /// The preferred encoding of the beacon chain RPC responses. SSZ is only used for the routes
+    /// that support it, and falls back to JSON.
+    #[serde(default)]
+    pub beacon_rpc_encoding: Encoding,
 
     #[serde(default)]
     pub max_cache_size: u32,
@@ -140,11 +153,12 @@ impl Plugin for Module {
     type Cmd = DefaultCmd;
 
     async fn new(config: Self::Config) -> anyhow::Result<Self> {
+        let transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
+
         let provider = DynProvider::new(
             ProviderBuilder::new()
                 // .layer(CacheLayer::new(config.max_cache_size))
-                .connect(&config.rpc_url)
-                .await?,
+                .connect_client(transport.into_client()),
         );
 
         let chain_id = ChainId::new(provider.get_chain_id().await?.to_string());
@@ -157,7 +171,9 @@ impl Plugin for Module {
             );
         }
 
-        let beacon_api_client = BeaconApiClient::new(config.beacon_rpc_url);
+        let beacon_api_client =
+            BeaconApiClient::new_pool(config.beacon_rpc_url.urls(), config.beacon_rpc_pool)?
+                .with_encoding(config.beacon_rpc_encoding);
 
         let spec = beacon_api_client.spec().await.map_err(|e| {
             ErrorObject::owned(
@@ -439,76 +455,14 @@ impl Module {
 
         let (lc_updates, last_update_block_number) = if trusted_period < target_period {
             // Eth chain is more than 1 signature period ahead of us. We need to do sync committee
-            // updates until we reach the `target_period - 1`.
-
-            // let target_period = sync_committee_period(finality_update.signature_slot, spec.period());
-
-            let light_client_updates = self
-                .beacon_api_client
-                .light_client_updates(trusted_period + 1, target_period - trusted_period)
-                .await
-                .map_err(|e| {
-                    ErrorObject::owned(
-                        -1,
-                        ErrorReporter(e).with_message("error fetching light client updates"),
-                        None::<()>,
-                    )
-                })?
-                .into_iter()
-                .map(|x| {
-                    x.fold::<ethereum_sync_protocol_types::LightClientUpdate>(
-                        |u| match u {},
-                        |_| todo!("altair is not supported"),
-                        |_| todo!("bellatrix is not supported"),
-                        |_| todo!("capella is not supported"),
-                        |u| u.into(),
-                        |u| u.into(),
-                    )
-                })
-                .collect::<Vec<_>>();
-
-            info!(
-                "fetched {} light client updates",
-                light_client_updates.len()
-            );
-
-            let (updates, last_update_block_number) = stream::iter(light_client_updates)
-                .map(<RpcResult<_>>::Ok)
-                .try_fold((VecDeque::new(), update_from_block_number.height()), {
-                    async |(mut vec, mut trusted_block_number), update| {
-                        let old_trusted_block_number = trusted_block_number;
-
-                        // REVIEW: Assert that this is greater (i.e. increasing)?
-                        trusted_block_number = update.finalized_header.execution.block_number;
-
-                        vec.push_back(
-                            self.make_header(
-                                old_trusted_block_number,
-                                LightClientUpdateData {
-                                    attested_header: update.attested_header,
-                                    finalized_header: update.finalized_header,
-                                    finality_branch: update.finality_branch,
-                                    sync_aggregate: update.sync_aggregate,
-                                    signature_slot: update.signature_slot,
-                                },
-                                Some((
-                                    update
-                                        .next_sync_committee
-                                        .expect("next_sync_committee should exist"),
-                                    update
-                                        .next_sync_committee_branch
-                                        .expect("next_sync_committee_branch should exist"),
-                                )),
-                            )
-                            .await?,
-                        );
-
-                        Ok((vec, trusted_block_number))
-                    }
-                })
-                .await?;
-
-            (updates, last_update_block_number)
+            // updates for every period until we reach the `target_period`.
+            self.fetch_period_change_headers(
+                trusted_period + 1,
+                target_period,
+                spec.period(),
+                update_from_block_number.height(),
+            )
+            .await?
         } else {
             ([].into(), update_from_block_number.height())
         };
@@ -594,6 +548,115 @@ impl Module {
         ]))
     }
 
+    /// Fetch the light client updates for all sync committee periods in
+    /// `from_period..=to_period`, and build an ordered sequence of period-rotating headers from
+    /// them, starting at `trusted_block_number`.
+    ///
+    /// Returns the headers and the block number the client will be at once all of them have been
+    /// applied.
+    #[instrument(
+        skip_all,
+        fields(
+            chain_id = %self.chain_id,
+            %from_period,
+            %to_period,
+            %trusted_block_number
+        )
+    )]
+    async fn fetch_period_change_headers(
+        &self,
+        from_period: u64,
+        to_period: u64,
+        period: u64,
+        trusted_block_number: u64,
+    ) -> RpcResult<(VecDeque<Header>, u64)> {
+        let mut light_client_updates = vec![];
+
+        // beacon nodes limit the amount of updates that can be fetched at once
+        for (start_period, count) in light_client_update_ranges(from_period, to_period) {
+            debug!(%start_period, %count, "fetching light client updates");
+
+            light_client_updates.extend(
+                self.beacon_api_client
+                    .light_client_updates(start_period, count)
+                    .await
+                    .map_err(|e| {
+                        ErrorObject::owned(
+                            -1,
+                            ErrorReporter(e).with_message("error fetching light client updates"),
+                            None::<()>,
+                        )
+                    })?
+                    .into_iter()
+                    .map(|x| {
+                        let unsupported = |version: &str| {
+                            Err(ErrorObject::owned(
+                                -1,
+                                format!(
+                                    "{version} light client updates are not supported \
+                                    (start period {start_period}, count {count})"
+                                ),
+                                None::<()>,
+                            ))
+                        };
+
+                        x.fold::<RpcResult<ethereum_sync_protocol_types::LightClientUpdate>>(
+                            |u| match u {},
+                            |_| unsupported("altair"),
+                            |_| unsupported("bellatrix"),
+                            |_| unsupported("capella"),
+                            |u| Ok(u.into()),
+                            |u| Ok(u.into()),
+                        )
+                    })
+                    .collect::<RpcResult<Vec<_>>>()?,
+            );
+        }
+
+        info!(
+            "fetched {} light client updates",
+            light_client_updates.len()
+        );
+
+        let updates = order_period_updates(
+            light_client_updates,
+            from_period,
+            to_period,
+            period,
+            trusted_block_number,
+        )
+        .map_err(|e| {
+            ErrorObject::owned(
+                -1,
+                ErrorReporter(e).with_message("invalid light client updates"),
+                None::<()>,
+            )
+        })?;
+
+        stream::iter(updates)
+            .map(<RpcResult<_>>::Ok)
+            .try_fold((VecDeque::new(), trusted_block_number), {
+                async |(mut vec, trusted_block_number), update| {
+                    let block_number = update.update_data.finalized_header.execution.block_number;
+
+                    vec.push_back(
+                        self.make_header(
+                            trusted_block_number,
+                            update.update_data,
+                            Some((
+                                update.next_sync_committee,
+                                update.next_sync_committee_branch,
+                            )),
+                        )
+                        .await?,
+                    );
+
+                    Ok((vec, block_number))
+                }
+            })
+            .await
+    }
+
     #[instrument(
         skip_all,
         fields(
@@ -18,6 +18,7 @@ use jsonrpsee::{
     types::{ErrorObject, ErrorObjectOwned},
     Extensions,
 };
+use rpc_pool::{Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::{debug, error, info, info_span, instrument, trace, warn};
@@ -45,7 +46,7 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::{ChainId, ClientInfo, ClientType, IbcSpec, QueryHeight},
     rpc::{types::PluginInfo, PluginServer, FATAL_JSONRPC_ERROR_CODE},
-    vm::{call, conc, data, noop, pass::PassResult, seq, Op},
+    vm::{call, conc, data, noop, pass::PassResult, schedule::Priority, seq, Op},
     ExtensionsExt, VoyagerClient,
 };
 use wasm_client_type::WasmClientType;
@@ -87,7 +88,9 @@ pub struct Module {
 pub struct Config {
     pub chain_id: ChainId,
 
-    pub rpc_url: String,
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
 
     #[serde(default = "default_chunk_block_fetch_size")]
     pub chunk_block_fetch_size: u64,
@@ -125,7 +128,8 @@ impl Plugin for Module {
     type Cmd = Cmd;
 
     async fn new(config: Self::Config) -> anyhow::Result<Self> {
-        let tm_client = cometbft_rpc::Client::new(config.rpc_url).await?;
+        let tm_client =
+            cometbft_rpc::Client::new_pool(config.rpc_url.urls(), config.rpc_pool).await?;
 
         let chain_id = tm_client.status().await?.node_info.network;
 
@@ -316,14 +320,15 @@ impl PluginServer<ModuleCall, Never> for Module {
             ready: msgs
                 .into_iter()
                 .map(|op| match op {
-                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => {
-                        call(PluginMessage::new(
+                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => call(
+                        PluginMessage::new(
                             self.plugin_name(),
                             ModuleCall::from(FetchBlocks {
                                 height: fetch.start_height,
                             }),
-                        ))
-                    }
+                        )
+                        .with_priority(Priority::Backfill),
+                    ),
                     op => op,
                 })
                 .enumerate()
@@ -409,12 +414,15 @@ impl Module {
                     height: next_height,
                     finalized: true,
                 }),
-                call(PluginMessage::new(
-                    self.plugin_name(),
-                    ModuleCall::from(FetchBlocks {
-                        height: next_height,
-                    }),
-                )),
+                call(
+                    PluginMessage::new(
+                        self.plugin_name(),
+                        ModuleCall::from(FetchBlocks {
+                            height: next_height,
+                        }),
+                    )
+                    .with_priority(Priority::Backfill),
+                ),
             ])
         };
 
@@ -436,13 +444,16 @@ impl Module {
                 Ok(conc(
                     (height.height()..next_height)
                         .map(|h| {
-                            call(PluginMessage::new(
-                                self.plugin_name(),
-                                ModuleCall::from(FetchBlock {
-                                    already_seen_events: None,
-                                    height: Height::new_with_revision(height.revision(), h),
-                                }),
-                            ))
+                            call(
+                                PluginMessage::new(
+                                    self.plugin_name(),
+                                    ModuleCall::from(FetchBlock {
+                                        already_seen_events: None,
+                                        height: Height::new_with_revision(height.revision(), h),
+                                    }),
+                                )
+                                .with_priority(Priority::Backfill),
+                            )
                         })
                         .chain([continuation(Height::new_with_revision(
                             height.revision(),
@@ -644,13 +655,16 @@ impl Module {
                         height: Height::new(height.height() + self.refetch_delay),
                         finalized: true,
                     }),
-                    call(PluginMessage::new(
-                        self.plugin_name(),
-                        ModuleCall::from(FetchBlock {
-                            height,
-                            already_seen_events: Some(found_events),
-                        }),
-                    )),
+                    call(
+                        PluginMessage::new(
+                            self.plugin_name(),
+                            ModuleCall::from(FetchBlock {
+                                height,
+                                already_seen_events: Some(found_events),
+                            }),
+                        )
+                        .with_priority(Priority::Backfill),
+                    ),
                 ])
             }),
         )))
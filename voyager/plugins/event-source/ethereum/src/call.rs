@@ -9,6 +9,7 @@ use unionlabs::primitives::H256;
 pub enum ModuleCall {
     FetchBlocks(FetchBlocks),
     FetchGetLogs(FetchGetLogs),
+    FetchGetLogsRange(FetchGetLogsRange),
     MakeFullEvent(MakeFullEvent),
 }
 
@@ -26,6 +27,19 @@ pub struct FetchGetLogs {
     pub block_number: u64,
 }
 
+/// Fetch all events in the blocks `from_block..=to_block` emitted by the `IBCHandler` via a single
+/// [`eth_getLogs`] request.
+///
+/// If the provider rejects the range (for example because it spans too many blocks or would return
+/// too many logs), it is split in half and each half is fetched separately.
+///
+/// [`eth_getLogs`]: https://ethereum.org/en/developers/docs/apis/json-rpc/#eth_getlogs
+#[model]
+pub struct FetchGetLogsRange {
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
 /// Construct a full ChainEvent from the given EVM event and associated metadata.
 #[model]
 pub struct MakeFullEvent {
@@ -0,0 +1,281 @@
+use std::{
+    collections::BTreeMap,
+    mem,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use alloy::{
+    providers::{DynProvider, Provider},
+    rpc::types::{Filter, Log},
+    transports::TransportError,
+};
+use tracing::{debug, info, warn};
+use unionlabs::ErrorReporter;
+
+/// How long to wait before resubscribing after the subscription fails or is closed.
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(5);
+
+/// Logs streamed from an `eth_subscribe("logs")` subscription, buffered until the blocks they were
+/// emitted in are finalized and fetched by [`FetchBlocks`](crate::call::FetchBlocks).
+///
+/// Logs that are removed due to a reorg are removed from the buffer. If the subscription is
+/// interrupted, the buffer is cleared and all blocks are fetched via `eth_getLogs` until the
+/// subscription has been re-established for the entire requested range.
+///
+/// Blocks without any matching logs can't be distinguished from blocks that the subscription
+/// hasn't received yet, so new block headers are additionally streamed via
+/// `eth_subscribe("newHeads")`. Once the header of a block has been received, all logs of the
+/// blocks before it have been received as well, since the node sends the logs of a block when it
+/// is imported (before any later block can be imported) and all notifications are delivered in
+/// order over the same connection.
+#[derive(Debug, Clone, Default)]
+pub struct LiveLogs {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The first block that the current subscription is guaranteed to have received all logs for,
+    /// or `None` if there is no active subscription.
+    live_from: Option<u64>,
+    /// The last block that the current subscription is guaranteed to have received all logs for,
+    /// or `None` if no block header has been received yet.
+    received_through: Option<u64>,
+    logs: BTreeMap<u64, Vec<Log>>,
+}
+
+impl LiveLogs {
+    /// Spawn a task that keeps a subscription to all logs matching `filter` alive.
+    pub fn spawn(provider: DynProvider, filter: Filter) -> Self {
+        let this = Self::default();
+
+        tokio::spawn(this.clone().run(provider, filter));
+
+        this
+    }
+
+    /// Take the logs for all blocks in `from_block..=to_block`, if the subscription has been live
+    /// for the entire range. Logs for blocks before `from_block` are discarded.
+    pub fn take(&self, from_block: u64, to_block: u64) -> Option<Vec<Log>> {
+        let mut inner = self.inner.lock().expect("mutex is poisoned");
+
+        if inner.live_from? > from_block || inner.received_through? < to_block {
+            return None;
+        }
+
+        let rest = inner.logs.split_off(&(to_block + 1));
+        let taken = mem::replace(&mut inner.logs, rest);
+
+        Some(
+            taken
+                .into_iter()
+                .filter(|(block_number, _)| *block_number >= from_block)
+                .flat_map(|(_, logs)| logs)
+                .collect(),
+        )
+    }
+
+    async fn run(self, provider: DynProvider, filter: Filter) {
+        loop {
+            match self.subscribe(&provider, &filter).await {
+                Ok(()) => warn!("log subscription closed, resubscribing"),
+                Err(err) => warn!(
+                    err = %ErrorReporter(err),
+                    "error subscribing to logs, resubscribing"
+                ),
+            }
+
+            {
+                let mut inner = self.inner.lock().expect("mutex is poisoned");
+                inner.live_from = None;
+                inner.received_through = None;
+                inner.logs.clear();
+            }
+
+            tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+        }
+    }
+
+    async fn subscribe(
+        &self,
+        provider: &DynProvider,
+        filter: &Filter,
+    ) -> Result<(), TransportError> {
+        let mut logs = provider.subscribe_logs(filter).await?;
+        let mut heads = provider.subscribe_blocks().await?;
+
+        // any logs in blocks after the current head are guaranteed to be received by the
+        // subscription
+        let head = provider.get_block_number().await?;
+
+        self.inner.lock().expect("mutex is poisoned").live_from = Some(head + 1);
+
+        info!(%head, "subscribed to logs");
+
+        // if either subscription lags or is closed, notifications may have been missed
+        loop {
+            tokio::select! {
+                log = logs.recv() => match log {
+                    Ok(log) => self.insert(log),
+                    Err(_) => break,
+                },
+                header = heads.recv() => match header {
+                    Ok(header) => self.received_header(header.number),
+                    Err(_) => break,
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record that the header of `block_number` has been received, and as such all logs of the
+    /// blocks before it have been received.
+    fn received_header(&self, block_number: u64) {
+        let mut inner = self.inner.lock().expect("mutex is poisoned");
+
+        let received_through = block_number.saturating_sub(1);
+
+        // headers can be received for a lower block number in the case of a reorg, the logs of the
+        // reorged blocks are removed by the logs subscription
+        if inner
+            .received_through
+            .is_none_or(|current| received_through > current)
+        {
+            inner.received_through = Some(received_through);
+        }
+    }
+
+    fn insert(&self, log: Log) {
+        let Some(block_number) = log.block_number else {
+            warn!(?log, "received log without a block number");
+            return;
+        };
+
+        let mut inner = self.inner.lock().expect("mutex is poisoned");
+
+        let logs = inner.logs.entry(block_number).or_default();
+
+        if log.removed {
+            debug!(%block_number, tx_hash = ?log.transaction_hash, "log removed");
+
+            logs.retain(|l| {
+                (l.transaction_hash, l.log_index) != (log.transaction_hash, log.log_index)
+            });
+        } else {
+            logs.push(log);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(block_number: u64, log_index: u64, removed: bool) -> Log {
+        Log {
+            block_number: Some(block_number),
+            log_index: Some(log_index),
+            removed,
+            ..Default::default()
+        }
+    }
+
+    fn live_logs(live_from: u64, logs: impl IntoIterator<Item = Log>) -> LiveLogs {
+        let live_logs = LiveLogs::default();
+
+        live_logs.inner.lock().unwrap().live_from = Some(live_from);
+
+        for log in logs {
+            live_logs.insert(log);
+        }
+
+        live_logs
+    }
+
+    fn block_numbers(logs: Option<Vec<Log>>) -> Option<Vec<u64>> {
+        logs.map(|logs| {
+            logs.into_iter()
+                .map(|log| log.block_number.unwrap())
+                .collect()
+        })
+    }
+
+    #[test]
+    fn take_requires_live_subscription() {
+        let live_logs = LiveLogs::default();
+        live_logs.insert(log(10, 0, false));
+        live_logs.received_header(20);
+
+        assert_eq!(live_logs.take(10, 10), None);
+    }
+
+    #[test]
+    fn take_before_live_from() {
+        let live_logs = live_logs(10, [log(10, 0, false)]);
+        live_logs.received_header(20);
+
+        assert_eq!(live_logs.take(9, 10), None);
+        assert_eq!(block_numbers(live_logs.take(10, 10)), Some(vec![10]));
+    }
+
+    #[test]
+    fn take_past_received_header() {
+        let live_logs = live_logs(10, [log(10, 0, false), log(12, 0, false)]);
+
+        // no header received yet, the subscription may not have caught up
+        assert_eq!(live_logs.take(10, 10), None);
+
+        // all logs up to and including block 11 have been received
+        live_logs.received_header(12);
+
+        assert_eq!(live_logs.take(10, 12), None);
+        assert_eq!(block_numbers(live_logs.take(10, 11)), Some(vec![10]));
+
+        // headers for lower blocks (i.e. after a reorg) don't lower the high-water mark
+        live_logs.received_header(11);
+        live_logs.received_header(13);
+
+        assert_eq!(block_numbers(live_logs.take(12, 12)), Some(vec![12]));
+    }
+
+    #[test]
+    fn take_removes_taken_and_earlier_logs() {
+        let live_logs = live_logs(
+            10,
+            [
+                log(10, 0, false),
+                log(11, 0, false),
+                log(11, 1, false),
+                log(12, 0, false),
+                log(13, 0, false),
+            ],
+        );
+        live_logs.received_header(20);
+
+        assert_eq!(
+            block_numbers(live_logs.take(11, 12)),
+            Some(vec![11, 11, 12])
+        );
+
+        // block 10 was discarded, and the blocks in the previous range were taken
+        assert_eq!(block_numbers(live_logs.take(10, 13)), Some(vec![13]));
+    }
+
+    #[test]
+    fn removed_logs_are_not_returned() {
+        let live_logs = live_logs(10, [log(10, 0, false), log(10, 1, false), log(10, 0, true)]);
+        live_logs.received_header(11);
+
+        assert_eq!(
+            live_logs
+                .take(10, 10)
+                .unwrap()
+                .into_iter()
+                .map(|log| log.log_index.unwrap())
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+    }
+}
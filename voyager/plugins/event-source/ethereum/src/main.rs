@@ -1,10 +1,17 @@
 // #![warn(clippy::unwrap_used)] // allow for now
 
-use std::{cmp::Ordering, collections::VecDeque};
+use std::{
+    cmp::Ordering,
+    collections::VecDeque,
+    sync::{
+        atomic::{self, AtomicU64},
+        Arc,
+    },
+};
 
 use alloy::{
     providers::{layers::CacheLayer, DynProvider, Provider, ProviderBuilder},
-    rpc::types::Filter,
+    rpc::types::{Filter, Log},
     sol_types::SolEventInterface,
 };
 use ibc_solidity::Ibc;
@@ -24,6 +31,7 @@ use jsonrpsee::{
     types::ErrorObject,
     Extensions,
 };
+use rpc_pool::{eth::PoolTransport, Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, info_span, instrument, trace, warn};
 use unionlabs::{
@@ -44,13 +52,17 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::{ChainId, ClientInfo, IbcSpec, QueryHeight},
     rpc::{types::PluginInfo, PluginServer},
-    vm::{call, conc, data, noop, pass::PassResult, seq, Op},
+    vm::{call, conc, data, noop, pass::PassResult, schedule::Priority, seq, Op},
     DefaultCmd, ExtensionsExt, VoyagerClient,
 };
 
-use crate::call::{FetchBlocks, FetchGetLogs, IbcEvents, MakeFullEvent, ModuleCall};
+use crate::{
+    call::{FetchBlocks, FetchGetLogs, FetchGetLogsRange, IbcEvents, MakeFullEvent, ModuleCall},
+    live::LiveLogs,
+};
 
 pub mod call;
+pub mod live;
 
 #[tokio::main]
 async fn main() {
@@ -63,10 +75,16 @@ pub struct Module {
 
     pub ibc_handler_address: H160,
 
-    pub chunk_block_fetch_size: u64,
+    /// The amount of blocks to fetch logs for in a single `eth_getLogs` request. This starts at
+    /// [`Config::chunk_block_fetch_size`] and is adjusted based on the errors returned by the
+    /// provider.
+    pub block_range: Arc<AtomicU64>,
+    pub max_block_range: u64,
     pub index_trivial_events: bool,
 
     pub provider: DynProvider,
+
+    pub live_logs: Option<LiveLogs>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -78,11 +96,28 @@ pub struct Config {
     /// The address of the `IBCHandler` smart contract.
     pub ibc_handler_address: H160,
 
+    /// The initial amount of blocks to fetch logs for in a single `eth_getLogs` request.
+    ///
+    /// This is halved whenever the provider rejects a range (down to a single block), and doubled
+    /// after every successful request that used the full range (up to `max_block_range`).
     #[serde(default = "default_chunk_block_fetch_size")]
     pub chunk_block_fetch_size: u64,
 
-    /// The RPC endpoint for the execution chain.
-    pub rpc_url: String,
+    /// The maximum amount of blocks to fetch logs for in a single `eth_getLogs` request.
+    #[serde(default = "default_max_block_range")]
+    pub max_block_range: u64,
+
+    /// The RPC endpoint(s) for the execution chain.
+    pub rpc_url: Endpoints,
+
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
+
+    /// A websocket endpoint for the execution chain. If set, `IBCHandler` logs are additionally
+    /// streamed via `eth_subscribe("logs")`, and used instead of `eth_getLogs` once the blocks they
+    /// were emitted in are finalized.
+    #[serde(default)]
+    pub ws_url: Option<String>,
 
     /// Whether or not to fully index events that do not produce a counterparty action (packet_recv, packet_acknowledgement, packet_timeout, update_client).
     #[serde(default)]
@@ -96,6 +131,28 @@ fn default_chunk_block_fetch_size() -> u64 {
     10
 }
 
+fn default_max_block_range() -> u64 {
+    1000
+}
+
+/// The new `eth_getLogs` block range after a request for `requested` blocks succeeded, if it should
+/// be changed. The range is only grown if the full range was used, such that a short range at the
+/// tip of the chain doesn't count as evidence that the provider accepts larger ranges.
+fn grow_block_range(current: u64, requested: u64, max: u64) -> Option<u64> {
+    (requested >= current).then(|| (current * 2).min(max))
+}
+
+/// The new `eth_getLogs` block range after a request for `rejected` blocks was rejected.
+fn shrink_block_range(current: u64, rejected: u64) -> u64 {
+    current.min(rejected / 2).max(1)
+}
+
+/// The last block of the first half of `from_block..=to_block`, which must contain at least two
+/// blocks. The second half is never smaller than the first.
+fn split_block_range(from_block: u64, to_block: u64) -> u64 {
+    from_block + ((to_block - from_block + 1) / 2) - 1
+}
+
 impl Plugin for Module {
     type Call = ModuleCall;
     type Callback = Never;
@@ -134,22 +191,39 @@ impl Module {
     }
 
     pub async fn new(config: Config) -> anyhow::Result<Self> {
+        let transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
+
         let provider = DynProvider::new(
             ProviderBuilder::new()
                 .layer(CacheLayer::new(config.max_cache_size))
-                .connect(&config.rpc_url)
-                .await?,
+                .connect_client(transport.into_client()),
         );
 
         // TODO: Assert chain id is correct
         let chain_id = provider.get_chain_id().await?;
 
+        let live_logs = match config.ws_url {
+            Some(ws_url) => Some(LiveLogs::spawn(
+                DynProvider::new(ProviderBuilder::new().connect(&ws_url).await?),
+                Filter::new().address(alloy::primitives::Address::from(
+                    config.ibc_handler_address.get(),
+                )),
+            )),
+            None => None,
+        };
+
         Ok(Self {
             chain_id: ChainId::new(chain_id.to_string()),
             ibc_handler_address: config.ibc_handler_address,
             index_trivial_events: config.index_trivial_events,
-            chunk_block_fetch_size: config.chunk_block_fetch_size,
+            block_range: Arc::new(AtomicU64::new(
+                config
+                    .chunk_block_fetch_size
+                    .clamp(1, config.max_block_range.max(1)),
+            )),
+            max_block_range: config.max_block_range.max(1),
             provider,
+            live_logs,
         })
     }
 
@@ -247,14 +321,15 @@ impl PluginServer<ModuleCall, Never> for Module {
             ready: msgs
                 .into_iter()
                 .map(|op| match op {
-                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => {
-                        call(PluginMessage::new(
+                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => call(
+                        PluginMessage::new(
                             self.plugin_name(),
                             ModuleCall::from(FetchBlocks {
                                 block_number: fetch.start_height.height(),
                             }),
-                        ))
-                    }
+                        )
+                        .with_priority(Priority::Backfill),
+                    ),
                     op => op,
                 })
                 .enumerate()
@@ -280,8 +355,12 @@ impl PluginServer<ModuleCall, Never> for Module {
                 self.fetch_blocks(e.voyager_client()?, block_number).await
             }
             ModuleCall::FetchGetLogs(FetchGetLogs { block_number }) => {
-                self.fetch_get_logs(block_number).await
+                self.fetch_get_logs(block_number, block_number).await
             }
+            ModuleCall::FetchGetLogsRange(FetchGetLogsRange {
+                from_block,
+                to_block,
+            }) => self.fetch_get_logs(from_block, to_block).await,
             ModuleCall::MakeFullEvent(MakeFullEvent {
                 block_number,
                 tx_hash,
@@ -316,22 +395,25 @@ impl Module {
                     height: Height::new(next_height),
                     finalized: true,
                 }),
-                call(PluginMessage::new(
-                    self.plugin_name(),
-                    ModuleCall::from(FetchBlocks {
-                        block_number: next_height,
-                    }),
-                )),
+                call(
+                    PluginMessage::new(
+                        self.plugin_name(),
+                        ModuleCall::from(FetchBlocks {
+                            block_number: next_height,
+                        }),
+                    )
+                    .with_priority(Priority::Backfill),
+                ),
             ])
         };
 
         match block_number.cmp(&latest_height) {
             // height < latest_height
-            // fetch transactions on all blocks height..next_height (*exclusive* on the upper bound!)
-            // and then queue the continuation starting at next_height
+            // fetch logs in all blocks height..next_height (*exclusive* on the upper bound!) and
+            // then queue the continuation starting at next_height
             Ordering::Equal | Ordering::Less => {
                 let next_height = (latest_height - block_number)
-                    .clamp(1, self.chunk_block_fetch_size)
+                    .clamp(1, self.block_range.load(atomic::Ordering::Relaxed))
                     + block_number;
 
                 info!(
@@ -340,16 +422,23 @@ impl Module {
                     "batch fetching blocks in range {block_number}..{next_height}"
                 );
 
-                Ok(conc(
-                    (block_number..next_height)
-                        .map(|block_number| {
-                            call(PluginMessage::new(
-                                self.plugin_name(),
-                                ModuleCall::from(FetchGetLogs { block_number }),
-                            ))
-                        })
-                        .chain([continuation(next_height)]),
-                ))
+                let fetch = match self
+                    .live_logs
+                    .as_ref()
+                    .and_then(|live_logs| live_logs.take(block_number, next_height - 1))
+                {
+                    Some(logs) => {
+                        debug!(
+                            logs_count = logs.len(),
+                            "using logs from the live subscription"
+                        );
+
+                        self.make_events(logs)
+                    }
+                    None => self.fetch_get_logs_range(block_number, next_height - 1),
+                };
+
+                Ok(conc([fetch, continuation(next_height)]))
             }
             // height > latest_height
             Ordering::Greater => {
@@ -363,9 +452,28 @@ impl Module {
         }
     }
 
-    #[instrument(skip_all, fields(%block_number))]
-    async fn fetch_get_logs(&self, block_number: u64) -> RpcResult<Op<VoyagerMessage>> {
-        debug!("fetching logs in execution block");
+    fn fetch_get_logs_range(&self, from_block: u64, to_block: u64) -> Op<VoyagerMessage> {
+        call(
+            PluginMessage::new(
+                self.plugin_name(),
+                ModuleCall::from(FetchGetLogsRange {
+                    from_block,
+                    to_block,
+                }),
+            )
+            .with_priority(Priority::Backfill),
+        )
+    }
+
+    #[instrument(skip_all, fields(%from_block, %to_block))]
+    async fn fetch_get_logs(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> RpcResult<Op<VoyagerMessage>> {
+        debug!("fetching logs in execution blocks");
+
+        let block_range = to_block - from_block + 1;
 
         let logs = self
             .provider
@@ -374,24 +482,63 @@ impl Module {
                     .address(alloy::primitives::Address::from(
                         self.ibc_handler_address.get(),
                     ))
-                    .from_block(block_number)
-                    .to_block(block_number),
+                    .from_block(from_block)
+                    .to_block(to_block),
             )
-            .await
-            .map_err(|e| {
-                ErrorObject::owned(
-                    -1,
-                    format!(
-                        "error fetching logs in block {block_number}: {}",
-                        ErrorReporter(e)
-                    ),
-                    None::<()>,
-                )
-            })?;
+            .await;
+
+        match logs {
+            Ok(logs) => {
+                info!(logs_count = logs.len(), "found logs");
+
+                // the full range was accepted, try a larger one next time
+                let _ = self.block_range.fetch_update(
+                    atomic::Ordering::Relaxed,
+                    atomic::Ordering::Relaxed,
+                    |current| grow_block_range(current, block_range, self.max_block_range),
+                );
+
+                Ok(self.make_events(logs))
+            }
+            // the provider responded, but rejected the request; this is almost always due to the
+            // range being too large or containing too many logs
+            Err(err) if err.is_error_resp() && block_range > 1 => {
+                let mid = split_block_range(from_block, to_block);
+
+                warn!(
+                    err = %ErrorReporter(err),
+                    "provider rejected block range {from_block}..={to_block}, splitting into \
+                    {from_block}..={mid} and {}..={to_block}",
+                    mid + 1
+                );
 
-        info!(logs_count = logs.len(), "found logs");
+                let _ = self.block_range.fetch_update(
+                    atomic::Ordering::Relaxed,
+                    atomic::Ordering::Relaxed,
+                    |current| Some(shrink_block_range(current, block_range)),
+                );
 
+                Ok(conc([
+                    self.fetch_get_logs_range(from_block, mid),
+                    self.fetch_get_logs_range(mid + 1, to_block),
+                ]))
+            }
+            Err(err) => Err(ErrorObject::owned(
+                -1,
+                format!(
+                    "error fetching logs in blocks {from_block}..={to_block}: {}",
+                    ErrorReporter(err)
+                ),
+                None::<()>,
+            )),
+        }
+    }
+
+    /// Decode the `IBCHandler` logs into [`MakeFullEvent`] calls, filtering out trivial events
+    /// if configured to do so.
+    fn make_events(&self, logs: Vec<Log>) -> Op<VoyagerMessage> {
         let events = logs.into_iter().flat_map(|log| {
+            let block_number = log.block_number.expect("log should have block_number");
             let tx_hash = log
                 .transaction_hash
                 .expect("log should have transaction_hash")
@@ -519,7 +666,7 @@ impl Module {
             })
         });
 
-        Ok(conc(events))
+        conc(events)
     }
 
     #[instrument(skip_all, fields(%block_number, %tx_hash))]
@@ -1354,3 +1501,41 @@ fn convert_packet(value: ibc_solidity::Packet) -> RpcResult<Packet> {
         timeout_timestamp: Timestamp::from_nanos(value.timeout_timestamp),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_range_grows_up_to_max() {
+        assert_eq!(grow_block_range(10, 10, 1000), Some(20));
+        assert_eq!(grow_block_range(600, 600, 1000), Some(1000));
+        assert_eq!(grow_block_range(1000, 1000, 1000), Some(1000));
+        // a partial range at the tip of the chain doesn't grow the range
+        assert_eq!(grow_block_range(10, 3, 1000), None);
+    }
+
+    #[test]
+    fn block_range_shrinks_to_half_of_rejected_range() {
+        assert_eq!(shrink_block_range(100, 100), 50);
+        // a smaller range was already accepted by a concurrent request
+        assert_eq!(shrink_block_range(20, 100), 20);
+        assert_eq!(shrink_block_range(100, 2), 1);
+        assert_eq!(shrink_block_range(1, 1), 1);
+    }
+
+    #[test]
+    fn block_range_splits_in_half() {
+        for (from_block, to_block) in [(10, 11), (10, 12), (10, 19), (0, 999)] {
+            let mid = split_block_range(from_block, to_block);
+
+            assert!((from_block..to_block).contains(&mid));
+            assert!(mid - from_block <= to_block - (mid + 1));
+            assert!((to_block - (mid + 1)) - (mid - from_block) <= 1);
+        }
+
+        assert_eq!(split_block_range(10, 11), 10);
+        assert_eq!(split_block_range(10, 12), 10);
+        assert_eq!(split_block_range(10, 19), 14);
+    }
+}
@@ -41,7 +41,7 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::{ChainId, ClientInfo, ClientType, IbcSpec, QueryHeight},
     rpc::{types::PluginInfo, PluginServer},
-    vm::{call, conc, data, pass::PassResult, seq, Op},
+    vm::{call, conc, data, pass::PassResult, schedule::Priority, seq, Op},
     DefaultCmd, ExtensionsExt, VoyagerClient,
 };
 
@@ -255,14 +255,15 @@ impl PluginServer<ModuleCall, Never> for Module {
             ready: msgs
                 .into_iter()
                 .map(|op| match op {
-                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => {
-                        call(PluginMessage::new(
+                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => call(
+                        PluginMessage::new(
                             self.plugin_name(),
                             ModuleCall::FetchBlocks(FetchBlocks {
                                 height: fetch.start_height.height(),
                             }),
-                        ))
-                    }
+                        )
+                        .with_priority(Priority::Backfill),
+                    ),
                     op => op,
                 })
                 .enumerate()
@@ -356,10 +357,13 @@ impl PluginServer<ModuleCall, Never> for Module {
                 Ok(conc(events))
             }
             ModuleCall::FetchBlocks(FetchBlocks { height }) => Ok(conc([
-                call(PluginMessage::new(
-                    self.plugin_name(),
-                    ModuleCall::from(FetchTransactions { height }),
-                )),
+                call(
+                    PluginMessage::new(
+                        self.plugin_name(),
+                        ModuleCall::from(FetchTransactions { height }),
+                    )
+                    .with_priority(Priority::Backfill),
+                ),
                 {
                     let latest_height = self
                         .aptos_client
@@ -375,17 +379,23 @@ impl PluginServer<ModuleCall, Never> for Module {
                             conc(
                                 ((height + 1)..next_height)
                                     .map(|height| {
-                                        call(PluginMessage::new(
-                                            self.plugin_name(),
-                                            ModuleCall::from(FetchTransactions { height }),
-                                        ))
+                                        call(
+                                            PluginMessage::new(
+                                                self.plugin_name(),
+                                                ModuleCall::from(FetchTransactions { height }),
+                                            )
+                                            .with_priority(Priority::Backfill),
+                                        )
                                     })
-                                    .chain([call(PluginMessage::new(
-                                        self.plugin_name(),
-                                        ModuleCall::from(FetchBlocks {
-                                            height: next_height,
-                                        }),
-                                    ))]),
+                                    .chain([call(
+                                        PluginMessage::new(
+                                            self.plugin_name(),
+                                            ModuleCall::from(FetchBlocks {
+                                                height: next_height,
+                                            }),
+                                        )
+                                        .with_priority(Priority::Backfill),
+                                    )]),
                             )
                         }
                         Ordering::Equal | Ordering::Greater => seq([
@@ -394,10 +404,13 @@ impl PluginServer<ModuleCall, Never> for Module {
                                 height: Height::new(height + 1),
                                 finalized: true,
                             }),
-                            call(PluginMessage::new(
-                                self.plugin_name(),
-                                ModuleCall::from(FetchBlocks { height: height + 1 }),
-                            )),
+                            call(
+                                PluginMessage::new(
+                                    self.plugin_name(),
+                                    ModuleCall::from(FetchBlocks { height: height + 1 }),
+                                )
+                                .with_priority(Priority::Backfill),
+                            ),
                         ]),
                     }
                 },
@@ -31,7 +31,7 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::{ChainId, ClientInfo, ClientType, IbcSpec, QueryHeight},
     rpc::{types::PluginInfo, PluginServer},
-    vm::{call, conc, data, pass::PassResult, seq, Op},
+    vm::{call, conc, data, pass::PassResult, schedule::Priority, seq, Op},
     DefaultCmd, ExtensionsExt, VoyagerClient,
 };
 
@@ -127,10 +127,13 @@ impl Module {
         height: u64,
     ) -> RpcResult<Op<VoyagerMessage>> {
         Ok(conc([
-            call(PluginMessage::new(
-                self.plugin_name(),
-                ModuleCall::from(FetchTransactions { height }),
-            )),
+            call(
+                PluginMessage::new(
+                    self.plugin_name(),
+                    ModuleCall::from(FetchTransactions { height }),
+                )
+                .with_priority(Priority::Backfill),
+            ),
             {
                 let latest_height = voyager_client
                     .query_latest_height(self.chain_id.clone(), true)
@@ -143,17 +146,23 @@ impl Module {
                         conc(
                             ((height + 1)..next_height)
                                 .map(|height| {
-                                    call(PluginMessage::new(
-                                        self.plugin_name(),
-                                        ModuleCall::from(FetchTransactions { height }),
-                                    ))
+                                    call(
+                                        PluginMessage::new(
+                                            self.plugin_name(),
+                                            ModuleCall::from(FetchTransactions { height }),
+                                        )
+                                        .with_priority(Priority::Backfill),
+                                    )
                                 })
-                                .chain([call(PluginMessage::new(
-                                    self.plugin_name(),
-                                    ModuleCall::from(FetchBlocks {
-                                        height: next_height,
-                                    }),
-                                ))]),
+                                .chain([call(
+                                    PluginMessage::new(
+                                        self.plugin_name(),
+                                        ModuleCall::from(FetchBlocks {
+                                            height: next_height,
+                                        }),
+                                    )
+                                    .with_priority(Priority::Backfill),
+                                )]),
                         )
                     }
                     Ordering::Equal | Ordering::Greater => seq([
@@ -162,10 +171,13 @@ impl Module {
                             height: Height::new(height + 1),
                             finalized: true,
                         }),
-                        call(PluginMessage::new(
-                            self.plugin_name(),
-                            ModuleCall::from(FetchBlocks { height: height + 1 }),
-                        )),
+                        call(
+                            PluginMessage::new(
+                                self.plugin_name(),
+                                ModuleCall::from(FetchBlocks { height: height + 1 }),
+                            )
+                            .with_priority(Priority::Backfill),
+                        ),
                     ]),
                 }
             },
@@ -266,14 +278,15 @@ impl PluginServer<ModuleCall, ModuleCallback> for Module {
             ready: msgs
                 .into_iter()
                 .map(|op| match op {
-                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => {
-                        call(PluginMessage::new(
+                    Op::Call(Call::FetchBlocks(fetch)) if fetch.chain_id == self.chain_id => call(
+                        PluginMessage::new(
                             self.plugin_name(),
                             ModuleCall::FetchBlocks(FetchBlocks {
                                 height: fetch.start_height.height(),
                             }),
-                        ))
-                    }
+                        )
+                        .with_priority(Priority::Backfill),
+                    ),
                     op => op,
                 })
                 .enumerate()
@@ -28,7 +28,7 @@ use voyager_sdk::{
     primitives::{ChainId, IbcSpec, QueryHeight},
     rpc::{types::PluginInfo, PluginServer, FATAL_JSONRPC_ERROR_CODE},
     types::{ProofType, RawClientId},
-    vm::{call, conc, noop, pass::PassResult, seq, Op},
+    vm::{call, conc, noop, pass::PassResult, schedule::Priority, seq, Op},
     DefaultCmd, ExtensionsExt, VoyagerClient,
 };
 
@@ -356,14 +356,17 @@ impl Module {
                 .into_iter()
                 .flatten(),
             ),
-            call(PluginMessage::new(
-                self.plugin_name(),
-                ModuleCall::from(MakeMsgTimeout {
-                    event,
-                    chain_id,
-                    counterparty_chain_id,
-                }),
-            )),
+            call(
+                PluginMessage::new(
+                    self.plugin_name(),
+                    ModuleCall::from(MakeMsgTimeout {
+                        event,
+                        chain_id,
+                        counterparty_chain_id,
+                    }),
+                )
+                .with_priority(Priority::Urgent),
+            ),
         ])
     }
 }
@@ -9,12 +9,16 @@ use std::{
 use either::Either;
 use futures::{stream::FuturesOrdered, StreamExt};
 use ibc_classic_spec::IbcClassic;
-use ibc_union_spec::IbcUnion;
+use ibc_union_spec::{ChannelId, IbcUnion};
 use itertools::Itertools;
 use jsonrpsee::{
     core::{async_trait, RpcResult},
     Extensions,
 };
+use relayer_fees::{
+    check_budget, spend_by_channel, BudgetDecision, ChannelBudget, ChannelSpend,
+    CHANNEL_SPEND_METHOD,
+};
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, instrument, trace, warn};
 use unionlabs::{ibc::core::client::height::Height, id::ClientId, traits::Member, ErrorReporter};
@@ -30,7 +34,7 @@ use voyager_sdk::{
     primitives::{ChainId, IbcSpec, QueryHeight},
     rpc::{types::PluginInfo, PluginServer},
     types::RawClientId,
-    vm::{call, conc, data, pass::PassResult, seq, Op},
+    vm::{call, conc, data, defer, now, pass::PassResult, seq, Op},
     DefaultCmd, ExtensionsExt, VoyagerClient,
 };
 
@@ -49,6 +53,7 @@ pub struct Module {
     // The destination chain (i.e. where the messages will be sent to)
     pub chain_id: ChainId,
     pub client_configs: ClientConfigs,
+    pub fee_policy: Option<FeePolicyConfig>,
 }
 
 #[derive(Debug, Clone)]
@@ -62,6 +67,36 @@ pub enum ClientConfigs {
 pub struct Config {
     pub chain_id: ChainId,
     pub client_configs: ClientConfigsSerde,
+    /// Check the estimated cost of submitting packets against per-channel budgets before batching
+    /// them. Only applies to IBC union packets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_policy: Option<FeePolicyConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeePolicyConfig {
+    /// The name of the transaction plugin for this chain, which is queried for the fees paid to
+    /// submit packets on each channel.
+    pub transaction_plugin: String,
+    /// The budgets of the channels on this chain. Packets on channels without a budget are always
+    /// submitted.
+    pub budgets: Vec<ChannelBudget>,
+    /// How long to wait before checking deferred packets against their budget again, in seconds.
+    #[serde(default = "default_defer_seconds")]
+    pub defer_seconds: u64,
+}
+
+fn default_defer_seconds() -> u64 {
+    60
+}
+
+impl FeePolicyConfig {
+    fn budget(&self, channel_id: ChannelId) -> Option<&ChannelBudget> {
+        self.budgets
+            .iter()
+            .find(|budget| budget.channel_id == channel_id)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -318,6 +353,7 @@ impl Module {
         Self {
             chain_id: config.chain_id,
             client_configs: ClientConfigs::new(config.client_configs),
+            fee_policy: config.fee_policy,
         }
     }
 }
@@ -488,6 +524,57 @@ impl Module {
                 };
             }
 
+            let mut deferred_union = vec![];
+
+            if let Some(fee_policy) = &self.fee_policy {
+                match e
+                    .voyager_client()?
+                    .plugin_custom::<Vec<ChannelSpend>>(
+                        fee_policy.transaction_plugin.clone(),
+                        CHANNEL_SPEND_METHOD.to_owned(),
+                        vec![],
+                    )
+                    .await
+                {
+                    Ok(spend) => {
+                        let spend = spend_by_channel(spend);
+
+                        for (client_id, events) in &mut batchers_union {
+                            let deferred = apply_fee_policy(fee_policy, &spend, events, now());
+
+                            if !deferred.is_empty() {
+                                let (idxs, events): (Vec<_>, Vec<_>) = deferred.into_iter().unzip();
+
+                                // deferred events are sent back through the queue after a delay, where
+                                // they will be picked up by this plugin again
+                                deferred_union.push((
+                                    idxs,
+                                    seq([
+                                        defer(now() + fee_policy.defer_seconds),
+                                        data(PluginMessage::new(
+                                            self.plugin_name(),
+                                            ModuleData::from(EventBatch {
+                                                client_id: *client_id,
+                                                events,
+                                            }),
+                                        )),
+                                    ]),
+                                ));
+                            }
+                        }
+
+                        batchers_union.retain(|_, events| !events.is_empty());
+                    }
+                    Err(err) => {
+                        warn!(
+                            transaction_plugin = fee_policy.transaction_plugin,
+                            error = %ErrorReporter(err),
+                            "error fetching channel spend, not applying the fee policy"
+                        );
+                    }
+                }
+            }
+
             let (ready_v1, optimize_further_v1) = batchers_classic
                 .into_iter()
                 .flat_map(|(client_id, events)| split_ready(client_id, events, self))
@@ -529,12 +616,75 @@ impl Module {
                     .chain(ready_v1_errored.into_iter().flatten())
                     .chain(ready_union_errored.into_iter().flatten())
                     .collect(),
-                ready: ready_v1.into_iter().chain(ready_union).collect(),
+                ready: ready_v1
+                    .into_iter()
+                    .chain(ready_union)
+                    .chain(deferred_union)
+                    .collect(),
             })
         })
     }
 }
 
+/// The channel on this chain that the packets of `event` will be submitted on, if it is a packet
+/// event.
+fn packet_channel(event: &EventUnion) -> Option<ChannelId> {
+    match event {
+        EventUnion::PacketSend(event) => Some(event.packet.destination_channel.channel_id),
+        EventUnion::BatchSend(event) => Some(event.destination_channel.channel_id),
+        EventUnion::WriteAck(event) => Some(event.packet.source_channel.channel_id),
+        _ => None,
+    }
+}
+
+/// Check the packet events in `events` against the budgets of their channels, removing events that
+/// exceed their budget at the unix timestamp `now` (in seconds). Dropped events are discarded, and
+/// deferred events are returned to be checked again after [`FeePolicyConfig::defer_seconds`].
+fn apply_fee_policy(
+    fee_policy: &FeePolicyConfig,
+    spend: &HashMap<ChannelId, ChannelSpend>,
+    events: &mut Vec<(usize, BatchableEvent<IbcUnion>)>,
+    now: u64,
+) -> Vec<(usize, BatchableEvent<IbcUnion>)> {
+    let mut deferred = vec![];
+
+    events.retain(|(idx, event)| {
+        let Some(channel_id) = packet_channel(&event.event) else {
+            return true;
+        };
+
+        let spend = spend.get(&channel_id);
+
+        match check_budget(fee_policy.budget(channel_id), spend, now) {
+            BudgetDecision::Submit => true,
+            BudgetDecision::Defer => {
+                info!(
+                    %channel_id,
+                    estimated_fee = spend.and_then(|spend| spend.estimated_fee_per_packet(now)),
+                    event = IbcUnion::event_name(&event.event),
+                    "estimated fee exceeds the channel budget, deferring event"
+                );
+
+                deferred.push((*idx, event.clone()));
+
+                false
+            }
+            BudgetDecision::Drop => {
+                warn!(
+                    %channel_id,
+                    estimated_fee = spend.and_then(|spend| spend.estimated_fee_per_packet(now)),
+                    event = IbcUnion::event_name(&event.event),
+                    "estimated fee exceeds the channel budget, dropping event"
+                );
+
+                false
+            }
+        }
+    });
+
+    deferred
+}
+
 #[allow(clippy::type_complexity)] // skill issue
 fn split_ready<V: IbcSpecExt>(
     client_id: V::ClientId,
@@ -724,10 +874,102 @@ where
 
 #[cfg(test)]
 mod tests {
+    use ibc_union_spec::{
+        event::{ChannelMetadata, ConnectionMetadata, PacketMetadata, PacketSend},
+        ConnectionId, Timestamp,
+    };
+    use relayer_fees::{BudgetExceededAction, FEE_ESTIMATE_HALF_LIFE_SECONDS};
     use serde_json::json;
+    use unionlabs::primitives::Bytes;
 
     use super::*;
 
+    const NOW: u64 = 1_700_000_000;
+
+    fn channel(raw: u32) -> ChannelId {
+        ChannelId::from_raw(raw).unwrap()
+    }
+
+    fn channel_metadata(channel_id: u32) -> ChannelMetadata {
+        ChannelMetadata {
+            channel_id: channel(channel_id),
+            version: "version".to_owned(),
+            connection: ConnectionMetadata {
+                client_id: ibc_union_spec::ClientId::from_raw(1).unwrap(),
+                connection_id: ConnectionId::from_raw(1).unwrap(),
+            },
+        }
+    }
+
+    fn packet_send(destination_channel_id: u32) -> (usize, BatchableEvent<IbcUnion>) {
+        (
+            0,
+            BatchableEvent {
+                first_seen_at: NOW * 1000,
+                provable_height: EventProvableHeight::Min(Height::new(1)),
+                event: EventUnion::PacketSend(PacketSend {
+                    packet_data: Bytes::default(),
+                    packet: PacketMetadata {
+                        source_channel: channel_metadata(1),
+                        destination_channel: channel_metadata(destination_channel_id),
+                        timeout_height: 0,
+                        timeout_timestamp: Timestamp::from_nanos(1),
+                    },
+                }),
+            },
+        )
+    }
+
+    #[test]
+    fn deferred_packets_are_submitted_once_fees_drop() {
+        let fee_policy = FeePolicyConfig {
+            transaction_plugin: "transaction".to_owned(),
+            budgets: vec![ChannelBudget {
+                channel_id: channel(2),
+                max_fee_per_packet: 100,
+                on_exceeded: BudgetExceededAction::Defer,
+            }],
+            defer_seconds: 60,
+        };
+
+        // the last packet submitted on channel 2 paid well over the budget
+        let spend = spend_by_channel([ChannelSpend {
+            channel_id: channel(2),
+            counterparty_channel_id: channel(1),
+            packets: 10,
+            gas_used: 10_000,
+            fee: 10_000,
+            recent_fee_per_packet: 1000,
+            last_packet_at: NOW,
+            denom: "denom".to_owned(),
+        }]);
+
+        let mut events = vec![packet_send(2), packet_send(3)];
+
+        let deferred = apply_fee_policy(&fee_policy, &spend, &mut events, NOW);
+
+        assert_eq!(deferred, [packet_send(2)]);
+        assert_eq!(events, [packet_send(3)]);
+
+        // no packets are submitted on the channel while its packets are deferred, so the spend
+        // stays the same; the estimate decays until the deferred packet fits the budget again
+        let mut now = NOW;
+        let mut events = deferred;
+
+        while !apply_fee_policy(&fee_policy, &spend, &mut events, now).is_empty() {
+            now += fee_policy.defer_seconds;
+
+            assert!(
+                now <= NOW + 10 * FEE_ESTIMATE_HALF_LIFE_SECONDS,
+                "the deferred packet was never submitted"
+            );
+
+            events = vec![packet_send(2)];
+        }
+
+        assert_eq!(events, [packet_send(2)]);
+    }
+
     #[test]
     fn config_serde() {
         let config_json = json!({
@@ -752,7 +994,8 @@ mod tests {
                     min_batch_size: 1,
                     max_batch_size: 3,
                     max_wait_time: Duration::from_secs(10)
-                })
+                }),
+                fee_policy: None,
             }
         );
     }
@@ -6,7 +6,7 @@ use aptos_types::{
     account_address::AccountAddress,
     transaction::{EntryFunction, RawTransaction},
 };
-use concurrent_keyring::{ConcurrentKeyring, KeyringConfig, KeyringEntry};
+use concurrent_keyring::{ConcurrentKeyring, KeyringConfig, KeyringConfigEntry, KeyringEntry};
 use ibc_union_spec::{datagram::Datagram, ChannelId, IbcUnion};
 use jsonrpsee::{
     core::{async_trait, RpcResult},
@@ -27,7 +27,7 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::ChainId,
     rpc::{types::PluginInfo, PluginServer},
-    vm::{self, call, noop, pass::PassResult, Op, Visit},
+    vm::{self, call, noop, pass::PassResult, schedule::Priority, Op, Visit},
     DefaultCmd,
 };
 
@@ -63,31 +63,41 @@ impl Plugin for Module {
 
         let chain_id = aptos_client.get_index().await?.inner().chain_id;
 
+        let keys = config
+            .keyring
+            .keys
+            .into_iter()
+            .map(|config| {
+                if let KeyringConfigEntry::Remote { name, .. } = &config {
+                    anyhow::bail!(
+                        "remote signers are not supported for aptos (keyring entry `{name}`)"
+                    );
+                }
+
+                let pk = aptos_crypto::ed25519::Ed25519PrivateKey::try_from(&*config.value()?)
+                    .map_err(|e| anyhow::anyhow!("invalid private key: {e}"))?;
+
+                let address = (*<H256>::from(
+                    sha3::Sha3_256::new()
+                        .chain_update(pk.public_key().to_bytes())
+                        .chain_update([0])
+                        .finalize(),
+                )
+                .get())
+                .into();
+
+                Ok(KeyringEntry {
+                    address,
+                    signer: Arc::new(pk),
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
         Ok(Self {
             chain_id: ChainId::new(chain_id.to_string()),
             ibc_handler_address: config.ibc_handler_address,
             aptos_client,
-            keyring: ConcurrentKeyring::new(
-                config.keyring.name,
-                config.keyring.keys.into_iter().map(|config| {
-                    let pk = aptos_crypto::ed25519::Ed25519PrivateKey::try_from(&*config.value())
-                        .unwrap();
-
-                    let address = (*<H256>::from(
-                        sha3::Sha3_256::new()
-                            .chain_update(pk.public_key().to_bytes())
-                            .chain_update([0])
-                            .finalize(),
-                    )
-                    .get())
-                    .into();
-
-                    KeyringEntry {
-                        address,
-                        signer: Arc::new(pk),
-                    }
-                }),
-            ),
+            keyring: ConcurrentKeyring::new(config.keyring.name, keys.into_iter()),
         })
     }
 
@@ -175,6 +185,7 @@ impl PluginServer<ModuleCall, Never> for Module {
                                     .collect(), // .collect::<Result<_, _>>()?,
                             ),
                         )
+                        .with_priority(Priority::Urgent)
                         .into()
                     })
                     .visit_op(&mut op);
@@ -255,10 +266,10 @@ impl PluginServer<ModuleCall, Never> for Module {
                 })
                 .await
                 .unwrap_or_else(|| {
-                    Ok(call(PluginMessage::new(
-                        self.plugin_name(),
-                        ModuleCall::SubmitTransaction(msgs),
-                    )))
+                    Ok(call(
+                        PluginMessage::new(self.plugin_name(), ModuleCall::SubmitTransaction(msgs))
+                            .with_priority(Priority::Urgent),
+                    ))
                 }),
         }
     }
@@ -10,11 +10,14 @@ use std::{
 };
 
 use cometbft_rpc::rpc_types::GrpcAbciQueryError;
-use concurrent_keyring::{ConcurrentKeyring, KeyringConfig, KeyringEntry};
+use concurrent_keyring::{
+    balance::{check_funding_key, monitor_balances, BalanceMonitorConfig, TopUpError},
+    ChainKeyring, ConcurrentKeyring, KeyringConfig, KeyringEntry, SignerBalance,
+};
 use cosmos_client::{
     gas::{any, feemarket, fixed, osmosis_eip1559_feemarket, GasFillerT},
     rpc::{Rpc, RpcT},
-    wallet::{LocalSigner, WalletT},
+    wallet::WalletT,
     BroadcastTxCommitError, TxClient,
 };
 use ibc_union::ContractErrorKind;
@@ -25,6 +28,8 @@ use jsonrpsee::{
     Extensions, MethodsError,
 };
 use prost::Message;
+use relayer_fees::{ChannelSpend, FeeLedger, PacketFee};
+use rpc_pool::{Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tracing::{debug, error, info, info_span, instrument, trace, warn};
@@ -46,13 +51,17 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::ChainId,
     rpc::{types::PluginInfo, PluginServer, FATAL_JSONRPC_ERROR_CODE},
-    vm::{call, noop, pass::PassResult, seq, BoxDynError, Op, Visit},
+    vm::{call, noop, pass::PassResult, schedule::Priority, seq, BoxDynError, Op, Visit},
     DefaultCmd,
 };
 
-use crate::call::{IbcMessage, ModuleCall};
+use crate::{
+    call::{IbcMessage, ModuleCall},
+    signer::CosmosSdkSigner,
+};
 
 pub mod call;
+pub mod signer;
 
 #[tokio::main]
 async fn main() {
@@ -66,13 +75,14 @@ pub struct Module(Arc<ModuleInner>);
 pub struct ModuleInner {
     pub chain_id: ChainId,
     pub ibc_host_contract_address: Bech32<H256>,
-    pub keyring: ConcurrentKeyring<Bech32<H160>, LocalSigner>,
+    pub keyring: ConcurrentKeyring<Bech32<H160>, CosmosSdkSigner>,
     pub rpc: Rpc,
     pub gas_config: any::GasFiller,
     pub bech32_prefix: String,
     pub fatal_errors: HashMap<(String, NonZeroU32), Option<String>>,
     pub gas_station_config: Vec<Coin>,
     pub fee_recipient: Option<Bech32<Bytes>>,
+    pub fees: FeeLedger,
 }
 
 impl Deref for Module {
@@ -89,7 +99,9 @@ pub struct Config {
     pub chain_id: ChainId,
     pub ibc_host_contract_address: Bech32<H256>,
     pub keyring: KeyringConfig,
-    pub rpc_url: String,
+    pub rpc_url: Endpoints,
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
     pub gas_config: GasFillerConfig,
     /// A list of (codespace, code) tuples that are to be considered non-recoverable.
     #[serde(default)]
@@ -98,6 +110,10 @@ pub struct Config {
     pub gas_station_config: Vec<Coin>,
     #[serde(default)]
     pub fee_recipient: Option<Bech32<Bytes>>,
+    /// Periodic checks of the signer balances, removing drained signers from the rotation and
+    /// optionally topping them up.
+    #[serde(default)]
+    pub balance_monitor: Option<BalanceMonitorConfig>,
 }
 
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
@@ -130,27 +146,30 @@ pub struct OsmosisEip1559FeemarketConfig {
 }
 
 impl GasFillerConfig {
-    async fn into_gas_filler(self, rpc_url: String) -> Result<any::GasFiller, BoxDynError> {
+    async fn into_gas_filler(
+        self,
+        client: cometbft_rpc::Client,
+    ) -> Result<any::GasFiller, BoxDynError> {
         Ok(match self {
             GasFillerConfig::Fixed(config) => any::GasFiller::Fixed(config),
             GasFillerConfig::Feemarket(config) => any::GasFiller::Feemarket(
-                feemarket::GasFiller::new(feemarket::Config {
-                    rpc_url,
-                    max_gas: config.max_gas,
-                    gas_multiplier: config.gas_multiplier,
-                    denom: config.denom,
-                })
+                feemarket::GasFiller::with_client(
+                    client,
+                    config.max_gas,
+                    config.gas_multiplier,
+                    config.denom,
+                )
                 .await?,
             ),
             GasFillerConfig::OsmosisEip1559Feemarket(config) => {
                 any::GasFiller::OsmosisEip1559Feemarket(
-                    osmosis_eip1559_feemarket::GasFiller::new(osmosis_eip1559_feemarket::Config {
-                        rpc_url,
-                        max_gas: config.max_gas,
-                        gas_multiplier: config.gas_multiplier,
-                        base_fee_multiplier: config.base_fee_multiplier,
-                        denom: config.denom,
-                    })
+                    osmosis_eip1559_feemarket::GasFiller::with_client(
+                        client,
+                        config.max_gas,
+                        config.gas_multiplier,
+                        config.base_fee_multiplier,
+                        config.denom,
+                    )
                     .await?,
                 )
             }
@@ -182,7 +201,10 @@ impl Plugin for Module {
     type Cmd = DefaultCmd;
 
     async fn new(config: Self::Config) -> anyhow::Result<Self> {
-        let rpc = Rpc::new(config.rpc_url.clone()).await?;
+        let rpc = Rpc::with_client(
+            cometbft_rpc::Client::new_pool(config.rpc_url.urls(), config.rpc_pool).await?,
+        )
+        .await?;
 
         let chain_id = rpc.client().status().await?.node_info.network.to_string();
 
@@ -207,27 +229,34 @@ impl Plugin for Module {
             .unwrap()
             .bech32_prefix;
 
-        Ok(Self(Arc::new(ModuleInner {
+        let gas_config = config
+            .gas_config
+            .into_gas_filler(rpc.client().clone())
+            .await
+            .map_err(|e| anyhow!(e))?;
+
+        let module = Self(Arc::new(ModuleInner {
             ibc_host_contract_address: config.ibc_host_contract_address,
             keyring: ConcurrentKeyring::new(
                 config.keyring.name,
-                config.keyring.keys.into_iter().map(|entry| {
-                    let signer =
-                        LocalSigner::new(entry.value().try_into().unwrap(), bech32_prefix.clone());
-
-                    KeyringEntry {
-                        address: signer.address(),
-                        signer,
-                    }
-                }),
+                config
+                    .keyring
+                    .keys
+                    .iter()
+                    .map(|entry| {
+                        let signer = CosmosSdkSigner::from_config(entry, &bech32_prefix)?;
+
+                        Ok(KeyringEntry {
+                            address: signer.address(),
+                            signer,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?
+                    .into_iter(),
             ),
             rpc,
             chain_id: ChainId::new(chain_id),
-            gas_config: config
-                .gas_config
-                .into_gas_filler(config.rpc_url.clone())
-                .await
-                .map_err(|e| anyhow!(e))?,
+            gas_config,
             bech32_prefix,
             fatal_errors: config
                 .fatal_errors
@@ -240,7 +269,43 @@ impl Plugin for Module {
                 .collect(),
             gas_station_config: config.gas_station_config,
             fee_recipient: config.fee_recipient,
-        })))
+            fees: FeeLedger::new(),
+        }));
+
+        if let Some(balance_monitor) = config.balance_monitor {
+            let funding_signer = balance_monitor
+                .top_up
+                .as_ref()
+                .map(|top_up| {
+                    CosmosSdkSigner::from_config(&top_up.funding_key, &module.bech32_prefix)
+                        .map(Arc::new)
+                })
+                .transpose()?;
+
+            if let Some(funding_signer) = &funding_signer {
+                check_funding_key(&module.keyring, &funding_signer.address())?;
+            }
+
+            tokio::spawn(monitor_balances(module.clone(), balance_monitor, {
+                let module = module.clone();
+
+                move |address, amount| {
+                    let module = module.clone();
+                    let funding_signer = funding_signer.clone();
+
+                    async move {
+                        let funding_signer = funding_signer
+                            .ok_or("top up requested without a funding key configured")?;
+
+                        module.top_up(&funding_signer, &address, amount).await?;
+
+                        Ok::<_, TopUpError>(())
+                    }
+                }
+            }));
+        }
+
+        Ok(module)
     }
 
     fn info(config: Self::Config) -> PluginInfo {
@@ -263,6 +328,14 @@ trait TransactionPlugin {
 
     #[method(name = "signerBalances")]
     async fn signer_balances(&self) -> RpcResult<BTreeMap<Bech32<H160>, String>>;
+
+    /// The fees paid for submitting packets, per channel and counterparty channel.
+    #[method(name = "channelSpend")]
+    async fn channel_spend(&self) -> RpcResult<Vec<ChannelSpend>>;
+
+    /// The fee paid for submitting the packet with the given hash.
+    #[method(name = "packetFee")]
+    async fn packet_fee(&self, packet_hash: H256) -> RpcResult<Option<PacketFee>>;
 }
 
 #[async_trait]
@@ -275,48 +348,79 @@ impl TransactionPluginServer for Module {
         let mut out = BTreeMap::new();
 
         for address in self.keyring.keys() {
-            let balance = self
-                .rpc
-                .client()
-                .grpc_abci_query::<_, protos::cosmos::bank::v1beta1::QueryBalanceResponse>(
-                    "/cosmos.bank.v1beta1.Query/Balance",
-                    &protos::cosmos::bank::v1beta1::QueryBalanceRequest {
-                        address: address.to_string(),
-                        denom: self.gas_config.mk_fee(0).await.amount[0].denom.clone(),
-                    },
-                    None,
-                    false,
-                )
-                .await
-                .map_err(|e| {
-                    ErrorObject::owned(
-                        -1,
-                        ErrorReporter(e).with_message("error fetching balance"),
-                        None::<()>,
-                    )
-                })?
-                .into_result()
-                .map_err(|e| {
-                    ErrorObject::owned(
-                        -1,
-                        ErrorReporter(e).with_message("error fetching balance"),
-                        None::<()>,
-                    )
-                })?
-                .ok_or_else(|| {
-                    ErrorObject::owned(-1, "empty response when fetching balance", None::<()>)
-                })?
-                .balance
-                .ok_or_else(|| {
-                    ErrorObject::owned(-1, "empty balance when fetching balance", None::<()>)
-                })?
-                .amount;
+            let balance = self.signer_balance(address).await?.amount;
 
             out.insert(address.clone(), balance);
         }
 
         Ok(out)
     }
+
+    async fn channel_spend(&self) -> RpcResult<Vec<ChannelSpend>> {
+        Ok(self.fees.channel_spend())
+    }
+
+    async fn packet_fee(&self, packet_hash: H256) -> RpcResult<Option<PacketFee>> {
+        Ok(self.fees.packet_fee(packet_hash))
+    }
+}
+
+impl ChainKeyring for Module {
+    type Address = Bech32<H160>;
+    type Signer = CosmosSdkSigner;
+
+    fn keyring(&self) -> &ConcurrentKeyring<Bech32<H160>, CosmosSdkSigner> {
+        &self.keyring
+    }
+
+    async fn balances(&self) -> Vec<SignerBalance<Bech32<H160>>> {
+        let mut out = vec![];
+
+        for address in self.keyring.keys() {
+            let coin = match self.signer_balance(address).await {
+                Ok(coin) => coin,
+                Err(err) => {
+                    warn!(
+                        %address,
+                        err = %ErrorReporter(err),
+                        "error fetching signer balance"
+                    );
+                    continue;
+                }
+            };
+
+            match coin.amount.parse() {
+                Ok(balance) => out.push(SignerBalance {
+                    key_name: address.to_string(),
+                    address: address.clone(),
+                    balance,
+                    denom: coin.denom,
+                }),
+                Err(err) => warn!(
+                    %address,
+                    amount = %coin.amount,
+                    err = %ErrorReporter(err),
+                    "invalid signer balance"
+                ),
+            }
+        }
+
+        out
+    }
+}
+
+/// The fee paid by a transaction, read from the auth info of the raw transaction bytes.
+fn tx_fee(tx: &[u8]) -> Option<(u128, String)> {
+    let tx_raw = protos::cosmos::tx::v1beta1::TxRaw::decode(tx).ok()?;
+
+    let coin = protos::cosmos::tx::v1beta1::AuthInfo::decode(&*tx_raw.auth_info_bytes)
+        .ok()?
+        .fee?
+        .amount
+        .into_iter()
+        .next()?;
+
+    Some((coin.amount.parse().ok()?, coin.denom))
 }
 
 fn plugin_name(chain_id: &ChainId) -> String {
@@ -326,6 +430,75 @@ fn plugin_name(chain_id: &ChainId) -> String {
 }
 
 impl Module {
+    /// The balance of `address` in the denom used for gas.
+    async fn signer_balance(
+        &self,
+        address: &Bech32<H160>,
+    ) -> RpcResult<protos::cosmos::base::v1beta1::Coin> {
+        self.rpc
+            .client()
+            .grpc_abci_query::<_, protos::cosmos::bank::v1beta1::QueryBalanceResponse>(
+                "/cosmos.bank.v1beta1.Query/Balance",
+                &protos::cosmos::bank::v1beta1::QueryBalanceRequest {
+                    address: address.to_string(),
+                    denom: self.gas_config.mk_fee(0).await.amount[0].denom.clone(),
+                },
+                None,
+                false,
+            )
+            .await
+            .map_err(|e| {
+                ErrorObject::owned(
+                    -1,
+                    ErrorReporter(e).with_message("error fetching balance"),
+                    None::<()>,
+                )
+            })?
+            .into_result()
+            .map_err(|e| {
+                ErrorObject::owned(
+                    -1,
+                    ErrorReporter(e).with_message("error fetching balance"),
+                    None::<()>,
+                )
+            })?
+            .ok_or_else(|| {
+                ErrorObject::owned(-1, "empty response when fetching balance", None::<()>)
+            })?
+            .balance
+            .ok_or_else(|| {
+                ErrorObject::owned(-1, "empty balance when fetching balance", None::<()>)
+            })
+    }
+
+    /// Send `amount` of the gas denom from `funding_signer` to `address`.
+    #[instrument(skip_all, fields(%address, amount))]
+    async fn top_up(
+        &self,
+        funding_signer: &CosmosSdkSigner,
+        address: &Bech32<H160>,
+        amount: u128,
+    ) -> Result<(), BroadcastTxCommitError> {
+        let tx_response = TxClient::new(funding_signer, &self.rpc, &self.gas_config)
+            .broadcast_tx_commit(
+                [mk_any(&protos::cosmos::bank::v1beta1::MsgSend {
+                    from_address: funding_signer.address().to_string(),
+                    to_address: address.to_string(),
+                    amount: vec![protos::cosmos::base::v1beta1::Coin {
+                        denom: self.gas_config.mk_fee(0).await.amount[0].denom.clone(),
+                        amount: amount.to_string(),
+                    }],
+                })],
+                format!("Voyager {} top up", env!("CARGO_PKG_VERSION")),
+                true,
+            )
+            .await?;
+
+        info!(tx_hash = %tx_response.hash, "top up tx included");
+
+        Ok(())
+    }
+
     fn plugin_name(&self) -> String {
         plugin_name(&self.chain_id)
     }
@@ -394,6 +567,25 @@ impl Module {
                                 info!(tx_hash = %tx_response.hash, %msg, "cosmos msg");
                             }
 
+                            match tx_fee(&tx_response.tx) {
+                                Some((fee, denom)) => {
+                                    self.fees.record_tx(
+                                        msgs.iter().filter_map(|(msg, _)| match msg {
+                                            IbcMessage::IbcUnion(datagram) => Some(datagram),
+                                            IbcMessage::IbcV1(_) => None,
+                                        }),
+                                        tx_response.hash.into_encoding(),
+                                        tx_response.tx_result.gas_used.inner().unsigned_abs(),
+                                        fee,
+                                        &denom,
+                                    );
+                                }
+                                None => warn!(
+                                    tx_hash = %tx_response.hash,
+                                    "unable to read the fee of the transaction"
+                                ),
+                            }
+
                             Ok(())
                         }
                         Err(err) => {
@@ -470,6 +662,7 @@ impl PluginServer<ModuleCall, Never> for Module {
                         .unwrap(),
                 ),
             )
+            .with_priority(Priority::Urgent)
             .into()
         });
 
@@ -613,10 +806,13 @@ impl PluginServer<ModuleCall, Never> for Module {
 
                                             Ok(noop())
                                         } else {
-                                            Ok(call(PluginMessage::new(
-                                                self.plugin_name(),
-                                                ModuleCall::SubmitTransaction(msgs),
-                                            )))
+                                            Ok(call(
+                                                PluginMessage::new(
+                                                    self.plugin_name(),
+                                                    ModuleCall::SubmitTransaction(msgs),
+                                                )
+                                                .with_priority(Priority::Urgent),
+                                            ))
                                         }
                                     }
                                 } else if log.contains("insufficient funds") {
@@ -631,10 +827,13 @@ impl PluginServer<ModuleCall, Never> for Module {
                                         Ok(noop())
                                     } else {
                                         Ok(seq(msgs.into_iter().map(|msg| {
-                                            call(PluginMessage::new(
-                                                self.plugin_name(),
-                                                ModuleCall::SubmitTransaction(vec![msg]),
-                                            ))
+                                            call(
+                                                PluginMessage::new(
+                                                    self.plugin_name(),
+                                                    ModuleCall::SubmitTransaction(vec![msg]),
+                                                )
+                                                .with_priority(Priority::Urgent),
+                                            )
                                         })))
                                     }
                                 }
@@ -684,7 +883,7 @@ impl PluginServer<ModuleCall, Never> for Module {
 
 fn process_msgs(
     msgs: Vec<IbcMessage>,
-    signer: &LocalSigner,
+    signer: &CosmosSdkSigner,
     ibc_host_contract_address: Bech32<H256>,
     gas_station_config: Vec<Coin>,
     fee_recipient: Option<&Bech32<Bytes>>,
@@ -1179,6 +1378,12 @@ mod tests {
                   "key": "0x0000000000000000000000000000000000000000000000000000000000000000",
                   "name": "name",
                   "type": "raw"
+                },
+                {
+                  "name": "remote",
+                  "public_key": "0x0102",
+                  "type": "remote",
+                  "url": "http://localhost:9000"
                 }
               ],
               "name": "name"
@@ -1198,12 +1403,20 @@ mod tests {
                         .unwrap(),
                 keyring: KeyringConfig {
                     name: "name".to_string(),
-                    keys: vec![KeyringConfigEntry::Raw {
-                        name: "name".to_string(),
-                        key: vec![0; 32],
-                    }]
+                    keys: vec![
+                        KeyringConfigEntry::Raw {
+                            name: "name".to_string(),
+                            key: vec![0; 32],
+                        },
+                        KeyringConfigEntry::Remote {
+                            name: "remote".to_string(),
+                            url: "http://localhost:9000".to_string(),
+                            public_key: vec![1, 2],
+                        }
+                    ]
                 },
-                rpc_url: "rpc_url".to_string(),
+                rpc_url: Endpoints::One("rpc_url".to_string()),
+                rpc_pool: PoolConfig::default(),
                 gas_config: GasFillerConfig::Feemarket(FeemarketConfig {
                     max_gas: 123456789,
                     gas_multiplier: Some(1.4),
@@ -1211,7 +1424,8 @@ mod tests {
                 }),
                 fatal_errors: HashMap::default(),
                 gas_station_config: vec![],
-                fee_recipient: None
+                fee_recipient: None,
+                balance_monitor: None
             }
         );
     }
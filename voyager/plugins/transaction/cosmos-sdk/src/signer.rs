@@ -0,0 +1,145 @@
+use bip32::secp256k1::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use concurrent_keyring::{
+    remote::{CosmosSignMode, RemoteSigner, RemoteSignerError},
+    KeyringConfigEntry,
+};
+use cosmos_client::wallet::{LocalSigner, SignError, WalletT};
+use unionlabs::{
+    bech32::Bech32,
+    primitives::{FixedBytes, H160, H512},
+    signer::cosmos_address,
+};
+use voyager_sdk::anyhow::{self, anyhow};
+
+/// A signer for transactions, either holding the private key locally or signing with a remote
+/// signer.
+#[derive(Debug)]
+pub enum CosmosSdkSigner {
+    Local(LocalSigner),
+    Remote {
+        remote: RemoteSigner,
+        address: Bech32<H160>,
+        public_key: FixedBytes<33>,
+    },
+}
+
+impl CosmosSdkSigner {
+    pub fn from_config(entry: &KeyringConfigEntry, bech32_prefix: &str) -> anyhow::Result<Self> {
+        match entry.remote_signer() {
+            Some(remote) => {
+                let public_key = FixedBytes::<33>::try_from(remote.public_key()).map_err(|_| {
+                    anyhow!("remote signer public key must be a compressed secp256k1 public key")
+                })?;
+
+                Ok(Self::Remote {
+                    address: cosmos_address(public_key.get(), bech32_prefix.to_owned()),
+                    public_key,
+                    remote,
+                })
+            }
+            None => Ok(Self::Local(LocalSigner::new(
+                entry
+                    .value()?
+                    .try_into()
+                    .map_err(|_| anyhow!("private key must be 32 bytes"))?,
+                bech32_prefix,
+            ))),
+        }
+    }
+}
+
+impl WalletT for CosmosSdkSigner {
+    fn address(&self) -> Bech32<H160> {
+        match self {
+            CosmosSdkSigner::Local(signer) => signer.address(),
+            CosmosSdkSigner::Remote { address, .. } => address.clone(),
+        }
+    }
+
+    fn public_key(&self) -> FixedBytes<33> {
+        match self {
+            CosmosSdkSigner::Local(signer) => signer.public_key(),
+            CosmosSdkSigner::Remote { public_key, .. } => *public_key,
+        }
+    }
+
+    async fn sign(&self, bz: &[u8]) -> Result<H512, SignError> {
+        match self {
+            CosmosSdkSigner::Local(signer) => signer.sign(bz).await,
+            CosmosSdkSigner::Remote {
+                remote, public_key, ..
+            } => {
+                let signature = remote.sign_cosmos(bz, CosmosSignMode::Direct).await?;
+
+                // the remote signer may be signing with a different key than the one configured,
+                // which would otherwise only be noticed once the transaction is rejected
+                let verifying_key = VerifyingKey::from_sec1_bytes(public_key.get())?;
+
+                verifying_key
+                    .verify(
+                        bz,
+                        &Signature::from_slice(signature.get())
+                            .map_err(|e| RemoteSignerError::InvalidSignature(e.to_string()))?,
+                    )
+                    .map_err(|_| {
+                        format!(
+                            "remote signer signature does not match the public key {public_key}"
+                        )
+                    })?;
+
+                Ok(signature)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bip32::secp256k1::ecdsa::SigningKey;
+    use concurrent_keyring::mock::MockSigner;
+
+    use super::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[0x11; 32]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn remote_signer_signs() {
+        let mock = MockSigner::spawn([signing_key()]).await;
+
+        let remote = CosmosSdkSigner::from_config(
+            &KeyringConfigEntry::Remote {
+                name: "remote".to_owned(),
+                url: mock.url(),
+                public_key: mock.remote_signer(&signing_key()).public_key().to_vec(),
+            },
+            "union",
+        )
+        .unwrap();
+
+        let local = CosmosSdkSigner::from_config(
+            &KeyringConfigEntry::Raw {
+                name: "local".to_owned(),
+                key: signing_key().to_bytes().to_vec(),
+            },
+            "union",
+        )
+        .unwrap();
+
+        assert_eq!(remote.address(), local.address());
+        assert_eq!(remote.public_key(), local.public_key());
+
+        let signature = remote.sign(b"sign doc").await.unwrap();
+
+        VerifyingKey::from_sec1_bytes(remote.public_key().get())
+            .unwrap()
+            .verify(
+                b"sign doc",
+                &Signature::from_slice(signature.get()).unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(signature, local.sign(b"sign doc").await.unwrap());
+    }
+}
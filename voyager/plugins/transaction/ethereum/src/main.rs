@@ -3,23 +3,28 @@ use std::{
     ops::Deref,
     panic::AssertUnwindSafe,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
 use alloy::{
     contract::{Error, RawCallBuilder},
-    network::{AnyNetwork, EthereumWallet},
-    primitives::Address,
+    network::{
+        AnyNetwork, AnyTransactionReceipt, EthereumWallet, Network, ReceiptResponse,
+        TransactionBuilder,
+    },
+    primitives::{Address, TxHash},
     providers::{
         fillers::RecommendedFillers, layers::CacheLayer, DynProvider, PendingTransactionError,
-        Provider, ProviderBuilder,
+        Provider, ProviderBuilder, WatchTxError,
     },
-    signers::local::LocalSigner,
     sol_types::{SolEvent, SolInterface},
     transports::TransportError,
 };
-use bip32::secp256k1::ecdsa::{self, SigningKey};
 use clap::Subcommand;
-use concurrent_keyring::{ConcurrentKeyring, KeyringConfig, KeyringEntry};
+use concurrent_keyring::{
+    balance::{check_funding_key, monitor_balances, BalanceMonitorConfig, TopUpError},
+    ChainKeyring, ConcurrentKeyring, KeyringConfig, KeyringEntry, SignerBalance,
+};
 use ibc_solidity::Ibc::{self, IbcErrors};
 use ibc_union_spec::{datagram::Datagram, IbcUnion};
 use jsonrpsee::{
@@ -28,6 +33,9 @@ use jsonrpsee::{
     types::{ErrorObject, ErrorObjectOwned},
     Extensions, MethodsError,
 };
+use opentelemetry::KeyValue;
+use relayer_fees::{ChannelSpend, FeeLedger, PacketFee};
+use rpc_pool::{eth::PoolTransport, Endpoints, PoolConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use tracing::{error, info, info_span, instrument, trace, warn, Instrument};
@@ -44,15 +52,24 @@ use voyager_sdk::{
     plugin::Plugin,
     primitives::ChainId,
     rpc::{types::PluginInfo, PluginServer, FATAL_JSONRPC_ERROR_CODE},
-    vm::{call, defer, now, pass::PassResult, seq, Op, Visit},
+    vm::{call, defer, now, pass::PassResult, schedule::Priority, seq, Op, Visit},
 };
 
 use crate::{
     call::ModuleCall,
+    metrics::Metrics,
     multicall::{Call3, Multicall, MulticallResult},
+    replacement::{
+        next_replacement_action, Fees, NonceTracker, PendingTx, ReplacementAction,
+        ReplacementConfig,
+    },
+    signer::EthSigner,
 };
 
 pub mod call;
+pub mod metrics;
+pub mod replacement;
+pub mod signer;
 
 #[tokio::main]
 async fn main() {
@@ -81,7 +98,7 @@ pub struct ModuleInner {
 
     pub provider: DynProvider<AnyNetwork>,
 
-    pub keyring: ConcurrentKeyring<alloy::primitives::Address, LocalSigner<SigningKey>>,
+    pub keyring: ConcurrentKeyring<alloy::primitives::Address, EthSigner>,
 
     pub max_gas_price: Option<u128>,
 
@@ -92,6 +109,14 @@ pub struct ModuleInner {
     pub legacy: bool,
 
     pub fee_recipient: Option<alloy::primitives::Address>,
+
+    pub replacement: ReplacementConfig,
+
+    pub nonces: NonceTracker,
+
+    pub metrics: Metrics,
+
+    pub fees: FeeLedger,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,8 +130,11 @@ pub struct Config {
     pub ibc_handler_address: H160,
     pub multicall_address: H160,
 
-    /// The RPC endpoint for the execution chain.
-    pub rpc_url: String,
+    /// The RPC endpoint(s) for the execution chain.
+    pub rpc_url: Endpoints,
+
+    #[serde(default)]
+    pub rpc_pool: PoolConfig,
 
     pub keyring: KeyringConfig,
 
@@ -128,6 +156,16 @@ pub struct Config {
 
     #[serde(default)]
     pub fee_recipient: Option<alloy::primitives::Address>,
+
+    /// Replacement of transactions that are pending for too long. Only applies to EIP-1559
+    /// transactions.
+    #[serde(default)]
+    pub replacement: ReplacementConfig,
+
+    /// Periodic checks of the signer balances, removing drained signers from the rotation and
+    /// optionally topping them up.
+    #[serde(default)]
+    pub balance_monitor: Option<BalanceMonitorConfig>,
 }
 
 #[derive(Subcommand)]
@@ -144,12 +182,13 @@ impl Plugin for Module {
     type Cmd = Cmd;
 
     async fn new(config: Self::Config) -> anyhow::Result<Self> {
+        let transport = PoolTransport::connect(config.rpc_url.urls(), config.rpc_pool).await?;
+
         let provider = DynProvider::new(
             ProviderBuilder::new()
                 .network::<AnyNetwork>()
                 .layer(CacheLayer::new(config.max_cache_size))
-                .connect(&config.rpc_url)
-                .await?,
+                .connect_client(transport.into_client()),
         );
 
         let raw_chain_id = provider.get_chain_id().await?;
@@ -163,7 +202,7 @@ impl Plugin for Module {
             );
         }
 
-        Ok(Self(Arc::new(ModuleInner {
+        let module = Self(Arc::new(ModuleInner {
             chain_id,
             additional_chain_ids: config.additional_chain_ids,
             ibc_handler_address: config.ibc_handler_address,
@@ -171,26 +210,69 @@ impl Plugin for Module {
             provider,
             keyring: ConcurrentKeyring::new(
                 config.keyring.name,
-                config.keyring.keys.into_iter().map(|config| {
-                    let signing_key = <ecdsa::SigningKey as bip32::PrivateKey>::from_bytes(
-                        &config.value().as_slice().try_into().unwrap(),
-                    )
-                    .unwrap();
-
-                    let signer = LocalSigner::from_signing_key(signing_key);
-
-                    KeyringEntry {
-                        address: signer.address(),
-                        signer,
-                    }
-                }),
+                config
+                    .keyring
+                    .keys
+                    .iter()
+                    .map(|config| {
+                        let signer = EthSigner::from_config(config)?;
+
+                        Ok(KeyringEntry {
+                            address: signer.address(),
+                            signer,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?
+                    .into_iter(),
             ),
             max_gas_price: config.max_gas_price,
             fixed_gas_price: config.fixed_gas_price,
             legacy: config.legacy,
             gas_multiplier: config.gas_multiplier,
             fee_recipient: config.fee_recipient,
-        })))
+            replacement: config.replacement,
+            nonces: NonceTracker::default(),
+            metrics: Metrics::new(),
+            fees: FeeLedger::new(),
+        }));
+
+        if let Some(balance_monitor) = config.balance_monitor {
+            let funding_signer = balance_monitor
+                .top_up
+                .as_ref()
+                .map(|top_up| EthSigner::from_config(&top_up.funding_key))
+                .transpose()?;
+
+            if let Some(funding_signer) = &funding_signer {
+                check_funding_key(&module.keyring, &funding_signer.address())?;
+            }
+
+            // a top up that is not included by the next balance check is abandoned, the signer
+            // will be topped up again if its balance is still low
+            let top_up_timeout = balance_monitor.interval();
+
+            tokio::spawn(monitor_balances(module.clone(), balance_monitor, {
+                let module = module.clone();
+
+                move |address, amount| {
+                    let module = module.clone();
+                    let funding_signer = funding_signer.clone();
+
+                    async move {
+                        let funding_signer = funding_signer
+                            .ok_or("top up requested without a funding key configured")?;
+
+                        module
+                            .top_up(&funding_signer, address, amount, top_up_timeout)
+                            .await?;
+
+                        Ok::<_, TopUpError>(())
+                    }
+                }
+            }));
+        }
+
+        Ok(module)
     }
 
     fn info(config: Self::Config) -> PluginInfo {
@@ -233,6 +315,14 @@ trait TransactionPlugin {
 
     #[method(name = "signerBalances")]
     async fn signer_balances(&self) -> RpcResult<BTreeMap<Address, U256>>;
+
+    /// The fees paid for submitting packets, per channel and counterparty channel.
+    #[method(name = "channelSpend")]
+    async fn channel_spend(&self) -> RpcResult<Vec<ChannelSpend>>;
+
+    /// The fee paid for submitting the packet with the given hash.
+    #[method(name = "packetFee")]
+    async fn packet_fee(&self, packet_hash: H256) -> RpcResult<Option<PacketFee>>;
 }
 
 #[async_trait]
@@ -258,6 +348,45 @@ impl TransactionPluginServer for Module {
 
         Ok(out)
     }
+
+    async fn channel_spend(&self) -> RpcResult<Vec<ChannelSpend>> {
+        Ok(self.fees.channel_spend())
+    }
+
+    async fn packet_fee(&self, packet_hash: H256) -> RpcResult<Option<PacketFee>> {
+        Ok(self.fees.packet_fee(packet_hash))
+    }
+}
+
+impl ChainKeyring for Module {
+    type Address = Address;
+    type Signer = EthSigner;
+
+    fn keyring(&self) -> &ConcurrentKeyring<Address, EthSigner> {
+        &self.keyring
+    }
+
+    async fn balances(&self) -> Vec<SignerBalance<Address>> {
+        let mut out = vec![];
+
+        for address in self.keyring.keys() {
+            match self.provider.get_balance(*address).await {
+                Ok(balance) => out.push(SignerBalance {
+                    key_name: address.to_string(),
+                    address: *address,
+                    balance: balance.saturating_to(),
+                    denom: "wei".to_owned(),
+                }),
+                Err(err) => warn!(
+                    %address,
+                    err = %ErrorReporter(err),
+                    "error fetching signer balance"
+                ),
+            }
+        }
+
+        out
+    }
 }
 
 fn plugin_name(chain_id: &ChainId) -> String {
@@ -290,6 +419,12 @@ pub enum TxSubmitError {
     RpcError(#[from] ErrorObjectOwned),
     #[error("batch too large")]
     BatchTooLarge,
+    #[error("transaction was unrecoverable and has been cancelled")]
+    Cancelled,
+    #[error("nonce {nonce} was used by an unknown transaction")]
+    NonceConsumed { nonce: u64 },
+    #[error("transaction with nonce {nonce} was not included within the max wait time")]
+    Abandoned { nonce: u64 },
 }
 
 #[async_trait]
@@ -323,6 +458,7 @@ impl PluginServer<ModuleCall, Never> for Module {
                                         .collect(),
                                 ),
                             )
+                            .with_priority(Priority::Urgent)
                             .into()
                         },
                     )
@@ -364,24 +500,46 @@ impl PluginServer<ModuleCall, Never> for Module {
                     Some(Err(TxSubmitError::OutOfGas)) => {
                         Err(ErrorObject::owned(-1, "out of gas", None::<()>))
                     }
+                    // the messages were not submitted, retry them later
+                    Some(Err(TxSubmitError::Cancelled | TxSubmitError::NonceConsumed { .. })) => {
+                        Ok(seq([
+                            defer(now() + 12),
+                            call(
+                                PluginMessage::new(
+                                    self.plugin_name(),
+                                    ModuleCall::SubmitMulticall(msgs),
+                                )
+                                .with_priority(Priority::Urgent),
+                            ),
+                        ]))
+                    }
                     Some(Err(TxSubmitError::EmptyRevert(msgs))) => Ok(seq([
                         defer(now() + 12),
-                        call(PluginMessage::new(
-                            self.plugin_name(),
-                            ModuleCall::SubmitMulticall(msgs),
-                        )),
+                        call(
+                            PluginMessage::new(
+                                self.plugin_name(),
+                                ModuleCall::SubmitMulticall(msgs),
+                            )
+                            .with_priority(Priority::Urgent),
+                        ),
                     ])),
                     Some(Err(TxSubmitError::BatchTooLarge)) => {
                         let new = msgs.split_off(msgs.len() / 2);
                         Ok(seq([
-                            call(PluginMessage::new(
-                                self.plugin_name(),
-                                ModuleCall::SubmitMulticall(msgs),
-                            )),
-                            call(PluginMessage::new(
-                                self.plugin_name(),
-                                ModuleCall::SubmitMulticall(new),
-                            )),
+                            call(
+                                PluginMessage::new(
+                                    self.plugin_name(),
+                                    ModuleCall::SubmitMulticall(msgs),
+                                )
+                                .with_priority(Priority::Urgent),
+                            ),
+                            call(
+                                PluginMessage::new(
+                                    self.plugin_name(),
+                                    ModuleCall::SubmitMulticall(new),
+                                )
+                                .with_priority(Priority::Urgent),
+                            ),
                         ]))
                     }
                     Some(Err(err)) => Err(ErrorObject::owned(
@@ -427,9 +585,57 @@ impl PluginServer<ModuleCall, Never> for Module {
 }
 
 impl Module {
+    /// Send `amount` wei from `funding_signer` to `address`, waiting at most `timeout` for the
+    /// transaction to be included.
+    #[instrument(skip_all, fields(%address, amount))]
+    async fn top_up(
+        &self,
+        funding_signer: &EthSigner,
+        address: Address,
+        amount: u128,
+        timeout: Duration,
+    ) -> anyhow::Result<()> {
+        let signer = DynProvider::new(
+            ProviderBuilder::new()
+                .network::<AnyNetwork>()
+                .filler(AnyNetwork::recommended_fillers())
+                .wallet(EthereumWallet::new(funding_signer.clone()))
+                .connect_provider(self.provider.clone()),
+        );
+
+        let pending = signer
+            .send_transaction(
+                <AnyNetwork as Network>::TransactionRequest::default()
+                    .with_to(address)
+                    .with_value(alloy::primitives::U256::from(amount)),
+            )
+            .await?;
+
+        let tx_hash = <H256>::from(*pending.tx_hash());
+
+        let receipt = match pending.with_timeout(Some(timeout)).get_receipt().await {
+            Ok(receipt) => receipt,
+            Err(PendingTransactionError::TxWatcher(WatchTxError::Timeout)) => {
+                bail!("top up tx {tx_hash} was not included within {timeout:?}")
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if !receipt.status() {
+            bail!(
+                "top up tx {} reverted",
+                <H256>::from(receipt.transaction_hash)
+            );
+        }
+
+        info!(tx_hash = %<H256>::from(receipt.transaction_hash), "top up tx included");
+
+        Ok(())
+    }
+
     async fn submit_transaction(
         &self,
-        wallet: &LocalSigner<SigningKey>,
+        wallet: &EthSigner,
         ibc_messages: Vec<Datagram>,
     ) -> Result<(), TxSubmitError> {
         let signer = DynProvider::new(
@@ -516,13 +722,73 @@ impl Module {
             call = call.gas_price(fixed_gas_price);
         }
 
-        match call.gas(gas_to_use).send().await {
+        call = call.gas(gas_to_use);
+
+        // the nonce and fees are set explicitly so that the transaction can be replaced if it gets
+        // stuck
+        let replaceable = if self.replacement_enabled() {
+            let pending_nonce = self
+                .provider
+                .get_transaction_count(wallet.address())
+                .pending()
+                .await
+                .map_err(Error::from)?;
+
+            let nonce = self.nonces.next_nonce(wallet.address(), pending_nonce);
+            let fees = self.estimate_fees().await?;
+
+            call = call
+                .nonce(nonce)
+                .max_fee_per_gas(fees.max_fee_per_gas)
+                .max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+            Some((nonce, fees))
+        } else {
+            None
+        };
+
+        match call.send().await {
             Ok(ok) => {
                 let tx_hash = <H256>::from(*ok.tx_hash());
                 async move {
-                    let receipt = ok.get_receipt().await?;
+                    let sent_at = Instant::now();
+
+                    let attributes = [
+                        KeyValue::new("chain_id", self.chain_id.to_string()),
+                        KeyValue::new("signer", wallet.address().to_string()),
+                    ];
+
+                    self.metrics.submitted_count.add(1, &attributes);
+
+                    let receipt = match replaceable {
+                        Some((nonce, fees)) => {
+                            self.nonces.sent(wallet.address(), nonce);
+
+                            let pending = PendingTx::new(nonce, fees, *ok.tx_hash());
+
+                            self.wait_for_inclusion(&signer, wallet.address(), pending, {
+                                let call = &call;
+                                async move |fees: Fees| {
+                                    call.clone()
+                                        .max_fee_per_gas(fees.max_fee_per_gas)
+                                        .max_priority_fee_per_gas(fees.max_priority_fee_per_gas)
+                                        .send()
+                                        .await
+                                        .map(|pending| *pending.tx_hash())
+                                }
+                            })
+                            .await?
+                            .ok_or(TxSubmitError::Cancelled)?
+                        }
+                        None => ok.get_receipt().await?,
+                    };
 
-                    info!(%tx_hash, "tx included");
+                    self.metrics.included_count.add(1, &attributes);
+                    self.metrics
+                        .inclusion_duration
+                        .record(sent_at.elapsed().as_secs_f64(), &attributes);
+
+                    info!(tx_hash = %<H256>::from(receipt.transaction_hash), "tx included");
 
                     let result = MulticallResult::decode_log_data(
                         receipt
@@ -541,6 +807,17 @@ impl Module {
                         "submitted batched evm messages"
                     );
 
+                    self.fees.record_tx(
+                        msgs.iter()
+                            .zip(&result._0)
+                            .filter(|(_, result)| result.success)
+                            .map(|((datagram, _), _)| datagram),
+                        receipt.transaction_hash.into(),
+                        receipt.gas_used,
+                        u128::from(receipt.gas_used) * receipt.effective_gas_price,
+                        "wei",
+                    );
+
                     for (idx, (result, (msg, msg_name))) in
                         result._0.into_iter().zip(msg_names).enumerate()
                     {
@@ -625,6 +902,183 @@ impl Module {
     }
 }
 
+impl Module {
+    fn replacement_enabled(&self) -> bool {
+        !self.legacy && self.fixed_gas_price.is_none()
+    }
+
+    async fn estimate_fees(&self) -> Result<Fees, TxSubmitError> {
+        let estimate = self
+            .provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(Error::from)?;
+
+        Ok(Fees {
+            max_fee_per_gas: estimate.max_fee_per_gas,
+            max_priority_fee_per_gas: estimate.max_priority_fee_per_gas,
+        })
+    }
+
+    /// Wait for one of the transactions sent with the nonce of `pending` to be included. If the
+    /// transaction is pending for longer than the configured timeout, it is replaced with higher
+    /// fees via `replace`, and cancelled once it is considered unrecoverable.
+    ///
+    /// Returns the receipt of the included transaction, or `None` if the cancellation was
+    /// included instead. Fails if the nonce is used by a transaction that wasn't sent from here,
+    /// or if none of the transactions are included within the configured max wait time.
+    #[instrument(skip_all, fields(%address, nonce = pending.nonce))]
+    async fn wait_for_inclusion(
+        &self,
+        signer: &DynProvider<AnyNetwork>,
+        address: Address,
+        mut pending: PendingTx<TxHash>,
+        replace: impl AsyncFn(Fees) -> Result<TxHash, Error>,
+    ) -> Result<Option<AnyTransactionReceipt>, TxSubmitError> {
+        let attributes = [
+            KeyValue::new("chain_id", self.chain_id.to_string()),
+            KeyValue::new("signer", address.to_string()),
+        ];
+
+        self.metrics
+            .pending_nonce
+            .record(pending.nonce, &attributes);
+
+        loop {
+            if pending.is_abandoned(&self.replacement) {
+                error!("tx was not included within the max wait time, abandoning it");
+
+                // the tx may have been dropped by the node, in which case its nonce must be reused
+                self.nonces.forget(address);
+
+                return Err(TxSubmitError::Abandoned {
+                    nonce: pending.nonce,
+                });
+            }
+
+            // this is fetched before the receipts, so that a transaction included in between the
+            // two requests is not mistaken for an unknown transaction
+            let latest_nonce = match self.provider.get_transaction_count(address).latest().await {
+                Ok(latest_nonce) => Some(latest_nonce),
+                Err(err) => {
+                    warn!(err = %ErrorReporter(err), "error fetching latest nonce");
+                    None
+                }
+            };
+
+            let mut all_receipts_fetched = true;
+
+            for hash in pending.hashes.iter().chain(&pending.cancellations) {
+                let receipt = match self.provider.get_transaction_receipt(*hash).await {
+                    Ok(Some(receipt)) => receipt,
+                    Ok(None) => continue,
+                    Err(err) => {
+                        warn!(
+                            tx_hash = %<H256>::from(*hash),
+                            err = %ErrorReporter(err),
+                            "error fetching receipt, retrying on the next poll"
+                        );
+                        all_receipts_fetched = false;
+                        continue;
+                    }
+                };
+
+                if pending.is_cancellation(hash) {
+                    warn!(tx_hash = %<H256>::from(*hash), "cancellation included");
+
+                    return Ok(None);
+                }
+
+                return Ok(Some(receipt));
+            }
+
+            if all_receipts_fetched && latest_nonce.is_some_and(|latest| latest > pending.nonce) {
+                error!("nonce was used by an unknown transaction");
+
+                return Err(TxSubmitError::NonceConsumed {
+                    nonce: pending.nonce,
+                });
+            }
+
+            if pending.is_stuck(&self.replacement) {
+                let timeouts = pending.timed_out();
+
+                let estimate = self.estimate_fees().await?;
+
+                match next_replacement_action(
+                    &self.replacement,
+                    pending.fees,
+                    estimate,
+                    timeouts,
+                    pending.is_cancelled(),
+                    self.max_gas_price,
+                ) {
+                    ReplacementAction::Replace(fees) => match replace(fees).await {
+                        Ok(hash) => {
+                            info!(
+                                tx_hash = %<H256>::from(hash),
+                                max_fee_per_gas = fees.max_fee_per_gas,
+                                max_priority_fee_per_gas = fees.max_priority_fee_per_gas,
+                                "replaced stuck tx"
+                            );
+
+                            self.metrics.replaced_count.add(1, &attributes);
+
+                            pending.replaced(hash, fees);
+                        }
+                        // the previous transaction may have been included in the meantime, which
+                        // will be picked up by the next check
+                        Err(err) => warn!(
+                            err = %ErrorReporter(err),
+                            "error replacing stuck tx"
+                        ),
+                    },
+                    ReplacementAction::Wait if pending.is_cancelled() => {
+                        warn!("tx is stuck, waiting for the cancellation to be included");
+                    }
+                    ReplacementAction::Wait => {
+                        warn!(
+                            max_gas_price = self.max_gas_price,
+                            "tx is stuck, but the fees can't be bumped without exceeding the max \
+                            gas price"
+                        );
+                    }
+                    ReplacementAction::Cancel(fees) => {
+                        let cancellation = <AnyNetwork as Network>::TransactionRequest::default()
+                            .with_to(address)
+                            .with_value(Default::default())
+                            .with_nonce(pending.nonce)
+                            .with_gas_limit(21_000)
+                            .with_max_fee_per_gas(fees.max_fee_per_gas)
+                            .with_max_priority_fee_per_gas(fees.max_priority_fee_per_gas);
+
+                        match signer.send_transaction(cancellation).await {
+                            Ok(ok) => {
+                                warn!(
+                                    tx_hash = %<H256>::from(*ok.tx_hash()),
+                                    max_fee_per_gas = fees.max_fee_per_gas,
+                                    max_priority_fee_per_gas = fees.max_priority_fee_per_gas,
+                                    "tx is unrecoverable, cancelled"
+                                );
+
+                                self.metrics.cancelled_count.add(1, &attributes);
+
+                                pending.cancelled(*ok.tx_hash(), fees);
+                            }
+                            Err(err) => warn!(
+                                err = %ErrorReporter(err),
+                                "error cancelling stuck tx"
+                            ),
+                        }
+                    }
+                }
+            }
+
+            tokio::time::sleep(self.replacement.poll_interval()).await;
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 fn process_msgs<'a>(
     ibc_handler: &'a ibc_solidity::Ibc::IbcInstance<&'a DynProvider<AnyNetwork>, AnyNetwork>,
@@ -0,0 +1,53 @@
+use opentelemetry::metrics::{Counter, Gauge, Histogram};
+
+#[derive(Debug, Clone)]
+pub struct Metrics {
+    pub submitted_count: Counter<u64>,
+    pub included_count: Counter<u64>,
+    pub replaced_count: Counter<u64>,
+    pub cancelled_count: Counter<u64>,
+    pub inclusion_duration: Histogram<f64>,
+    pub pending_nonce: Gauge<u64>,
+}
+
+impl Metrics {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            submitted_count: opentelemetry::global::meter("transaction_plugin_ethereum")
+                .u64_counter("transaction_plugin_ethereum_submitted_count")
+                .with_description("Total count of transactions submitted, per signer.")
+                .build(),
+            included_count: opentelemetry::global::meter("transaction_plugin_ethereum")
+                .u64_counter("transaction_plugin_ethereum_included_count")
+                .with_description("Total count of transactions included, per signer.")
+                .build(),
+            replaced_count: opentelemetry::global::meter("transaction_plugin_ethereum")
+                .u64_counter("transaction_plugin_ethereum_replaced_count")
+                .with_description(
+                    "Total count of stuck transactions replaced with higher fees, per signer.",
+                )
+                .build(),
+            cancelled_count: opentelemetry::global::meter("transaction_plugin_ethereum")
+                .u64_counter("transaction_plugin_ethereum_cancelled_count")
+                .with_description(
+                    "Total count of unrecoverable transactions cancelled, per signer.",
+                )
+                .build(),
+            inclusion_duration: opentelemetry::global::meter("transaction_plugin_ethereum")
+                .f64_histogram("transaction_plugin_ethereum_inclusion_duration_seconds")
+                .with_description(
+                    "The time between first submitting a transaction and it being included, per \
+                    signer.",
+                )
+                .with_boundaries(vec![
+                    0.0, 1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1200.0,
+                ])
+                .build(),
+            pending_nonce: opentelemetry::global::meter("transaction_plugin_ethereum")
+                .u64_gauge("transaction_plugin_ethereum_pending_nonce")
+                .with_description("The nonce of the last transaction sent, per signer.")
+                .build(),
+        }
+    }
+}
@@ -0,0 +1,527 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+
+/// The minimum percentage that the fees of a replacement transaction must be increased by for it to
+/// be accepted into the mempool of a node (this is the default `txpool.pricebump` of geth).
+pub const MIN_FEE_BUMP_PERCENT: u64 = 10;
+
+/// Configuration for the replacement of transactions that have been pending for too long.
+///
+/// Replacement is only done for EIP-1559 transactions, i.e. it is disabled if the plugin is
+/// configured with `legacy` or a `fixed_gas_price`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplacementConfig {
+    /// How long a transaction can be pending before it is replaced with a transaction with higher
+    /// fees.
+    #[serde(default = "default_pending_timeout_seconds")]
+    pub pending_timeout_seconds: u64,
+    /// The percentage to increase the fees of a transaction by when it is replaced. This will be
+    /// at least [`MIN_FEE_BUMP_PERCENT`].
+    #[serde(default = "default_fee_bump_percent")]
+    pub fee_bump_percent: u64,
+    /// The amount of times a transaction can be stuck (and replaced, if the max gas price allows
+    /// it) before it is considered unrecoverable and is cancelled.
+    #[serde(default = "default_max_replacements")]
+    pub max_replacements: u32,
+    /// The interval between checks of whether a pending transaction has been included.
+    #[serde(default = "default_poll_interval_seconds")]
+    pub poll_interval_seconds: u64,
+    /// How long to wait for a transaction (or any of its replacements) to be included before
+    /// giving up on it.
+    #[serde(default = "default_max_wait_seconds")]
+    pub max_wait_seconds: u64,
+}
+
+impl Default for ReplacementConfig {
+    fn default() -> Self {
+        Self {
+            pending_timeout_seconds: default_pending_timeout_seconds(),
+            fee_bump_percent: default_fee_bump_percent(),
+            max_replacements: default_max_replacements(),
+            poll_interval_seconds: default_poll_interval_seconds(),
+            max_wait_seconds: default_max_wait_seconds(),
+        }
+    }
+}
+
+impl ReplacementConfig {
+    pub fn pending_timeout(&self) -> Duration {
+        Duration::from_secs(self.pending_timeout_seconds)
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_interval_seconds)
+    }
+
+    pub fn max_wait(&self) -> Duration {
+        Duration::from_secs(self.max_wait_seconds)
+    }
+}
+
+#[must_use]
+pub const fn default_pending_timeout_seconds() -> u64 {
+    60
+}
+
+#[must_use]
+pub const fn default_fee_bump_percent() -> u64 {
+    20
+}
+
+#[must_use]
+pub const fn default_max_replacements() -> u32 {
+    5
+}
+
+#[must_use]
+pub const fn default_poll_interval_seconds() -> u64 {
+    2
+}
+
+#[must_use]
+pub const fn default_max_wait_seconds() -> u64 {
+    15 * 60
+}
+
+/// The EIP-1559 fees of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl Fees {
+    /// Increase both fees by `percent` (at least [`MIN_FEE_BUMP_PERCENT`]), rounding up.
+    #[must_use]
+    pub fn bumped(self, percent: u64) -> Self {
+        let bump =
+            |fee: u128| (fee * u128::from(100 + percent.max(MIN_FEE_BUMP_PERCENT))).div_ceil(100);
+
+        Self {
+            max_fee_per_gas: bump(self.max_fee_per_gas),
+            max_priority_fee_per_gas: bump(self.max_priority_fee_per_gas),
+        }
+    }
+
+    /// The fees that are at least as high as both `self` and `other`.
+    #[must_use]
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            max_fee_per_gas: self.max_fee_per_gas.max(other.max_fee_per_gas),
+            max_priority_fee_per_gas: self
+                .max_priority_fee_per_gas
+                .max(other.max_priority_fee_per_gas),
+        }
+    }
+}
+
+/// What to do with a transaction that has been pending for longer than the configured
+/// [`ReplacementConfig::pending_timeout_seconds`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplacementAction {
+    /// Replace the transaction with the same transaction, with the provided fees.
+    Replace(Fees),
+    /// The fees can't be bumped any further without exceeding the max gas price, or the transaction
+    /// has already been cancelled, keep waiting for the current transaction.
+    Wait,
+    /// The transaction is unrecoverable, replace it with an empty transaction with the provided
+    /// fees to free up the nonce.
+    Cancel(Fees),
+}
+
+/// Decide how to replace a stuck transaction sent with `previous` fees, given the current fee
+/// `estimate` of the chain, the amount of times the transaction has previously been stuck and
+/// whether it has already been `cancelled`.
+///
+/// Replacement and cancellation fees are the bumped previous fees or the current estimate,
+/// whichever is higher, capped at `max_gas_price`. If the capped fees are not enough to replace the
+/// transaction, it is waited on instead. Since the fees of a cancellation would otherwise compound
+/// on every timeout, only one cancellation is sent per nonce.
+pub fn next_replacement_action(
+    config: &ReplacementConfig,
+    previous: Fees,
+    estimate: Fees,
+    timeouts: u32,
+    cancelled: bool,
+    max_gas_price: Option<u128>,
+) -> ReplacementAction {
+    if cancelled {
+        return ReplacementAction::Wait;
+    }
+
+    let bumped = previous.bumped(config.fee_bump_percent).max(estimate);
+
+    let action = if timeouts >= config.max_replacements {
+        ReplacementAction::Cancel
+    } else {
+        ReplacementAction::Replace
+    };
+
+    let Some(max_gas_price) = max_gas_price else {
+        return action(bumped);
+    };
+
+    if bumped.max_fee_per_gas <= max_gas_price {
+        return action(bumped);
+    }
+
+    // the fees can only be capped if the capped fees are still enough to replace the transaction
+    let min = previous.bumped(MIN_FEE_BUMP_PERCENT);
+
+    if min.max_fee_per_gas <= max_gas_price && min.max_priority_fee_per_gas <= max_gas_price {
+        action(Fees {
+            max_fee_per_gas: max_gas_price,
+            max_priority_fee_per_gas: bumped.max_priority_fee_per_gas.min(max_gas_price),
+        })
+    } else {
+        ReplacementAction::Wait
+    }
+}
+
+/// Tracks the next nonce of each signer, so that transactions are not sent with a nonce that is
+/// already used by a pending (or replaced) transaction if the node lags behind.
+#[derive(Debug, Default)]
+pub struct NonceTracker {
+    next_nonces: Mutex<HashMap<Address, u64>>,
+}
+
+impl NonceTracker {
+    /// The nonce to use for the next transaction of `signer`, given the `pending` nonce reported
+    /// by the node.
+    pub fn next_nonce(&self, signer: Address, pending_nonce: u64) -> u64 {
+        self.next_nonces
+            .lock()
+            .expect("mutex is poisoned")
+            .get(&signer)
+            .map_or(pending_nonce, |next_nonce| pending_nonce.max(*next_nonce))
+    }
+
+    /// Record that a transaction of `signer` has been sent with `nonce`. This must be called as
+    /// soon as the transaction is accepted by the node, so that the next transaction doesn't reuse
+    /// the nonce while this one is still pending.
+    pub fn sent(&self, signer: Address, nonce: u64) {
+        let mut next_nonces = self.next_nonces.lock().expect("mutex is poisoned");
+
+        let next_nonce = next_nonces.entry(signer).or_default();
+        *next_nonce = (*next_nonce).max(nonce + 1);
+    }
+
+    /// Forget the nonces recorded for `signer`, so that the next transaction uses the pending
+    /// nonce reported by the node. This is used when the state of a sent transaction is unknown,
+    /// since it may have been dropped by the node, in which case its nonce must be reused.
+    pub fn forget(&self, signer: Address) {
+        self.next_nonces
+            .lock()
+            .expect("mutex is poisoned")
+            .remove(&signer);
+    }
+}
+
+/// A transaction that is waiting to be included, along with all of the transactions that it has
+/// been replaced with.
+#[derive(Debug)]
+pub struct PendingTx<H> {
+    pub nonce: u64,
+    /// The fees of the most recently sent transaction.
+    pub fees: Fees,
+    /// All hashes that have been sent with this nonce, including the replaced ones, since any of
+    /// them may end up being included.
+    pub hashes: Vec<H>,
+    /// The hashes of the cancellation transactions sent with this nonce.
+    pub cancellations: Vec<H>,
+    /// The amount of times the transaction has been pending for longer than the pending timeout.
+    pub timeouts: u32,
+    pub first_sent_at: Instant,
+    last_checked_at: Instant,
+}
+
+impl<H: PartialEq> PendingTx<H> {
+    pub fn new(nonce: u64, fees: Fees, hash: H) -> Self {
+        let now = Instant::now();
+
+        Self {
+            nonce,
+            fees,
+            hashes: vec![hash],
+            cancellations: vec![],
+            timeouts: 0,
+            first_sent_at: now,
+            last_checked_at: now,
+        }
+    }
+
+    /// Whether the transaction has been waited on for longer than the max wait time since it was
+    /// first sent.
+    pub fn is_abandoned(&self, config: &ReplacementConfig) -> bool {
+        self.first_sent_at.elapsed() >= config.max_wait()
+    }
+
+    /// Whether the transaction has been pending for longer than the pending timeout since it was
+    /// last sent or checked.
+    pub fn is_stuck(&self, config: &ReplacementConfig) -> bool {
+        self.last_checked_at.elapsed() >= config.pending_timeout()
+    }
+
+    /// Record that the transaction was found to be stuck, returning the amount of previous
+    /// timeouts.
+    pub fn timed_out(&mut self) -> u32 {
+        self.last_checked_at = Instant::now();
+        self.timeouts += 1;
+        self.timeouts - 1
+    }
+
+    /// Record that the transaction was replaced with `hash`, sent with `fees`.
+    pub fn replaced(&mut self, hash: H, fees: Fees) {
+        self.hashes.push(hash);
+        self.fees = fees;
+    }
+
+    /// Record that the transaction was cancelled with `hash`, sent with `fees`.
+    pub fn cancelled(&mut self, hash: H, fees: Fees) {
+        self.cancellations.push(hash);
+        self.fees = fees;
+    }
+
+    pub fn is_cancellation(&self, hash: &H) -> bool {
+        self.cancellations.contains(hash)
+    }
+
+    /// Whether a cancellation has been sent for this nonce.
+    pub fn is_cancelled(&self) -> bool {
+        !self.cancellations.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GWEI: u128 = 1_000_000_000;
+
+    fn fees(max_fee_per_gas: u128, max_priority_fee_per_gas: u128) -> Fees {
+        Fees {
+            max_fee_per_gas: max_fee_per_gas * GWEI,
+            max_priority_fee_per_gas: max_priority_fee_per_gas * GWEI,
+        }
+    }
+
+    #[test]
+    fn bump() {
+        assert_eq!(
+            fees(10, 2).bumped(20),
+            Fees {
+                max_fee_per_gas: 12 * GWEI,
+                max_priority_fee_per_gas: 2_400_000_000
+            }
+        );
+
+        // always bumped by at least the minimum
+        assert_eq!(
+            fees(10, 1).bumped(0),
+            Fees {
+                max_fee_per_gas: 11 * GWEI,
+                max_priority_fee_per_gas: 1_100_000_000
+            }
+        );
+
+        // rounds up
+        assert_eq!(
+            Fees {
+                max_fee_per_gas: 1,
+                max_priority_fee_per_gas: 1
+            }
+            .bumped(10),
+            Fees {
+                max_fee_per_gas: 2,
+                max_priority_fee_per_gas: 2
+            }
+        );
+    }
+
+    #[test]
+    fn replacement_uses_estimate_if_higher() {
+        let config = ReplacementConfig::default();
+
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(50, 1), 0, false, None),
+            ReplacementAction::Replace(Fees {
+                max_fee_per_gas: 50 * GWEI,
+                max_priority_fee_per_gas: 1_200_000_000
+            })
+        );
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 0, false, None),
+            ReplacementAction::Replace(Fees {
+                max_fee_per_gas: 12 * GWEI,
+                max_priority_fee_per_gas: 1_200_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn replacement_is_capped() {
+        let config = ReplacementConfig::default();
+
+        // capped, but still enough of a bump
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 0, false, Some(11 * GWEI)),
+            ReplacementAction::Replace(Fees {
+                max_fee_per_gas: 11 * GWEI,
+                max_priority_fee_per_gas: 1_200_000_000
+            })
+        );
+
+        // the minimum bump exceeds the cap
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 0, false, Some(10 * GWEI)),
+            ReplacementAction::Wait
+        );
+    }
+
+    #[test]
+    fn cancels_after_max_replacements() {
+        let config = ReplacementConfig {
+            max_replacements: 2,
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 1, false, Some(10 * GWEI)),
+            ReplacementAction::Wait
+        ));
+
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 2, false, None),
+            ReplacementAction::Cancel(Fees {
+                max_fee_per_gas: 12 * GWEI,
+                max_priority_fee_per_gas: 1_200_000_000
+            })
+        );
+    }
+
+    #[test]
+    fn cancellation_is_capped() {
+        let config = ReplacementConfig {
+            max_replacements: 0,
+            ..Default::default()
+        };
+
+        // capped, but still enough of a bump
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 0, false, Some(11 * GWEI)),
+            ReplacementAction::Cancel(Fees {
+                max_fee_per_gas: 11 * GWEI,
+                max_priority_fee_per_gas: 1_200_000_000
+            })
+        );
+
+        // the minimum bump exceeds the cap
+        assert_eq!(
+            next_replacement_action(&config, fees(10, 1), fees(1, 1), 0, false, Some(10 * GWEI)),
+            ReplacementAction::Wait
+        );
+    }
+
+    #[test]
+    fn cancels_once() {
+        let config = ReplacementConfig {
+            max_replacements: 0,
+            ..Default::default()
+        };
+
+        let mut pending = PendingTx::new(1, fees(10, 1), 1);
+
+        let mut cancellations = vec![];
+
+        for hash in 2..10 {
+            match next_replacement_action(
+                &config,
+                pending.fees,
+                fees(1, 1),
+                pending.timed_out(),
+                pending.is_cancelled(),
+                Some(12 * GWEI),
+            ) {
+                ReplacementAction::Cancel(fees) => {
+                    pending.cancelled(hash, fees);
+                    cancellations.push(fees);
+                }
+                ReplacementAction::Wait => {}
+                ReplacementAction::Replace(_) => panic!("replaced after max replacements"),
+            }
+        }
+
+        // the fees of the cancellation are not compounded on further timeouts
+        assert_eq!(
+            cancellations,
+            [Fees {
+                max_fee_per_gas: 12 * GWEI,
+                max_priority_fee_per_gas: 1_200_000_000
+            }]
+        );
+        assert_eq!(pending.cancellations, [2]);
+        assert_eq!(pending.fees, cancellations[0]);
+    }
+
+    #[test]
+    fn nonce_tracking() {
+        let tracker = NonceTracker::default();
+        let a = Address::repeat_byte(1);
+        let b = Address::repeat_byte(2);
+
+        assert_eq!(tracker.next_nonce(a, 5), 5);
+
+        tracker.sent(a, 5);
+        tracker.sent(a, 6);
+
+        // the node is lagging behind
+        assert_eq!(tracker.next_nonce(a, 5), 7);
+        // the node is ahead, i.e. the signer was used elsewhere
+        assert_eq!(tracker.next_nonce(a, 10), 10);
+
+        // nonces never go backwards
+        tracker.sent(a, 3);
+        assert_eq!(tracker.next_nonce(a, 0), 7);
+
+        assert_eq!(tracker.next_nonce(b, 0), 0);
+
+        tracker.forget(a);
+        assert_eq!(tracker.next_nonce(a, 5), 5);
+    }
+
+    #[test]
+    fn pending_tx() {
+        let config = ReplacementConfig {
+            pending_timeout_seconds: 0,
+            ..Default::default()
+        };
+
+        let mut pending = PendingTx::new(1, fees(10, 1), 1);
+
+        assert!(pending.is_stuck(&config));
+        assert_eq!(pending.timed_out(), 0);
+        pending.replaced(2, fees(12, 2));
+        assert_eq!(pending.timed_out(), 1);
+        pending.cancelled(3, fees(15, 3));
+
+        assert_eq!(pending.hashes, [1, 2]);
+        assert_eq!(pending.fees, fees(15, 3));
+        assert!(pending.is_cancellation(&3));
+        assert!(!pending.is_cancellation(&2));
+        assert!(pending.is_cancelled());
+        assert!(!pending.is_stuck(&ReplacementConfig::default()));
+
+        assert!(!pending.is_abandoned(&ReplacementConfig::default()));
+        assert!(pending.is_abandoned(&ReplacementConfig {
+            max_wait_seconds: 0,
+            ..Default::default()
+        }));
+    }
+}
@@ -0,0 +1,151 @@
+use alloy::{
+    consensus::SignableTransaction,
+    network::TxSigner,
+    primitives::{Address, Signature},
+    signers::{local::LocalSigner, utils::public_key_to_address},
+};
+use bip32::secp256k1::ecdsa::{SigningKey, VerifyingKey};
+use concurrent_keyring::{remote::RemoteSigner, KeyringConfigEntry};
+use jsonrpsee::core::async_trait;
+use voyager_sdk::anyhow;
+
+/// A signer for transactions, either holding the private key locally or signing with a remote
+/// signer.
+#[derive(Debug, Clone)]
+pub enum EthSigner {
+    Local(LocalSigner<SigningKey>),
+    Remote {
+        remote: RemoteSigner,
+        address: Address,
+    },
+}
+
+impl EthSigner {
+    pub fn from_config(entry: &KeyringConfigEntry) -> anyhow::Result<Self> {
+        match entry.remote_signer() {
+            Some(remote) => {
+                let verifying_key = VerifyingKey::from_sec1_bytes(remote.public_key())
+                    .map_err(|e| anyhow::anyhow!("invalid remote signer public key: {e}"))?;
+
+                Ok(Self::Remote {
+                    address: public_key_to_address(&verifying_key),
+                    remote,
+                })
+            }
+            None => {
+                let signing_key = <SigningKey as bip32::PrivateKey>::from_bytes(
+                    &entry.value()?.as_slice().try_into()?,
+                )
+                .map_err(|e| anyhow::anyhow!("invalid private key: {e}"))?;
+
+                Ok(Self::Local(LocalSigner::from_signing_key(signing_key)))
+            }
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        match self {
+            EthSigner::Local(signer) => signer.address(),
+            EthSigner::Remote { address, .. } => *address,
+        }
+    }
+}
+
+#[async_trait]
+impl TxSigner<Signature> for EthSigner {
+    fn address(&self) -> Address {
+        EthSigner::address(self)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &mut dyn SignableTransaction<Signature>,
+    ) -> alloy::signers::Result<Signature> {
+        match self {
+            EthSigner::Local(signer) => signer.sign_transaction(tx).await,
+            EthSigner::Remote { remote, address } => {
+                let signature_hash = tx.signature_hash();
+
+                let signature = remote
+                    .sign_eth1(signature_hash.0.into())
+                    .await
+                    .map_err(alloy::signers::Error::other)?;
+
+                let signature =
+                    Signature::from_raw(signature.get()).map_err(alloy::signers::Error::other)?;
+
+                // the remote signer may be signing with a different key than the one configured,
+                // which would otherwise only be noticed once the transaction is rejected
+                let recovered = signature
+                    .recover_address_from_prehash(&signature_hash)
+                    .map_err(alloy::signers::Error::other)?;
+
+                if recovered != *address {
+                    return Err(alloy::signers::Error::message(format!(
+                        "remote signer signed with {recovered}, expected {address}"
+                    )));
+                }
+
+                Ok(signature)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        consensus::TxLegacy,
+        primitives::{TxKind, U256},
+    };
+    use concurrent_keyring::mock::MockSigner;
+
+    use super::*;
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_slice(&[0x11; 32]).unwrap()
+    }
+
+    #[tokio::test]
+    async fn remote_signer_signs_transactions() {
+        let mock = MockSigner::spawn([signing_key()]).await;
+
+        let remote = EthSigner::from_config(&KeyringConfigEntry::Remote {
+            name: "remote".to_owned(),
+            url: mock.url(),
+            public_key: mock.remote_signer(&signing_key()).public_key().to_vec(),
+        })
+        .unwrap();
+
+        let local = EthSigner::from_config(&KeyringConfigEntry::Raw {
+            name: "local".to_owned(),
+            key: signing_key().to_bytes().to_vec(),
+        })
+        .unwrap();
+
+        assert_eq!(remote.address(), local.address());
+
+        let mut tx = TxLegacy {
+            chain_id: Some(1),
+            nonce: 1,
+            gas_price: 1,
+            gas_limit: 21_000,
+            to: TxKind::Call(Address::ZERO),
+            value: U256::from(1),
+            input: Default::default(),
+        };
+
+        let signature = TxSigner::sign_transaction(&remote, &mut tx).await.unwrap();
+
+        assert_eq!(
+            signature
+                .recover_address_from_prehash(&tx.signature_hash())
+                .unwrap(),
+            local.address()
+        );
+        assert_eq!(
+            signature,
+            TxSigner::sign_transaction(&local, &mut tx).await.unwrap()
+        );
+    }
+}
@@ -4,14 +4,17 @@ use std::{
 };
 
 use alloy::sol_types::SolValue;
-use concurrent_keyring::{ConcurrentKeyring, KeyringConfig, KeyringEntry};
-use fastcrypto::{hash::HashFunction, traits::Signer};
+use concurrent_keyring::{
+    balance::{monitor_balances, BalanceMonitorConfig, TopUpError},
+    ChainKeyring, ConcurrentKeyring, KeyringConfig, KeyringEntry, SignerBalance,
+};
 use hex_literal::hex;
 use ibc_union_spec::{datagram::Datagram, ChannelId, IbcUnion};
 use jsonrpsee::{
     core::{async_trait, RpcResult},
+    proc_macros::rpc,
     types::ErrorObject,
-    Extensions,
+    Extensions, MethodsError,
 };
 use move_core_types_sui::{
     account_address::AccountAddress,
@@ -19,17 +22,19 @@ use move_core_types_sui::{
     identifier::Identifier as MoveIdentifier,
     language_storage::{StructTag, TypeTag},
 };
+use relayer_fees::{ChannelSpend, FeeLedger, PacketFee};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sha3::{Digest, Keccak256};
 use shared_crypto::intent::{Intent, IntentMessage};
 use sui_sdk::{
     rpc_types::{
-        ObjectChange, SuiObjectDataOptions, SuiTransactionBlockResponse,
-        SuiTransactionBlockResponseOptions, SuiTypeTag,
+        ObjectChange, SuiObjectDataOptions, SuiTransactionBlockEffectsAPI,
+        SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions, SuiTypeTag,
     },
     types::{
         base_types::{ObjectID, SequenceNumber, SuiAddress},
-        crypto::{DefaultHash, SignatureScheme, SuiKeyPair, SuiSignature},
+        crypto::SuiSignature,
         programmable_transaction_builder::ProgrammableTransactionBuilder,
         signature::GenericSignature,
         transaction::{
@@ -40,10 +45,10 @@ use sui_sdk::{
     },
     SuiClient, SuiClientBuilder,
 };
-use tracing::{info, instrument};
+use tracing::{info, instrument, warn};
 use ucs03_zkgm::com::{FungibleAssetOrder, ZkgmPacket};
 use unionlabs::{
-    primitives::{encoding::HexPrefixed, Bytes, U256},
+    primitives::{H256, U256},
     ErrorReporter,
 };
 use voyager_sdk::{
@@ -52,16 +57,17 @@ use voyager_sdk::{
     message::{data::Data, PluginMessage, VoyagerMessage},
     plugin::Plugin,
     primitives::ChainId,
-    rpc::{types::PluginInfo, PluginServer},
-    vm::{call, noop, pass::PassResult, Op, Visit},
+    rpc::{types::PluginInfo, PluginServer, FATAL_JSONRPC_ERROR_CODE},
+    vm::{call, noop, pass::PassResult, schedule::Priority, Op, Visit},
     DefaultCmd,
 };
 
-use crate::{call::ModuleCall, callback::ModuleCallback};
+use crate::{call::ModuleCall, callback::ModuleCallback, signer::SuiSigner};
 
 pub mod call;
 pub mod callback;
 pub mod data;
+pub mod signer;
 
 const TOKEN_BYTECODE: [&[u8]; 2] = [
     hex!("a11ceb0b060000000a01000e020e1e032c27045308055b5607b101d1010882036006e2034b0aad04050cb2042b000a010d020602070212021302140001020001020701000003000c01000103030c0100010504020006050700000b000100010c010601000211030400030808090102040e0b01010c040f0e01010c05100c030001050307040a050d02080007080400020b020108000b030108000105010f010805010b01010900010800070900020a020a020a020b01010805070804020b030109000b02010900010b0201080001090001060804010b03010800020900050c436f696e4d657461646174610e46554e4749424c455f544f4b454e064f7074696f6e0b5472656173757279436170095478436f6e746578740355726c076164647265737304636f696e0f6372656174655f63757272656e63790b64756d6d795f6669656c640e66756e6769626c655f746f6b656e04696e6974046e6f6e65066f7074696f6e137075626c69635f73686172655f6f626a6563740f7075626c69635f7472616e736665720673656e64657207746f5f75323536087472616e736665720a74785f636f6e746578740375726c0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000020520").as_slice(),
@@ -83,9 +89,11 @@ pub struct Module {
 
     pub sui_client: sui_sdk::SuiClient,
 
-    pub keyring: ConcurrentKeyring<SuiAddress, Arc<SuiKeyPair>>,
+    pub keyring: ConcurrentKeyring<SuiAddress, Arc<SuiSigner>>,
 
     pub ibc_store_initial_seq: SequenceNumber,
+
+    pub fees: Arc<FeeLedger>,
 }
 
 impl Plugin for Module {
@@ -115,30 +123,59 @@ impl Plugin for Module {
             .start_version()
             .expect("ibc store is shared, hence it has a start version");
 
-        Ok(Self {
+        let module = Self {
             chain_id: ChainId::new(chain_id.to_string()),
             ibc_handler_address: config.ibc_handler_address,
             sui_client,
             ibc_store_initial_seq,
             keyring: ConcurrentKeyring::new(
                 config.keyring.name,
-                config.keyring.keys.into_iter().map(|config| {
-                    println!("{}", Bytes::<HexPrefixed>::new(config.value()));
-                    let pk = SuiKeyPair::decode(
-                        &String::from_utf8(config.value()).expect("priv keys are utf8 strings"),
-                    )
-                    .expect("private key is valid");
-
-                    let address = SuiAddress::from(&pk.public());
+                config
+                    .keyring
+                    .keys
+                    .iter()
+                    .map(|config| {
+                        let signer = SuiSigner::from_config(config)?;
 
-                    KeyringEntry {
-                        address,
-                        signer: Arc::new(pk),
-                    }
-                }),
+                        Ok(KeyringEntry {
+                            address: SuiAddress::from(&signer.public()),
+                            signer: Arc::new(signer),
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()?
+                    .into_iter(),
             ),
             ibc_store: config.ibc_store,
-        })
+            fees: Arc::new(FeeLedger::new()),
+        };
+
+        if let Some(balance_monitor) = config.balance_monitor {
+            let funding_signer = balance_monitor
+                .top_up
+                .as_ref()
+                .map(|top_up| SuiSigner::from_config(&top_up.funding_key).map(Arc::new))
+                .transpose()?;
+
+            tokio::spawn(monitor_balances(module.clone(), balance_monitor, {
+                let module = module.clone();
+
+                move |address, amount| {
+                    let module = module.clone();
+                    let funding_signer = funding_signer.clone();
+
+                    async move {
+                        let funding_signer = funding_signer
+                            .ok_or("top up requested without a funding key configured")?;
+
+                        module.top_up(&funding_signer, address, amount).await?;
+
+                        Ok::<_, TopUpError>(())
+                    }
+                }
+            }));
+        }
+
+        Ok(module)
     }
 
     fn info(config: Self::Config) -> PluginInfo {
@@ -162,6 +199,69 @@ pub struct Config {
     pub ibc_store: SuiAddress,
 
     pub keyring: KeyringConfig,
+
+    /// Periodic checks of the signer balances, removing drained signers from the rotation and
+    /// optionally topping them up.
+    #[serde(default)]
+    pub balance_monitor: Option<BalanceMonitorConfig>,
+}
+
+#[rpc(server)]
+trait TransactionPlugin {
+    /// The fees paid for submitting packets, per channel and counterparty channel.
+    #[method(name = "channelSpend")]
+    async fn channel_spend(&self) -> RpcResult<Vec<ChannelSpend>>;
+
+    /// The fee paid for submitting the packet with the given hash.
+    #[method(name = "packetFee")]
+    async fn packet_fee(&self, packet_hash: H256) -> RpcResult<Option<PacketFee>>;
+}
+
+#[async_trait]
+impl TransactionPluginServer for Module {
+    async fn channel_spend(&self) -> RpcResult<Vec<ChannelSpend>> {
+        Ok(self.fees.channel_spend())
+    }
+
+    async fn packet_fee(&self, packet_hash: H256) -> RpcResult<Option<PacketFee>> {
+        Ok(self.fees.packet_fee(packet_hash))
+    }
+}
+
+impl ChainKeyring for Module {
+    type Address = SuiAddress;
+    type Signer = Arc<SuiSigner>;
+
+    fn keyring(&self) -> &ConcurrentKeyring<SuiAddress, Arc<SuiSigner>> {
+        &self.keyring
+    }
+
+    async fn balances(&self) -> Vec<SignerBalance<SuiAddress>> {
+        let mut out = vec![];
+
+        for address in self.keyring.keys() {
+            match self
+                .sui_client
+                .coin_read_api()
+                .get_balance(*address, None)
+                .await
+            {
+                Ok(balance) => out.push(SignerBalance {
+                    key_name: address.to_string(),
+                    address: *address,
+                    balance: balance.total_balance,
+                    denom: balance.coin_type,
+                }),
+                Err(err) => warn!(
+                    %address,
+                    err = %ErrorReporter(err),
+                    "error fetching signer balance"
+                ),
+            }
+        }
+
+        out
+    }
 }
 
 fn plugin_name(chain_id: &ChainId) -> String {
@@ -174,6 +274,57 @@ impl Module {
     fn plugin_name(&self) -> String {
         plugin_name(&self.chain_id)
     }
+
+    /// Send `amount` MIST from `funding_signer` to `address`.
+    #[instrument(skip_all, fields(%address, amount))]
+    async fn top_up(
+        &self,
+        funding_signer: &Arc<SuiSigner>,
+        address: SuiAddress,
+        amount: u128,
+    ) -> anyhow::Result<()> {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+
+        ptb.transfer_sui(address, Some(amount.try_into()?));
+
+        let response = send_transactions(self, funding_signer, ptb.finish()).await?;
+
+        info!(digest = %response.digest, "top up tx submitted");
+
+        Ok(())
+    }
+
+    /// Record the fee paid by the tx in `response` for the packets in `datagrams`.
+    fn record_fees(&self, datagrams: &[Datagram], response: &SuiTransactionBlockResponse) {
+        let Some(effects) = &response.effects else {
+            warn!(
+                digest = %response.digest,
+                "unable to read the fee of the transaction"
+            );
+            return;
+        };
+
+        // the commands of a programmable transaction are executed atomically, so if the tx failed
+        // none of the packets were submitted
+        if !effects.status().is_ok() {
+            warn!(
+                digest = %response.digest,
+                status = ?effects.status(),
+                "tx failed, not recording the packet fees"
+            );
+            return;
+        }
+
+        let gas_cost = effects.gas_cost_summary();
+
+        self.fees.record_tx(
+            datagrams,
+            H256::new(response.digest.into_inner()),
+            gas_cost.gas_used(),
+            gas_cost.net_gas_usage().max(0).unsigned_abs().into(),
+            "mist",
+        );
+    }
 }
 
 #[async_trait]
@@ -202,6 +353,7 @@ impl PluginServer<ModuleCall, ModuleCallback> for Module {
                                     .collect(),
                             ),
                         )
+                        .with_priority(Priority::Urgent)
                         .into()
                     })
                     .visit_op(&mut op);
@@ -223,6 +375,11 @@ impl PluginServer<ModuleCall, ModuleCallback> for Module {
                     AssertUnwindSafe(async move {
                         let msgs = process_msgs(self, pk, msgs, sender).await;
 
+                        let datagrams = msgs
+                            .iter()
+                            .map(|(_, datagram, ..)| datagram.clone())
+                            .collect::<Vec<_>>();
+
                         let mut ptb = ProgrammableTransactionBuilder::new();
 
                         for (contract_addr, _, module, entry_fn, arguments, type_args) in
@@ -242,16 +399,19 @@ impl PluginServer<ModuleCall, ModuleCallback> for Module {
                         }
 
                         let builder = ptb.finish();
-                        let _ = send_transactions(self, pk, builder).await?;
+                        let response = send_transactions(self, pk, builder).await?;
+
+                        self.record_fees(&datagrams, &response);
+
                         Ok(noop())
                     })
                 })
                 .await
                 .unwrap_or_else(|| {
-                    Ok(call(PluginMessage::new(
-                        self.plugin_name(),
-                        ModuleCall::SubmitTransaction(msgs),
-                    )))
+                    Ok(call(
+                        PluginMessage::new(self.plugin_name(), ModuleCall::SubmitTransaction(msgs))
+                            .with_priority(Priority::Urgent),
+                    ))
                 }),
         }
     }
@@ -265,6 +425,26 @@ impl PluginServer<ModuleCall, ModuleCallback> for Module {
     ) -> RpcResult<Op<VoyagerMessage>> {
         match cb {}
     }
+
+    #[instrument(skip_all, fields(chain_id = %self.chain_id))]
+    async fn custom(&self, _: &Extensions, method: String, params: Vec<Value>) -> RpcResult<Value> {
+        TransactionPluginServer::into_rpc(self.clone())
+            .call::<Vec<Value>, Value>(&method, params)
+            .await
+            .map_err(|e| match e {
+                MethodsError::Parse(error) => ErrorObject::owned(
+                    FATAL_JSONRPC_ERROR_CODE,
+                    ErrorReporter(error).with_message("error parsing args"),
+                    None::<()>,
+                ),
+                MethodsError::JsonRpc(error_object) => error_object,
+                MethodsError::InvalidSubscriptionId(_) => ErrorObject::owned(
+                    FATAL_JSONRPC_ERROR_CODE,
+                    "subscriptions are not supported",
+                    None::<()>,
+                ),
+            })
+    }
 }
 
 // module: Identifier,
@@ -274,7 +454,7 @@ impl PluginServer<ModuleCall, ModuleCallback> for Module {
 #[allow(clippy::type_complexity)]
 async fn process_msgs(
     module: &Module,
-    pk: &Arc<SuiKeyPair>,
+    pk: &Arc<SuiSigner>,
     msgs: Vec<Datagram>,
     fee_recipient: SuiAddress,
 ) -> Vec<(
@@ -650,7 +830,7 @@ fn predict_wrapped_denom(path: U256, channel: ChannelId, base_token: Vec<u8>) ->
 
 async fn register_token_if_zkgm(
     module: &Module,
-    pk: &Arc<SuiKeyPair>,
+    pk: &Arc<SuiSigner>,
     packet: &ibc_union_spec::Packet,
     module_info: &ModuleInfo,
     store_initial_seq: SequenceNumber,
@@ -880,7 +1060,7 @@ pub async fn parse_port(_: &SuiClient, port_id: &str) -> ModuleInfo {
 
 pub async fn send_transactions(
     module: &Module,
-    pk: &Arc<SuiKeyPair>,
+    pk: &Arc<SuiSigner>,
     ptb: ProgrammableTransaction,
 ) -> RpcResult<SuiTransactionBlockResponse> {
     let sender = SuiAddress::from(&pk.public());
@@ -918,17 +1098,24 @@ pub async fn send_transactions(
     );
 
     let intent_msg = IntentMessage::new(Intent::sui_transaction(), tx_data);
-    let raw_tx = bcs::to_bytes(&intent_msg).expect("bcs should not fail");
-    let mut hasher = DefaultHash::default();
-    hasher.update(raw_tx.clone());
-    let digest = hasher.finalize().digest;
 
-    // use SuiKeyPair to sign the digest.
-    let sui_sig = pk.sign(&digest);
+    let sui_sig = pk.sign(&intent_msg).await.map_err(|e| {
+        ErrorObject::owned(
+            -1,
+            ErrorReporter(e).with_message("error signing the tx"),
+            None::<()>,
+        )
+    })?;
 
     sui_sig
-        .verify_secure(&intent_msg, sender, SignatureScheme::ED25519)
-        .expect("sender has a valid signature");
+        .verify_secure(&intent_msg, sender, pk.public().scheme())
+        .map_err(|e| {
+            ErrorObject::owned(
+                -1,
+                ErrorReporter(e).with_message("the signature of the tx is invalid for the sender"),
+                None::<()>,
+            )
+        })?;
 
     info!("submitting sui tx");
 
@@ -940,7 +1127,7 @@ pub async fn send_transactions(
                 intent_msg.value,
                 vec![GenericSignature::Signature(sui_sig)],
             ),
-            SuiTransactionBlockResponseOptions::default(),
+            SuiTransactionBlockResponseOptions::new().with_effects(),
             None,
         )
         .await
@@ -0,0 +1,147 @@
+use concurrent_keyring::{
+    remote::{RemoteSigner, RemoteSignerError},
+    KeyringConfigEntry,
+};
+use fastcrypto::{
+    hash::HashFunction,
+    traits::{Signer, ToFromBytes},
+};
+use serde::Serialize;
+use shared_crypto::intent::IntentMessage;
+use sui_sdk::types::{
+    base_types::SuiAddress,
+    crypto::{DefaultHash, PublicKey, Signature, SignatureScheme, SuiKeyPair, SuiSignature},
+};
+use voyager_sdk::anyhow::{self, anyhow};
+
+/// A signer for transactions, either holding the private key locally or signing with a remote
+/// signer.
+#[derive(Debug)]
+pub enum SuiSigner {
+    Local(SuiKeyPair),
+    Remote {
+        remote: RemoteSigner,
+        public_key: PublicKey,
+    },
+}
+
+impl SuiSigner {
+    pub fn from_config(entry: &KeyringConfigEntry) -> anyhow::Result<Self> {
+        match entry.remote_signer() {
+            Some(remote) => {
+                // the remote signer is configured with the `flag || public key` encoding
+                let (flag, key) = remote
+                    .public_key()
+                    .split_first()
+                    .ok_or(anyhow!("remote signer public key is empty"))?;
+
+                let scheme = SignatureScheme::from_flag_byte(flag)
+                    .map_err(|e| anyhow!("invalid remote signer public key flag: {e}"))?;
+
+                let public_key = PublicKey::try_from_bytes(scheme, key)
+                    .map_err(|e| anyhow!("invalid remote signer public key: {e}"))?;
+
+                Ok(Self::Remote { remote, public_key })
+            }
+            None => Ok(Self::Local(
+                SuiKeyPair::decode(&String::from_utf8(entry.value()?)?)
+                    .map_err(|e| anyhow!("invalid private key: {e}"))?,
+            )),
+        }
+    }
+
+    pub fn public(&self) -> PublicKey {
+        match self {
+            SuiSigner::Local(pk) => pk.public(),
+            SuiSigner::Remote { public_key, .. } => public_key.clone(),
+        }
+    }
+
+    /// Sign the digest of an intent message.
+    pub async fn sign<T: Serialize>(
+        &self,
+        intent_msg: &IntentMessage<T>,
+    ) -> Result<Signature, RemoteSignerError> {
+        let mut hasher = DefaultHash::default();
+        hasher.update(bcs::to_bytes(intent_msg).expect("bcs should not fail"));
+        let digest = hasher.finalize().digest;
+
+        match self {
+            SuiSigner::Local(pk) => Ok(pk.sign(&digest)),
+            SuiSigner::Remote { remote, public_key } => {
+                let signature = remote.sign_sui(digest).await?;
+
+                // a sui signature is encoded as `flag || signature || public key`
+                let signature = Signature::from_bytes(
+                    &[
+                        &[public_key.flag()][..],
+                        signature.get(),
+                        public_key.as_ref(),
+                    ]
+                    .concat(),
+                )
+                .map_err(|e| RemoteSignerError::InvalidSignature(e.to_string()))?;
+
+                // the remote signer may be signing with a different key than the one configured,
+                // which would otherwise only be noticed once the transaction is rejected
+                signature
+                    .verify_secure(
+                        intent_msg,
+                        SuiAddress::from(public_key),
+                        public_key.scheme(),
+                    )
+                    .map_err(|e| RemoteSignerError::InvalidSignature(e.to_string()))?;
+
+                Ok(signature)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use concurrent_keyring::mock::{MockKey, MockSigner};
+    use fastcrypto::ed25519::Ed25519KeyPair;
+    use shared_crypto::intent::Intent;
+
+    use super::*;
+
+    const PRIVATE_KEY: [u8; 32] = [0x22; 32];
+
+    #[tokio::test]
+    async fn remote_signer_signs() {
+        let key = MockKey::from(ed25519_dalek::SigningKey::from_bytes(&PRIVATE_KEY));
+
+        let mock = MockSigner::spawn([key.clone()]).await;
+
+        let remote = SuiSigner::from_config(&KeyringConfigEntry::Remote {
+            name: "remote".to_owned(),
+            url: mock.url(),
+            public_key: key.sui_public_key(),
+        })
+        .unwrap();
+
+        let local = SuiSigner::Local(SuiKeyPair::Ed25519(
+            Ed25519KeyPair::from_bytes(&PRIVATE_KEY).unwrap(),
+        ));
+
+        assert_eq!(remote.public().as_ref(), local.public().as_ref());
+
+        let intent_msg = IntentMessage::new(Intent::sui_transaction(), "tx");
+
+        let signature = remote.sign(&intent_msg).await.unwrap();
+
+        signature
+            .verify_secure(
+                &intent_msg,
+                SuiAddress::from(&remote.public()),
+                remote.public().scheme(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            signature.as_ref(),
+            local.sign(&intent_msg).await.unwrap().as_ref()
+        );
+    }
+}
@@ -150,12 +150,19 @@ pub enum QueueCmd {
         #[arg(long, global = true)]
         rest_url: Option<String>,
     },
-
-    // History {
-    //     id: PgId,
-    //     #[arg(long, default_value_t = result_unwrap!(Pg32::new(10)))]
-    //     max_depth: Pg32,
-    // },
+    /// Print the lineage of an op: all of the ops it was derived from, and all of the ops that were
+    /// derived from it.
+    History {
+        id: Pg64,
+        /// The maximum amount of levels to walk in either direction.
+        #[arg(long, default_value_t = 10)]
+        max_depth: u32,
+        /// Print the raw history as JSON instead of rendering it as a tree.
+        #[arg(long)]
+        json: bool,
+        #[arg(long)]
+        rpc_url: Option<String>,
+    },
     /// Query all failed messages.
     QueryFailed {
         #[arg(long, default_value_t = result_unwrap!(Pg64::new_const(1)))]
@@ -173,6 +180,37 @@ pub enum QueueCmd {
         ///  {"a": {"b": "c"}}
         /// ````
         ///
+        /// When using the `sqlite` queue, the item is stored as fully compact JSON (`{"a":{"b":"c"}}`).
+        ///
+        /// This can be specified multiple times to specify multiple filters.
+        #[arg(long = "item-filter", short = 'i')]
+        item_filters: Vec<String>,
+        /// SQL filters for failure message.
+        ///
+        /// This can be specified multiple times to specify multiple filters.
+        #[arg(long = "message-filter", short = 'm')]
+        message_filters: Vec<String>,
+    },
+    /// Move all failed messages matching the provided filters back into the queue.
+    ///
+    /// The messages are requeued with their original ID and parents. Messages that can no longer
+    /// be decoded are left in the failed table, and their IDs are reported as `undecodable`.
+    RequeueFailed {
+        /// SQL filters for the item. See `query-failed` for more information.
+        ///
+        /// This can be specified multiple times to specify multiple filters.
+        #[arg(long = "item-filter", short = 'i')]
+        item_filters: Vec<String>,
+        /// SQL filters for failure message.
+        ///
+        /// This can be specified multiple times to specify multiple filters.
+        #[arg(long = "message-filter", short = 'm')]
+        message_filters: Vec<String>,
+    },
+    /// Delete all failed messages matching the provided filters.
+    DeleteFailed {
+        /// SQL filters for the item. See `query-failed` for more information.
+        ///
         /// This can be specified multiple times to specify multiple filters.
         #[arg(long = "item-filter", short = 'i')]
         item_filters: Vec<String>,
@@ -209,6 +247,22 @@ pub enum PluginCmd {
     },
     /// List all available plugins.
     List,
+    /// Run the interest filters of all enabled plugins over a recorded stream of ops, reporting
+    /// which plugins would have picked up each op and which ops no plugin is interested in.
+    ///
+    /// The input is a stream of JSON values (for example newline-delimited JSON), where each value
+    /// is either an op, a queue row with the op under `item` (as produced by
+    /// `select row_to_json(done) from done`), or an array of either.
+    Simulate {
+        /// The file to read the ops from, or `-` to read from stdin.
+        file: PathBuf,
+        /// Only report the ops that no plugin is interested in.
+        #[arg(long)]
+        uninterested: bool,
+        /// Print the report as JSON instead of as text.
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -350,6 +404,13 @@ pub enum RpcCmd {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true, hide = true)]
         args: Vec<String>,
     },
+    /// Gracefully stop the running voyager instance. In-flight items are processed to completion,
+    /// the optimizer buffers are flushed, and the queue is checkpointed before voyager exits.
+    Drain,
+    /// Re-read the config file and apply any changes to the plugins and modules of the running
+    /// voyager instance. Only the plugins and modules whose configuration changed are restarted;
+    /// the queue and cache are left as-is.
+    Reload,
 }
 
 #[derive(Debug, Subcommand)]
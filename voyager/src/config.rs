@@ -7,6 +7,7 @@ use voyager_core::{
     default_ipc_client_request_timeout, default_metrics_endpoint,
     default_optimizer_delay_milliseconds, default_rest_laddr, default_rpc_laddr,
     equivalent_chain_ids::EquivalentChainIds,
+    supervisor::SupervisorConfig,
 };
 
 use crate::queue::QueueConfig;
@@ -42,4 +43,6 @@ pub struct VoyagerConfig {
     #[serde(default = "default_ipc_client_request_timeout")]
     pub ipc_client_request_timeout: Duration,
     pub cache: voyager_core::cache::Config,
+    #[serde(default)]
+    pub supervisor: SupervisorConfig,
 }
@@ -11,7 +11,14 @@
     clippy::missing_errors_doc
 )]
 
-use std::{collections::HashMap, fmt::Write, iter, process::ExitCode, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+    fs, io, iter,
+    pin::pin,
+    process::ExitCode,
+    time::Duration,
+};
 
 use anyhow::{anyhow, Context as _};
 use clap::Parser;
@@ -26,14 +33,16 @@ use schemars::gen::{SchemaGenerator, SchemaSettings};
 use serde::Serialize;
 use serde_json::Value;
 use tikv_jemallocator::Jemalloc;
-use tracing::info;
+use tracing::{info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
 use voyager_client::VoyagerClient;
 use voyager_core::{
     context::ModulesConfig,
     default_metrics_endpoint, default_rest_laddr, default_rpc_laddr,
     equivalent_chain_ids::EquivalentChainIds,
-    filter::{make_filter, run_filter, JaqFilterResult},
+    filter::{
+        make_filter, run_filter, InterestFilters, JaqFilterResult, SimulatedInterest, Simulation,
+    },
     get_plugin_info,
     ibc_spec_handlers::IbcSpecHandler,
     Engine,
@@ -44,8 +53,8 @@ use voyager_message::{
     VoyagerMessage,
 };
 use voyager_primitives::{IbcSpec, QueryHeight};
-use voyager_rpc::{types::IbcStateResponse, VoyagerRpcClient};
-use voyager_vm::{call, promise, Op, Queue};
+use voyager_rpc::{types::IbcStateResponse, VoyagerQueueRpcClient, VoyagerRpcClient};
+use voyager_vm::{call, promise, HistoryItem, ItemId, ItemStatus, Op, Queue};
 
 #[global_allocator]
 static GLOBAL: Jemalloc = Jemalloc;
@@ -55,7 +64,7 @@ use crate::{
         get_voyager_config, App, Command, ConfigCmd, LogFormat, MsgCmd, PluginCmd, QueueCmd, RpcCmd,
     },
     config::{Config, VoyagerConfig},
-    queue::{QueueConfig, QueueImpl},
+    queue::{PersistentQueue, QueueConfig, QueueImpl},
 };
 
 #[cfg(windows)]
@@ -169,10 +178,13 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
                         ),
                         retryable_error_expo_backoff_multiplier:
                             default_retryable_error_expo_backoff_multiplier(),
+                        retry_policies: vec![],
+                        scheduling: Default::default(),
                     }),
                     optimizer_delay_milliseconds: 100,
                     ipc_client_request_timeout: Duration::new(60, 0),
                     cache: voyager_core::cache::Config::default(),
+                    supervisor: Default::default(),
                 },
             }),
             ConfigCmd::Schema => print_json(
@@ -194,6 +206,14 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
                 .with_modules(config.modules)
                 .with_ipc_client_request_timeout(config.voyager.ipc_client_request_timeout)
                 .with_cache_config(config.voyager.cache)
+                .with_supervisor_config(config.voyager.supervisor)
+                .with_config_loader({
+                    let config_file_path = app.config_file_path.clone();
+                    move || {
+                        let config = cli::get_voyager_config(config_file_path.as_deref())?;
+                        Ok((config.plugins, config.modules))
+                    }
+                })
                 .with_metrics_endpoint(config.voyager.metrics_endpoint)
                 .with_num_workers(config.voyager.num_workers.into())
                 .with_rest_laddr(config.voyager.rest_laddr)
@@ -207,7 +227,26 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
 
             info!("starting relay service");
 
-            voyager.run().await;
+            {
+                let mut run = pin!(voyager.run());
+
+                tokio::select! {
+                    () = &mut run => {}
+                    () = shutdown_signal() => {
+                        info!("received shutdown signal, draining");
+                        voyager.drain();
+
+                        tokio::select! {
+                            () = run => {}
+                            () = shutdown_signal() => {
+                                warn!("received second shutdown signal, exiting without draining");
+                            }
+                        }
+                    }
+                }
+            }
+
+            voyager.shutdown();
         }
         Command::Plugin(cmd) => match cmd {
             PluginCmd::Interest {
@@ -279,19 +318,63 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
 
                 print_json(&list);
             }
+            PluginCmd::Simulate {
+                file,
+                uninterested,
+                json,
+            } => {
+                let interest_filters = InterestFilters::new(
+                    get_voyager_config()?
+                        .plugins
+                        .iter()
+                        .filter(|plugin_config| plugin_config.enabled)
+                        .map(get_plugin_info)
+                        .collect::<anyhow::Result<_>>()?,
+                )?;
+
+                let input = if file.as_os_str() == "-" {
+                    io::read_to_string(io::stdin())?
+                } else {
+                    fs::read_to_string(&file)
+                        .with_context(|| format!("reading ops from {}", file.display()))?
+                };
+
+                let ops = read_recorded_ops(&input)?;
+
+                let mut report = SimulationReport {
+                    total: ops.len(),
+                    uninterested: 0,
+                    ops: vec![],
+                };
+
+                for (idx, (id, op)) in ops.into_iter().enumerate() {
+                    let simulation = interest_filters.simulate(&op);
+
+                    if simulation.is_interesting() {
+                        if uninterested {
+                            continue;
+                        }
+                    } else {
+                        report.uninterested += 1;
+                    }
+
+                    report.ops.push(SimulatedOp {
+                        idx,
+                        id,
+                        op,
+                        simulation,
+                    });
+                }
+
+                if json {
+                    print_json(&report);
+                } else {
+                    print!("{}", render_simulation_report(&report));
+                }
+            }
         },
         Command::Queue(cli_msg) => {
-            let db = || {
-                Ok(match get_voyager_config()?.voyager.queue {
-                    QueueConfig::PgQueue(cfg) => pg_queue::PgQueue::<VoyagerMessage>::new(cfg),
-                    QueueConfig::InMemory => {
-                        return Err(anyhow!(
-                            "no database set in config, queue commands \
-                            require the `pg-queue` database backend"
-                        ))
-                    }
-                })
-            };
+            let db = async || PersistentQueue::new(get_voyager_config()?.voyager.queue).await;
 
             match cli_msg {
                 QueueCmd::Enqueue { op, rest_url } => {
@@ -299,35 +382,62 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
 
                     send_enqueue(&rest_url, op).await?;
                 }
-                // NOTE: Temporarily disabled until i figure out a better way to implement this with the new queue design
-                // cli::QueueCmd::History { id, max_depth } => {
-                //     // let results = query_as!(
-                //     //     Record,
-                //     //     r#"SELECT id as "id!", parent, item as "item!: Item" FROM get_list($1, $2) ORDER BY id ASC"#,
-                //     //     id.inner(),
-                //     //     max_depth.inner()
-                //     // )
-                //     // .fetch_all(&db)
-                //     // .await
-                //     // .unwrap();
-
-                //     // println!("{}", serde_json::to_string_pretty(&results).unwrap());
-
-                //     todo!();
-                // }
+                QueueCmd::History {
+                    id,
+                    max_depth,
+                    json,
+                    rpc_url,
+                } => {
+                    let rpc_url = get_rpc_url(rpc_url);
+
+                    let voyager_client =
+                        jsonrpsee::http_client::HttpClient::builder().build(rpc_url)?;
+
+                    let history = voyager_client
+                        .queue_history(ItemId::new(id.inner())?, max_depth)
+                        .await?;
+
+                    if json {
+                        print_json(&history);
+                    } else {
+                        print!("{}", render_history(&history));
+                    }
+                }
                 QueueCmd::QueryFailed {
                     page,
                     per_page,
                     item_filters,
                     message_filters,
                 } => {
-                    let record = db()?
+                    let record = db()
                         .await?
                         .query_failed(page.into(), per_page.into(), item_filters, message_filters)
                         .await?;
 
                     print_json(&record);
                 }
+                QueueCmd::RequeueFailed {
+                    item_filters,
+                    message_filters,
+                } => {
+                    let result = db()
+                        .await?
+                        .requeue_failed(item_filters, message_filters)
+                        .await?;
+
+                    print_json(&result);
+                }
+                QueueCmd::DeleteFailed {
+                    item_filters,
+                    message_filters,
+                } => {
+                    let ids = db()
+                        .await?
+                        .delete_failed(item_filters, message_filters)
+                        .await?;
+
+                    print_json(&ids);
+                }
                 QueueCmd::QueryFailedById {
                     id,
                     requeue,
@@ -335,12 +445,12 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
                 } => {
                     let rest_url = get_rest_url(rest_url);
 
-                    let q = db()?.await?;
+                    let q = db().await?;
 
                     let record = q.query_failed_by_id(id.inner()).await?;
 
                     if requeue {
-                        if let Some(op) = record.as_ref().map(|r| r.item.0.clone()) {
+                        if let Some(op) = record.as_ref().map(|r| r.item.clone()) {
                             send_enqueue(&rest_url, op).await?;
                             println!("requeued");
                         }
@@ -610,6 +720,14 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
                         .await?;
                     print_json(&response);
                 }
+                RpcCmd::Drain => {
+                    voyager_client.drain().await?;
+                    println!("draining");
+                }
+                RpcCmd::Reload => {
+                    let response = voyager_client.reload().await?;
+                    print_json(&response);
+                }
             }
         }
         Command::Msg(msg) => match msg {
@@ -730,6 +848,25 @@ async fn do_main(app: cli::App) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Resolves once either SIGINT (ctrl-c) or SIGTERM is received.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("unable to install SIGTERM handler");
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = sigterm.recv() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
 async fn send_enqueue(
     rest_laddr: &str,
     op: Op<VoyagerMessage>,
@@ -741,6 +878,126 @@ async fn send_enqueue(
         .await?)
 }
 
+/// Parse a recorded stream of ops, as accepted by `voyager plugin simulate`.
+///
+/// Returns the id of the queue row the op was exported from (if any) along with the op itself.
+fn read_recorded_ops(input: &str) -> anyhow::Result<Vec<(Option<Value>, Op<VoyagerMessage>)>> {
+    fn parse(
+        value: Value,
+        ops: &mut Vec<(Option<Value>, Op<VoyagerMessage>)>,
+    ) -> anyhow::Result<()> {
+        match value {
+            Value::Array(values) => {
+                for value in values {
+                    parse(value, ops)?;
+                }
+            }
+            Value::Object(mut row) if !row.contains_key("@type") && row.contains_key("item") => {
+                let item = row.remove("item").expect("key exists; qed;");
+                let id = row.remove("id");
+
+                let op = serde_json::from_value(item).with_context(|| {
+                    format!("invalid op in row {}", id.clone().unwrap_or_default())
+                })?;
+
+                ops.push((id, op));
+            }
+            value => ops.push((
+                None,
+                serde_json::from_value(value)
+                    .with_context(|| format!("invalid op at index {}", ops.len()))?,
+            )),
+        }
+
+        Ok(())
+    }
+
+    let mut ops = vec![];
+
+    for value in serde_json::Deserializer::from_str(input).into_iter::<Value>() {
+        parse(value?, &mut ops)?;
+    }
+
+    Ok(ops)
+}
+
+#[derive(Debug, Serialize)]
+struct SimulationReport {
+    /// The total amount of ops read from the input.
+    total: usize,
+    /// The amount of ops that no plugin is interested in.
+    uninterested: usize,
+    ops: Vec<SimulatedOp>,
+}
+
+#[derive(Debug, Serialize)]
+struct SimulatedOp {
+    /// The position of this op in the input.
+    idx: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Value>,
+    op: Op<VoyagerMessage>,
+    simulation: Simulation,
+}
+
+fn render_simulation_report(report: &SimulationReport) -> String {
+    let mut out = String::new();
+
+    for SimulatedOp {
+        idx,
+        id,
+        op: _,
+        simulation,
+    } in &report.ops
+    {
+        match id {
+            Some(id) => write!(out, "[{idx}] (id {id})"),
+            None => write!(out, "[{idx}]"),
+        }
+        .unwrap();
+
+        if simulation.is_interesting() {
+            write!(
+                out,
+                " tags: {}{}",
+                simulation.tags.join(", "),
+                if simulation.remove { " (removed)" } else { "" }
+            )
+            .unwrap();
+        } else {
+            write!(out, " no interest").unwrap();
+        }
+
+        writeln!(out).unwrap();
+
+        for plugin in &simulation.plugins {
+            let result = match plugin.result {
+                SimulatedInterest::Take => "take",
+                SimulatedInterest::Copy => "copy",
+                SimulatedInterest::NoInterest => continue,
+                SimulatedInterest::Failed => "failed",
+            };
+
+            writeln!(
+                out,
+                "    {}: {result}{}",
+                plugin.plugin,
+                if plugin.shadowed { " (shadowed)" } else { "" }
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(
+        out,
+        "{} ops, {} with no interest",
+        report.total, report.uninterested
+    )
+    .unwrap();
+
+    out
+}
+
 fn print_json<T: Serialize>(t: &T) {
     println!(
         "{}",
@@ -748,6 +1005,89 @@ fn print_json<T: Serialize>(t: &T) {
     );
 }
 
+/// Render the lineage of an op as a tree, starting from the items whose parents are not part of the
+/// history.
+fn render_history(history: &[HistoryItem<VoyagerMessage>]) -> String {
+    fn render_item(
+        out: &mut String,
+        history: &[HistoryItem<VoyagerMessage>],
+        item: &HistoryItem<VoyagerMessage>,
+        depth: usize,
+        seen: &mut HashSet<i64>,
+    ) {
+        let status = match item.status {
+            ItemStatus::Queued => "queued",
+            ItemStatus::Optimize => "optimize",
+            ItemStatus::Done => "done",
+            ItemStatus::Failed => "failed",
+        };
+
+        write!(
+            out,
+            "{:indent$}{} [{status}] {}",
+            "",
+            item.id.raw(),
+            op_summary(&item.op),
+            indent = depth * 2
+        )
+        .expect("writing to a string is infallible; qed;");
+
+        if let Some(message) = &item.message {
+            write!(out, ": {message}").expect("writing to a string is infallible; qed;");
+        }
+
+        // items with multiple parents are only expanded the first time they're rendered
+        if !seen.insert(item.id.raw()) {
+            writeln!(out, " (see above)").expect("writing to a string is infallible; qed;");
+            return;
+        }
+
+        writeln!(out).expect("writing to a string is infallible; qed;");
+
+        for child in history
+            .iter()
+            .filter(|child| child.parents.contains(&item.id))
+        {
+            render_item(out, history, child, depth + 1, seen);
+        }
+    }
+
+    let mut out = String::new();
+    let mut seen = HashSet::new();
+
+    for root in history.iter().filter(|item| {
+        !item
+            .parents
+            .iter()
+            .any(|parent| history.iter().any(|item| item.id == *parent))
+    }) {
+        render_item(&mut out, history, root, 0, &mut seen);
+    }
+
+    out
+}
+
+/// Summarize an op by it's (nested) `@type` tags, i.e. `call/plugin/<plugin name>`.
+fn op_summary(op: &Op<VoyagerMessage>) -> String {
+    let value = serde_json::to_value(op).expect("serialization is infallible; qed;");
+
+    let mut parts = vec![];
+    let mut value = &value;
+
+    while let Some(ty) = value.get("@type").and_then(Value::as_str) {
+        parts.push(ty.to_owned());
+
+        value = &value["@value"];
+
+        if let Some(plugin) = value.get("plugin").and_then(Value::as_str) {
+            parts.push(plugin.to_owned());
+            break;
+        }
+    }
+
+    parts.join("/")
+}
+
 // // TODO: Extract all logic here to a plugin
 pub mod utils {
     use anyhow::bail;
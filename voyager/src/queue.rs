@@ -2,34 +2,41 @@
 
 use std::fmt::Debug;
 
+use anyhow::anyhow;
 use futures::Future;
 use pg_queue::{PgQueue, PgQueueConfig};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use sqlite_queue::{SqliteQueue, SqliteQueueConfig};
 use voyager_message::VoyagerMessage;
 use voyager_vm::{
-    filter::InterestFilter, in_memory::InMemoryQueue, pass::Pass, Captures, EnqueueResult, ItemId,
-    Op, Queue, QueueError,
+    filter::InterestFilter,
+    in_memory::{InMemoryQueue, InMemoryQueueConfig, InMemoryQueueError},
+    pass::Pass,
+    Captures, EnqueueResult, HistoryItem, ItemId, Op, Queue, QueueError,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case", tag = "type")]
 pub enum QueueConfig {
-    InMemory,
+    InMemory(InMemoryQueueConfig),
     PgQueue(PgQueueConfig),
+    Sqlite(SqliteQueueConfig),
 }
 
 #[derive(Debug, Clone)]
 pub enum QueueImpl {
     InMemory(InMemoryQueue<VoyagerMessage>),
     PgQueue(PgQueue<VoyagerMessage>),
+    Sqlite(SqliteQueue<VoyagerMessage>),
 }
 
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
 pub enum AnyQueueError {
-    InMemory(std::convert::Infallible),
+    InMemory(InMemoryQueueError),
     PgQueue(sqlx::Error),
+    Sqlite(sqlx::Error),
 }
 
 impl Queue<VoyagerMessage> for QueueImpl {
@@ -38,7 +45,7 @@ impl Queue<VoyagerMessage> for QueueImpl {
 
     async fn new(cfg: Self::Config) -> Result<Self, Self::Error> {
         match cfg {
-            QueueConfig::InMemory => InMemoryQueue::new(())
+            QueueConfig::InMemory(cfg) => InMemoryQueue::new(cfg)
                 .await
                 .map_err(AnyQueueError::InMemory)
                 .map(Self::InMemory),
@@ -46,6 +53,10 @@ impl Queue<VoyagerMessage> for QueueImpl {
                 .await
                 .map_err(AnyQueueError::PgQueue)
                 .map(Self::PgQueue),
+            QueueConfig::Sqlite(cfg) => SqliteQueue::new(cfg)
+                .await
+                .map_err(AnyQueueError::Sqlite)
+                .map(Self::Sqlite),
         }
     }
 
@@ -63,6 +74,10 @@ impl Queue<VoyagerMessage> for QueueImpl {
                 .enqueue(item, filter)
                 .await
                 .map_err(AnyQueueError::PgQueue),
+            QueueImpl::Sqlite(queue) => queue
+                .enqueue(item, filter)
+                .await
+                .map_err(AnyQueueError::Sqlite),
         }
     }
 
@@ -86,6 +101,10 @@ impl Queue<VoyagerMessage> for QueueImpl {
                 .process(filter, f)
                 .await
                 .map_err(AnyQueueError::PgQueue),
+            QueueImpl::Sqlite(queue) => queue
+                .process(filter, f)
+                .await
+                .map_err(AnyQueueError::Sqlite),
         }
     }
 
@@ -104,6 +123,175 @@ impl Queue<VoyagerMessage> for QueueImpl {
                 .optimize(tag, filter, optimizer)
                 .await
                 .map_err(|e| e.map_left(AnyQueueError::PgQueue)),
+            QueueImpl::Sqlite(queue) => queue
+                .optimize(tag, filter, optimizer)
+                .await
+                .map_err(|e| e.map_left(AnyQueueError::Sqlite)),
+        }
+    }
+
+    async fn history(
+        &self,
+        id: ItemId,
+        max_depth: u32,
+    ) -> Result<Vec<HistoryItem<VoyagerMessage>>, Self::Error> {
+        match self {
+            QueueImpl::InMemory(queue) => queue
+                .history(id, max_depth)
+                .await
+                .map_err(AnyQueueError::InMemory),
+            QueueImpl::PgQueue(queue) => queue
+                .history(id, max_depth)
+                .await
+                .map_err(AnyQueueError::PgQueue),
+            QueueImpl::Sqlite(queue) => queue
+                .history(id, max_depth)
+                .await
+                .map_err(AnyQueueError::Sqlite),
+        }
+    }
+
+    async fn checkpoint(&self) -> Result<(), Self::Error> {
+        match self {
+            QueueImpl::InMemory(queue) => queue.checkpoint().await.map_err(AnyQueueError::InMemory),
+            QueueImpl::PgQueue(queue) => queue.checkpoint().await.map_err(AnyQueueError::PgQueue),
+            QueueImpl::Sqlite(queue) => queue.checkpoint().await.map_err(AnyQueueError::Sqlite),
+        }
+    }
+}
+
+/// A queue that persists failed items, and as such supports querying and requeueing them.
+#[derive(Debug, Clone)]
+pub enum PersistentQueue {
+    PgQueue(PgQueue<VoyagerMessage>),
+    Sqlite(SqliteQueue<VoyagerMessage>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FailedRecord {
+    pub id: i64,
+    pub parents: Vec<i64>,
+    pub item: Op<VoyagerMessage>,
+    pub message: String,
+}
+
+impl From<pg_queue::FailedRecord<VoyagerMessage>> for FailedRecord {
+    fn from(record: pg_queue::FailedRecord<VoyagerMessage>) -> Self {
+        Self {
+            id: record.id,
+            parents: record.parents,
+            item: record.item.0,
+            message: record.message,
+        }
+    }
+}
+
+impl From<sqlite_queue::FailedRecord<VoyagerMessage>> for FailedRecord {
+    fn from(record: sqlite_queue::FailedRecord<VoyagerMessage>) -> Self {
+        Self {
+            id: record.id,
+            parents: record.parents.0,
+            item: record.item.0,
+            message: record.message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RequeueFailedResult {
+    pub requeued: Vec<i64>,
+    pub undecodable: Vec<i64>,
+}
+
+impl From<pg_queue::RequeueFailedResult> for RequeueFailedResult {
+    fn from(result: pg_queue::RequeueFailedResult) -> Self {
+        Self {
+            requeued: result.requeued,
+            undecodable: result.undecodable,
+        }
+    }
+}
+
+impl From<sqlite_queue::RequeueFailedResult> for RequeueFailedResult {
+    fn from(result: sqlite_queue::RequeueFailedResult) -> Self {
+        Self {
+            requeued: result.requeued,
+            undecodable: result.undecodable,
+        }
+    }
+}
+
+impl PersistentQueue {
+    pub async fn new(cfg: QueueConfig) -> anyhow::Result<Self> {
+        match cfg {
+            QueueConfig::InMemory(_) => Err(anyhow!(
+                "no database set in config, queue commands \
+                require the `pg-queue` or `sqlite` database backend"
+            )),
+            QueueConfig::PgQueue(cfg) => Ok(Self::PgQueue(PgQueue::new(cfg).await?)),
+            QueueConfig::Sqlite(cfg) => Ok(Self::Sqlite(SqliteQueue::open(cfg).await?)),
+        }
+    }
+
+    pub async fn query_failed(
+        &self,
+        page: i64,
+        per_page: i64,
+        item_filters: Vec<String>,
+        message_filters: Vec<String>,
+    ) -> Result<Vec<FailedRecord>, sqlx::Error> {
+        Ok(match self {
+            PersistentQueue::PgQueue(queue) => queue
+                .query_failed(page, per_page, item_filters, message_filters)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            PersistentQueue::Sqlite(queue) => queue
+                .query_failed(page, per_page, item_filters, message_filters)
+                .await?
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        })
+    }
+
+    pub async fn query_failed_by_id(&self, id: i64) -> Result<Option<FailedRecord>, sqlx::Error> {
+        Ok(match self {
+            PersistentQueue::PgQueue(queue) => queue.query_failed_by_id(id).await?.map(Into::into),
+            PersistentQueue::Sqlite(queue) => queue.query_failed_by_id(id).await?.map(Into::into),
+        })
+    }
+
+    pub async fn requeue_failed(
+        &self,
+        item_filters: Vec<String>,
+        message_filters: Vec<String>,
+    ) -> Result<RequeueFailedResult, sqlx::Error> {
+        Ok(match self {
+            PersistentQueue::PgQueue(queue) => queue
+                .requeue_failed(item_filters, message_filters)
+                .await?
+                .into(),
+            PersistentQueue::Sqlite(queue) => queue
+                .requeue_failed(item_filters, message_filters)
+                .await?
+                .into(),
+        })
+    }
+
+    pub async fn delete_failed(
+        &self,
+        item_filters: Vec<String>,
+        message_filters: Vec<String>,
+    ) -> Result<Vec<i64>, sqlx::Error> {
+        match self {
+            PersistentQueue::PgQueue(queue) => {
+                queue.delete_failed(item_filters, message_filters).await
+            }
+            PersistentQueue::Sqlite(queue) => {
+                queue.delete_failed(item_filters, message_filters).await
+            }
         }
     }
 }